@@ -0,0 +1,133 @@
+use std::ops::ControlFlow;
+
+use crate::ast::{Cte, Ident, ObjectName, Query, Statement};
+use crate::visitor::{walk_cte, walk_query, walk_statement, Visitor};
+
+/// Represents a column reference as written in the statement; no name resolution is done,
+/// so a bare name may also be a select alias
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct ColumnRef {
+    /// The qualifier joined with dots, such as `t` in `t.a` or `s.t` in `s.t.a`
+    pub table: Option<String>,
+    pub column: String,
+}
+
+impl Statement {
+    /// Returns the tables and views this statement reads or writes, in order of first
+    /// appearance and without duplicates. Names that refer to a CTE in scope are skipped,
+    /// while the same name outside that scope is still reported as a real table.
+    pub fn referenced_tables(&self) -> Vec<ObjectName> {
+        let mut collector = TableCollector::default();
+        let _ = collector.visit_statement(self);
+        collector.tables
+    }
+
+    /// Returns the columns this statement references, in order of first appearance and
+    /// without duplicates. Columns listed in an INSERT are qualified with the target table.
+    pub fn referenced_columns(&self) -> Vec<ColumnRef> {
+        let mut collector = ColumnCollector::default();
+        let _ = collector.visit_statement(self);
+        collector.columns
+    }
+}
+
+#[derive(Default)]
+struct TableCollector {
+    tables: Vec<ObjectName>,
+    /// CTE names visible at the current point of the walk
+    ctes: Vec<String>,
+}
+
+impl TableCollector {
+    fn is_cte(&self, name: &ObjectName) -> bool {
+        match name.0.as_slice() {
+            [ident] => self.ctes.iter().any(|cte| match ident.quote_style {
+                Some(_) => *cte == ident.value,
+                None => cte.eq_ignore_ascii_case(&ident.value),
+            }),
+            _ => false,
+        }
+    }
+}
+
+impl Visitor for TableCollector {
+    type Break = ();
+
+    fn visit_query(&mut self, query: &Query) -> ControlFlow<()> {
+        let scope = self.ctes.len();
+        // A recursive CTE can refer to itself and to the CTEs after it
+        if let Some(with) = query.with.as_ref().filter(|with| with.recursive) {
+            self.ctes
+                .extend(with.ctes.iter().map(|cte| cte.name.clone()));
+        }
+        let result = walk_query(self, query);
+        self.ctes.truncate(scope);
+        result
+    }
+
+    fn visit_cte(&mut self, cte: &Cte) -> ControlFlow<()> {
+        walk_cte(self, cte)?;
+        // Only the CTEs after this one and the query body can see its name
+        self.ctes.push(cte.name.clone());
+        ControlFlow::Continue(())
+    }
+
+    fn visit_table_name(&mut self, name: &ObjectName) -> ControlFlow<()> {
+        if !self.is_cte(name) && !self.tables.contains(name) {
+            self.tables.push(name.clone());
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+#[derive(Default)]
+struct ColumnCollector {
+    columns: Vec<ColumnRef>,
+}
+
+impl ColumnCollector {
+    fn add(&mut self, table: Option<String>, column: String) {
+        let column = ColumnRef { table, column };
+        if !self.columns.contains(&column) {
+            self.columns.push(column);
+        }
+    }
+}
+
+impl Visitor for ColumnCollector {
+    type Break = ();
+
+    fn visit_statement(&mut self, statement: &Statement) -> ControlFlow<()> {
+        match statement {
+            Statement::Insert { table, columns, .. } => {
+                let table = table.to_string();
+                for column in columns {
+                    self.add(Some(table.clone()), column.clone());
+                }
+            }
+            Statement::Update { assignments, .. } => {
+                for assignment in assignments {
+                    if let Some((column, qualifier)) = assignment.column.split_last() {
+                        let table = (!qualifier.is_empty()).then(|| qualifier.join("."));
+                        self.add(table, column.clone());
+                    }
+                }
+            }
+            _ => {}
+        }
+        walk_statement(self, statement)
+    }
+
+    fn visit_identifier(&mut self, ident: &Ident) -> ControlFlow<()> {
+        self.add(None, ident.value.to_string());
+        ControlFlow::Continue(())
+    }
+
+    fn visit_compound_identifier(&mut self, parts: &[Ident]) -> ControlFlow<()> {
+        if let Some((column, qualifier)) = parts.split_last() {
+            let qualifier: Vec<&str> = qualifier.iter().map(|part| part.value.as_str()).collect();
+            self.add(Some(qualifier.join(".")), column.value.to_string());
+        }
+        ControlFlow::Continue(())
+    }
+}
@@ -0,0 +1,317 @@
+//! Random AST generation for fuzzing, enabled by the `arbitrary` feature. Generated trees
+//! are ones the parser can produce, so printing one and parsing the text back should give
+//! the same tree.
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::ast::{
+    Assignment, BinaryOperator, DataType, Distinct, Expression, Float, Ident, InsertSource,
+    Keyword, ObjectName, OrderByItem, Query, SelectItem, SelectStatement, SetExpr, Statement,
+    TableAlias, TableReference, TableWithJoins, UnaryOperator, Value,
+};
+
+/// How deeply generated expressions nest, keeping trees small enough to shrink well
+const MAX_DEPTH: u32 = 4;
+
+impl<'a> Arbitrary<'a> for Value {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(match u.int_in_range(0..=4u8)? {
+            0 => Value::Number(u64::arbitrary(u)?),
+            // A leading minus is parsed as an operator, so literals are never negative
+            1 => {
+                let x = f64::arbitrary(u)?.abs();
+                Value::Float(Float(if x.is_finite() { x } else { 0.5 }))
+            }
+            2 => Value::SingleQuotedString(string(u)?),
+            3 => Value::Boolean(bool::arbitrary(u)?),
+            _ => Value::Null,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for BinaryOperator {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        u.choose(&[
+            BinaryOperator::Plus,
+            BinaryOperator::Minus,
+            BinaryOperator::Multiply,
+            BinaryOperator::Divide,
+            BinaryOperator::Equal,
+            BinaryOperator::NotEqual,
+            BinaryOperator::LessThan,
+            BinaryOperator::LessThanOrEqual,
+            BinaryOperator::GreaterThan,
+            BinaryOperator::GreaterThanOrEqual,
+            BinaryOperator::And,
+            BinaryOperator::Or,
+            BinaryOperator::Concat,
+        ])
+        .cloned()
+    }
+}
+
+impl<'a> Arbitrary<'a> for UnaryOperator {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        u.choose(&[
+            UnaryOperator::Not,
+            UnaryOperator::Minus,
+            UnaryOperator::Plus,
+        ])
+        .cloned()
+    }
+}
+
+impl<'a> Arbitrary<'a> for DataType {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let length = |u: &mut Unstructured<'a>| -> Result<Option<u64>> {
+            Ok(if bool::arbitrary(u)? {
+                Some(u.int_in_range(1..=255)?)
+            } else {
+                None
+            })
+        };
+        Ok(match u.int_in_range(0..=12u8)? {
+            0 => DataType::Int,
+            1 => DataType::BigInt,
+            2 => DataType::SmallInt,
+            3 => DataType::Varchar(length(u)?),
+            4 => DataType::Char(length(u)?),
+            5 => DataType::Text,
+            6 => DataType::Boolean,
+            7 => DataType::Float,
+            8 => DataType::Double,
+            9 => {
+                let precision = length(u)?;
+                let scale = match precision {
+                    Some(_) => length(u)?,
+                    None => None,
+                };
+                DataType::Decimal(precision, scale)
+            }
+            10 => DataType::Date,
+            11 => DataType::Timestamp,
+            _ => DataType::TimestampWithTimeZone,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Ident {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        if u.ratio(1u8, 4u8)? {
+            // Quoting keeps case and lets a name collide with a keyword
+            let value = name(u)?.to_uppercase();
+            Ok(Ident {
+                value: value.into(),
+                quote_style: Some('"'),
+            })
+        } else {
+            Ok(Ident::new(name(u)?))
+        }
+    }
+}
+
+impl<'a> Arbitrary<'a> for Expression {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        expression(u, MAX_DEPTH)
+    }
+}
+
+impl<'a> Arbitrary<'a> for Query {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let select = SelectStatement {
+            distinct: if u.ratio(1u8, 4u8)? {
+                Some(Distinct::Distinct)
+            } else {
+                None
+            },
+            top: None,
+            projection: list(u, 1, 3, select_item)?,
+            from: Some(TableWithJoins {
+                relation: table(u)?,
+                joins: vec![],
+            }),
+            selection: optional(u, |u| expression(u, MAX_DEPTH))?,
+            group_by: vec![],
+            having: None,
+        };
+
+        Ok(Query {
+            with: None,
+            body: SetExpr::Select(Box::new(select)),
+            order_by: list(u, 0, 2, |u| {
+                Ok(OrderByItem {
+                    expr: expression(u, 1)?,
+                    asc: u.choose(&[None, Some(true), Some(false)])?.to_owned(),
+                    nulls_first: None,
+                })
+            })?,
+            limit: optional(u, |u| {
+                Ok(Expression::Value(Value::Number(u64::arbitrary(u)?)))
+            })?,
+            offset: None,
+            with_ties: false,
+            locking: None,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Statement {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(match u.int_in_range(0..=3u8)? {
+            0 => Statement::Query(Box::new(Query::arbitrary(u)?)),
+            1 => {
+                let columns = list(u, 1, 3, name)?;
+                let width = columns.len();
+                Statement::Insert {
+                    table: object_name(u)?,
+                    columns,
+                    source: InsertSource::Values(list(u, 1, 3, |u| {
+                        (0..width).map(|_| expression(u, 1)).collect()
+                    })?),
+                    on_conflict: None,
+                    returning: None,
+                }
+            }
+            2 => Statement::Update {
+                table: table(u)?,
+                assignments: list(u, 1, 3, |u| {
+                    Ok(Assignment {
+                        column: vec![name(u)?],
+                        value: expression(u, 2)?,
+                    })
+                })?,
+                from: None,
+                selection: optional(u, |u| expression(u, MAX_DEPTH))?,
+                returning: None,
+            },
+            _ => Statement::Delete {
+                table: table(u)?,
+                using: None,
+                selection: optional(u, |u| expression(u, MAX_DEPTH))?,
+                returning: None,
+            },
+        })
+    }
+}
+
+/// Generates an expression nesting at most `depth` operators deep
+fn expression(u: &mut Unstructured, depth: u32) -> Result<Expression> {
+    let choices: u8 = if depth == 0 { 3 } else { 8 };
+    Ok(match u.int_in_range(0..=choices)? {
+        0 | 1 => Expression::Value(Value::arbitrary(u)?),
+        2 => Expression::Identifier(Ident::arbitrary(u)?),
+        3 => Expression::CompoundIdentifier(list(u, 2, 3, Ident::arbitrary)?),
+        4 | 5 => Expression::BinaryOperation {
+            left_operand: Box::new(expression(u, depth - 1)?),
+            operator: BinaryOperator::arbitrary(u)?,
+            right_operand: Box::new(expression(u, depth - 1)?),
+        },
+        6 => Expression::UnaryOperation {
+            operator: UnaryOperator::arbitrary(u)?,
+            operand: Box::new(expression(u, depth - 1)?),
+        },
+        7 => {
+            let expr = Box::new(expression(u, depth - 1)?);
+            let negated = bool::arbitrary(u)?;
+            match u.int_in_range(0..=2u8)? {
+                0 => Expression::InList {
+                    expr,
+                    list: list(u, 1, 3, |u| expression(u, depth - 1))?,
+                    negated,
+                },
+                1 => Expression::IsNull { expr, negated },
+                _ => Expression::Like {
+                    expr,
+                    pattern: Box::new(expression(u, depth - 1)?),
+                    negated,
+                },
+            }
+        }
+        _ => {
+            if bool::arbitrary(u)? {
+                Expression::Cast {
+                    expr: Box::new(expression(u, depth - 1)?),
+                    data_type: DataType::arbitrary(u)?,
+                }
+            } else {
+                Expression::Function {
+                    name: name(u)?,
+                    args: list(u, 0, 3, |u| expression(u, depth - 1))?,
+                }
+            }
+        }
+    })
+}
+
+fn select_item(u: &mut Unstructured) -> Result<SelectItem> {
+    Ok(match u.int_in_range(0..=4u8)? {
+        0 => SelectItem::Wildcard,
+        1 | 2 => SelectItem::ExprWithAlias {
+            expr: expression(u, 2)?,
+            alias: Ident::arbitrary(u)?,
+        },
+        _ => SelectItem::Expression(expression(u, 2)?),
+    })
+}
+
+fn table(u: &mut Unstructured) -> Result<TableReference> {
+    Ok(TableReference::Table {
+        name: object_name(u)?,
+        alias: optional(u, |u| {
+            Ok(TableAlias {
+                name: Ident::arbitrary(u)?,
+                columns: vec![],
+            })
+        })?,
+    })
+}
+
+fn object_name(u: &mut Unstructured) -> Result<ObjectName> {
+    Ok(ObjectName(list(u, 1, 2, Ident::arbitrary)?))
+}
+
+/// Generates a lower-case name that is not a keyword, so it reads back unquoted
+fn name(u: &mut Unstructured) -> Result<String> {
+    const ALPHABET: &[u8] = b"abcdefghijklmnopqrstuvwxyz_";
+    let mut name = String::new();
+    for i in 0..u.int_in_range(1..=6u8)? {
+        let letters = if i == 0 { 25 } else { ALPHABET.len() - 1 };
+        name.push(ALPHABET[u.int_in_range(0..=letters)?] as char);
+    }
+    if name.parse::<Keyword>().is_ok() {
+        name.push('_');
+    }
+    Ok(name)
+}
+
+/// Generates string contents, including quotes that must be escaped when printed
+fn string(u: &mut Unstructured) -> Result<String> {
+    const ALPHABET: &[char] = &['a', 'B', ' ', '\'', '"', '%', '\\', 'é'];
+    let mut s = String::new();
+    for _ in 0..u.int_in_range(0..=8u8)? {
+        s.push(*u.choose(ALPHABET)?);
+    }
+    Ok(s)
+}
+
+fn optional<'a, T>(
+    u: &mut Unstructured<'a>,
+    generate: impl FnOnce(&mut Unstructured<'a>) -> Result<T>,
+) -> Result<Option<T>> {
+    Ok(if bool::arbitrary(u)? {
+        Some(generate(u)?)
+    } else {
+        None
+    })
+}
+
+fn list<'a, T>(
+    u: &mut Unstructured<'a>,
+    min: usize,
+    max: usize,
+    mut generate: impl FnMut(&mut Unstructured<'a>) -> Result<T>,
+) -> Result<Vec<T>> {
+    (0..u.int_in_range(min..=max)?)
+        .map(|_| generate(u))
+        .collect()
+}
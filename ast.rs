@@ -1,90 +1,2839 @@
-use std::str::FromStr;
-
-/// Represents SQL keywords like SELECT, CREATE, WHERE, etc.
-#[derive(Debug, PartialEq, Clone)]
-pub enum Keyword {
-    Select,
-    Create,
-    Table,
-    Where,
-    From,
-    Order,
-    By,
-    And,
-    Or,
-    Not,
-}
-
-impl FromStr for Keyword {
-    type Err = ();
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.to_uppercase().as_str() {
-            "SELECT" => Ok(Keyword::Select),
-            "CREATE" => Ok(Keyword::Create),
-            "TABLE" => Ok(Keyword::Table),
-            "WHERE" => Ok(Keyword::Where),
-            "FROM" => Ok(Keyword::From),
-            "ORDER" => Ok(Keyword::Order),
-            "BY" => Ok(Keyword::By),
-            "AND" => Ok(Keyword::And),
-            "OR" => Ok(Keyword::Or),
-            "NOT" => Ok(Keyword::Not),
-            _ => Err(()),
-        }
-    }
-}
-
-/// Represents all possible token types in the SQL language
-#[derive(Debug, PartialEq, Clone)]
-pub enum Token {
-    Keyword(Keyword),
-    Identifier(String),
-    String(String),
-    Number(u64),
-    Invalid(char),
-
-    // Punctuation
-    RightParentheses,
-    LeftParentheses,
-    Comma,
-    Semicolon,
-
-    // Comparison Operators
-    GreaterThan,
-    GreaterThanOrEqual,
-    LessThan,
-    LessThanOrEqual,
-    Equal,
-    NotEqual,
-
-    // Arithmetic Operators
-    Multiply,
-    Divide,
-    Minus,
-    Plus,
-
-    // Special Tokens
-    Eof,
-}
-
-/// Represents binary operators for mathematical and logical operations
-#[derive(Debug, PartialEq, Clone)]
-pub enum BinaryOperator {
-    Plus,
-    Minus,
-    Multiply,
-    Divide,
-}
-
-/// Represents SQL expressions
-#[derive(Debug, PartialEq, Clone)]
-pub enum Expression {
-    BinaryOperation {
-        left_operand: Box<Expression>,
-        operator: BinaryOperator,
-        right_operand: Box<Expression>,
-    },
-    Number(u64),
-    Identifier(String),
-}
+use std::borrow::Borrow;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// Represents SQL keywords like SELECT, CREATE, WHERE, etc.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Keyword {
+    Select,
+    Create,
+    Table,
+    Where,
+    From,
+    Order,
+    By,
+    And,
+    Or,
+    Not,
+    True,
+    False,
+    Null,
+    Asc,
+    Desc,
+    Nulls,
+    First,
+    Last,
+    Limit,
+    Offset,
+    Fetch,
+    Next,
+    Row,
+    Rows,
+    Only,
+    With,
+    Ties,
+    Group,
+    Having,
+    Grouping,
+    Sets,
+    Rollup,
+    Cube,
+    Distinct,
+    On,
+    All,
+    As,
+    Join,
+    Inner,
+    Left,
+    Right,
+    Full,
+    Outer,
+    Cross,
+    Natural,
+    Using,
+    Union,
+    Intersect,
+    Except,
+    Recursive,
+    Values,
+    Insert,
+    Into,
+    Conflict,
+    Constraint,
+    Do,
+    Nothing,
+    Update,
+    Set,
+    Returning,
+    Delete,
+    Default,
+    Primary,
+    Key,
+    Unique,
+    Foreign,
+    References,
+    Check,
+    Cascade,
+    Restrict,
+    No,
+    Action,
+    If,
+    Exists,
+    Temp,
+    Temporary,
+    Alter,
+    Add,
+    Drop,
+    Column,
+    Rename,
+    To,
+    Index,
+    View,
+    Replace,
+    Materialized,
+    Truncate,
+    Restart,
+    Continue,
+    Identity,
+    Begin,
+    Start,
+    Transaction,
+    Work,
+    Commit,
+    Rollback,
+    Savepoint,
+    Release,
+    Isolation,
+    Level,
+    Read,
+    Committed,
+    Uncommitted,
+    Repeatable,
+    Serializable,
+    Show,
+    Describe,
+    In,
+    Like,
+    Is,
+    Pragma,
+    For,
+    Share,
+    Of,
+    Nowait,
+    Skip,
+    Locked,
+    Merge,
+    Matched,
+    When,
+    Then,
+}
+
+impl FromStr for Keyword {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_uppercase().as_str() {
+            "SELECT" => Ok(Keyword::Select),
+            "CREATE" => Ok(Keyword::Create),
+            "TABLE" => Ok(Keyword::Table),
+            "WHERE" => Ok(Keyword::Where),
+            "FROM" => Ok(Keyword::From),
+            "ORDER" => Ok(Keyword::Order),
+            "BY" => Ok(Keyword::By),
+            "AND" => Ok(Keyword::And),
+            "OR" => Ok(Keyword::Or),
+            "NOT" => Ok(Keyword::Not),
+            "TRUE" => Ok(Keyword::True),
+            "FALSE" => Ok(Keyword::False),
+            "NULL" => Ok(Keyword::Null),
+            "ASC" => Ok(Keyword::Asc),
+            "DESC" => Ok(Keyword::Desc),
+            "NULLS" => Ok(Keyword::Nulls),
+            "FIRST" => Ok(Keyword::First),
+            "LAST" => Ok(Keyword::Last),
+            "LIMIT" => Ok(Keyword::Limit),
+            "OFFSET" => Ok(Keyword::Offset),
+            "FETCH" => Ok(Keyword::Fetch),
+            "NEXT" => Ok(Keyword::Next),
+            "ROW" => Ok(Keyword::Row),
+            "ROWS" => Ok(Keyword::Rows),
+            "ONLY" => Ok(Keyword::Only),
+            "WITH" => Ok(Keyword::With),
+            "TIES" => Ok(Keyword::Ties),
+            "GROUP" => Ok(Keyword::Group),
+            "HAVING" => Ok(Keyword::Having),
+            "GROUPING" => Ok(Keyword::Grouping),
+            "SETS" => Ok(Keyword::Sets),
+            "ROLLUP" => Ok(Keyword::Rollup),
+            "CUBE" => Ok(Keyword::Cube),
+            "DISTINCT" => Ok(Keyword::Distinct),
+            "ON" => Ok(Keyword::On),
+            "ALL" => Ok(Keyword::All),
+            "AS" => Ok(Keyword::As),
+            "JOIN" => Ok(Keyword::Join),
+            "INNER" => Ok(Keyword::Inner),
+            "LEFT" => Ok(Keyword::Left),
+            "RIGHT" => Ok(Keyword::Right),
+            "FULL" => Ok(Keyword::Full),
+            "OUTER" => Ok(Keyword::Outer),
+            "CROSS" => Ok(Keyword::Cross),
+            "NATURAL" => Ok(Keyword::Natural),
+            "USING" => Ok(Keyword::Using),
+            "UNION" => Ok(Keyword::Union),
+            "INTERSECT" => Ok(Keyword::Intersect),
+            "EXCEPT" => Ok(Keyword::Except),
+            "RECURSIVE" => Ok(Keyword::Recursive),
+            "VALUES" => Ok(Keyword::Values),
+            "INSERT" => Ok(Keyword::Insert),
+            "INTO" => Ok(Keyword::Into),
+            "CONFLICT" => Ok(Keyword::Conflict),
+            "CONSTRAINT" => Ok(Keyword::Constraint),
+            "DO" => Ok(Keyword::Do),
+            "NOTHING" => Ok(Keyword::Nothing),
+            "UPDATE" => Ok(Keyword::Update),
+            "SET" => Ok(Keyword::Set),
+            "RETURNING" => Ok(Keyword::Returning),
+            "DELETE" => Ok(Keyword::Delete),
+            "DEFAULT" => Ok(Keyword::Default),
+            "PRIMARY" => Ok(Keyword::Primary),
+            "KEY" => Ok(Keyword::Key),
+            "UNIQUE" => Ok(Keyword::Unique),
+            "FOREIGN" => Ok(Keyword::Foreign),
+            "REFERENCES" => Ok(Keyword::References),
+            "CHECK" => Ok(Keyword::Check),
+            "CASCADE" => Ok(Keyword::Cascade),
+            "RESTRICT" => Ok(Keyword::Restrict),
+            "NO" => Ok(Keyword::No),
+            "ACTION" => Ok(Keyword::Action),
+            "IF" => Ok(Keyword::If),
+            "EXISTS" => Ok(Keyword::Exists),
+            "TEMP" => Ok(Keyword::Temp),
+            "TEMPORARY" => Ok(Keyword::Temporary),
+            "ALTER" => Ok(Keyword::Alter),
+            "ADD" => Ok(Keyword::Add),
+            "DROP" => Ok(Keyword::Drop),
+            "COLUMN" => Ok(Keyword::Column),
+            "RENAME" => Ok(Keyword::Rename),
+            "TO" => Ok(Keyword::To),
+            "INDEX" => Ok(Keyword::Index),
+            "VIEW" => Ok(Keyword::View),
+            "REPLACE" => Ok(Keyword::Replace),
+            "MATERIALIZED" => Ok(Keyword::Materialized),
+            "TRUNCATE" => Ok(Keyword::Truncate),
+            "RESTART" => Ok(Keyword::Restart),
+            "CONTINUE" => Ok(Keyword::Continue),
+            "IDENTITY" => Ok(Keyword::Identity),
+            "BEGIN" => Ok(Keyword::Begin),
+            "START" => Ok(Keyword::Start),
+            "TRANSACTION" => Ok(Keyword::Transaction),
+            "WORK" => Ok(Keyword::Work),
+            "COMMIT" => Ok(Keyword::Commit),
+            "ROLLBACK" => Ok(Keyword::Rollback),
+            "SAVEPOINT" => Ok(Keyword::Savepoint),
+            "RELEASE" => Ok(Keyword::Release),
+            "ISOLATION" => Ok(Keyword::Isolation),
+            "LEVEL" => Ok(Keyword::Level),
+            "READ" => Ok(Keyword::Read),
+            "COMMITTED" => Ok(Keyword::Committed),
+            "UNCOMMITTED" => Ok(Keyword::Uncommitted),
+            "REPEATABLE" => Ok(Keyword::Repeatable),
+            "SERIALIZABLE" => Ok(Keyword::Serializable),
+            "SHOW" => Ok(Keyword::Show),
+            "DESCRIBE" => Ok(Keyword::Describe),
+            "IN" => Ok(Keyword::In),
+            "LIKE" => Ok(Keyword::Like),
+            "IS" => Ok(Keyword::Is),
+            "PRAGMA" => Ok(Keyword::Pragma),
+            "FOR" => Ok(Keyword::For),
+            "SHARE" => Ok(Keyword::Share),
+            "OF" => Ok(Keyword::Of),
+            "NOWAIT" => Ok(Keyword::Nowait),
+            "SKIP" => Ok(Keyword::Skip),
+            "LOCKED" => Ok(Keyword::Locked),
+            "MERGE" => Ok(Keyword::Merge),
+            "MATCHED" => Ok(Keyword::Matched),
+            "WHEN" => Ok(Keyword::When),
+            "THEN" => Ok(Keyword::Then),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for Keyword {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", format!("{:?}", self).to_uppercase())
+    }
+}
+
+impl Keyword {
+    /// Returns true if the keyword can never be used as an unquoted identifier.
+    ///
+    /// Roughly follows the ANSI reserved list, plus every word that can end a
+    /// select item or table reference, since those would otherwise read as aliases.
+    pub fn is_reserved(&self) -> bool {
+        matches!(
+            self,
+            Keyword::All
+                | Keyword::Alter
+                | Keyword::And
+                | Keyword::As
+                | Keyword::Asc
+                | Keyword::By
+                | Keyword::Check
+                | Keyword::Column
+                | Keyword::Constraint
+                | Keyword::Create
+                | Keyword::Cross
+                | Keyword::Default
+                | Keyword::Delete
+                | Keyword::Desc
+                | Keyword::Distinct
+                | Keyword::Drop
+                | Keyword::Except
+                | Keyword::False
+                | Keyword::Fetch
+                | Keyword::For
+                | Keyword::Foreign
+                | Keyword::From
+                | Keyword::Full
+                | Keyword::Group
+                | Keyword::Having
+                | Keyword::In
+                | Keyword::Inner
+                | Keyword::Insert
+                | Keyword::Intersect
+                | Keyword::Into
+                | Keyword::Is
+                | Keyword::Join
+                | Keyword::Left
+                | Keyword::Like
+                | Keyword::Limit
+                | Keyword::Natural
+                | Keyword::Not
+                | Keyword::Null
+                | Keyword::Of
+                | Keyword::Offset
+                | Keyword::On
+                | Keyword::Only
+                | Keyword::Or
+                | Keyword::Order
+                | Keyword::Outer
+                | Keyword::Primary
+                | Keyword::References
+                | Keyword::Returning
+                | Keyword::Right
+                | Keyword::Select
+                | Keyword::Set
+                | Keyword::Table
+                | Keyword::Then
+                | Keyword::To
+                | Keyword::True
+                | Keyword::Union
+                | Keyword::Unique
+                | Keyword::Update
+                | Keyword::Using
+                | Keyword::Values
+                | Keyword::When
+                | Keyword::Where
+                | Keyword::With
+        )
+    }
+}
+
+/// Represents all possible token types in the SQL language
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Token {
+    Keyword(Keyword),
+    Identifier(Name),
+    QuotedIdentifier(Name),
+    String(String),
+    Number(u64),
+    Float(Float),
+    Placeholder(String),
+    Invalid(char),
+
+    // Punctuation
+    RightParentheses,
+    LeftParentheses,
+    Comma,
+    Semicolon,
+    Dot,
+    DoubleColon,
+    LeftBracket,
+    RightBracket,
+
+    // Comparison Operators
+    GreaterThan,
+    GreaterThanOrEqual,
+    LessThan,
+    LessThanOrEqual,
+    Equal,
+    NotEqual,
+
+    // Arithmetic Operators
+    Multiply,
+    Divide,
+    Minus,
+    Plus,
+
+    // String Operators
+    Concat,
+
+    // Special Tokens
+    Eof,
+}
+
+impl fmt::Display for Token {
+    /// Writes the token as it reads in SQL; `Eof` has no text and is written as
+    /// `end of input`
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let symbol = match self {
+            Token::Keyword(keyword) => return write!(f, "{}", keyword),
+            Token::Identifier(name) => return write!(f, "{}", name.as_str()),
+            Token::QuotedIdentifier(name) => {
+                return write!(f, "\"{}\"", name.as_str().replace('"', "\"\""))
+            }
+            Token::String(s) => return write!(f, "'{}'", s.replace('\'', "''")),
+            Token::Number(n) => return write!(f, "{}", n),
+            Token::Float(x) => return write!(f, "{}", x),
+            Token::Placeholder(text) => return write!(f, "{}", text),
+            Token::Invalid(ch) => return write!(f, "{}", ch),
+            Token::RightParentheses => ")",
+            Token::LeftParentheses => "(",
+            Token::Comma => ",",
+            Token::Semicolon => ";",
+            Token::Dot => ".",
+            Token::DoubleColon => "::",
+            Token::LeftBracket => "[",
+            Token::RightBracket => "]",
+            Token::GreaterThan => ">",
+            Token::GreaterThanOrEqual => ">=",
+            Token::LessThan => "<",
+            Token::LessThanOrEqual => "<=",
+            Token::Equal => "=",
+            Token::NotEqual => "<>",
+            Token::Multiply => "*",
+            Token::Divide => "/",
+            Token::Minus => "-",
+            Token::Plus => "+",
+            Token::Concat => "||",
+            Token::Eof => "end of input",
+        };
+        f.write_str(symbol)
+    }
+}
+
+impl Token {
+    /// Returns the kind of the token, setting aside the name or value it carries
+    pub fn kind(&self) -> TokenKind {
+        match self {
+            Token::Keyword(keyword) => TokenKind::Keyword(keyword.clone()),
+            Token::Identifier(_) | Token::QuotedIdentifier(_) => TokenKind::Identifier,
+            Token::String(_) => TokenKind::String,
+            Token::Number(_) | Token::Float(_) => TokenKind::Number,
+            Token::Placeholder(_) => TokenKind::Placeholder,
+            Token::Invalid(_) => TokenKind::Invalid,
+            Token::RightParentheses => TokenKind::RightParentheses,
+            Token::LeftParentheses => TokenKind::LeftParentheses,
+            Token::Comma => TokenKind::Comma,
+            Token::Semicolon => TokenKind::Semicolon,
+            Token::Dot => TokenKind::Dot,
+            Token::DoubleColon => TokenKind::DoubleColon,
+            Token::LeftBracket => TokenKind::LeftBracket,
+            Token::RightBracket => TokenKind::RightBracket,
+            Token::GreaterThan => TokenKind::GreaterThan,
+            Token::GreaterThanOrEqual => TokenKind::GreaterThanOrEqual,
+            Token::LessThan => TokenKind::LessThan,
+            Token::LessThanOrEqual => TokenKind::LessThanOrEqual,
+            Token::Equal => TokenKind::Equal,
+            Token::NotEqual => TokenKind::NotEqual,
+            Token::Multiply => TokenKind::Multiply,
+            Token::Divide => TokenKind::Divide,
+            Token::Minus => TokenKind::Minus,
+            Token::Plus => TokenKind::Plus,
+            Token::Concat => TokenKind::Concat,
+            Token::Eof => TokenKind::Eof,
+        }
+    }
+}
+
+/// Represents what a token is, without the name or value it carries: a particular keyword
+/// or symbol, or any identifier, string, number or placeholder. Errors and completions use
+/// it to say what would have been accepted.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TokenKind {
+    Keyword(Keyword),
+    /// An identifier, quoted or not
+    Identifier,
+    String,
+    /// An integer or decimal number
+    Number,
+    Placeholder,
+    Invalid,
+    RightParentheses,
+    LeftParentheses,
+    Comma,
+    Semicolon,
+    Dot,
+    DoubleColon,
+    LeftBracket,
+    RightBracket,
+    GreaterThan,
+    GreaterThanOrEqual,
+    LessThan,
+    LessThanOrEqual,
+    Equal,
+    NotEqual,
+    Multiply,
+    Divide,
+    Minus,
+    Plus,
+    Concat,
+    Eof,
+}
+
+impl TokenKind {
+    /// Whether the kind reads as a word in a message: a keyword, any name or literal, or the
+    /// end of the input
+    pub fn is_word(&self) -> bool {
+        matches!(
+            self,
+            TokenKind::Keyword(_)
+                | TokenKind::Identifier
+                | TokenKind::String
+                | TokenKind::Number
+                | TokenKind::Placeholder
+                | TokenKind::Eof
+        )
+    }
+}
+
+impl fmt::Display for TokenKind {
+    /// Writes a keyword or symbol as it reads in SQL, and any other kind as a description,
+    /// such as `identifier`
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let text = match self {
+            TokenKind::Keyword(keyword) => return write!(f, "{}", keyword),
+            TokenKind::Identifier => "identifier",
+            TokenKind::String => "string",
+            TokenKind::Number => "number",
+            TokenKind::Placeholder => "placeholder",
+            TokenKind::Invalid => "invalid character",
+            TokenKind::RightParentheses => ")",
+            TokenKind::LeftParentheses => "(",
+            TokenKind::Comma => ",",
+            TokenKind::Semicolon => ";",
+            TokenKind::Dot => ".",
+            TokenKind::DoubleColon => "::",
+            TokenKind::LeftBracket => "[",
+            TokenKind::RightBracket => "]",
+            TokenKind::GreaterThan => ">",
+            TokenKind::GreaterThanOrEqual => ">=",
+            TokenKind::LessThan => "<",
+            TokenKind::LessThanOrEqual => "<=",
+            TokenKind::Equal => "=",
+            TokenKind::NotEqual => "<>",
+            TokenKind::Multiply => "*",
+            TokenKind::Divide => "/",
+            TokenKind::Minus => "-",
+            TokenKind::Plus => "+",
+            TokenKind::Concat => "||",
+            TokenKind::Eof => "end of input",
+        };
+        f.write_str(text)
+    }
+}
+
+/// Represents binary operators for mathematical and logical operations
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BinaryOperator {
+    Plus,
+    Minus,
+    Multiply,
+    Divide,
+    Equal,
+    NotEqual,
+    LessThan,
+    LessThanOrEqual,
+    GreaterThan,
+    GreaterThanOrEqual,
+    And,
+    Or,
+    /// String concatenation, `||`
+    Concat,
+}
+
+impl BinaryOperator {
+    /// Returns the binding power the parser gives this operator; higher binds tighter
+    pub fn precedence(&self) -> u8 {
+        match self {
+            BinaryOperator::Or => 1,
+            BinaryOperator::And => 2,
+            BinaryOperator::Equal
+            | BinaryOperator::NotEqual
+            | BinaryOperator::LessThan
+            | BinaryOperator::LessThanOrEqual
+            | BinaryOperator::GreaterThan
+            | BinaryOperator::GreaterThanOrEqual => 4,
+            BinaryOperator::Concat => 6,
+            BinaryOperator::Plus | BinaryOperator::Minus => 7,
+            BinaryOperator::Multiply | BinaryOperator::Divide => 8,
+        }
+    }
+
+    /// Returns true for `=`, `<>`, `<`, `<=`, `>` and `>=`
+    pub fn is_comparison(&self) -> bool {
+        matches!(
+            self,
+            BinaryOperator::Equal
+                | BinaryOperator::NotEqual
+                | BinaryOperator::LessThan
+                | BinaryOperator::LessThanOrEqual
+                | BinaryOperator::GreaterThan
+                | BinaryOperator::GreaterThanOrEqual
+        )
+    }
+
+    /// Returns true if `operand` needs parentheses as the left operand of this operator. A
+    /// comparison on the left of another keeps them although the two bind equally, so that
+    /// `(a < b) < c` still parses when chained comparisons are rejected.
+    pub(crate) fn parenthesizes_left(&self, operand: &Expression) -> bool {
+        let comparison_on_left = matches!(
+            operand,
+            Expression::BinaryOperation { operator, .. } if operator.is_comparison()
+        );
+        operand.precedence() < self.precedence() || self.is_comparison() && comparison_on_left
+    }
+}
+
+impl fmt::Display for BinaryOperator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let symbol = match self {
+            BinaryOperator::Plus => "+",
+            BinaryOperator::Minus => "-",
+            BinaryOperator::Multiply => "*",
+            BinaryOperator::Divide => "/",
+            BinaryOperator::Equal => "=",
+            BinaryOperator::NotEqual => "<>",
+            BinaryOperator::LessThan => "<",
+            BinaryOperator::LessThanOrEqual => "<=",
+            BinaryOperator::GreaterThan => ">",
+            BinaryOperator::GreaterThanOrEqual => ">=",
+            BinaryOperator::And => "AND",
+            BinaryOperator::Or => "OR",
+            BinaryOperator::Concat => "||",
+        };
+        write!(f, "{}", symbol)
+    }
+}
+
+/// Represents prefix operators
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum UnaryOperator {
+    Not,
+    Minus,
+    Plus,
+}
+
+impl UnaryOperator {
+    /// Returns the binding power of the operand that follows this operator
+    pub fn precedence(&self) -> u8 {
+        match self {
+            UnaryOperator::Not => 5,
+            UnaryOperator::Minus | UnaryOperator::Plus => 8,
+        }
+    }
+}
+
+impl fmt::Display for UnaryOperator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let symbol = match self {
+            UnaryOperator::Not => "NOT",
+            UnaryOperator::Minus => "-",
+            UnaryOperator::Plus => "+",
+        };
+        write!(f, "{}", symbol)
+    }
+}
+
+/// Represents a float literal. It is compared and hashed by its bit pattern so that the AST
+/// can be `Eq` and `Hash`: a NaN equals a NaN with the same bits, and `0.0` differs from
+/// `-0.0`. The parser never produces NaN or infinity; they only come from built ASTs.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Float(pub f64);
+
+impl PartialEq for Float {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.to_bits() == other.0.to_bits()
+    }
+}
+
+impl Eq for Float {}
+
+impl Hash for Float {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.to_bits().hash(state);
+    }
+}
+
+impl From<f64> for Float {
+    fn from(x: f64) -> Self {
+        Float(x)
+    }
+}
+
+impl fmt::Display for Float {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Debug keeps the fraction of whole numbers, so `2.0` does not read back as `2`
+        write!(f, "{:?}", self.0)
+    }
+}
+
+/// Represents a literal value
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Value {
+    Number(u64),
+    /// A number written with a fraction or an exponent, such as `1.5` or `2e10`
+    Float(Float),
+    SingleQuotedString(String),
+    Boolean(bool),
+    Null,
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Number(n) => write!(f, "{}", n),
+            Value::Float(x) => write!(f, "{}", x),
+            Value::SingleQuotedString(s) => write!(f, "'{}'", s.replace('\'', "''")),
+            Value::Boolean(true) => write!(f, "TRUE"),
+            Value::Boolean(false) => write!(f, "FALSE"),
+            Value::Null => write!(f, "NULL"),
+        }
+    }
+}
+
+/// Represents SQL expressions. Clone, comparison, hashing, printing and dropping follow
+/// the left operands of binary operations in a loop, since a generated chain of many
+/// thousands of `OR` terms nests that deep on its left.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Expression {
+    BinaryOperation {
+        left_operand: Box<Expression>,
+        operator: BinaryOperator,
+        right_operand: Box<Expression>,
+    },
+    UnaryOperation {
+        operator: UnaryOperator,
+        operand: Box<Expression>,
+    },
+    Value(Value),
+    /// A parameter marker
+    Placeholder {
+        /// The marker as written: `?`, `$1` or `:name`
+        text: String,
+        /// Zero-based position among the statement's placeholders, in the order they are
+        /// written
+        ordinal: usize,
+    },
+    Identifier(Ident),
+    CompoundIdentifier(Vec<Ident>),
+    Function {
+        name: String,
+        args: Vec<Expression>,
+    },
+    Cast {
+        expr: Box<Expression>,
+        data_type: DataType,
+    },
+    /// The `*` argument of an aggregate such as `COUNT(*)`
+    Wildcard,
+    /// A parenthesized query used as a scalar value
+    Subquery(Box<Query>),
+    /// `expr [NOT] IN (list)`
+    InList {
+        expr: Box<Expression>,
+        list: Vec<Expression>,
+        negated: bool,
+    },
+    /// `expr IS [NOT] NULL`
+    IsNull {
+        expr: Box<Expression>,
+        negated: bool,
+    },
+    /// `expr [NOT] LIKE pattern`, where `%` in the pattern matches any run of characters
+    /// and `_` any single character
+    Like {
+        expr: Box<Expression>,
+        pattern: Box<Expression>,
+        negated: bool,
+    },
+}
+
+impl Expression {
+    /// Returns how tightly this expression holds together when printed as an operand
+    pub(crate) fn precedence(&self) -> u8 {
+        match self {
+            Expression::BinaryOperation { operator, .. } => operator.precedence(),
+            Expression::UnaryOperation { operator, .. } => operator.precedence(),
+            Expression::InList { .. } | Expression::IsNull { .. } | Expression::Like { .. } => 4,
+            Expression::Cast { .. } => 9,
+            _ => u8::MAX,
+        }
+    }
+
+    /// Moves the operands that have operands of their own onto `stack`, leaving
+    /// placeholders behind
+    fn take_operands(&mut self, stack: &mut Vec<Expression>) {
+        let mut take = |operand: &mut Box<Expression>| {
+            if matches!(
+                **operand,
+                Expression::BinaryOperation { .. }
+                    | Expression::UnaryOperation { .. }
+                    | Expression::Cast { .. }
+                    | Expression::InList { .. }
+                    | Expression::IsNull { .. }
+                    | Expression::Like { .. }
+            ) {
+                stack.push(std::mem::replace(&mut **operand, Expression::Wildcard));
+            }
+        };
+        match self {
+            Expression::BinaryOperation {
+                left_operand,
+                right_operand,
+                ..
+            } => {
+                take(left_operand);
+                take(right_operand);
+            }
+            Expression::UnaryOperation { operand, .. } => take(operand),
+            Expression::Cast { expr, .. } | Expression::InList { expr, .. } => take(expr),
+            Expression::IsNull { expr, .. } => take(expr),
+            Expression::Like { expr, pattern, .. } => {
+                take(expr);
+                take(pattern);
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Drop for Expression {
+    fn drop(&mut self) {
+        // A generated chain of many thousands of `OR` terms nests that deep on its left, so
+        // dropping it one level per call could run out of stack
+        let mut stack = vec![];
+        self.take_operands(&mut stack);
+        while let Some(mut operand) = stack.pop() {
+            operand.take_operands(&mut stack);
+        }
+    }
+}
+
+impl Clone for Expression {
+    fn clone(&self) -> Self {
+        // Copy the right operands down the left spine, then rebuild it from the bottom up
+        let mut spine = vec![];
+        let mut node = self;
+        while let Expression::BinaryOperation {
+            left_operand,
+            operator,
+            right_operand,
+        } = node
+        {
+            spine.push((operator.clone(), right_operand.clone()));
+            node = left_operand;
+        }
+        let mut expr = match node {
+            Expression::BinaryOperation { .. } => unreachable!("the loop above goes past these"),
+            Expression::UnaryOperation { operator, operand } => Expression::UnaryOperation {
+                operator: operator.clone(),
+                operand: operand.clone(),
+            },
+            Expression::Value(value) => Expression::Value(value.clone()),
+            Expression::Placeholder { text, ordinal } => Expression::Placeholder {
+                text: text.clone(),
+                ordinal: *ordinal,
+            },
+            Expression::Identifier(ident) => Expression::Identifier(ident.clone()),
+            Expression::CompoundIdentifier(parts) => Expression::CompoundIdentifier(parts.clone()),
+            Expression::Function { name, args } => Expression::Function {
+                name: name.clone(),
+                args: args.clone(),
+            },
+            Expression::Cast { expr, data_type } => Expression::Cast {
+                expr: expr.clone(),
+                data_type: data_type.clone(),
+            },
+            Expression::Wildcard => Expression::Wildcard,
+            Expression::Subquery(query) => Expression::Subquery(query.clone()),
+            Expression::InList {
+                expr,
+                list,
+                negated,
+            } => Expression::InList {
+                expr: expr.clone(),
+                list: list.clone(),
+                negated: *negated,
+            },
+            Expression::IsNull { expr, negated } => Expression::IsNull {
+                expr: expr.clone(),
+                negated: *negated,
+            },
+            Expression::Like {
+                expr,
+                pattern,
+                negated,
+            } => Expression::Like {
+                expr: expr.clone(),
+                pattern: pattern.clone(),
+                negated: *negated,
+            },
+        };
+        while let Some((operator, right_operand)) = spine.pop() {
+            expr = Expression::BinaryOperation {
+                left_operand: Box::new(expr),
+                operator,
+                right_operand,
+            };
+        }
+        expr
+    }
+}
+
+impl PartialEq for Expression {
+    fn eq(&self, other: &Self) -> bool {
+        let (mut a, mut b) = (self, other);
+        loop {
+            match (a, b) {
+                (
+                    Expression::BinaryOperation {
+                        left_operand: a_left,
+                        operator: a_operator,
+                        right_operand: a_right,
+                    },
+                    Expression::BinaryOperation {
+                        left_operand: b_left,
+                        operator: b_operator,
+                        right_operand: b_right,
+                    },
+                ) => {
+                    if a_operator != b_operator || a_right != b_right {
+                        return false;
+                    }
+                    a = a_left;
+                    b = b_left;
+                }
+                (
+                    Expression::UnaryOperation { operator, operand },
+                    Expression::UnaryOperation {
+                        operator: b_operator,
+                        operand: b_operand,
+                    },
+                ) => return operator == b_operator && operand == b_operand,
+                (Expression::Value(value), Expression::Value(b_value)) => return value == b_value,
+                (
+                    Expression::Placeholder { text, ordinal },
+                    Expression::Placeholder {
+                        text: b_text,
+                        ordinal: b_ordinal,
+                    },
+                ) => return text == b_text && ordinal == b_ordinal,
+                (Expression::Identifier(ident), Expression::Identifier(b_ident)) => {
+                    return ident == b_ident
+                }
+                (
+                    Expression::CompoundIdentifier(parts),
+                    Expression::CompoundIdentifier(b_parts),
+                ) => return parts == b_parts,
+                (
+                    Expression::Function { name, args },
+                    Expression::Function {
+                        name: b_name,
+                        args: b_args,
+                    },
+                ) => return name == b_name && args == b_args,
+                (
+                    Expression::Cast { expr, data_type },
+                    Expression::Cast {
+                        expr: b_expr,
+                        data_type: b_data_type,
+                    },
+                ) => return expr == b_expr && data_type == b_data_type,
+                (Expression::Wildcard, Expression::Wildcard) => return true,
+                (Expression::Subquery(query), Expression::Subquery(b_query)) => {
+                    return query == b_query
+                }
+                (
+                    Expression::InList {
+                        expr,
+                        list,
+                        negated,
+                    },
+                    Expression::InList {
+                        expr: b_expr,
+                        list: b_list,
+                        negated: b_negated,
+                    },
+                ) => return expr == b_expr && list == b_list && negated == b_negated,
+                (
+                    Expression::IsNull { expr, negated },
+                    Expression::IsNull {
+                        expr: b_expr,
+                        negated: b_negated,
+                    },
+                ) => return expr == b_expr && negated == b_negated,
+                (
+                    Expression::Like {
+                        expr,
+                        pattern,
+                        negated,
+                    },
+                    Expression::Like {
+                        expr: b_expr,
+                        pattern: b_pattern,
+                        negated: b_negated,
+                    },
+                ) => return expr == b_expr && pattern == b_pattern && negated == b_negated,
+                _ => return false,
+            }
+        }
+    }
+}
+
+impl Eq for Expression {}
+
+impl Hash for Expression {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let mut node = self;
+        while let Expression::BinaryOperation {
+            left_operand,
+            operator,
+            right_operand,
+        } = node
+        {
+            std::mem::discriminant(node).hash(state);
+            operator.hash(state);
+            right_operand.hash(state);
+            node = left_operand;
+        }
+        std::mem::discriminant(node).hash(state);
+        match node {
+            Expression::BinaryOperation { .. } => unreachable!("the loop above goes past these"),
+            Expression::UnaryOperation { operator, operand } => {
+                operator.hash(state);
+                operand.hash(state);
+            }
+            Expression::Value(value) => value.hash(state),
+            Expression::Placeholder { text, ordinal } => {
+                text.hash(state);
+                ordinal.hash(state);
+            }
+            Expression::Identifier(ident) => ident.hash(state),
+            Expression::CompoundIdentifier(parts) => parts.hash(state),
+            Expression::Function { name, args } => {
+                name.hash(state);
+                args.hash(state);
+            }
+            Expression::Cast { expr, data_type } => {
+                expr.hash(state);
+                data_type.hash(state);
+            }
+            Expression::Wildcard => {}
+            Expression::Subquery(query) => query.hash(state),
+            Expression::InList {
+                expr,
+                list,
+                negated,
+            } => {
+                expr.hash(state);
+                list.hash(state);
+                negated.hash(state);
+            }
+            Expression::IsNull { expr, negated } => {
+                expr.hash(state);
+                negated.hash(state);
+            }
+            Expression::Like {
+                expr,
+                pattern,
+                negated,
+            } => {
+                expr.hash(state);
+                pattern.hash(state);
+                negated.hash(state);
+            }
+        }
+    }
+}
+
+impl fmt::Display for Expression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expression::BinaryOperation { .. } => {
+                // Walk down the left operands that print without parentheses, write the
+                // innermost one, then each operator and right operand on the way back up
+                let mut spine = vec![];
+                let mut node = self;
+                while let Expression::BinaryOperation {
+                    left_operand,
+                    operator,
+                    right_operand,
+                } = node
+                {
+                    spine.push((operator, right_operand));
+                    let parenthesize = operator.parenthesizes_left(left_operand);
+                    if parenthesize || !matches!(**left_operand, Expression::BinaryOperation { .. })
+                    {
+                        write_operand(f, left_operand, parenthesize)?;
+                        break;
+                    }
+                    node = left_operand;
+                }
+                for (operator, right_operand) in spine.into_iter().rev() {
+                    write!(f, " {} ", operator)?;
+                    // Operators associate to the left, so an equally binding right operand
+                    // keeps its parentheses; a prefix operator already stops before such an
+                    // operator
+                    let precedence = operator.precedence();
+                    let parenthesize = match **right_operand {
+                        Expression::UnaryOperation { .. } => {
+                            right_operand.precedence() < precedence
+                        }
+                        _ => right_operand.precedence() <= precedence,
+                    };
+                    write_operand(f, right_operand, parenthesize)?;
+                }
+                Ok(())
+            }
+            Expression::UnaryOperation { operator, operand } => {
+                // `NOT NOT a` reads back fine, but `- -a` must not become `--a`
+                let parenthesize = match (operator, &**operand) {
+                    (
+                        UnaryOperator::Not,
+                        Expression::UnaryOperation {
+                            operator: UnaryOperator::Not,
+                            ..
+                        },
+                    ) => false,
+                    _ => operand.precedence() <= operator.precedence(),
+                };
+                match operator {
+                    UnaryOperator::Not => write!(f, "NOT ")?,
+                    _ => write!(f, "{}", operator)?,
+                }
+                write_operand(f, operand, parenthesize)
+            }
+            Expression::Value(value) => write!(f, "{}", value),
+            Expression::Placeholder { text, .. } => write!(f, "{}", text),
+            Expression::Identifier(ident) => write!(f, "{}", DisplayName(ident)),
+            Expression::CompoundIdentifier(parts) => {
+                write_separated(f, parts.iter().map(DisplayName), ".")
+            }
+            Expression::Function { name, args } => {
+                write!(f, "{}(", DisplayIdent(name))?;
+                write_separated(f, args, ", ")?;
+                write!(f, ")")
+            }
+            Expression::Cast { expr, data_type } => {
+                write_operand(f, expr, expr.precedence() < self.precedence())?;
+                write!(f, "::{}", data_type)
+            }
+            Expression::Wildcard => write!(f, "*"),
+            Expression::Subquery(query) => write!(f, "({})", query),
+            Expression::InList {
+                expr,
+                list,
+                negated,
+            } => {
+                write_operand(f, expr, expr.precedence() < self.precedence())?;
+                write!(f, " {}IN (", if *negated { "NOT " } else { "" })?;
+                write_separated(f, list, ", ")?;
+                write!(f, ")")
+            }
+            Expression::IsNull { expr, negated } => {
+                write_operand(f, expr, expr.precedence() < self.precedence())?;
+                write!(f, " IS {}NULL", if *negated { "NOT " } else { "" })
+            }
+            Expression::Like {
+                expr,
+                pattern,
+                negated,
+            } => {
+                write_operand(f, expr, expr.precedence() < self.precedence())?;
+                write!(f, " {}LIKE ", if *negated { "NOT " } else { "" })?;
+                write_operand(f, pattern, pattern.precedence() <= self.precedence())
+            }
+        }
+    }
+}
+
+/// Represents a complete SQL statement
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Statement {
+    Query(Box<Query>),
+    Insert {
+        table: ObjectName,
+        columns: Vec<String>,
+        source: InsertSource,
+        on_conflict: Option<OnConflict>,
+        returning: Option<Vec<SelectItem>>,
+    },
+    Update {
+        table: TableReference,
+        assignments: Vec<Assignment>,
+        /// The Postgres `UPDATE ... FROM` extension
+        from: Option<TableWithJoins>,
+        selection: Option<Expression>,
+        returning: Option<Vec<SelectItem>>,
+    },
+    Delete {
+        table: TableReference,
+        /// The Postgres `DELETE ... USING` extension
+        using: Option<TableWithJoins>,
+        selection: Option<Expression>,
+        returning: Option<Vec<SelectItem>>,
+    },
+    CreateTable {
+        temporary: bool,
+        if_not_exists: bool,
+        name: ObjectName,
+        columns: Vec<ColumnDef>,
+        constraints: Vec<TableConstraint>,
+        /// The query of `CREATE TABLE ... AS SELECT`
+        query: Option<Box<Query>>,
+    },
+    CreateView {
+        name: ObjectName,
+        columns: Vec<String>,
+        query: Box<Query>,
+        or_replace: bool,
+        materialized: bool,
+    },
+    CreateIndex {
+        name: String,
+        table: ObjectName,
+        columns: Vec<OrderByItem>,
+        unique: bool,
+        if_not_exists: bool,
+    },
+    AlterTable {
+        name: ObjectName,
+        if_exists: bool,
+        operation: AlterTableOperation,
+    },
+    Drop {
+        object_type: ObjectType,
+        if_exists: bool,
+        names: Vec<ObjectName>,
+        /// The table of a MySQL `DROP INDEX idx ON table`
+        table: Option<ObjectName>,
+        /// `Some(true)` for CASCADE, `Some(false)` for RESTRICT
+        cascade: Option<bool>,
+    },
+    Truncate {
+        tables: Vec<ObjectName>,
+        /// `Some(true)` for RESTART IDENTITY, `Some(false)` for CONTINUE IDENTITY
+        restart_identity: Option<bool>,
+        /// `Some(true)` for CASCADE, `Some(false)` for RESTRICT
+        cascade: Option<bool>,
+    },
+    /// `BEGIN` or `START TRANSACTION`
+    StartTransaction {
+        isolation_level: Option<IsolationLevel>,
+    },
+    Commit,
+    Rollback {
+        savepoint: Option<String>,
+    },
+    Savepoint {
+        name: String,
+    },
+    ReleaseSavepoint {
+        name: String,
+    },
+    Show {
+        full: bool,
+        /// What is being listed, such as `TABLES`, `COLUMNS`, `CREATE TABLE` or a variable
+        /// name, with its words separated by single spaces
+        object: String,
+        /// The object named by `SHOW CREATE TABLE name` and the like
+        name: Option<ObjectName>,
+        from: Option<ObjectName>,
+        filter: Option<ShowFilter>,
+    },
+    Describe {
+        table: ObjectName,
+    },
+    Pragma {
+        name: ObjectName,
+        value: Option<PragmaValue>,
+        /// `true` for the call form `PRAGMA name(value)` rather than `PRAGMA name = value`
+        call: bool,
+    },
+    Merge {
+        target: TableReference,
+        source: TableReference,
+        on: Expression,
+        clauses: Vec<MergeClause>,
+    },
+}
+
+impl fmt::Display for Statement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Statement::Query(query) => write!(f, "{}", query),
+            Statement::Insert {
+                table,
+                columns,
+                source,
+                on_conflict,
+                returning,
+            } => {
+                write!(f, "INSERT INTO {}", table)?;
+                if !columns.is_empty() {
+                    write!(f, " (")?;
+                    write_idents(f, columns, ", ")?;
+                    write!(f, ")")?;
+                }
+                write!(f, " {}", source)?;
+                if let Some(on_conflict) = on_conflict {
+                    write!(f, " {}", on_conflict)?;
+                }
+                write_returning(f, returning)
+            }
+            Statement::Update {
+                table,
+                assignments,
+                from,
+                selection,
+                returning,
+            } => {
+                write!(f, "UPDATE {} SET ", table)?;
+                write_separated(f, assignments, ", ")?;
+                if let Some(from) = from {
+                    write!(f, " FROM {}", from)?;
+                }
+                if let Some(selection) = selection {
+                    write!(f, " WHERE {}", selection)?;
+                }
+                write_returning(f, returning)
+            }
+            Statement::Delete {
+                table,
+                using,
+                selection,
+                returning,
+            } => {
+                write!(f, "DELETE FROM {}", table)?;
+                if let Some(using) = using {
+                    write!(f, " USING {}", using)?;
+                }
+                if let Some(selection) = selection {
+                    write!(f, " WHERE {}", selection)?;
+                }
+                write_returning(f, returning)
+            }
+            Statement::CreateTable {
+                temporary,
+                if_not_exists,
+                name,
+                columns,
+                constraints,
+                query,
+            } => {
+                write!(f, "CREATE ")?;
+                if *temporary {
+                    write!(f, "TEMPORARY ")?;
+                }
+                write!(f, "TABLE ")?;
+                if *if_not_exists {
+                    write!(f, "IF NOT EXISTS ")?;
+                }
+                write!(f, "{}", name)?;
+                if !columns.is_empty() || !constraints.is_empty() || query.is_none() {
+                    write!(f, " (")?;
+                    write_separated(f, columns, ", ")?;
+                    if !columns.is_empty() && !constraints.is_empty() {
+                        write!(f, ", ")?;
+                    }
+                    write_separated(f, constraints, ", ")?;
+                    write!(f, ")")?;
+                }
+                if let Some(query) = query {
+                    write!(f, " AS {}", query)?;
+                }
+                Ok(())
+            }
+            Statement::CreateView {
+                name,
+                columns,
+                query,
+                or_replace,
+                materialized,
+            } => {
+                write!(f, "CREATE ")?;
+                if *or_replace {
+                    write!(f, "OR REPLACE ")?;
+                }
+                if *materialized {
+                    write!(f, "MATERIALIZED ")?;
+                }
+                write!(f, "VIEW {}", name)?;
+                if !columns.is_empty() {
+                    write!(f, " (")?;
+                    write_idents(f, columns, ", ")?;
+                    write!(f, ")")?;
+                }
+                write!(f, " AS {}", query)
+            }
+            Statement::CreateIndex {
+                name,
+                table,
+                columns,
+                unique,
+                if_not_exists,
+            } => {
+                write!(f, "CREATE ")?;
+                if *unique {
+                    write!(f, "UNIQUE ")?;
+                }
+                write!(f, "INDEX ")?;
+                if *if_not_exists {
+                    write!(f, "IF NOT EXISTS ")?;
+                }
+                write!(f, "{} ON {} (", DisplayIdent(name), table)?;
+                write_separated(f, columns, ", ")?;
+                write!(f, ")")
+            }
+            Statement::AlterTable {
+                name,
+                if_exists,
+                operation,
+            } => {
+                write!(f, "ALTER TABLE ")?;
+                if *if_exists {
+                    write!(f, "IF EXISTS ")?;
+                }
+                write!(f, "{} {}", name, operation)
+            }
+            Statement::Drop {
+                object_type,
+                if_exists,
+                names,
+                table,
+                cascade,
+            } => {
+                write!(f, "DROP {} ", object_type)?;
+                if *if_exists {
+                    write!(f, "IF EXISTS ")?;
+                }
+                write_separated(f, names, ", ")?;
+                if let Some(table) = table {
+                    write!(f, " ON {}", table)?;
+                }
+                match cascade {
+                    Some(true) => write!(f, " CASCADE"),
+                    Some(false) => write!(f, " RESTRICT"),
+                    None => Ok(()),
+                }
+            }
+            Statement::Truncate {
+                tables,
+                restart_identity,
+                cascade,
+            } => {
+                write!(f, "TRUNCATE TABLE ")?;
+                write_separated(f, tables, ", ")?;
+                match restart_identity {
+                    Some(true) => write!(f, " RESTART IDENTITY")?,
+                    Some(false) => write!(f, " CONTINUE IDENTITY")?,
+                    None => {}
+                }
+                match cascade {
+                    Some(true) => write!(f, " CASCADE"),
+                    Some(false) => write!(f, " RESTRICT"),
+                    None => Ok(()),
+                }
+            }
+            Statement::StartTransaction { isolation_level } => {
+                write!(f, "START TRANSACTION")?;
+                if let Some(level) = isolation_level {
+                    write!(f, " ISOLATION LEVEL {}", level)?;
+                }
+                Ok(())
+            }
+            Statement::Commit => write!(f, "COMMIT"),
+            Statement::Rollback { savepoint } => {
+                write!(f, "ROLLBACK")?;
+                if let Some(savepoint) = savepoint {
+                    write!(f, " TO SAVEPOINT {}", DisplayIdent(savepoint))?;
+                }
+                Ok(())
+            }
+            Statement::Savepoint { name } => write!(f, "SAVEPOINT {}", DisplayIdent(name)),
+            Statement::ReleaseSavepoint { name } => {
+                write!(f, "RELEASE SAVEPOINT {}", DisplayIdent(name))
+            }
+            Statement::Show {
+                full,
+                object,
+                name,
+                from,
+                filter,
+            } => {
+                write!(f, "SHOW ")?;
+                if *full {
+                    write!(f, "FULL ")?;
+                }
+                write!(f, "{}", object)?;
+                if let Some(name) = name {
+                    write!(f, " {}", name)?;
+                }
+                if let Some(from) = from {
+                    write!(f, " FROM {}", from)?;
+                }
+                if let Some(filter) = filter {
+                    write!(f, " {}", filter)?;
+                }
+                Ok(())
+            }
+            Statement::Describe { table } => write!(f, "DESCRIBE {}", table),
+            Statement::Pragma { name, value, call } => {
+                write!(f, "PRAGMA {}", name)?;
+                match value {
+                    Some(value) if *call => write!(f, "({})", value),
+                    Some(value) => write!(f, " = {}", value),
+                    None => Ok(()),
+                }
+            }
+            Statement::Merge {
+                target,
+                source,
+                on,
+                clauses,
+            } => {
+                write!(f, "MERGE INTO {} USING {} ON {}", target, source, on)?;
+                for clause in clauses {
+                    write!(f, " {}", clause)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Represents one `WHEN [NOT] MATCHED [AND condition] THEN action` branch of MERGE
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MergeClause {
+    pub matched: bool,
+    pub predicate: Option<Expression>,
+    pub action: MergeAction,
+}
+
+impl fmt::Display for MergeClause {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "WHEN ")?;
+        if !self.matched {
+            write!(f, "NOT ")?;
+        }
+        write!(f, "MATCHED")?;
+        if let Some(predicate) = &self.predicate {
+            write!(f, " AND {}", predicate)?;
+        }
+        write!(f, " THEN {}", self.action)
+    }
+}
+
+/// Represents what a MERGE branch does to the target row
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MergeAction {
+    Update {
+        assignments: Vec<Assignment>,
+    },
+    Delete,
+    Insert {
+        columns: Vec<String>,
+        values: Vec<Expression>,
+    },
+    DoNothing,
+}
+
+impl fmt::Display for MergeAction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MergeAction::Update { assignments } => {
+                write!(f, "UPDATE SET ")?;
+                write_separated(f, assignments, ", ")
+            }
+            MergeAction::Delete => write!(f, "DELETE"),
+            MergeAction::Insert { columns, values } => {
+                write!(f, "INSERT ")?;
+                if !columns.is_empty() {
+                    write!(f, "(")?;
+                    write_idents(f, columns, ", ")?;
+                    write!(f, ") ")?;
+                }
+                write!(f, "VALUES (")?;
+                write_separated(f, values, ", ")?;
+                write!(f, ")")
+            }
+            MergeAction::DoNothing => write!(f, "DO NOTHING"),
+        }
+    }
+}
+
+/// Represents the value given to a SQLite PRAGMA
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PragmaValue {
+    Identifier(String),
+    Number(i64),
+    Float(Float),
+    String(String),
+}
+
+impl fmt::Display for PragmaValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            // Keyword values such as ON and FULL are kept as written rather than quoted
+            PragmaValue::Identifier(name) if Keyword::from_str(name).is_ok() => {
+                write!(f, "{}", name)
+            }
+            PragmaValue::Identifier(name) => write!(f, "{}", DisplayIdent(name)),
+            PragmaValue::Number(n) => write!(f, "{}", n),
+            PragmaValue::Float(x) => write!(f, "{}", x),
+            PragmaValue::String(s) => write!(f, "'{}'", s.replace('\'', "''")),
+        }
+    }
+}
+
+/// Represents the filter at the end of a SHOW statement
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ShowFilter {
+    Like(String),
+    Where(Expression),
+}
+
+impl fmt::Display for ShowFilter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ShowFilter::Like(pattern) => write!(f, "LIKE '{}'", pattern.replace('\'', "''")),
+            ShowFilter::Where(expr) => write!(f, "WHERE {}", expr),
+        }
+    }
+}
+
+/// Represents the isolation level requested when a transaction starts
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum IsolationLevel {
+    ReadUncommitted,
+    ReadCommitted,
+    RepeatableRead,
+    Serializable,
+}
+
+impl fmt::Display for IsolationLevel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let level = match self {
+            IsolationLevel::ReadUncommitted => "READ UNCOMMITTED",
+            IsolationLevel::ReadCommitted => "READ COMMITTED",
+            IsolationLevel::RepeatableRead => "REPEATABLE READ",
+            IsolationLevel::Serializable => "SERIALIZABLE",
+        };
+        write!(f, "{}", level)
+    }
+}
+
+/// Represents a possibly qualified object name such as `schema.table`
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ObjectName(pub Vec<Ident>);
+
+impl fmt::Display for ObjectName {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, part) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ".")?;
+            }
+            write!(f, "{}", part)?;
+        }
+        Ok(())
+    }
+}
+
+/// Represents the text of an identifier. Clones share one allocation, and the tokenizer
+/// hands out a single copy of each distinct name, so a name repeated throughout a schema is
+/// stored once. It derefs to `str`, and compares, hashes, prints and serializes as one.
+#[derive(PartialEq, Eq, Hash, PartialOrd, Ord, Clone, Default)]
+pub struct Name(Arc<str>);
+
+impl Name {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Deref for Name {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for Name {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Borrow<str> for Name {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<&str> for Name {
+    fn from(name: &str) -> Self {
+        Name(name.into())
+    }
+}
+
+impl From<String> for Name {
+    fn from(name: String) -> Self {
+        Name(name.into())
+    }
+}
+
+impl From<Name> for String {
+    fn from(name: Name) -> Self {
+        name.0.to_string()
+    }
+}
+
+impl PartialEq<str> for Name {
+    fn eq(&self, other: &str) -> bool {
+        &*self.0 == other
+    }
+}
+
+impl PartialEq<&str> for Name {
+    fn eq(&self, other: &&str) -> bool {
+        &*self.0 == *other
+    }
+}
+
+impl PartialEq<String> for Name {
+    fn eq(&self, other: &String) -> bool {
+        *self.0 == **other
+    }
+}
+
+impl PartialEq<Name> for str {
+    fn eq(&self, other: &Name) -> bool {
+        self == &*other.0
+    }
+}
+
+impl PartialEq<Name> for String {
+    fn eq(&self, other: &Name) -> bool {
+        **self == *other.0
+    }
+}
+
+impl fmt::Debug for Name {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&*self.0, f)
+    }
+}
+
+impl fmt::Display for Name {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Name {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Name {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        <String as serde::Deserialize>::deserialize(deserializer).map(Name::from)
+    }
+}
+
+/// Represents one part of an object name, remembering how it was quoted
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Ident {
+    pub value: Name,
+    /// The quote character, such as `"`, or `None` for a bare name
+    pub quote_style: Option<char>,
+}
+
+impl Ident {
+    /// Creates an unquoted identifier
+    pub fn new(value: impl Into<Name>) -> Self {
+        Ident {
+            value: value.into(),
+            quote_style: None,
+        }
+    }
+}
+
+impl fmt::Display for Ident {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.quote_style {
+            Some(q) => {
+                let escaped = self.value.replace(q, &format!("{}{}", q, q));
+                write!(f, "{}{}{}", q, escaped, q)
+            }
+            None => write!(f, "{}", self.value),
+        }
+    }
+}
+
+/// Formats a plain name as SQL, double-quoting it only when it would not read back unchanged
+pub(crate) struct DisplayIdent<'a>(pub(crate) &'a str);
+
+impl fmt::Display for DisplayIdent<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = self.0;
+        if needs_quotes(name) {
+            write!(f, "\"{}\"", name.replace('"', "\"\""))
+        } else {
+            write!(f, "{}", name)
+        }
+    }
+}
+
+/// Whether `name` must be double-quoted to read back unchanged
+pub(crate) fn needs_quotes(name: &str) -> bool {
+    let bare = name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && match Keyword::from_str(name) {
+            // Non-reserved keywords are accepted as names, but folded to lower case
+            Ok(keyword) => !keyword.is_reserved() && name == name.to_lowercase(),
+            Err(_) => true,
+        };
+    !bare
+}
+
+/// Formats an identifier, keeping its quotes or quoting a bare name that would not read
+/// back unchanged, as for names built programmatically
+pub(crate) struct DisplayName<'a>(pub(crate) &'a Ident);
+
+impl fmt::Display for DisplayName<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0.quote_style {
+            Some(_) => write!(f, "{}", self.0),
+            None => write!(f, "{}", DisplayIdent(&self.0.value)),
+        }
+    }
+}
+
+/// Writes `items` with `separator` between them
+fn write_separated<I>(f: &mut fmt::Formatter, items: I, separator: &str) -> fmt::Result
+where
+    I: IntoIterator,
+    I::Item: fmt::Display,
+{
+    for (i, item) in items.into_iter().enumerate() {
+        if i > 0 {
+            write!(f, "{}", separator)?;
+        }
+        write!(f, "{}", item)?;
+    }
+    Ok(())
+}
+
+/// Writes plain names with `separator` between them, quoting each as needed
+fn write_idents(f: &mut fmt::Formatter, names: &[String], separator: &str) -> fmt::Result {
+    write_separated(f, names.iter().map(|name| DisplayIdent(name)), separator)
+}
+
+/// Writes `operand`, wrapped in parentheses when `parenthesize` is set
+fn write_operand<T: fmt::Display>(
+    f: &mut fmt::Formatter,
+    operand: &T,
+    parenthesize: bool,
+) -> fmt::Result {
+    if parenthesize {
+        write!(f, "({})", operand)
+    } else {
+        write!(f, "{}", operand)
+    }
+}
+
+/// Writes the parenthesized rows of a VALUES list
+fn write_rows(f: &mut fmt::Formatter, rows: &[Vec<Expression>]) -> fmt::Result {
+    for (i, row) in rows.iter().enumerate() {
+        if i > 0 {
+            write!(f, ", ")?;
+        }
+        write!(f, "(")?;
+        write_separated(f, row, ", ")?;
+        write!(f, ")")?;
+    }
+    Ok(())
+}
+
+/// Writes a trailing `RETURNING item, ...` list, if present
+fn write_returning(f: &mut fmt::Formatter, returning: &Option<Vec<SelectItem>>) -> fmt::Result {
+    match returning {
+        Some(items) => {
+            write!(f, " RETURNING ")?;
+            write_separated(f, items, ", ")
+        }
+        None => Ok(()),
+    }
+}
+
+/// Represents the kind of object a DROP statement removes
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ObjectType {
+    Table,
+    Index,
+    View,
+}
+
+impl fmt::Display for ObjectType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", format!("{:?}", self).to_uppercase())
+    }
+}
+
+/// Represents the change made by an ALTER TABLE statement
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AlterTableOperation {
+    AddColumn {
+        if_not_exists: bool,
+        column_def: ColumnDef,
+    },
+    DropColumn {
+        if_exists: bool,
+        column_name: String,
+        cascade: bool,
+    },
+    RenameColumn {
+        old_name: String,
+        new_name: String,
+    },
+    RenameTable {
+        new_name: String,
+    },
+}
+
+impl fmt::Display for AlterTableOperation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AlterTableOperation::AddColumn {
+                if_not_exists,
+                column_def,
+            } => {
+                write!(f, "ADD COLUMN ")?;
+                if *if_not_exists {
+                    write!(f, "IF NOT EXISTS ")?;
+                }
+                write!(f, "{}", column_def)
+            }
+            AlterTableOperation::DropColumn {
+                if_exists,
+                column_name,
+                cascade,
+            } => {
+                write!(f, "DROP COLUMN ")?;
+                if *if_exists {
+                    write!(f, "IF EXISTS ")?;
+                }
+                write!(f, "{}", DisplayIdent(column_name))?;
+                if *cascade {
+                    write!(f, " CASCADE")?;
+                }
+                Ok(())
+            }
+            AlterTableOperation::RenameColumn { old_name, new_name } => write!(
+                f,
+                "RENAME COLUMN {} TO {}",
+                DisplayIdent(old_name),
+                DisplayIdent(new_name)
+            ),
+            AlterTableOperation::RenameTable { new_name } => {
+                write!(f, "RENAME TO {}", DisplayIdent(new_name))
+            }
+        }
+    }
+}
+
+/// Represents the rows inserted by an INSERT statement
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InsertSource {
+    Values(Vec<Vec<Expression>>),
+    Query(Box<Query>),
+}
+
+impl fmt::Display for InsertSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InsertSource::Values(rows) => {
+                write!(f, "VALUES ")?;
+                write_rows(f, rows)
+            }
+            InsertSource::Query(query) => write!(f, "{}", query),
+        }
+    }
+}
+
+/// Represents the `ON CONFLICT` upsert clause of an INSERT
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OnConflict {
+    pub target: Option<ConflictTarget>,
+    pub action: OnConflictAction,
+}
+
+impl fmt::Display for OnConflict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ON CONFLICT ")?;
+        match &self.target {
+            Some(ConflictTarget::Columns(columns)) => {
+                write!(f, "(")?;
+                write_idents(f, columns, ", ")?;
+                write!(f, ") ")?;
+            }
+            Some(ConflictTarget::OnConstraint(name)) => {
+                write!(f, "ON CONSTRAINT {} ", DisplayIdent(name))?;
+            }
+            None => {}
+        }
+        match &self.action {
+            OnConflictAction::DoNothing => write!(f, "DO NOTHING"),
+            OnConflictAction::DoUpdate {
+                assignments,
+                selection,
+            } => {
+                write!(f, "DO UPDATE SET ")?;
+                write_separated(f, assignments, ", ")?;
+                if let Some(selection) = selection {
+                    write!(f, " WHERE {}", selection)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Represents what an ON CONFLICT clause matches against
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ConflictTarget {
+    Columns(Vec<String>),
+    OnConstraint(String),
+}
+
+/// Represents what happens to a conflicting row
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OnConflictAction {
+    DoNothing,
+    DoUpdate {
+        assignments: Vec<Assignment>,
+        selection: Option<Expression>,
+    },
+}
+
+/// Represents `column = value` in a SET list
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Assignment {
+    pub column: Vec<String>,
+    pub value: Expression,
+}
+
+impl fmt::Display for Assignment {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_idents(f, &self.column, ".")?;
+        write!(f, " = {}", self.value)
+    }
+}
+
+/// Represents a column definition in CREATE TABLE
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ColumnDef {
+    pub name: Name,
+    pub data_type: DataType,
+    pub options: Vec<ColumnOption>,
+}
+
+impl fmt::Display for ColumnDef {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", DisplayIdent(&self.name))?;
+        if self.data_type != DataType::Unspecified {
+            write!(f, " {}", self.data_type)?;
+        }
+        for option in &self.options {
+            write!(f, " {}", option)?;
+        }
+        Ok(())
+    }
+}
+
+/// Represents a constraint or default attached to a single column
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ColumnOption {
+    NotNull,
+    Null,
+    Default(Expression),
+    PrimaryKey,
+    Unique,
+}
+
+impl fmt::Display for ColumnOption {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ColumnOption::NotNull => write!(f, "NOT NULL"),
+            ColumnOption::Null => write!(f, "NULL"),
+            ColumnOption::Default(expr) => write!(f, "DEFAULT {}", expr),
+            ColumnOption::PrimaryKey => write!(f, "PRIMARY KEY"),
+            ColumnOption::Unique => write!(f, "UNIQUE"),
+        }
+    }
+}
+
+/// Represents a constraint declared alongside the columns of CREATE TABLE
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TableConstraint {
+    PrimaryKey {
+        name: Option<String>,
+        columns: Vec<String>,
+    },
+    Unique {
+        name: Option<String>,
+        columns: Vec<String>,
+    },
+    ForeignKey {
+        name: Option<String>,
+        columns: Vec<String>,
+        foreign_table: ObjectName,
+        referred_columns: Vec<String>,
+        on_delete: Option<ReferentialAction>,
+        on_update: Option<ReferentialAction>,
+    },
+    Check {
+        name: Option<String>,
+        expr: Expression,
+    },
+}
+
+impl fmt::Display for TableConstraint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            TableConstraint::PrimaryKey { name, .. }
+            | TableConstraint::Unique { name, .. }
+            | TableConstraint::ForeignKey { name, .. }
+            | TableConstraint::Check { name, .. } => name,
+        };
+        if let Some(name) = name {
+            write!(f, "CONSTRAINT {} ", DisplayIdent(name))?;
+        }
+
+        match self {
+            TableConstraint::PrimaryKey { columns, .. } => {
+                write!(f, "PRIMARY KEY (")?;
+                write_idents(f, columns, ", ")?;
+                write!(f, ")")
+            }
+            TableConstraint::Unique { columns, .. } => {
+                write!(f, "UNIQUE (")?;
+                write_idents(f, columns, ", ")?;
+                write!(f, ")")
+            }
+            TableConstraint::ForeignKey {
+                columns,
+                foreign_table,
+                referred_columns,
+                on_delete,
+                on_update,
+                ..
+            } => {
+                write!(f, "FOREIGN KEY (")?;
+                write_idents(f, columns, ", ")?;
+                write!(f, ") REFERENCES {}", foreign_table)?;
+                if !referred_columns.is_empty() {
+                    write!(f, " (")?;
+                    write_idents(f, referred_columns, ", ")?;
+                    write!(f, ")")?;
+                }
+                if let Some(action) = on_delete {
+                    write!(f, " ON DELETE {}", action)?;
+                }
+                if let Some(action) = on_update {
+                    write!(f, " ON UPDATE {}", action)?;
+                }
+                Ok(())
+            }
+            TableConstraint::Check { expr, .. } => write!(f, "CHECK ({})", expr),
+        }
+    }
+}
+
+/// Represents what a foreign key does when the referenced row changes
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ReferentialAction {
+    Cascade,
+    SetNull,
+    SetDefault,
+    Restrict,
+    NoAction,
+}
+
+impl fmt::Display for ReferentialAction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let action = match self {
+            ReferentialAction::Cascade => "CASCADE",
+            ReferentialAction::SetNull => "SET NULL",
+            ReferentialAction::SetDefault => "SET DEFAULT",
+            ReferentialAction::Restrict => "RESTRICT",
+            ReferentialAction::NoAction => "NO ACTION",
+        };
+        write!(f, "{}", action)
+    }
+}
+
+/// Represents a column data type
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DataType {
+    Int,
+    BigInt,
+    SmallInt,
+    Varchar(Option<u64>),
+    Char(Option<u64>),
+    Text,
+    Boolean,
+    Float,
+    Double,
+    /// `DECIMAL(precision, scale)`, also spelled `NUMERIC`
+    Decimal(Option<u64>, Option<u64>),
+    Date,
+    Timestamp,
+    TimestampWithTimeZone,
+    /// An array of the element type, written with a `[]` suffix
+    Array(Box<DataType>),
+    /// A type name this parser does not know about, kept as written
+    Custom(ObjectName),
+    /// No type was written, as in the column list of `CREATE TABLE t (a, b) AS ...`
+    Unspecified,
+}
+
+impl fmt::Display for DataType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DataType::Int => write!(f, "INT"),
+            DataType::BigInt => write!(f, "BIGINT"),
+            DataType::SmallInt => write!(f, "SMALLINT"),
+            DataType::Varchar(None) => write!(f, "VARCHAR"),
+            DataType::Varchar(Some(length)) => write!(f, "VARCHAR({})", length),
+            DataType::Char(None) => write!(f, "CHAR"),
+            DataType::Char(Some(length)) => write!(f, "CHAR({})", length),
+            DataType::Text => write!(f, "TEXT"),
+            DataType::Boolean => write!(f, "BOOLEAN"),
+            DataType::Float => write!(f, "FLOAT"),
+            DataType::Double => write!(f, "DOUBLE PRECISION"),
+            DataType::Decimal(None, _) => write!(f, "DECIMAL"),
+            DataType::Decimal(Some(precision), None) => write!(f, "DECIMAL({})", precision),
+            DataType::Decimal(Some(precision), Some(scale)) => {
+                write!(f, "DECIMAL({}, {})", precision, scale)
+            }
+            DataType::Date => write!(f, "DATE"),
+            DataType::Timestamp => write!(f, "TIMESTAMP"),
+            DataType::TimestampWithTimeZone => write!(f, "TIMESTAMP WITH TIME ZONE"),
+            DataType::Array(element) => write!(f, "{}[]", element),
+            DataType::Custom(name) => write!(f, "{}", name),
+            DataType::Unspecified => Ok(()),
+        }
+    }
+}
+
+/// Represents a complete query: a set expression with its trailing ORDER BY and row limits
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Query {
+    pub with: Option<With>,
+    pub body: SetExpr,
+    pub order_by: Vec<OrderByItem>,
+    /// Row limit from either `LIMIT n` or `FETCH FIRST n ROWS`
+    pub limit: Option<Expression>,
+    pub offset: Option<Expression>,
+    /// Set by `FETCH ... WITH TIES`
+    pub with_ties: bool,
+    /// A trailing `FOR UPDATE` or `FOR SHARE` row locking clause
+    pub locking: Option<LockingClause>,
+}
+
+impl fmt::Display for Query {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(with) = &self.with {
+            write!(f, "{} ", with)?;
+        }
+        write!(f, "{}", self.body)?;
+        if !self.order_by.is_empty() {
+            write!(f, " ORDER BY ")?;
+            write_separated(f, &self.order_by, ", ")?;
+        }
+        if self.with_ties {
+            // WITH TIES can only be spelled with the ANSI FETCH form
+            if let Some(offset) = &self.offset {
+                write!(f, " OFFSET {} ROWS", offset)?;
+            }
+            if let Some(limit) = &self.limit {
+                write!(f, " FETCH FIRST {} ROWS WITH TIES", limit)?;
+            }
+        } else {
+            if let Some(limit) = &self.limit {
+                write!(f, " LIMIT {}", limit)?;
+            }
+            if let Some(offset) = &self.offset {
+                write!(f, " OFFSET {}", offset)?;
+            }
+        }
+        if let Some(locking) = &self.locking {
+            write!(f, " {}", locking)?;
+        }
+        Ok(())
+    }
+}
+
+/// Represents `FOR {UPDATE | SHARE} [OF table, ...] [NOWAIT | SKIP LOCKED]`
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LockingClause {
+    pub strength: LockStrength,
+    pub of: Vec<ObjectName>,
+    pub wait: Option<WaitPolicy>,
+}
+
+impl fmt::Display for LockingClause {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "FOR {}", self.strength)?;
+        if !self.of.is_empty() {
+            write!(f, " OF ")?;
+            write_separated(f, &self.of, ", ")?;
+        }
+        if let Some(wait) = &self.wait {
+            write!(f, " {}", wait)?;
+        }
+        Ok(())
+    }
+}
+
+/// Represents how strongly selected rows are locked
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LockStrength {
+    Update,
+    NoKeyUpdate,
+    Share,
+    KeyShare,
+}
+
+impl fmt::Display for LockStrength {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let strength = match self {
+            LockStrength::Update => "UPDATE",
+            LockStrength::NoKeyUpdate => "NO KEY UPDATE",
+            LockStrength::Share => "SHARE",
+            LockStrength::KeyShare => "KEY SHARE",
+        };
+        write!(f, "{}", strength)
+    }
+}
+
+/// Represents what happens when a selected row is already locked
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WaitPolicy {
+    Nowait,
+    SkipLocked,
+}
+
+impl fmt::Display for WaitPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WaitPolicy::Nowait => write!(f, "NOWAIT"),
+            WaitPolicy::SkipLocked => write!(f, "SKIP LOCKED"),
+        }
+    }
+}
+
+/// Represents a `WITH [RECURSIVE]` clause
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct With {
+    pub recursive: bool,
+    pub ctes: Vec<Cte>,
+}
+
+impl fmt::Display for With {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "WITH ")?;
+        if self.recursive {
+            write!(f, "RECURSIVE ")?;
+        }
+        write_separated(f, &self.ctes, ", ")
+    }
+}
+
+/// Represents a single common table expression, `name [(columns)] AS (query)`
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Cte {
+    pub name: String,
+    pub columns: Vec<String>,
+    pub query: Box<Query>,
+}
+
+impl fmt::Display for Cte {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", DisplayIdent(&self.name))?;
+        if !self.columns.is_empty() {
+            write!(f, " (")?;
+            write_idents(f, &self.columns, ", ")?;
+            write!(f, ")")?;
+        }
+        write!(f, " AS ({})", self.query)
+    }
+}
+
+/// Represents the body of a query, possibly combining several SELECTs
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SetExpr {
+    Select(Box<SelectStatement>),
+    /// A parenthesized query used as an operand, as in `(SELECT ...) UNION (SELECT ...)`
+    Query(Box<Query>),
+    SetOperation {
+        op: SetOperator,
+        all: bool,
+        left: Box<SetExpr>,
+        right: Box<SetExpr>,
+    },
+    /// `VALUES (...), (...)`; rows may differ in length
+    Values(Vec<Vec<Expression>>),
+}
+
+impl SetExpr {
+    /// Returns how tightly this operand holds together; INTERSECT binds tighter than UNION and EXCEPT
+    pub(crate) fn precedence(&self) -> u8 {
+        match self {
+            SetExpr::SetOperation {
+                op: SetOperator::Intersect,
+                ..
+            } => 2,
+            SetExpr::SetOperation { .. } => 1,
+            _ => u8::MAX,
+        }
+    }
+}
+
+impl fmt::Display for SetExpr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SetExpr::Select(select) => write!(f, "{}", select),
+            SetExpr::Query(query) => write!(f, "({})", query),
+            SetExpr::SetOperation {
+                op,
+                all,
+                left,
+                right,
+            } => {
+                let precedence = self.precedence();
+                write_operand(f, left, left.precedence() < precedence)?;
+                write!(f, " {}", op)?;
+                if *all {
+                    write!(f, " ALL")?;
+                }
+                write!(f, " ")?;
+                write_operand(f, right, right.precedence() <= precedence)
+            }
+            SetExpr::Values(rows) => {
+                write!(f, "VALUES ")?;
+                write_rows(f, rows)
+            }
+        }
+    }
+}
+
+/// Represents UNION, INTERSECT and EXCEPT
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SetOperator {
+    Union,
+    Intersect,
+    Except,
+}
+
+impl fmt::Display for SetOperator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", format!("{:?}", self).to_uppercase())
+    }
+}
+
+/// Represents a SELECT statement
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SelectStatement {
+    pub distinct: Option<Distinct>,
+    /// SQL Server's `TOP n`, only parsed when the dialect allows it
+    pub top: Option<Top>,
+    pub projection: Vec<SelectItem>,
+    pub from: Option<TableWithJoins>,
+    pub selection: Option<Expression>,
+    pub group_by: Vec<GroupByExpr>,
+    pub having: Option<Expression>,
+}
+
+impl fmt::Display for SelectStatement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SELECT")?;
+        if let Some(distinct) = &self.distinct {
+            write!(f, " {}", distinct)?;
+        }
+        if let Some(top) = &self.top {
+            write!(f, " {}", top)?;
+        }
+        write!(f, " ")?;
+        write_separated(f, &self.projection, ", ")?;
+        if let Some(from) = &self.from {
+            write!(f, " FROM {}", from)?;
+        }
+        if let Some(selection) = &self.selection {
+            write!(f, " WHERE {}", selection)?;
+        }
+        if !self.group_by.is_empty() {
+            write!(f, " GROUP BY ")?;
+            write_separated(f, &self.group_by, ", ")?;
+        }
+        if let Some(having) = &self.having {
+            write!(f, " HAVING {}", having)?;
+        }
+        Ok(())
+    }
+}
+
+/// Represents `TOP n [PERCENT] [WITH TIES]`
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Top {
+    pub quantity: Expression,
+    pub percent: bool,
+    pub with_ties: bool,
+}
+
+impl fmt::Display for Top {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Anything but a bare number or placeholder must be parenthesized after TOP
+        match &self.quantity {
+            quantity @ (Expression::Value(Value::Number(_)) | Expression::Placeholder { .. }) => {
+                write!(f, "TOP {}", quantity)?
+            }
+            quantity => write!(f, "TOP ({})", quantity)?,
+        }
+        if self.percent {
+            write!(f, " PERCENT")?;
+        }
+        if self.with_ties {
+            write!(f, " WITH TIES")?;
+        }
+        Ok(())
+    }
+}
+
+/// Represents the set quantifier written between SELECT and the projection list
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Distinct {
+    All,
+    Distinct,
+    DistinctOn(Vec<Expression>),
+}
+
+impl fmt::Display for Distinct {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Distinct::All => write!(f, "ALL"),
+            Distinct::Distinct => write!(f, "DISTINCT"),
+            Distinct::DistinctOn(exprs) => {
+                write!(f, "DISTINCT ON (")?;
+                write_separated(f, exprs, ", ")?;
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+/// Represents a single item in the projection list of a SELECT
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SelectItem {
+    Expression(Expression),
+    ExprWithAlias { expr: Expression, alias: Ident },
+    Wildcard,
+    QualifiedWildcard(Vec<String>),
+}
+
+impl fmt::Display for SelectItem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SelectItem::Expression(expr) => write!(f, "{}", expr),
+            SelectItem::ExprWithAlias { expr, alias } => {
+                write!(f, "{} AS {}", expr, DisplayName(alias))
+            }
+            SelectItem::Wildcard => write!(f, "*"),
+            SelectItem::QualifiedWildcard(qualifier) => {
+                write_idents(f, qualifier, ".")?;
+                write!(f, ".*")
+            }
+        }
+    }
+}
+
+/// Represents a single GROUP BY item; each inner `Vec` of ROLLUP, CUBE and GROUPING SETS
+/// is one parenthesized column group, empty for the grand-total set `()`
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GroupByExpr {
+    Expression(Expression),
+    Rollup(Vec<Vec<Expression>>),
+    Cube(Vec<Vec<Expression>>),
+    GroupingSets(Vec<Vec<Expression>>),
+}
+
+impl fmt::Display for GroupByExpr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (keyword, sets) = match self {
+            GroupByExpr::Expression(expr) => return write!(f, "{}", expr),
+            GroupByExpr::Rollup(sets) => ("ROLLUP", sets),
+            GroupByExpr::Cube(sets) => ("CUBE", sets),
+            GroupByExpr::GroupingSets(sets) => ("GROUPING SETS", sets),
+        };
+        write!(f, "{} (", keyword)?;
+        for (i, set) in sets.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            if set.len() == 1 {
+                write!(f, "{}", set[0])?;
+            } else {
+                write!(f, "(")?;
+                write_separated(f, set, ", ")?;
+                write!(f, ")")?;
+            }
+        }
+        write!(f, ")")
+    }
+}
+
+/// Represents a single ORDER BY key; `asc` and `nulls_first` are `None` when not written.
+/// Positional keys such as `ORDER BY 2` are kept as `Value::Number` for callers to resolve.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OrderByItem {
+    pub expr: Expression,
+    pub asc: Option<bool>,
+    pub nulls_first: Option<bool>,
+}
+
+impl fmt::Display for OrderByItem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.expr)?;
+        match self.asc {
+            Some(true) => write!(f, " ASC")?,
+            Some(false) => write!(f, " DESC")?,
+            None => {}
+        }
+        match self.nulls_first {
+            Some(true) => write!(f, " NULLS FIRST"),
+            Some(false) => write!(f, " NULLS LAST"),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Represents the FROM clause: a table factor followed by joins applied left to right
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TableWithJoins {
+    pub relation: TableReference,
+    pub joins: Vec<Join>,
+}
+
+impl fmt::Display for TableWithJoins {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.relation)?;
+        for join in &self.joins {
+            match join.join_type {
+                JoinType::Implicit => write!(f, ", {}", join)?,
+                _ => write!(f, " {}", join)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Represents a single join onto the tables to its left
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Join {
+    pub relation: TableReference,
+    pub join_type: JoinType,
+    pub constraint: JoinConstraint,
+}
+
+impl fmt::Display for Join {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.constraint == JoinConstraint::Natural {
+            write!(f, "NATURAL ")?;
+        }
+        let keyword = match self.join_type {
+            JoinType::Inner => "JOIN ",
+            JoinType::LeftOuter => "LEFT JOIN ",
+            JoinType::RightOuter => "RIGHT JOIN ",
+            JoinType::FullOuter => "FULL JOIN ",
+            JoinType::Cross => "CROSS JOIN ",
+            // The comma is written by the enclosing FROM list
+            JoinType::Implicit => "",
+        };
+        write!(f, "{}{}", keyword, self.relation)?;
+        match &self.constraint {
+            JoinConstraint::On(expr) => write!(f, " ON {}", expr),
+            JoinConstraint::Using(columns) => {
+                write!(f, " USING (")?;
+                write_idents(f, columns, ", ")?;
+                write!(f, ")")
+            }
+            JoinConstraint::Natural | JoinConstraint::None => Ok(()),
+        }
+    }
+}
+
+/// Represents the kind of join
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum JoinType {
+    Inner,
+    LeftOuter,
+    RightOuter,
+    FullOuter,
+    Cross,
+    /// A table listed after a comma, as in `FROM a, b`
+    Implicit,
+}
+
+/// Represents the condition a join matches rows on
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum JoinConstraint {
+    On(Expression),
+    Using(Vec<String>),
+    /// A NATURAL join, matching on all identically named columns
+    Natural,
+    /// No condition, as for CROSS JOIN and comma-separated tables
+    None,
+}
+
+/// Represents a table referenced in a FROM clause
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TableReference {
+    Table {
+        name: ObjectName,
+        alias: Option<TableAlias>,
+    },
+    /// A parenthesized subquery; the alias is optional here even though most dialects require it
+    Derived {
+        subquery: Box<Query>,
+        alias: Option<TableAlias>,
+    },
+    /// A set-returning function such as `generate_series(1, 10)`
+    Function {
+        name: ObjectName,
+        args: Vec<Expression>,
+        alias: Option<TableAlias>,
+    },
+}
+
+impl fmt::Display for TableReference {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let alias = match self {
+            TableReference::Table { name, alias } => {
+                write!(f, "{}", name)?;
+                alias
+            }
+            TableReference::Derived { subquery, alias } => {
+                write!(f, "({})", subquery)?;
+                alias
+            }
+            TableReference::Function { name, args, alias } => {
+                write!(f, "{}(", name)?;
+                write_separated(f, args, ", ")?;
+                write!(f, ")")?;
+                alias
+            }
+        };
+        match alias {
+            Some(alias) => write!(f, " {}", alias),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Represents `[AS] name [(column, ...)]` after a table in the FROM clause
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TableAlias {
+    pub name: Ident,
+    pub columns: Vec<Ident>,
+}
+
+impl fmt::Display for TableAlias {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "AS {}", DisplayName(&self.name))?;
+        if !self.columns.is_empty() {
+            write!(f, " (")?;
+            write_separated(f, self.columns.iter().map(DisplayName), ", ")?;
+            write!(f, ")")?;
+        }
+        Ok(())
+    }
+}
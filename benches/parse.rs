@@ -0,0 +1,105 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rust_sql_parser::parser::PrattParser;
+use rust_sql_parser::{parse_statement, parse_statements};
+
+const TERMS: usize = 100_000;
+
+fn or_chain(terms: usize) -> String {
+    let mut sql = String::from("SELECT a FROM t WHERE x0 = 0");
+    for i in 1..terms {
+        sql.push_str(&format!(" OR x{} = {}", i, i));
+    }
+    sql
+}
+
+fn in_list(elements: usize) -> String {
+    let list: Vec<String> = (0..elements).map(|i| i.to_string()).collect();
+    format!("SELECT a FROM t WHERE x IN ({})", list.join(", "))
+}
+
+fn long_or_chain(c: &mut Criterion) {
+    let sql = or_chain(TERMS);
+    c.bench_function("parse 100k-term OR chain", |b| {
+        b.iter(|| parse_statement(black_box(&sql)).unwrap())
+    });
+    let statement = parse_statement(&sql).unwrap();
+    c.bench_function("print 100k-term OR chain", |b| {
+        b.iter(|| black_box(&statement).to_string())
+    });
+}
+
+fn long_in_list(c: &mut Criterion) {
+    let sql = in_list(TERMS);
+    c.bench_function("parse 100k-element IN list", |b| {
+        b.iter(|| parse_statement(black_box(&sql)).unwrap())
+    });
+    let statement = parse_statement(&sql).unwrap();
+    c.bench_function("print 100k-element IN list", |b| {
+        b.iter(|| black_box(&statement).to_string())
+    });
+}
+
+/// Short queries like those an application sends, each parsed once
+fn short_queries() -> Vec<String> {
+    (0..1_000)
+        .map(|i| {
+            format!(
+                "SELECT id, name FROM users WHERE id = {} AND status = 'active' ORDER BY name",
+                i
+            )
+        })
+        .collect()
+}
+
+fn fresh_and_reused_parsers(c: &mut Criterion) {
+    let queries = short_queries();
+    c.bench_function("parse 1k short queries with a new parser each", |b| {
+        b.iter(|| {
+            for sql in &queries {
+                black_box(PrattParser::new(black_box(sql)).parse_statement().unwrap());
+            }
+        })
+    });
+    c.bench_function("parse 1k short queries with one reset parser", |b| {
+        let mut parser = PrattParser::new("");
+        b.iter(|| {
+            for sql in &queries {
+                parser.reset(black_box(sql));
+                black_box(parser.parse_statement().unwrap());
+            }
+        })
+    });
+}
+
+/// A schema of wide tables that all use the same column names, which the tokenizer stores
+/// once each however often they repeat
+fn wide_tables(tables: usize, columns: usize) -> String {
+    let mut sql = String::new();
+    for table in 0..tables {
+        let definitions: Vec<String> = (0..columns)
+            .map(|column| format!("column_{} VARCHAR(20) NOT NULL", column))
+            .collect();
+        sql.push_str(&format!(
+            "CREATE TABLE table_{} ({});\n",
+            table,
+            definitions.join(", ")
+        ));
+    }
+    sql
+}
+
+fn repeated_names(c: &mut Criterion) {
+    let sql = wide_tables(200, 100);
+    c.bench_function("parse 200 CREATE TABLEs of 100 shared column names", |b| {
+        b.iter(|| parse_statements(black_box(&sql)).unwrap())
+    });
+}
+
+criterion_group!(
+    benches,
+    long_or_chain,
+    long_in_list,
+    fresh_and_reused_parsers,
+    repeated_names
+);
+criterion_main!(benches);
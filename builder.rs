@@ -0,0 +1,345 @@
+use std::ops::{Add, Div, Mul, Neg, Not, Sub};
+
+use crate::ast::{
+    BinaryOperator, DataType, Distinct, Expression, Float, GroupByExpr, Ident, Join,
+    JoinConstraint, JoinType, ObjectName, OrderByItem, Query, SelectItem, SelectStatement, SetExpr,
+    Statement, TableReference, TableWithJoins, UnaryOperator, Value,
+};
+
+/// Returns a column reference; a dotted name such as `"t.a"` becomes a qualified reference
+pub fn col(name: &str) -> Expression {
+    let mut parts: Vec<Ident> = name.split('.').map(Ident::new).collect();
+    if parts.len() == 1 {
+        Expression::Identifier(parts.remove(0))
+    } else {
+        Expression::CompoundIdentifier(parts)
+    }
+}
+
+/// Returns a literal; accepts numbers, strings and booleans
+pub fn lit(value: impl Into<Expression>) -> Expression {
+    value.into()
+}
+
+/// Returns the NULL literal
+pub fn null() -> Expression {
+    Expression::Value(Value::Null)
+}
+
+/// Returns a call to the function `name`
+pub fn func(name: &str, args: Vec<Expression>) -> Expression {
+    Expression::Function {
+        name: name.to_string(),
+        args,
+    }
+}
+
+impl From<u64> for Expression {
+    fn from(n: u64) -> Self {
+        Expression::Value(Value::Number(n))
+    }
+}
+
+impl From<f64> for Expression {
+    fn from(x: f64) -> Self {
+        Expression::Value(Value::Float(Float(x)))
+    }
+}
+
+impl From<&str> for Expression {
+    fn from(s: &str) -> Self {
+        Expression::Value(Value::SingleQuotedString(s.to_string()))
+    }
+}
+
+impl From<String> for Expression {
+    fn from(s: String) -> Self {
+        Expression::Value(Value::SingleQuotedString(s))
+    }
+}
+
+impl From<bool> for Expression {
+    fn from(b: bool) -> Self {
+        Expression::Value(Value::Boolean(b))
+    }
+}
+
+impl From<&str> for ObjectName {
+    /// Splits a dotted name such as `"schema.table"` into unquoted parts
+    fn from(name: &str) -> Self {
+        ObjectName(name.split('.').map(Ident::new).collect())
+    }
+}
+
+impl Expression {
+    fn binary(self, operator: BinaryOperator, right: Expression) -> Expression {
+        Expression::BinaryOperation {
+            left_operand: Box::new(self),
+            operator,
+            right_operand: Box::new(right),
+        }
+    }
+
+    fn unary(self, operator: UnaryOperator) -> Expression {
+        Expression::UnaryOperation {
+            operator,
+            operand: Box::new(self),
+        }
+    }
+
+    pub fn eq(self, right: Expression) -> Expression {
+        self.binary(BinaryOperator::Equal, right)
+    }
+
+    pub fn not_eq(self, right: Expression) -> Expression {
+        self.binary(BinaryOperator::NotEqual, right)
+    }
+
+    pub fn lt(self, right: Expression) -> Expression {
+        self.binary(BinaryOperator::LessThan, right)
+    }
+
+    pub fn lt_eq(self, right: Expression) -> Expression {
+        self.binary(BinaryOperator::LessThanOrEqual, right)
+    }
+
+    pub fn gt(self, right: Expression) -> Expression {
+        self.binary(BinaryOperator::GreaterThan, right)
+    }
+
+    pub fn gt_eq(self, right: Expression) -> Expression {
+        self.binary(BinaryOperator::GreaterThanOrEqual, right)
+    }
+
+    pub fn and(self, right: Expression) -> Expression {
+        self.binary(BinaryOperator::And, right)
+    }
+
+    pub fn or(self, right: Expression) -> Expression {
+        self.binary(BinaryOperator::Or, right)
+    }
+
+    pub fn concat(self, right: Expression) -> Expression {
+        self.binary(BinaryOperator::Concat, right)
+    }
+
+    /// Returns `self::data_type`
+    pub fn cast(self, data_type: DataType) -> Expression {
+        Expression::Cast {
+            expr: Box::new(self),
+            data_type,
+        }
+    }
+}
+
+impl Add for Expression {
+    type Output = Expression;
+
+    fn add(self, right: Expression) -> Expression {
+        self.binary(BinaryOperator::Plus, right)
+    }
+}
+
+impl Sub for Expression {
+    type Output = Expression;
+
+    fn sub(self, right: Expression) -> Expression {
+        self.binary(BinaryOperator::Minus, right)
+    }
+}
+
+impl Mul for Expression {
+    type Output = Expression;
+
+    fn mul(self, right: Expression) -> Expression {
+        self.binary(BinaryOperator::Multiply, right)
+    }
+}
+
+impl Div for Expression {
+    type Output = Expression;
+
+    fn div(self, right: Expression) -> Expression {
+        self.binary(BinaryOperator::Divide, right)
+    }
+}
+
+impl Neg for Expression {
+    type Output = Expression;
+
+    fn neg(self) -> Expression {
+        self.unary(UnaryOperator::Minus)
+    }
+}
+
+impl Not for Expression {
+    type Output = Expression;
+
+    fn not(self) -> Expression {
+        self.unary(UnaryOperator::Not)
+    }
+}
+
+/// Assembles a single SELECT query clause by clause, producing the same AST the parser does
+#[derive(Debug, Clone, Default)]
+pub struct SelectBuilder {
+    distinct: bool,
+    projection: Vec<SelectItem>,
+    from: Option<TableWithJoins>,
+    selection: Option<Expression>,
+    group_by: Vec<GroupByExpr>,
+    having: Option<Expression>,
+    order_by: Vec<OrderByItem>,
+    limit: Option<Expression>,
+    offset: Option<Expression>,
+}
+
+impl SelectBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn distinct(mut self) -> Self {
+        self.distinct = true;
+        self
+    }
+
+    pub fn column(mut self, expr: Expression) -> Self {
+        self.projection.push(SelectItem::Expression(expr));
+        self
+    }
+
+    pub fn column_as(mut self, expr: Expression, alias: &str) -> Self {
+        self.projection.push(SelectItem::ExprWithAlias {
+            expr,
+            alias: Ident::new(alias),
+        });
+        self
+    }
+
+    /// Adds `*` to the projection
+    pub fn wildcard(mut self) -> Self {
+        self.projection.push(SelectItem::Wildcard);
+        self
+    }
+
+    /// Sets the first table of the FROM clause, replacing any joins added so far
+    pub fn from(mut self, table: &str) -> Self {
+        self.from = Some(TableWithJoins {
+            relation: table_reference(table),
+            joins: vec![],
+        });
+        self
+    }
+
+    /// Adds `JOIN table ON condition`; `from` must be called first
+    pub fn join(self, table: &str, on: Expression) -> Self {
+        self.add_join(JoinType::Inner, table, JoinConstraint::On(on))
+    }
+
+    /// Adds `LEFT JOIN table ON condition`; `from` must be called first
+    pub fn left_join(self, table: &str, on: Expression) -> Self {
+        self.add_join(JoinType::LeftOuter, table, JoinConstraint::On(on))
+    }
+
+    fn add_join(mut self, join_type: JoinType, table: &str, constraint: JoinConstraint) -> Self {
+        let from = self
+            .from
+            .as_mut()
+            .expect("SelectBuilder::from must be called before adding a join");
+        from.joins.push(Join {
+            relation: table_reference(table),
+            join_type,
+            constraint,
+        });
+        self
+    }
+
+    /// Adds a WHERE condition, combined with AND when one is already set
+    pub fn filter(mut self, condition: Expression) -> Self {
+        self.selection = Some(match self.selection.take() {
+            Some(existing) => existing.and(condition),
+            None => condition,
+        });
+        self
+    }
+
+    pub fn group_by(mut self, expr: Expression) -> Self {
+        self.group_by.push(GroupByExpr::Expression(expr));
+        self
+    }
+
+    /// Adds a HAVING condition, combined with AND when one is already set
+    pub fn having(mut self, condition: Expression) -> Self {
+        self.having = Some(match self.having.take() {
+            Some(existing) => existing.and(condition),
+            None => condition,
+        });
+        self
+    }
+
+    pub fn order_by(self, expr: Expression) -> Self {
+        self.add_order_by(expr, None)
+    }
+
+    pub fn order_by_asc(self, expr: Expression) -> Self {
+        self.add_order_by(expr, Some(true))
+    }
+
+    pub fn order_by_desc(self, expr: Expression) -> Self {
+        self.add_order_by(expr, Some(false))
+    }
+
+    fn add_order_by(mut self, expr: Expression, asc: Option<bool>) -> Self {
+        self.order_by.push(OrderByItem {
+            expr,
+            asc,
+            nulls_first: None,
+        });
+        self
+    }
+
+    pub fn limit(mut self, limit: u64) -> Self {
+        self.limit = Some(Expression::Value(Value::Number(limit)));
+        self
+    }
+
+    pub fn offset(mut self, offset: u64) -> Self {
+        self.offset = Some(Expression::Value(Value::Number(offset)));
+        self
+    }
+
+    pub fn build(self) -> Query {
+        let select = SelectStatement {
+            distinct: self.distinct.then_some(Distinct::Distinct),
+            top: None,
+            projection: self.projection,
+            from: self.from,
+            selection: self.selection,
+            group_by: self.group_by,
+            having: self.having,
+        };
+
+        Query {
+            with: None,
+            body: SetExpr::Select(Box::new(select)),
+            order_by: self.order_by,
+            limit: self.limit,
+            offset: self.offset,
+            with_ties: false,
+            locking: None,
+        }
+    }
+
+    /// Builds the query wrapped as a statement, as `PrattParser::parse_statement` returns it
+    pub fn build_statement(self) -> Statement {
+        Statement::Query(Box::new(self.build()))
+    }
+}
+
+fn table_reference(table: &str) -> TableReference {
+    TableReference::Table {
+        name: ObjectName::from(table),
+        alias: None,
+    }
+}
@@ -0,0 +1,791 @@
+//! Conversions into the AST of the `sqlparser` crate, enabled by the `sqlparser-compat`
+//! feature. Each node maps onto its `sqlparser` counterpart field by field, so the result is
+//! what `sqlparser` produces for this crate's printed SQL, including the `Nested` wrappers
+//! for the parentheses that printing adds. Queries, INSERT, UPDATE and DELETE convert;
+//! other statements, and the few constructs `sqlparser` has no node for, are a
+//! `ConversionError`.
+
+use sqlparser::ast as sql;
+
+use crate::ast::{
+    needs_quotes, Assignment, BinaryOperator, ConflictTarget, Cte, DataType, Distinct, Expression,
+    GroupByExpr, Ident, InsertSource, Join, JoinConstraint, JoinType, LockStrength, LockingClause,
+    ObjectName, OnConflict, OnConflictAction, OrderByItem, Query, SelectItem, SelectStatement,
+    SetExpr, SetOperator, Statement, TableAlias, TableReference, TableWithJoins, Top,
+    UnaryOperator, Value, WaitPolicy, With,
+};
+use crate::error::ConversionError;
+
+impl TryFrom<Expression> for sql::Expr {
+    type Error = ConversionError;
+
+    fn try_from(expr: Expression) -> Result<Self, Self::Error> {
+        sql::Expr::try_from(&expr)
+    }
+}
+
+impl TryFrom<&Expression> for sql::Expr {
+    type Error = ConversionError;
+
+    fn try_from(expr: &Expression) -> Result<Self, Self::Error> {
+        if let Expression::Wildcard = expr {
+            return Err(ConversionError::Unsupported(
+                "a bare * is only valid as a select item or function argument".into(),
+            ));
+        }
+        expression(expr)
+    }
+}
+
+impl TryFrom<Statement> for sql::Statement {
+    type Error = ConversionError;
+
+    fn try_from(statement: Statement) -> Result<Self, Self::Error> {
+        sql::Statement::try_from(&statement)
+    }
+}
+
+impl TryFrom<&Statement> for sql::Statement {
+    type Error = ConversionError;
+
+    fn try_from(statement: &Statement) -> Result<Self, Self::Error> {
+        Ok(match statement {
+            Statement::Query(query) => sql::Statement::Query(Box::new(self::query(query)?)),
+            Statement::Insert {
+                table,
+                columns,
+                source,
+                on_conflict,
+                returning,
+            } => sql::Statement::Insert(sql::Insert {
+                or: None,
+                ignore: false,
+                into: true,
+                table_name: object_name(table),
+                table_alias: None,
+                columns: columns.iter().map(|column| name(column)).collect(),
+                overwrite: false,
+                source: Some(Box::new(match source {
+                    InsertSource::Values(rows) => body_query(values(rows)?),
+                    InsertSource::Query(query) => self::query(query)?,
+                })),
+                partitioned: None,
+                after_columns: vec![],
+                table: false,
+                on: on_conflict
+                    .as_ref()
+                    .map(|on_conflict| {
+                        self::on_conflict(on_conflict).map(sql::OnInsert::OnConflict)
+                    })
+                    .transpose()?,
+                returning: returning.as_deref().map(select_items).transpose()?,
+                replace_into: false,
+                priority: None,
+                insert_alias: None,
+            }),
+            Statement::Update {
+                table,
+                assignments,
+                from,
+                selection,
+                returning,
+            } => sql::Statement::Update {
+                table: sql::TableWithJoins {
+                    relation: table_factor(table)?,
+                    joins: vec![],
+                },
+                assignments: assignments
+                    .iter()
+                    .map(assignment)
+                    .collect::<Result<_, _>>()?,
+                from: match from {
+                    Some(from) => Some(single_from(from)?),
+                    None => None,
+                },
+                selection: selection.as_ref().map(expression).transpose()?,
+                returning: returning.as_deref().map(select_items).transpose()?,
+            },
+            Statement::Delete {
+                table,
+                using,
+                selection,
+                returning,
+            } => sql::Statement::Delete(sql::Delete {
+                tables: vec![],
+                from: sql::FromTable::WithFromKeyword(vec![sql::TableWithJoins {
+                    relation: table_factor(table)?,
+                    joins: vec![],
+                }]),
+                using: using.as_ref().map(from_list).transpose()?,
+                selection: selection.as_ref().map(expression).transpose()?,
+                returning: returning.as_deref().map(select_items).transpose()?,
+                order_by: vec![],
+                limit: None,
+            }),
+            other => {
+                return Err(ConversionError::Unsupported(format!(
+                    "only queries, INSERT, UPDATE and DELETE are converted, not {}",
+                    other
+                )))
+            }
+        })
+    }
+}
+
+impl TryFrom<Query> for sql::Query {
+    type Error = ConversionError;
+
+    fn try_from(query: Query) -> Result<Self, Self::Error> {
+        sql::Query::try_from(&query)
+    }
+}
+
+impl TryFrom<&Query> for sql::Query {
+    type Error = ConversionError;
+
+    fn try_from(query: &Query) -> Result<Self, Self::Error> {
+        self::query(query)
+    }
+}
+
+impl TryFrom<SelectStatement> for sql::Select {
+    type Error = ConversionError;
+
+    fn try_from(select: SelectStatement) -> Result<Self, Self::Error> {
+        sql::Select::try_from(&select)
+    }
+}
+
+impl TryFrom<&SelectStatement> for sql::Select {
+    type Error = ConversionError;
+
+    fn try_from(select: &SelectStatement) -> Result<Self, Self::Error> {
+        self::select(select)
+    }
+}
+
+impl TryFrom<TableReference> for sql::TableFactor {
+    type Error = ConversionError;
+
+    fn try_from(table: TableReference) -> Result<Self, Self::Error> {
+        sql::TableFactor::try_from(&table)
+    }
+}
+
+impl TryFrom<&TableReference> for sql::TableFactor {
+    type Error = ConversionError;
+
+    fn try_from(table: &TableReference) -> Result<Self, Self::Error> {
+        table_factor(table)
+    }
+}
+
+fn unsupported<T>(what: &str) -> Result<T, ConversionError> {
+    Err(ConversionError::Unsupported(format!(
+        "sqlparser has no counterpart for {}",
+        what
+    )))
+}
+
+/// Converts a plain name, quoting it as printing would
+fn name(name: &str) -> sql::Ident {
+    sql::Ident {
+        value: name.to_string(),
+        quote_style: needs_quotes(name).then_some('"'),
+    }
+}
+
+/// Converts an identifier, keeping its quotes or quoting a bare name as printing would
+fn ident(ident: &Ident) -> sql::Ident {
+    match ident.quote_style {
+        Some(quote) => sql::Ident::with_quote(quote, ident.value.as_str()),
+        None => name(&ident.value),
+    }
+}
+
+fn object_name(name: &ObjectName) -> sql::ObjectName {
+    sql::ObjectName(name.0.iter().map(ident).collect())
+}
+
+fn expressions(exprs: &[Expression]) -> Result<Vec<sql::Expr>, ConversionError> {
+    exprs.iter().map(expression).collect()
+}
+
+fn expression(expr: &Expression) -> Result<sql::Expr, ConversionError> {
+    let precedence = expr.precedence();
+    Ok(match expr {
+        Expression::BinaryOperation {
+            left_operand,
+            operator,
+            right_operand,
+        } => {
+            // Parenthesized as `Display` does it; operators associate to the left
+            let right_parenthesized = match **right_operand {
+                Expression::UnaryOperation { .. } => right_operand.precedence() < precedence,
+                _ => right_operand.precedence() <= precedence,
+            };
+            sql::Expr::BinaryOp {
+                left: Box::new(operand(
+                    left_operand,
+                    left_operand.precedence() < precedence,
+                )?),
+                op: binary_operator(operator),
+                right: Box::new(operand(right_operand, right_parenthesized)?),
+            }
+        }
+        Expression::UnaryOperation {
+            operator,
+            operand: inner,
+        } => {
+            let parenthesized = match (operator, &**inner) {
+                (
+                    UnaryOperator::Not,
+                    Expression::UnaryOperation {
+                        operator: UnaryOperator::Not,
+                        ..
+                    },
+                ) => false,
+                _ => inner.precedence() <= operator.precedence(),
+            };
+            sql::Expr::UnaryOp {
+                op: match operator {
+                    UnaryOperator::Not => sql::UnaryOperator::Not,
+                    UnaryOperator::Minus => sql::UnaryOperator::Minus,
+                    UnaryOperator::Plus => sql::UnaryOperator::Plus,
+                },
+                expr: Box::new(operand(inner, parenthesized)?),
+            }
+        }
+        Expression::Value(value) => sql::Expr::Value(self::value(value)),
+        Expression::Placeholder { text, .. } => {
+            sql::Expr::Value(sql::Value::Placeholder(text.clone()))
+        }
+        Expression::Identifier(name) => sql::Expr::Identifier(ident(name)),
+        Expression::CompoundIdentifier(parts) => {
+            sql::Expr::CompoundIdentifier(parts.iter().map(ident).collect())
+        }
+        Expression::Function { name, args } => sql::Expr::Function(sql::Function {
+            name: sql::ObjectName(vec![self::name(name)]),
+            parameters: sql::FunctionArguments::None,
+            args: sql::FunctionArguments::List(sql::FunctionArgumentList {
+                duplicate_treatment: None,
+                args: args
+                    .iter()
+                    .map(|arg| {
+                        Ok(sql::FunctionArg::Unnamed(match arg {
+                            Expression::Wildcard => sql::FunctionArgExpr::Wildcard,
+                            arg => sql::FunctionArgExpr::Expr(expression(arg)?),
+                        }))
+                    })
+                    .collect::<Result<_, ConversionError>>()?,
+                clauses: vec![],
+            }),
+            filter: None,
+            null_treatment: None,
+            over: None,
+            within_group: vec![],
+        }),
+        Expression::Cast {
+            expr: inner,
+            data_type,
+        } => sql::Expr::Cast {
+            // Printed as `expr::type`
+            kind: sql::CastKind::DoubleColon,
+            expr: Box::new(operand(inner, inner.precedence() < precedence)?),
+            data_type: self::data_type(data_type)?,
+            format: None,
+        },
+        Expression::Wildcard => return unsupported("a bare * inside an expression"),
+        Expression::Subquery(query) => sql::Expr::Subquery(Box::new(self::query(query)?)),
+        Expression::InList {
+            expr: inner,
+            list,
+            negated,
+        } => sql::Expr::InList {
+            expr: Box::new(operand(inner, inner.precedence() < precedence)?),
+            list: expressions(list)?,
+            negated: *negated,
+        },
+        Expression::IsNull {
+            expr: inner,
+            negated,
+        } => {
+            let inner = Box::new(operand(inner, inner.precedence() < precedence)?);
+            if *negated {
+                sql::Expr::IsNotNull(inner)
+            } else {
+                sql::Expr::IsNull(inner)
+            }
+        }
+        Expression::Like {
+            expr: inner,
+            pattern,
+            negated,
+        } => sql::Expr::Like {
+            negated: *negated,
+            any: false,
+            expr: Box::new(operand(inner, inner.precedence() < precedence)?),
+            pattern: Box::new(operand(pattern, pattern.precedence() <= precedence)?),
+            escape_char: None,
+        },
+    })
+}
+
+/// Converts an operand, wrapping it in `Nested` where printing parenthesizes it
+fn operand(expr: &Expression, parenthesized: bool) -> Result<sql::Expr, ConversionError> {
+    let converted = expression(expr)?;
+    Ok(if parenthesized {
+        sql::Expr::Nested(Box::new(converted))
+    } else {
+        converted
+    })
+}
+
+fn binary_operator(operator: &BinaryOperator) -> sql::BinaryOperator {
+    match operator {
+        BinaryOperator::Plus => sql::BinaryOperator::Plus,
+        BinaryOperator::Minus => sql::BinaryOperator::Minus,
+        BinaryOperator::Multiply => sql::BinaryOperator::Multiply,
+        BinaryOperator::Divide => sql::BinaryOperator::Divide,
+        BinaryOperator::Equal => sql::BinaryOperator::Eq,
+        BinaryOperator::NotEqual => sql::BinaryOperator::NotEq,
+        BinaryOperator::LessThan => sql::BinaryOperator::Lt,
+        BinaryOperator::LessThanOrEqual => sql::BinaryOperator::LtEq,
+        BinaryOperator::GreaterThan => sql::BinaryOperator::Gt,
+        BinaryOperator::GreaterThanOrEqual => sql::BinaryOperator::GtEq,
+        BinaryOperator::And => sql::BinaryOperator::And,
+        BinaryOperator::Or => sql::BinaryOperator::Or,
+        BinaryOperator::Concat => sql::BinaryOperator::StringConcat,
+    }
+}
+
+fn value(value: &Value) -> sql::Value {
+    match value {
+        Value::Number(n) => sql::Value::Number(n.to_string(), false),
+        // Kept as printed, which is how sqlparser keeps number literals
+        Value::Float(x) => sql::Value::Number(x.to_string(), false),
+        Value::SingleQuotedString(s) => sql::Value::SingleQuotedString(s.clone()),
+        Value::Boolean(b) => sql::Value::Boolean(*b),
+        Value::Null => sql::Value::Null,
+    }
+}
+
+fn data_type(data_type: &DataType) -> Result<sql::DataType, ConversionError> {
+    let length = |length: &Option<u64>| {
+        length.map(|length| sql::CharacterLength::IntegerLength { length, unit: None })
+    };
+    Ok(match data_type {
+        DataType::Int => sql::DataType::Int(None),
+        DataType::BigInt => sql::DataType::BigInt(None),
+        DataType::SmallInt => sql::DataType::SmallInt(None),
+        DataType::Varchar(n) => sql::DataType::Varchar(length(n)),
+        DataType::Char(n) => sql::DataType::Char(length(n)),
+        DataType::Text => sql::DataType::Text,
+        DataType::Boolean => sql::DataType::Boolean,
+        DataType::Float => sql::DataType::Float(None),
+        DataType::Double => sql::DataType::DoublePrecision,
+        DataType::Decimal(precision, scale) => sql::DataType::Decimal(match (precision, scale) {
+            (Some(precision), Some(scale)) => {
+                sql::ExactNumberInfo::PrecisionAndScale(*precision, *scale)
+            }
+            (Some(precision), None) => sql::ExactNumberInfo::Precision(*precision),
+            (None, _) => sql::ExactNumberInfo::None,
+        }),
+        DataType::Date => sql::DataType::Date,
+        DataType::Timestamp => sql::DataType::Timestamp(None, sql::TimezoneInfo::None),
+        DataType::TimestampWithTimeZone => {
+            sql::DataType::Timestamp(None, sql::TimezoneInfo::WithTimeZone)
+        }
+        DataType::Array(element) => sql::DataType::Array(sql::ArrayElemTypeDef::SquareBracket(
+            Box::new(self::data_type(element)?),
+            None,
+        )),
+        DataType::Custom(name) => sql::DataType::Custom(object_name(name), vec![]),
+        DataType::Unspecified => sql::DataType::Unspecified,
+    })
+}
+
+fn query(query: &Query) -> Result<sql::Query, ConversionError> {
+    let (limit, offset, fetch) = if query.with_ties {
+        (
+            None,
+            query
+                .offset
+                .as_ref()
+                .map(|value| offset(value, sql::OffsetRows::Rows))
+                .transpose()?,
+            Some(sql::Fetch {
+                with_ties: true,
+                percent: false,
+                quantity: query.limit.as_ref().map(expression).transpose()?,
+            }),
+        )
+    } else {
+        (
+            query.limit.as_ref().map(expression).transpose()?,
+            query
+                .offset
+                .as_ref()
+                .map(|value| offset(value, sql::OffsetRows::None))
+                .transpose()?,
+            None,
+        )
+    };
+    Ok(sql::Query {
+        with: query.with.as_ref().map(with).transpose()?,
+        body: Box::new(set_expr(&query.body)?),
+        order_by: order_by(&query.order_by)?,
+        limit,
+        limit_by: vec![],
+        offset,
+        fetch,
+        locks: match &query.locking {
+            Some(locking) => vec![lock_clause(locking)?],
+            None => vec![],
+        },
+        for_clause: None,
+        settings: None,
+        format_clause: None,
+    })
+}
+
+/// Wraps a query body with no clauses of its own into a query
+fn body_query(body: sql::SetExpr) -> sql::Query {
+    sql::Query {
+        with: None,
+        body: Box::new(body),
+        order_by: None,
+        limit: None,
+        limit_by: vec![],
+        offset: None,
+        fetch: None,
+        locks: vec![],
+        for_clause: None,
+        settings: None,
+        format_clause: None,
+    }
+}
+
+fn offset(value: &Expression, rows: sql::OffsetRows) -> Result<sql::Offset, ConversionError> {
+    Ok(sql::Offset {
+        value: expression(value)?,
+        rows,
+    })
+}
+
+fn with(with: &With) -> Result<sql::With, ConversionError> {
+    Ok(sql::With {
+        recursive: with.recursive,
+        cte_tables: with.ctes.iter().map(cte).collect::<Result<_, _>>()?,
+    })
+}
+
+fn cte(cte: &Cte) -> Result<sql::Cte, ConversionError> {
+    Ok(sql::Cte {
+        alias: sql::TableAlias {
+            name: name(&cte.name),
+            columns: cte.columns.iter().map(|column| name(column)).collect(),
+        },
+        query: Box::new(query(&cte.query)?),
+        from: None,
+        materialized: None,
+    })
+}
+
+fn order_by(items: &[OrderByItem]) -> Result<Option<sql::OrderBy>, ConversionError> {
+    if items.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(sql::OrderBy {
+        exprs: items.iter().map(order_by_expr).collect::<Result<_, _>>()?,
+        interpolate: None,
+    }))
+}
+
+fn order_by_expr(item: &OrderByItem) -> Result<sql::OrderByExpr, ConversionError> {
+    Ok(sql::OrderByExpr {
+        expr: expression(&item.expr)?,
+        asc: item.asc,
+        nulls_first: item.nulls_first,
+        with_fill: None,
+    })
+}
+
+fn lock_clause(locking: &LockingClause) -> Result<sql::LockClause, ConversionError> {
+    let lock_type = match locking.strength {
+        LockStrength::Update => sql::LockType::Update,
+        LockStrength::Share => sql::LockType::Share,
+        LockStrength::NoKeyUpdate => return unsupported("FOR NO KEY UPDATE"),
+        LockStrength::KeyShare => return unsupported("FOR KEY SHARE"),
+    };
+    let of = match locking.of.as_slice() {
+        [] => None,
+        [table] => Some(object_name(table)),
+        _ => return unsupported("a locking clause naming several tables"),
+    };
+    Ok(sql::LockClause {
+        lock_type,
+        of,
+        nonblock: locking.wait.as_ref().map(|wait| match wait {
+            WaitPolicy::Nowait => sql::NonBlock::Nowait,
+            WaitPolicy::SkipLocked => sql::NonBlock::SkipLocked,
+        }),
+    })
+}
+
+fn set_expr(body: &SetExpr) -> Result<sql::SetExpr, ConversionError> {
+    Ok(match body {
+        SetExpr::Select(select) => sql::SetExpr::Select(Box::new(self::select(select)?)),
+        SetExpr::Query(query) => sql::SetExpr::Query(Box::new(self::query(query)?)),
+        SetExpr::SetOperation {
+            op,
+            all,
+            left,
+            right,
+        } => {
+            // Parenthesized operands read back as queries, as `Display` writes them
+            let precedence = body.precedence();
+            let operand = |operand: &SetExpr, parenthesized: bool| {
+                let converted = set_expr(operand)?;
+                Ok::<_, ConversionError>(Box::new(if parenthesized {
+                    sql::SetExpr::Query(Box::new(body_query(converted)))
+                } else {
+                    converted
+                }))
+            };
+            sql::SetExpr::SetOperation {
+                op: match op {
+                    SetOperator::Union => sql::SetOperator::Union,
+                    SetOperator::Intersect => sql::SetOperator::Intersect,
+                    SetOperator::Except => sql::SetOperator::Except,
+                },
+                set_quantifier: if *all {
+                    sql::SetQuantifier::All
+                } else {
+                    sql::SetQuantifier::None
+                },
+                left: operand(left, left.precedence() < precedence)?,
+                right: operand(right, right.precedence() <= precedence)?,
+            }
+        }
+        SetExpr::Values(rows) => values(rows)?,
+    })
+}
+
+fn values(rows: &[Vec<Expression>]) -> Result<sql::SetExpr, ConversionError> {
+    Ok(sql::SetExpr::Values(sql::Values {
+        explicit_row: false,
+        rows: rows
+            .iter()
+            .map(|row| expressions(row))
+            .collect::<Result<_, _>>()?,
+    }))
+}
+
+fn select(select: &SelectStatement) -> Result<sql::Select, ConversionError> {
+    let mut group_by = vec![];
+    for item in &select.group_by {
+        let sets = |sets: &[Vec<Expression>]| -> Result<Vec<Vec<sql::Expr>>, ConversionError> {
+            sets.iter().map(|set| expressions(set)).collect()
+        };
+        group_by.push(match item {
+            GroupByExpr::Expression(expr) => expression(expr)?,
+            GroupByExpr::Rollup(sets_) => sql::Expr::Rollup(sets(sets_)?),
+            GroupByExpr::Cube(sets_) => sql::Expr::Cube(sets(sets_)?),
+            GroupByExpr::GroupingSets(sets_) => sql::Expr::GroupingSets(sets(sets_)?),
+        });
+    }
+    Ok(sql::Select {
+        distinct: match &select.distinct {
+            // sqlparser reads `SELECT ALL` as no quantifier
+            None | Some(Distinct::All) => None,
+            Some(Distinct::Distinct) => Some(sql::Distinct::Distinct),
+            Some(Distinct::DistinctOn(exprs)) => Some(sql::Distinct::On(expressions(exprs)?)),
+        },
+        top: select.top.as_ref().map(top).transpose()?,
+        top_before_distinct: false,
+        projection: select_items(&select.projection)?,
+        into: None,
+        from: match &select.from {
+            Some(from) => from_list(from)?,
+            None => vec![],
+        },
+        lateral_views: vec![],
+        prewhere: None,
+        selection: select.selection.as_ref().map(expression).transpose()?,
+        group_by: sql::GroupByExpr::Expressions(group_by, vec![]),
+        cluster_by: vec![],
+        distribute_by: vec![],
+        sort_by: vec![],
+        having: select.having.as_ref().map(expression).transpose()?,
+        named_window: vec![],
+        qualify: None,
+        window_before_qualify: false,
+        value_table_mode: None,
+        connect_by: None,
+    })
+}
+
+fn top(top: &Top) -> Result<sql::Top, ConversionError> {
+    Ok(sql::Top {
+        with_ties: top.with_ties,
+        percent: top.percent,
+        quantity: Some(match &top.quantity {
+            Expression::Value(Value::Number(n)) => sql::TopQuantity::Constant(*n),
+            quantity => sql::TopQuantity::Expr(expression(quantity)?),
+        }),
+    })
+}
+
+fn select_items(items: &[SelectItem]) -> Result<Vec<sql::SelectItem>, ConversionError> {
+    items.iter().map(select_item).collect()
+}
+
+fn select_item(item: &SelectItem) -> Result<sql::SelectItem, ConversionError> {
+    Ok(match item {
+        SelectItem::Expression(expr) => sql::SelectItem::UnnamedExpr(expression(expr)?),
+        SelectItem::ExprWithAlias { expr, alias } => sql::SelectItem::ExprWithAlias {
+            expr: expression(expr)?,
+            alias: ident(alias),
+        },
+        SelectItem::Wildcard => sql::SelectItem::Wildcard(Default::default()),
+        SelectItem::QualifiedWildcard(qualifier) => sql::SelectItem::QualifiedWildcard(
+            sql::ObjectName(qualifier.iter().map(|part| name(part)).collect()),
+            Default::default(),
+        ),
+    })
+}
+
+/// Converts a FROM clause into `sqlparser`'s list, which starts a new entry at each comma
+fn from_list(from: &TableWithJoins) -> Result<Vec<sql::TableWithJoins>, ConversionError> {
+    let mut tables = vec![sql::TableWithJoins {
+        relation: table_factor(&from.relation)?,
+        joins: vec![],
+    }];
+    for join in &from.joins {
+        if join.join_type == JoinType::Implicit {
+            tables.push(sql::TableWithJoins {
+                relation: table_factor(&join.relation)?,
+                joins: vec![],
+            });
+        } else {
+            let last = tables.len() - 1;
+            tables[last].joins.push(self::join(join)?);
+        }
+    }
+    Ok(tables)
+}
+
+/// Converts a FROM clause that `sqlparser` holds as a single table with its joins
+fn single_from(from: &TableWithJoins) -> Result<sql::TableWithJoins, ConversionError> {
+    let mut tables = from_list(from)?;
+    match tables.len() {
+        1 => Ok(tables.remove(0)),
+        _ => unsupported("a comma-separated UPDATE ... FROM list"),
+    }
+}
+
+fn join(join: &Join) -> Result<sql::Join, ConversionError> {
+    let constraint = match &join.constraint {
+        JoinConstraint::On(expr) => sql::JoinConstraint::On(expression(expr)?),
+        JoinConstraint::Using(columns) => {
+            sql::JoinConstraint::Using(columns.iter().map(|column| name(column)).collect())
+        }
+        JoinConstraint::Natural => sql::JoinConstraint::Natural,
+        JoinConstraint::None => sql::JoinConstraint::None,
+    };
+    let join_operator = match join.join_type {
+        JoinType::Inner => sql::JoinOperator::Inner(constraint),
+        JoinType::LeftOuter => sql::JoinOperator::LeftOuter(constraint),
+        JoinType::RightOuter => sql::JoinOperator::RightOuter(constraint),
+        JoinType::FullOuter => sql::JoinOperator::FullOuter(constraint),
+        JoinType::Cross => sql::JoinOperator::CrossJoin,
+        JoinType::Implicit => unreachable!("comma joins start a new entry in `from_list`"),
+    };
+    Ok(sql::Join {
+        relation: table_factor(&join.relation)?,
+        global: false,
+        join_operator,
+    })
+}
+
+fn table_factor(table: &TableReference) -> Result<sql::TableFactor, ConversionError> {
+    Ok(match table {
+        TableReference::Table { name, alias } => sql::TableFactor::Table {
+            name: object_name(name),
+            alias: alias.as_ref().map(table_alias),
+            args: None,
+            with_hints: vec![],
+            version: None,
+            with_ordinality: false,
+            partitions: vec![],
+        },
+        TableReference::Derived { subquery, alias } => sql::TableFactor::Derived {
+            lateral: false,
+            subquery: Box::new(query(subquery)?),
+            alias: alias.as_ref().map(table_alias),
+        },
+        // sqlparser reads a function in FROM as a table with arguments
+        TableReference::Function { name, args, alias } => sql::TableFactor::Table {
+            name: object_name(name),
+            alias: alias.as_ref().map(table_alias),
+            args: Some(sql::TableFunctionArgs {
+                args: args
+                    .iter()
+                    .map(|arg| {
+                        Ok(sql::FunctionArg::Unnamed(sql::FunctionArgExpr::Expr(
+                            expression(arg)?,
+                        )))
+                    })
+                    .collect::<Result<_, ConversionError>>()?,
+                settings: None,
+            }),
+            with_hints: vec![],
+            version: None,
+            with_ordinality: false,
+            partitions: vec![],
+        },
+    })
+}
+
+fn table_alias(alias: &TableAlias) -> sql::TableAlias {
+    sql::TableAlias {
+        name: ident(&alias.name),
+        columns: alias.columns.iter().map(ident).collect(),
+    }
+}
+
+fn assignment(assignment: &Assignment) -> Result<sql::Assignment, ConversionError> {
+    Ok(sql::Assignment {
+        target: sql::AssignmentTarget::ColumnName(sql::ObjectName(
+            assignment.column.iter().map(|part| name(part)).collect(),
+        )),
+        value: expression(&assignment.value)?,
+    })
+}
+
+fn on_conflict(on_conflict: &OnConflict) -> Result<sql::OnConflict, ConversionError> {
+    Ok(sql::OnConflict {
+        conflict_target: on_conflict.target.as_ref().map(|target| match target {
+            ConflictTarget::Columns(columns) => {
+                sql::ConflictTarget::Columns(columns.iter().map(|column| name(column)).collect())
+            }
+            ConflictTarget::OnConstraint(constraint) => {
+                sql::ConflictTarget::OnConstraint(sql::ObjectName(vec![name(constraint)]))
+            }
+        }),
+        action: match &on_conflict.action {
+            OnConflictAction::DoNothing => sql::OnConflictAction::DoNothing,
+            OnConflictAction::DoUpdate {
+                assignments,
+                selection,
+            } => sql::OnConflictAction::DoUpdate(sql::DoUpdate {
+                assignments: assignments
+                    .iter()
+                    .map(assignment)
+                    .collect::<Result<_, _>>()?,
+                selection: selection.as_ref().map(expression).transpose()?,
+            }),
+        },
+    })
+}
@@ -0,0 +1,71 @@
+use crate::ast::{Keyword, TokenKind};
+use crate::options::ParserOptions;
+use crate::parser::PrattParser;
+
+/// How deeply expressions may nest while completing, so that unbalanced input typed into an
+/// editor cannot exhaust the stack
+const MAX_DEPTH: usize = 64;
+
+/// Represents what may be typed at a cursor position
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct CompletionResult {
+    /// Keywords that may come next and start with the word being typed, in the order the
+    /// parser tried them
+    pub keywords: Vec<Keyword>,
+    /// Punctuation and operators that may come next, as written, such as `,` or `>=`. Empty
+    /// while a word is being typed.
+    pub punctuation: Vec<String>,
+    /// Whether an identifier, such as a table or column name, may come next
+    pub identifier_expected: bool,
+    /// Byte offset where the word being typed starts; a suggestion replaces the text from
+    /// here up to the cursor
+    pub start: usize,
+}
+
+/// Returns what may be typed at byte offset `cursor` of `sql_prefix`, based on the text
+/// before it. A word the cursor is at the end of is taken to be unfinished: suggestions
+/// are the keywords it could be the start of. Nothing is suggested when the text before
+/// the word does not parse, or when the cursor is inside a string or quoted identifier.
+pub fn completions(sql_prefix: &str, cursor: usize) -> CompletionResult {
+    let mut cursor = cursor.min(sql_prefix.len());
+    while !sql_prefix.is_char_boundary(cursor) {
+        cursor -= 1;
+    }
+    let prefix = &sql_prefix[..cursor];
+    let start = prefix
+        .char_indices()
+        .rev()
+        .take_while(|(_, c)| c.is_alphanumeric() || *c == '_')
+        .last()
+        .map_or(cursor, |(i, _)| i);
+    let word = &prefix[start..];
+
+    let mut result = CompletionResult {
+        start,
+        ..CompletionResult::default()
+    };
+    let options = ParserOptions::new().with_max_expression_depth(MAX_DEPTH);
+    let expected = match PrattParser::expected_at_end(&prefix[..start], options) {
+        Some(expected) => expected,
+        None => return result,
+    };
+
+    for token in expected {
+        match token {
+            TokenKind::Keyword(keyword) => {
+                let name = keyword.to_string();
+                if name.len() >= word.len() && name[..word.len()].eq_ignore_ascii_case(word) {
+                    result.keywords.push(keyword);
+                }
+            }
+            TokenKind::Identifier => result.identifier_expected = true,
+            // Symbols print as they are typed
+            token if word.is_empty() && !token.is_word() => {
+                result.punctuation.push(token.to_string())
+            }
+            _ => {}
+        }
+    }
+    result
+}
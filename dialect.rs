@@ -0,0 +1,17 @@
+/// Represents the SQL dialect a parser accepts, gating vendor-specific syntax
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum Dialect {
+    #[default]
+    Generic,
+    PostgreSql,
+    MySql,
+    SQLite,
+    MsSql,
+}
+
+impl Dialect {
+    /// Returns true if `SELECT TOP n` is accepted
+    pub fn supports_top(&self) -> bool {
+        matches!(self, Dialect::MsSql)
+    }
+}
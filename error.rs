@@ -1,28 +1,744 @@
-use std::fmt;
-
-#[derive(Debug, Clone)]
-pub enum ParseError {
-    UnexpectedToken(String),
-    ExpectedToken(String),
-    ExpectedIdentifier(String),
-    ExpectedType(String),
-    ExpectedKeyword(String),
-    ExpectedNumber(String),
-    UnexpectedEndOfInput(String),
-    InvalidInput(String),
-}
-
-impl fmt::Display for ParseError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self {
-            ParseError::UnexpectedToken(msg) => write!(f, "Unexpected token: {}", msg),
-            ParseError::ExpectedToken(msg) => write!(f, "Expected token: {}", msg),
-            ParseError::ExpectedIdentifier(msg) => write!(f, "Expected identifier: {}", msg),
-            ParseError::ExpectedType(msg) => write!(f, "Expected type: {}", msg),
-            ParseError::ExpectedKeyword(msg) => write!(f, "Expected keyword: {}", msg),
-            ParseError::ExpectedNumber(msg) => write!(f, "Expected number: {}", msg),
-            ParseError::UnexpectedEndOfInput(msg) => write!(f, "Unexpected end of input: {}", msg),
-            ParseError::InvalidInput(msg) => write!(f, "Invalid input: {}", msg),
-        }
-    }
-}
+use std::fmt;
+
+use crate::ast::{Keyword, Token, TokenKind};
+use crate::source_map::SourceMap;
+use crate::tokenizer::{Location, Span};
+
+/// Represents a lexical or syntax error: what went wrong, where, and what the parser was
+/// doing. The span points at the offending token, or at the end of the input when it ended
+/// too early. `Display` builds the message from the fields.
+#[derive(Debug, Clone)]
+pub struct ParseError {
+    /// What went wrong
+    pub problem: Problem,
+    /// Where in the input, if known
+    pub span: Option<Span>,
+    /// What the parser was doing and what it would have accepted
+    pub context: ErrorContext,
+}
+
+/// Represents what the parser was doing when a syntax error occurred. Lexical errors leave
+/// it empty.
+#[derive(Debug, Clone, Default)]
+pub struct ErrorContext {
+    /// The constructs being parsed, outermost first
+    pub parsing: Vec<ParseContext>,
+    /// The statement of a script the error occurred in, counting from 1
+    pub statement: Option<usize>,
+    /// What would have been accepted instead, described for the message, such as `','` or
+    /// `an operator`
+    pub alternatives: Vec<String>,
+    /// The kinds of token that would have been accepted instead
+    pub expected: Vec<TokenKind>,
+}
+
+/// Represents what went wrong in a `ParseError`. The `String` fields describe the position
+/// or the thing that was needed for the message, such as `after CREATE` or `table name`.
+#[derive(Debug, Clone)]
+pub enum Problem {
+    /// A token that cannot appear where it was found, such as `after end of statement`
+    UnexpectedToken { found: Token, position: String },
+    /// A token other than one of the `expected` ones, such as `')'` `to close IN list`
+    ExpectedToken {
+        expected: Vec<TokenKind>,
+        position: String,
+        found: Token,
+    },
+    /// A token other than one of the `keywords`, such as `TABLE, INDEX or VIEW` `after CREATE`
+    ExpectedKeyword {
+        keywords: Vec<Keyword>,
+        position: String,
+        found: Token,
+    },
+    /// A token other than the name `what` describes, such as `table name`
+    ExpectedIdentifier { what: String, found: Token },
+    /// A token other than the type `what` describes, such as `data type for column a`
+    ExpectedType { what: String, found: Token },
+    /// A token other than the number `what` describes
+    ExpectedNumber { what: String, found: Token },
+    /// A token other than the expression `what` describes, such as `expression after WHERE`
+    ExpectedExpression { what: String, found: Token },
+    /// The end of the input where `what` was needed
+    UnexpectedEndOfInput { what: String },
+    /// A string literal or quoted identifier missing its closing `quote`; the span starts at
+    /// the opening quote
+    UnterminatedString { quote: char },
+    /// A character that starts no token, or `!` and `:` not followed by what they need
+    UnexpectedCharacter { found: char },
+    /// A number literal that does not fit its type
+    InvalidNumber { text: String },
+    /// An expression nested deeper than `ParserOptions::max_expression_depth` allows
+    ExpressionTooDeep { max_depth: usize },
+    /// Input that is well-formed token by token but not allowed, such as conflicting clauses
+    InvalidInput { message: String },
+}
+
+impl Problem {
+    /// Whether the problem was found by the tokenizer, outside any construct or statement
+    fn is_lexical(&self) -> bool {
+        matches!(
+            self,
+            Problem::UnterminatedString { .. }
+                | Problem::UnexpectedCharacter { .. }
+                | Problem::InvalidNumber { .. }
+        )
+    }
+
+    /// Whether the problem is a token or the end of the input where others were accepted
+    fn has_alternatives(&self) -> bool {
+        !self.is_lexical()
+            && !matches!(
+                self,
+                Problem::ExpressionTooDeep { .. } | Problem::InvalidInput { .. }
+            )
+    }
+}
+
+impl fmt::Display for Problem {
+    /// Writes the message without the list of alternatives
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Problem::UnexpectedToken { found, position } => {
+                write!(f, "{} {}", describe(found), position)
+            }
+            Problem::ExpectedToken {
+                expected,
+                position,
+                found,
+            } => {
+                let expected: Vec<String> = expected.iter().map(describe_kind).collect();
+                write!(
+                    f,
+                    "{} {}, found {}",
+                    either(&expected),
+                    position,
+                    describe(found)
+                )
+            }
+            Problem::ExpectedKeyword {
+                keywords,
+                position,
+                found,
+            } => {
+                let keywords: Vec<String> = keywords.iter().map(Keyword::to_string).collect();
+                write!(
+                    f,
+                    "{} {}, found {}",
+                    either(&keywords),
+                    position,
+                    describe(found)
+                )
+            }
+            Problem::ExpectedIdentifier { what, found }
+            | Problem::ExpectedType { what, found }
+            | Problem::ExpectedNumber { what, found }
+            | Problem::ExpectedExpression { what, found } => {
+                write!(f, "{}, found {}", what, describe(found))
+            }
+            Problem::UnexpectedEndOfInput { what } => write!(f, "expected {}", what),
+            Problem::UnterminatedString { quote: '"' } => {
+                write!(f, "no closing \" for quoted identifier")
+            }
+            Problem::UnterminatedString { quote } => {
+                write!(f, "no closing {} for string literal", quote)
+            }
+            Problem::UnexpectedCharacter { found } => write!(f, "'{}'", found),
+            Problem::InvalidNumber { text } => write!(f, "{} is out of range", text),
+            Problem::ExpressionTooDeep { max_depth } => {
+                write!(f, "expression nested deeper than {} levels", max_depth)
+            }
+            Problem::InvalidInput { message } => f.write_str(message),
+        }
+    }
+}
+
+/// Joins `items` as in `a, b or c`
+fn either(items: &[String]) -> String {
+    match items {
+        [] => String::new(),
+        [item] => item.clone(),
+        [init @ .., last] => format!("{} or {}", init.join(", "), last),
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.title(), self.description())?;
+        if let Some(span) = self.span {
+            write!(
+                f,
+                " at line {}, column {}",
+                span.start.line, span.start.column
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl From<Problem> for ParseError {
+    fn from(problem: Problem) -> Self {
+        ParseError::new(problem)
+    }
+}
+
+impl ParseError {
+    /// Creates an error with no location or context yet
+    pub fn new(problem: Problem) -> Self {
+        ParseError {
+            problem,
+            span: None,
+            context: ErrorContext::default(),
+        }
+    }
+
+    /// Returns the description of the error without its kind or location
+    pub fn message(&self) -> String {
+        let mut message = self.problem.to_string();
+        match self.alternatives() {
+            // Other errors already name what they expected
+            [alternative] if matches!(self.problem, Problem::UnexpectedToken { .. }) => {
+                message.push_str(&format!(", expected {}", alternative));
+            }
+            [_, _, ..] => {
+                message.push_str(&format!(
+                    ", expected one of: {}",
+                    self.alternatives().join(", ")
+                ));
+            }
+            _ => {}
+        }
+        message
+    }
+
+    /// Renders the error for a terminal, with the lines of `source` it covers and a caret
+    /// under the offending text:
+    ///
+    /// ```text
+    /// error: Unexpected token: '=' at the start of an expression
+    ///  --> line 1, column 27
+    ///   |
+    /// 1 | SELECT a FROM t WHERE a = = 1
+    ///   |                           ^
+    /// ```
+    ///
+    /// `source` must be the input that was parsed. Tabs are expanded to four columns, and
+    /// an error at the end of the input points just past its last character. A span over
+    /// more than three lines shows its first two lines and its last. Without a span, only
+    /// the message line is rendered.
+    pub fn render(&self, source: &str) -> String {
+        self.render_with(&SourceMap::new(source))
+    }
+
+    /// Renders the error like `render`, taking the lines from `map`, which saves indexing
+    /// the source again for each of several errors in it
+    pub fn render_with(&self, map: &SourceMap) -> String {
+        let mut out = format!("error: {}: {}", self.title(), self.description());
+        let span = match self.span {
+            Some(span) => span,
+            None => return out,
+        };
+        let start = span.start;
+        // A span that ends at the start of a line does not cover anything on that line
+        let last_line = if span.end.line > start.line && span.end.column == 1 {
+            span.end.line - 1
+        } else {
+            span.end.line.max(start.line)
+        };
+        let width = last_line.to_string().len();
+        let gutter = " ".repeat(width);
+
+        out.push_str(&format!(
+            "\n{}--> line {}, column {}\n{} |",
+            gutter, start.line, start.column, gutter
+        ));
+        for number in start.line..=last_line {
+            // Of a long span, show the first two lines and the last
+            if number > start.line + 1 && number < last_line {
+                if number == start.line + 2 {
+                    out.push_str("\n...");
+                }
+                continue;
+            }
+            let line = map
+                .line(number)
+                .map_or("", |line| line.strip_suffix('\r').unwrap_or(line));
+            let chars: Vec<char> = line.chars().collect();
+            let from = if number == start.line {
+                start.column
+            } else {
+                1
+            };
+            let to = if number == span.end.line {
+                span.end.column
+            } else {
+                chars.len() + 1
+            };
+            // Columns count characters from 1; convert them to positions on screen
+            let screen = |column: usize| -> usize {
+                let before = chars.iter().take(column.saturating_sub(1));
+                let tabs = before.clone().filter(|&&ch| ch == '\t').count();
+                before.count() + tabs * 3 + column.saturating_sub(chars.len() + 1)
+            };
+            let indent = screen(from);
+            let carets = screen(to).saturating_sub(indent).max(1);
+            out.push_str(&format!(
+                "\n{:>width$} | {}\n{} | {}{}",
+                number,
+                line.replace('\t', "    "),
+                gutter,
+                " ".repeat(indent),
+                "^".repeat(carets),
+                width = width
+            ));
+        }
+        out
+    }
+
+    /// Returns the broad category of the error. An error that found the end of the input
+    /// where it expected something else is `UnexpectedEof`, whichever problem reports it.
+    pub fn kind(&self) -> ErrorKind {
+        match &self.problem {
+            Problem::UnexpectedToken {
+                found: Token::Eof, ..
+            }
+            | Problem::ExpectedToken {
+                found: Token::Eof, ..
+            }
+            | Problem::ExpectedKeyword {
+                found: Token::Eof, ..
+            }
+            | Problem::ExpectedIdentifier {
+                found: Token::Eof, ..
+            }
+            | Problem::ExpectedType {
+                found: Token::Eof, ..
+            }
+            | Problem::ExpectedNumber {
+                found: Token::Eof, ..
+            }
+            | Problem::ExpectedExpression {
+                found: Token::Eof, ..
+            }
+            | Problem::UnexpectedEndOfInput { .. } => ErrorKind::UnexpectedEof,
+            Problem::UnexpectedToken { .. }
+            | Problem::ExpectedToken { .. }
+            | Problem::ExpectedKeyword { .. }
+            | Problem::ExpectedIdentifier { .. }
+            | Problem::ExpectedType { .. }
+            | Problem::ExpectedNumber { .. }
+            | Problem::ExpectedExpression { .. } => ErrorKind::UnexpectedToken,
+            Problem::UnexpectedCharacter { .. } => ErrorKind::InvalidCharacter,
+            Problem::UnterminatedString { .. } => ErrorKind::UnterminatedString,
+            Problem::InvalidNumber { .. } => ErrorKind::InvalidLiteral,
+            Problem::ExpressionTooDeep { .. } => ErrorKind::LimitExceeded,
+            Problem::InvalidInput { .. } => ErrorKind::InvalidInput,
+        }
+    }
+
+    /// Returns the message followed by the innermost one or two constructs being parsed and
+    /// the statement they were in
+    fn description(&self) -> String {
+        let mut description = self.message();
+        let mut chain = self.parsing().iter().rev();
+        if let Some(inner) = chain.next() {
+            description.push_str(&format!(", while parsing {}", inner));
+            if let Some(outer) = chain.next() {
+                description.push_str(&format!(" in {}", outer));
+            }
+        }
+        if let Some(number) = self.statement() {
+            description.push_str(&format!(" in statement {}", number));
+        }
+        description
+    }
+
+    /// Returns the name of the problem as shown by `Display`
+    fn title(&self) -> &'static str {
+        match self.problem {
+            Problem::UnexpectedToken { .. } => "Unexpected token",
+            Problem::ExpectedToken { .. } => "Expected token",
+            Problem::ExpectedKeyword { .. } => "Expected keyword",
+            Problem::ExpectedIdentifier { .. } => "Expected identifier",
+            Problem::ExpectedType { .. } => "Expected type",
+            Problem::ExpectedNumber { .. } => "Expected number",
+            Problem::ExpectedExpression { .. } => "Expected expression",
+            Problem::UnexpectedEndOfInput { .. } => "Unexpected end of input",
+            Problem::UnterminatedString { .. } => "Unterminated string",
+            Problem::UnexpectedCharacter { .. } => "Unexpected character",
+            Problem::InvalidNumber { .. } => "Invalid number",
+            Problem::ExpressionTooDeep { .. } => "Expression too deep",
+            Problem::InvalidInput { .. } => "Invalid input",
+        }
+    }
+
+    /// Returns where in the input the error occurred
+    pub fn span(&self) -> Option<Span> {
+        self.span
+    }
+
+    /// Returns what was being parsed when the error occurred, outermost first, such as the
+    /// WHERE clause of a SELECT and then a function's arguments. Lexical errors and errors
+    /// outside any tracked construct return an empty chain.
+    pub fn parsing(&self) -> &[ParseContext] {
+        &self.context.parsing
+    }
+
+    /// Returns the same error recording `chain` as what was being parsed, unless it already
+    /// has a chain. Lexical errors are returned unchanged.
+    pub fn while_parsing(mut self, chain: &[ParseContext]) -> ParseError {
+        if !self.problem.is_lexical() && self.context.parsing.is_empty() {
+            self.context.parsing = chain.to_vec();
+        }
+        self
+    }
+
+    /// Returns what would have been accepted where the error occurred, in the order the
+    /// parser tried them and without duplicates. The message lists them when there are two
+    /// or more; a single one is only added to `UnexpectedToken`, as the other errors already
+    /// name what they expected.
+    pub fn alternatives(&self) -> &[String] {
+        &self.context.alternatives
+    }
+
+    /// Returns the same error recording `accepted` as the alternatives, unless it already
+    /// has some. Errors that are not about an unexpected token are returned unchanged.
+    pub fn with_alternatives(mut self, accepted: &[String]) -> ParseError {
+        if self.problem.has_alternatives() && self.context.alternatives.is_empty() {
+            self.context.alternatives = accepted.to_vec();
+        }
+        self
+    }
+
+    /// Returns the kinds of token that would have been accepted where the error occurred, as
+    /// far as the parser tried them, in the order it did. Unlike `alternatives`, which are
+    /// described for the message, these can be matched on, such as
+    /// `TokenKind::Keyword(Keyword::From)` after a select list.
+    pub fn expected_tokens(&self) -> &[TokenKind] {
+        &self.context.expected
+    }
+
+    /// Returns the same error recording `accepted` as the expected tokens, unless it already
+    /// has some. Errors that are not about an unexpected token are returned unchanged.
+    pub fn with_expected_tokens(mut self, accepted: &[TokenKind]) -> ParseError {
+        if self.problem.has_alternatives() && self.context.expected.is_empty() {
+            self.context.expected = accepted.to_vec();
+        }
+        self
+    }
+
+    /// Returns the same error located at `span`, unless it already has a location
+    pub fn with_span(mut self, location: Span) -> ParseError {
+        self.span.get_or_insert(location);
+        self
+    }
+
+    /// Returns the same error with its span, taken within a piece of a larger input that
+    /// starts at `origin`, moved to the corresponding place in the larger input
+    pub(crate) fn offset_by(mut self, origin: Location) -> ParseError {
+        if let Some(span) = &mut self.span {
+            span.start = span.start.offset_by(origin);
+            span.end = span.end.offset_by(origin);
+        }
+        self
+    }
+
+    /// Returns the number of the statement of a script the error occurred in, counting from
+    /// 1, or `None` for an error outside a script and for lexical errors
+    pub fn statement(&self) -> Option<usize> {
+        self.context.statement
+    }
+
+    /// Returns the same error recording that it occurred in the `number`th statement of a
+    /// script. Lexical errors are returned unchanged; their span still locates them.
+    pub fn in_statement(mut self, number: usize) -> ParseError {
+        if !self.problem.is_lexical() {
+            self.context.statement = Some(number);
+        }
+        self
+    }
+}
+
+/// Describes `token` for an error message: symbols are quoted, as in `','`, while keywords,
+/// names and literals are written as they read in SQL, and the end of the input as such
+pub(crate) fn describe(token: &Token) -> String {
+    match token.kind().is_word() {
+        true => token.to_string(),
+        false => format!("'{}'", token),
+    }
+}
+
+/// Describes `kind` for an error message like `describe`, naming classes such as `identifier`
+fn describe_kind(kind: &TokenKind) -> String {
+    match kind.is_word() {
+        true => kind.to_string(),
+        false => format!("'{}'", kind),
+    }
+}
+
+/// Represents a construct the parser was in the middle of when an error occurred
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+#[non_exhaustive]
+pub enum ParseContext {
+    Select,
+    Insert,
+    Update,
+    Delete,
+    CreateTable,
+    /// The expressions between SELECT and FROM
+    SelectList,
+    FromClause,
+    JoinCondition,
+    WhereClause,
+    GroupByClause,
+    HavingClause,
+    OrderByClause,
+    LimitClause,
+    /// A parenthesized query used as an expression or a derived table
+    Subquery,
+    FunctionArgs,
+    /// The column definitions and constraints of CREATE TABLE
+    CreateTableColumns,
+    /// The expression of a column's DEFAULT option
+    ColumnDefault,
+    /// The rows of a VALUES list
+    ValuesRows,
+    /// The assignments of an UPDATE's SET clause
+    Assignments,
+}
+
+impl fmt::Display for ParseContext {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let label = match self {
+            ParseContext::Select => "SELECT",
+            ParseContext::Insert => "INSERT",
+            ParseContext::Update => "UPDATE",
+            ParseContext::Delete => "DELETE",
+            ParseContext::CreateTable => "CREATE TABLE",
+            ParseContext::SelectList => "the select list",
+            ParseContext::FromClause => "the FROM clause",
+            ParseContext::JoinCondition => "a join condition",
+            ParseContext::WhereClause => "the WHERE clause",
+            ParseContext::GroupByClause => "the GROUP BY clause",
+            ParseContext::HavingClause => "the HAVING clause",
+            ParseContext::OrderByClause => "the ORDER BY clause",
+            ParseContext::LimitClause => "the LIMIT clause",
+            ParseContext::Subquery => "a subquery",
+            ParseContext::FunctionArgs => "function arguments",
+            ParseContext::CreateTableColumns => "the column definitions",
+            ParseContext::ColumnDefault => "a column default",
+            ParseContext::ValuesRows => "VALUES rows",
+            ParseContext::Assignments => "the SET assignments",
+        };
+        f.write_str(label)
+    }
+}
+
+/// Represents the category of a `ParseError`, for mapping failures to error codes or metrics
+/// without matching on messages.
+///
+/// Each kind has a code such as `"E0001"`. Codes are stable: a kind keeps its code across
+/// releases, and the code of a removed kind is never given to another. New kinds may be
+/// added, so matches need a wildcard arm.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// A token other than the ones allowed at that point
+    UnexpectedToken,
+    /// The input ended in the middle of a statement or expression
+    UnexpectedEof,
+    /// A character that cannot start a token
+    InvalidCharacter,
+    /// A string literal or quoted identifier with no closing quote
+    UnterminatedString,
+    /// A literal whose value cannot be represented, such as a number out of range
+    InvalidLiteral,
+    /// Input beyond a limit set in `ParserOptions`
+    LimitExceeded,
+    /// Tokens that are valid on their own but not allowed together
+    InvalidInput,
+}
+
+impl ErrorKind {
+    /// Returns the kind's stable code
+    pub fn code(&self) -> &'static str {
+        match self {
+            ErrorKind::UnexpectedToken => "E0001",
+            ErrorKind::UnexpectedEof => "E0002",
+            ErrorKind::InvalidCharacter => "E0003",
+            ErrorKind::UnterminatedString => "E0004",
+            ErrorKind::InvalidLiteral => "E0005",
+            ErrorKind::LimitExceeded => "E0006",
+            ErrorKind::InvalidInput => "E0007",
+        }
+    }
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+/// Serializes as the kind's code
+#[cfg(feature = "serde")]
+impl serde::Serialize for ErrorKind {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.code())
+    }
+}
+
+/// Represents every error found in one input, in source order
+#[derive(Debug, Clone, Default)]
+pub struct ParseErrors(pub Vec<ParseError>);
+
+impl ParseErrors {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, ParseError> {
+        self.0.iter()
+    }
+}
+
+impl fmt::Display for ParseErrors {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, error) in self.0.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", error)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ParseErrors {
+    /// The first error, as the one the others may have followed from
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.0.first().map(|e| e as _)
+    }
+}
+
+impl IntoIterator for ParseErrors {
+    type Item = ParseError;
+    type IntoIter = std::vec::IntoIter<ParseError>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a ParseErrors {
+    type Item = &'a ParseError;
+    type IntoIter = std::slice::Iter<'a, ParseError>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+/// Represents a failure to evaluate an expression against a row
+#[derive(Debug, Clone)]
+pub enum EvalError {
+    UnknownIdentifier(String),
+    TypeMismatch(String),
+    DivisionByZero(String),
+    Overflow(String),
+    Unsupported(String),
+}
+
+impl fmt::Display for EvalError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EvalError::UnknownIdentifier(msg) => write!(f, "Unknown identifier: {}", msg),
+            EvalError::TypeMismatch(msg) => write!(f, "Type mismatch: {}", msg),
+            EvalError::DivisionByZero(msg) => write!(f, "Division by zero: {}", msg),
+            EvalError::Overflow(msg) => write!(f, "Overflow: {}", msg),
+            EvalError::Unsupported(msg) => write!(f, "Unsupported: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for EvalError {}
+
+/// Represents a failure to bind values to a statement's placeholders
+#[derive(Debug, Clone)]
+pub enum BindError {
+    CountMismatch(String),
+    MissingValue(String),
+    InvalidPlaceholder(String),
+}
+
+impl fmt::Display for BindError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BindError::CountMismatch(msg) => write!(f, "Count mismatch: {}", msg),
+            BindError::MissingValue(msg) => write!(f, "Missing value: {}", msg),
+            BindError::InvalidPlaceholder(msg) => write!(f, "Invalid placeholder: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for BindError {}
+
+/// Represents a statement rejected for exceeding one of the configured complexity limits
+#[derive(Debug, Clone)]
+pub enum ComplexityError {
+    TooManyNodes(String),
+    TooDeep(String),
+    TooManyJoins(String),
+    TooManySubqueries(String),
+}
+
+impl fmt::Display for ComplexityError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ComplexityError::TooManyNodes(msg) => write!(f, "Too many nodes: {}", msg),
+            ComplexityError::TooDeep(msg) => write!(f, "Too deep: {}", msg),
+            ComplexityError::TooManyJoins(msg) => write!(f, "Too many joins: {}", msg),
+            ComplexityError::TooManySubqueries(msg) => write!(f, "Too many subqueries: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for ComplexityError {}
+
+/// Represents a failure to convert this crate's AST into the `sqlparser` crate's AST
+#[derive(Debug, Clone)]
+pub enum ConversionError {
+    /// The construct has no counterpart in `sqlparser`
+    Unsupported(String),
+}
+
+impl fmt::Display for ConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConversionError::Unsupported(msg) => write!(f, "Unsupported: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for ConversionError {}
+
+/// Represents an expression that cannot be compiled into a row predicate
+#[derive(Debug, Clone)]
+pub enum CompileError {
+    Unsupported(String),
+}
+
+impl fmt::Display for CompileError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CompileError::Unsupported(msg) => write!(f, "Unsupported: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for CompileError {}
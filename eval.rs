@@ -0,0 +1,437 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+use crate::ast::{self, BinaryOperator, DataType, Expression, UnaryOperator};
+use crate::error::EvalError;
+
+/// Represents a runtime value produced by evaluating an expression
+#[derive(Debug, PartialEq, Clone)]
+pub enum Value {
+    Integer(i64),
+    Float(f64),
+    String(String),
+    Boolean(bool),
+    Null,
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Integer(n) => write!(f, "{}", n),
+            Value::Float(x) => write!(f, "{}", x),
+            Value::String(s) => write!(f, "{}", s),
+            Value::Boolean(true) => write!(f, "TRUE"),
+            Value::Boolean(false) => write!(f, "FALSE"),
+            Value::Null => write!(f, "NULL"),
+        }
+    }
+}
+
+impl Expression {
+    /// Evaluates this expression with SQL semantics, looking up column references in `row`.
+    /// NULL propagates through arithmetic and comparisons, AND and OR use three-valued logic
+    /// and stop early once the result is known. Qualified names are looked up joined with
+    /// dots, as in `t.a`.
+    pub fn evaluate(&self, row: &dyn Fn(&str) -> Option<Value>) -> Result<Value, EvalError> {
+        match self {
+            Expression::Value(ast::Value::Number(n)) => i64::try_from(*n)
+                .map(Value::Integer)
+                .map_err(|_| EvalError::Overflow(format!("{} does not fit in an integer", n))),
+            Expression::Value(ast::Value::Float(x)) => Ok(Value::Float(x.0)),
+            Expression::Value(ast::Value::SingleQuotedString(s)) => Ok(Value::String(s.clone())),
+            Expression::Value(ast::Value::Boolean(b)) => Ok(Value::Boolean(*b)),
+            Expression::Value(ast::Value::Null) => Ok(Value::Null),
+            Expression::Identifier(ident) => row(&ident.value)
+                .ok_or_else(|| EvalError::UnknownIdentifier(ident.value.to_string())),
+            Expression::CompoundIdentifier(parts) => {
+                let name = parts
+                    .iter()
+                    .map(|part| part.value.as_str())
+                    .collect::<Vec<_>>()
+                    .join(".");
+                row(&name).ok_or(EvalError::UnknownIdentifier(name))
+            }
+            Expression::UnaryOperation {
+                operator: UnaryOperator::Minus,
+                operand,
+            } => match **operand {
+                Expression::Value(ast::Value::Number(n)) => negative_literal(n),
+                ref operand => evaluate_unary(&UnaryOperator::Minus, operand.evaluate(row)?),
+            },
+            Expression::UnaryOperation { operator, operand } => {
+                evaluate_unary(operator, operand.evaluate(row)?)
+            }
+            Expression::BinaryOperation {
+                left_operand,
+                operator: operator @ (BinaryOperator::And | BinaryOperator::Or),
+                right_operand,
+            } => evaluate_logical(operator, left_operand, right_operand, row),
+            Expression::BinaryOperation {
+                left_operand,
+                operator,
+                right_operand,
+            } => evaluate_binary(
+                left_operand.evaluate(row)?,
+                operator,
+                right_operand.evaluate(row)?,
+            ),
+            Expression::Cast { expr, data_type } => cast(expr.evaluate(row)?, data_type),
+            Expression::Placeholder { text, .. } => Err(EvalError::Unsupported(format!(
+                "placeholder {} has no value",
+                text
+            ))),
+            Expression::Function { name, .. } => {
+                Err(EvalError::Unsupported(format!("function call {}", name)))
+            }
+            Expression::Wildcard => Err(EvalError::Unsupported("wildcard".into())),
+            Expression::Subquery(_) => Err(EvalError::Unsupported("subquery".into())),
+            Expression::InList {
+                expr,
+                list,
+                negated,
+            } => evaluate_in_list(expr.evaluate(row)?, list, *negated, row),
+            Expression::IsNull { expr, negated } => Ok(Value::Boolean(
+                (expr.evaluate(row)? == Value::Null) != *negated,
+            )),
+            Expression::Like {
+                expr,
+                pattern,
+                negated,
+            } => match (expr.evaluate(row)?, pattern.evaluate(row)?) {
+                (Value::Null, _) | (_, Value::Null) => Ok(Value::Null),
+                (Value::String(text), Value::String(pattern)) => {
+                    Ok(Value::Boolean(like(&text, &pattern) != *negated))
+                }
+                (text, pattern) => Err(EvalError::TypeMismatch(format!(
+                    "LIKE expects strings, found {} and {}",
+                    type_name(&text),
+                    type_name(&pattern)
+                ))),
+            },
+        }
+    }
+}
+
+/// Evaluates `-n` for a literal `n`. The smallest integer's magnitude does not fit in one,
+/// so the literal is negated before it is narrowed.
+fn negative_literal(n: u64) -> Result<Value, EvalError> {
+    i128::from(n)
+        .checked_neg()
+        .and_then(|n| i64::try_from(n).ok())
+        .map(Value::Integer)
+        .ok_or_else(|| EvalError::Overflow(format!("-{} does not fit in an integer", n)))
+}
+
+fn evaluate_unary(operator: &UnaryOperator, value: Value) -> Result<Value, EvalError> {
+    match (operator, value) {
+        (_, Value::Null) => Ok(Value::Null),
+        (UnaryOperator::Not, Value::Boolean(b)) => Ok(Value::Boolean(!b)),
+        (UnaryOperator::Minus, Value::Integer(n)) => n
+            .checked_neg()
+            .map(Value::Integer)
+            .ok_or_else(|| EvalError::Overflow(format!("-({})", n))),
+        (UnaryOperator::Minus, Value::Float(x)) => Ok(Value::Float(-x)),
+        (UnaryOperator::Plus, value @ (Value::Integer(_) | Value::Float(_))) => Ok(value),
+        (operator, value) => Err(EvalError::TypeMismatch(format!(
+            "cannot apply {} to {}",
+            operator,
+            type_name(&value)
+        ))),
+    }
+}
+
+/// Evaluates AND or OR with three-valued logic, skipping the right operand when the left
+/// one already decides the result
+fn evaluate_logical(
+    operator: &BinaryOperator,
+    left: &Expression,
+    right: &Expression,
+    row: &dyn Fn(&str) -> Option<Value>,
+) -> Result<Value, EvalError> {
+    // FALSE decides an AND, TRUE decides an OR
+    let decisive = *operator == BinaryOperator::Or;
+
+    let left = truth_value(operator, left.evaluate(row)?)?;
+    if left == Some(decisive) {
+        return Ok(Value::Boolean(decisive));
+    }
+    let right = truth_value(operator, right.evaluate(row)?)?;
+    if right == Some(decisive) {
+        return Ok(Value::Boolean(decisive));
+    }
+
+    match (left, right) {
+        (Some(_), Some(_)) => Ok(Value::Boolean(!decisive)),
+        _ => Ok(Value::Null),
+    }
+}
+
+/// Reads an operand of AND or OR, where NULL stands for unknown
+fn truth_value(operator: &BinaryOperator, value: Value) -> Result<Option<bool>, EvalError> {
+    match value {
+        Value::Boolean(b) => Ok(Some(b)),
+        Value::Null => Ok(None),
+        value => Err(EvalError::TypeMismatch(format!(
+            "{} expects booleans, found {}",
+            operator,
+            type_name(&value)
+        ))),
+    }
+}
+
+fn evaluate_binary(
+    left: Value,
+    operator: &BinaryOperator,
+    right: Value,
+) -> Result<Value, EvalError> {
+    if left == Value::Null || right == Value::Null {
+        return Ok(Value::Null);
+    }
+
+    let ordering = match operator {
+        BinaryOperator::Plus
+        | BinaryOperator::Minus
+        | BinaryOperator::Multiply
+        | BinaryOperator::Divide => return evaluate_arithmetic(left, operator, right),
+        BinaryOperator::Concat => return concatenate(left, right),
+        _ => compare(&left, &right).ok_or_else(|| {
+            EvalError::TypeMismatch(format!(
+                "cannot compare {} with {}",
+                type_name(&left),
+                type_name(&right)
+            ))
+        })?,
+    };
+
+    let result = match operator {
+        BinaryOperator::Equal => ordering == Ordering::Equal,
+        BinaryOperator::NotEqual => ordering != Ordering::Equal,
+        BinaryOperator::LessThan => ordering == Ordering::Less,
+        BinaryOperator::LessThanOrEqual => ordering != Ordering::Greater,
+        BinaryOperator::GreaterThan => ordering == Ordering::Greater,
+        BinaryOperator::GreaterThanOrEqual => ordering != Ordering::Less,
+        _ => unreachable!("arithmetic and logical operators are handled above"),
+    };
+    Ok(Value::Boolean(result))
+}
+
+fn evaluate_arithmetic(
+    left: Value,
+    operator: &BinaryOperator,
+    right: Value,
+) -> Result<Value, EvalError> {
+    let description = || format!("{} {} {}", left, operator, right);
+
+    match (&left, &right) {
+        (Value::Integer(l), Value::Integer(r)) => {
+            let result = match operator {
+                BinaryOperator::Plus => l.checked_add(*r),
+                BinaryOperator::Minus => l.checked_sub(*r),
+                BinaryOperator::Multiply => l.checked_mul(*r),
+                _ if *r == 0 => return Err(EvalError::DivisionByZero(description())),
+                _ => l.checked_div(*r),
+            };
+            result
+                .map(Value::Integer)
+                .ok_or_else(|| EvalError::Overflow(description()))
+        }
+        _ => {
+            let (l, r) = match (as_float(&left), as_float(&right)) {
+                (Some(l), Some(r)) => (l, r),
+                _ => {
+                    return Err(EvalError::TypeMismatch(format!(
+                        "cannot apply {} to {} and {}",
+                        operator,
+                        type_name(&left),
+                        type_name(&right)
+                    )))
+                }
+            };
+            let result = match operator {
+                BinaryOperator::Plus => l + r,
+                BinaryOperator::Minus => l - r,
+                BinaryOperator::Multiply => l * r,
+                _ if r == 0.0 => return Err(EvalError::DivisionByZero(description())),
+                _ => l / r,
+            };
+            Ok(Value::Float(result))
+        }
+    }
+}
+
+fn concatenate(left: Value, right: Value) -> Result<Value, EvalError> {
+    match (left, right) {
+        (Value::String(l), Value::String(r)) => Ok(Value::String(l + &r)),
+        (left, right) => Err(EvalError::TypeMismatch(format!(
+            "cannot concatenate {} and {}",
+            type_name(&left),
+            type_name(&right)
+        ))),
+    }
+}
+
+/// Evaluates `value [NOT] IN (list)`. As with a chain of `=` joined by OR, the result is
+/// NULL rather than FALSE when nothing matches but the value or an item is NULL.
+fn evaluate_in_list(
+    value: Value,
+    list: &[Expression],
+    negated: bool,
+    row: &dyn Fn(&str) -> Option<Value>,
+) -> Result<Value, EvalError> {
+    let mut unknown = value == Value::Null;
+    for item in list {
+        let item = item.evaluate(row)?;
+        if item == Value::Null || value == Value::Null {
+            unknown = true;
+            continue;
+        }
+        let ordering = compare(&value, &item).ok_or_else(|| {
+            EvalError::TypeMismatch(format!(
+                "cannot compare {} with {}",
+                type_name(&value),
+                type_name(&item)
+            ))
+        })?;
+        if ordering == Ordering::Equal {
+            return Ok(Value::Boolean(!negated));
+        }
+    }
+    Ok(if unknown {
+        Value::Null
+    } else {
+        Value::Boolean(negated)
+    })
+}
+
+/// Matches `text` against a LIKE pattern, where `%` matches any run of characters and `_`
+/// any single character. Matching is case-sensitive and has no escape character.
+fn like(text: &str, pattern: &str) -> bool {
+    let text: Vec<char> = text.chars().collect();
+    let pattern: Vec<char> = pattern.chars().collect();
+    let (mut t, mut p) = (0, 0);
+    // The pattern position after the latest `%` and the text position it matches up to, to
+    // retry from with the `%` taking one more character
+    let mut retry = None;
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('%') => {
+                p += 1;
+                retry = Some((p, t));
+            }
+            Some(&c) if c == '_' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match retry {
+                Some((after_percent, matched)) => {
+                    p = after_percent;
+                    t = matched + 1;
+                    retry = Some((after_percent, t));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '%')
+}
+
+/// Orders two non-NULL values of compatible types; integers and floats compare numerically
+fn compare(left: &Value, right: &Value) -> Option<Ordering> {
+    match (left, right) {
+        (Value::Integer(l), Value::Integer(r)) => Some(l.cmp(r)),
+        (Value::String(l), Value::String(r)) => Some(l.cmp(r)),
+        (Value::Boolean(l), Value::Boolean(r)) => Some(l.cmp(r)),
+        _ => as_float(left)?.partial_cmp(&as_float(right)?),
+    }
+}
+
+fn as_float(value: &Value) -> Option<f64> {
+    match value {
+        Value::Integer(n) => Some(*n as f64),
+        Value::Float(x) => Some(*x),
+        _ => None,
+    }
+}
+
+/// Converts `value` to one of the built-in types named by a `::` cast
+fn cast(value: Value, data_type: &DataType) -> Result<Value, EvalError> {
+    if value == Value::Null {
+        return Ok(Value::Null);
+    }
+    let mismatch = |value: &Value| {
+        EvalError::TypeMismatch(format!("cannot cast {} to {}", type_name(value), data_type))
+    };
+
+    match data_type {
+        DataType::Int | DataType::BigInt | DataType::SmallInt => {
+            let n = match &value {
+                Value::Integer(n) => *n,
+                Value::Float(x) if !x.is_finite() => return Err(mismatch(&value)),
+                // Fractions are an error rather than truncated, so no cast loses information
+                Value::Float(x) if x.fract() != 0.0 => {
+                    return Err(EvalError::TypeMismatch(format!(
+                        "cannot cast {} to {} without losing its fraction",
+                        x, data_type
+                    )))
+                }
+                // i64::MAX rounds up to 2^63 as a float, so the upper bound is exclusive
+                Value::Float(x) if *x >= i64::MIN as f64 && *x < i64::MAX as f64 => *x as i64,
+                Value::Float(x) => {
+                    return Err(EvalError::Overflow(format!(
+                        "{} does not fit in {}",
+                        x, data_type
+                    )))
+                }
+                Value::String(s) => s.trim().parse().map_err(|_| mismatch(&value))?,
+                Value::Boolean(b) => i64::from(*b),
+                _ => return Err(mismatch(&value)),
+            };
+            let fits = match data_type {
+                DataType::SmallInt => i16::try_from(n).is_ok(),
+                DataType::Int => i32::try_from(n).is_ok(),
+                _ => true,
+            };
+            if !fits {
+                return Err(EvalError::Overflow(format!(
+                    "{} does not fit in {}",
+                    n, data_type
+                )));
+            }
+            Ok(Value::Integer(n))
+        }
+        DataType::Float | DataType::Double | DataType::Decimal(..) => match &value {
+            Value::String(s) => s
+                .trim()
+                .parse()
+                .map(Value::Float)
+                .map_err(|_| mismatch(&value)),
+            _ => as_float(&value)
+                .map(Value::Float)
+                .ok_or_else(|| mismatch(&value)),
+        },
+        DataType::Text | DataType::Varchar(_) | DataType::Char(_) => {
+            Ok(Value::String(value.to_string()))
+        }
+        DataType::Boolean => match &value {
+            Value::Boolean(_) => Ok(value),
+            Value::Integer(n) => Ok(Value::Boolean(*n != 0)),
+            Value::String(s) => match s.trim().to_lowercase().as_str() {
+                "true" | "t" | "yes" | "on" | "1" => Ok(Value::Boolean(true)),
+                "false" | "f" | "no" | "off" | "0" => Ok(Value::Boolean(false)),
+                _ => Err(mismatch(&value)),
+            },
+            _ => Err(mismatch(&value)),
+        },
+        _ => Err(EvalError::Unsupported(format!("cast to {}", data_type))),
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Integer(_) => "integer",
+        Value::Float(_) => "float",
+        Value::String(_) => "string",
+        Value::Boolean(_) => "boolean",
+        Value::Null => "NULL",
+    }
+}
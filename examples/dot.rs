@@ -0,0 +1,23 @@
+//! Prints the Graphviz DOT graph of a SQL statement given on the command line, e.g.
+//! `cargo run --example dot -- "SELECT a FROM t WHERE b > 1" | dot -Tpng -o ast.png`
+
+use std::env;
+use std::process;
+
+use rust_sql_parser::parser::PrattParser;
+
+fn main() {
+    let sql = env::args().skip(1).collect::<Vec<_>>().join(" ");
+    if sql.trim().is_empty() {
+        eprintln!("usage: dot <sql>");
+        process::exit(2);
+    }
+
+    match PrattParser::new(&sql).parse_statement() {
+        Ok(statement) => print!("{}", statement.to_dot()),
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,280 @@
+//! C interface for embedding the parser in other languages, enabled by the `ffi` feature.
+//! `include/sqlp.h` is generated from this module with `cbindgen --config cbindgen.toml`.
+//!
+//! Ownership follows one rule: whatever a `sqlp_` function returns through a `*mut`
+//! pointer belongs to the caller and goes back through the matching `sqlp_free_` function,
+//! while `*const` results are borrowed from their argument. Every function accepts null
+//! pointers, returning null or an empty value for them, and no panic unwinds into C.
+
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+
+use crate::ast::Statement;
+use crate::error::{ErrorKind, ParseError};
+use crate::options::ParserOptions;
+use crate::parser::PrattParser;
+
+/// The deepest expression nesting `sqlp_parse` accepts. The parser recurses once per level
+/// of parentheses or subqueries, and running out of stack would abort the host process
+/// rather than fail the call.
+const MAX_EXPRESSION_DEPTH: usize = 100;
+
+/// A parsed statement, created by `sqlp_parse` and released with `sqlp_free_statement`
+pub struct SqlpStatement {
+    statement: Statement,
+    tables: Vec<CString>,
+}
+
+/// Describes why `sqlp_parse` failed. `line` and `column` count from 1 and are 0 when the
+/// error has no position.
+#[repr(C)]
+pub struct SqlpError {
+    /// The stable code of the error kind, such as `"E0001"`; a static string that must not
+    /// be freed
+    pub code: *const c_char,
+    /// The error message, owned by the error until `sqlp_free_error`
+    pub message: *mut c_char,
+    pub line: usize,
+    pub column: usize,
+}
+
+/// The kind of a statement; `Invalid` is returned for a null statement
+#[repr(C)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum SqlpStatementKind {
+    Invalid,
+    Query,
+    Insert,
+    Update,
+    Delete,
+    CreateTable,
+    CreateView,
+    CreateIndex,
+    AlterTable,
+    Drop,
+    Truncate,
+    /// Any of BEGIN, COMMIT, ROLLBACK, SAVEPOINT and RELEASE
+    Transaction,
+    Show,
+    Describe,
+    Pragma,
+    Merge,
+}
+
+/// Parses `sql`, a NUL-terminated UTF-8 string holding one statement optionally followed
+/// by a semicolon. Returns the statement, or null on failure after filling in `out_err`
+/// if it is not null; the caller then owns the error's message. Expressions nested more
+/// than 100 levels deep are rejected with the limit error `"E0006"`.
+///
+/// # Safety
+///
+/// `sql` must be null or point to a NUL-terminated string, and `out_err` must be null or
+/// point to writable memory for a `SqlpError`.
+#[no_mangle]
+pub unsafe extern "C" fn sqlp_parse(
+    sql: *const c_char,
+    out_err: *mut SqlpError,
+) -> *mut SqlpStatement {
+    let result = catch(|| {
+        if sql.is_null() {
+            return Err(invalid_input("SQL is a null pointer"));
+        }
+        let sql = match CStr::from_ptr(sql).to_str() {
+            Ok(sql) => sql,
+            Err(_) => return Err(invalid_input("SQL is not valid UTF-8")),
+        };
+        let options = ParserOptions::new().with_max_expression_depth(MAX_EXPRESSION_DEPTH);
+        let statement = PrattParser::new_with_options(sql, options)
+            .parse_statement()
+            .map_err(|e| error(&e))?;
+        let tables = statement
+            .referenced_tables()
+            .iter()
+            .filter_map(|table| CString::new(table.to_string()).ok())
+            .collect();
+        Ok(Box::into_raw(Box::new(SqlpStatement { statement, tables })))
+    })
+    .unwrap_or_else(|| Err(invalid_input("the parser panicked")));
+
+    match result {
+        Ok(statement) => statement,
+        Err(e) => {
+            if out_err.is_null() {
+                free_message(e.message);
+            } else {
+                out_err.write(e);
+            }
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Returns the kind of `statement`
+///
+/// # Safety
+///
+/// `statement` must be null or a statement returned by `sqlp_parse` and not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn sqlp_statement_kind(statement: *const SqlpStatement) -> SqlpStatementKind {
+    let statement = match statement.as_ref() {
+        Some(statement) => &statement.statement,
+        None => return SqlpStatementKind::Invalid,
+    };
+    match statement {
+        Statement::Query(_) => SqlpStatementKind::Query,
+        Statement::Insert { .. } => SqlpStatementKind::Insert,
+        Statement::Update { .. } => SqlpStatementKind::Update,
+        Statement::Delete { .. } => SqlpStatementKind::Delete,
+        Statement::CreateTable { .. } => SqlpStatementKind::CreateTable,
+        Statement::CreateView { .. } => SqlpStatementKind::CreateView,
+        Statement::CreateIndex { .. } => SqlpStatementKind::CreateIndex,
+        Statement::AlterTable { .. } => SqlpStatementKind::AlterTable,
+        Statement::Drop { .. } => SqlpStatementKind::Drop,
+        Statement::Truncate { .. } => SqlpStatementKind::Truncate,
+        Statement::StartTransaction { .. }
+        | Statement::Commit
+        | Statement::Rollback { .. }
+        | Statement::Savepoint { .. }
+        | Statement::ReleaseSavepoint { .. } => SqlpStatementKind::Transaction,
+        Statement::Show { .. } => SqlpStatementKind::Show,
+        Statement::Describe { .. } => SqlpStatementKind::Describe,
+        Statement::Pragma { .. } => SqlpStatementKind::Pragma,
+        Statement::Merge { .. } => SqlpStatementKind::Merge,
+    }
+}
+
+/// Returns how many tables `statement` reads or writes, as listed by `sqlp_table_name`
+///
+/// # Safety
+///
+/// `statement` must be null or a statement returned by `sqlp_parse` and not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn sqlp_table_count(statement: *const SqlpStatement) -> usize {
+    statement
+        .as_ref()
+        .map_or(0, |statement| statement.tables.len())
+}
+
+/// Returns the name of the `index`th table `statement` references, in order of first
+/// appearance, or null when `index` is out of range. The name is borrowed from the
+/// statement and stays valid until it is freed.
+///
+/// # Safety
+///
+/// `statement` must be null or a statement returned by `sqlp_parse` and not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn sqlp_table_name(
+    statement: *const SqlpStatement,
+    index: usize,
+) -> *const c_char {
+    statement
+        .as_ref()
+        .and_then(|statement| statement.tables.get(index))
+        .map_or(ptr::null(), |name| name.as_ptr())
+}
+
+/// Returns `statement` printed as SQL in canonical form, to be released with
+/// `sqlp_free_string`, or null if `statement` is null
+///
+/// # Safety
+///
+/// `statement` must be null or a statement returned by `sqlp_parse` and not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn sqlp_to_sql(statement: *const SqlpStatement) -> *mut c_char {
+    let statement = match statement.as_ref() {
+        Some(statement) => &statement.statement,
+        None => return ptr::null_mut(),
+    };
+    catch(|| CString::new(statement.to_string()).ok())
+        .flatten()
+        .map_or(ptr::null_mut(), CString::into_raw)
+}
+
+/// Releases a statement returned by `sqlp_parse`, along with its table names
+///
+/// # Safety
+///
+/// `statement` must be null or a statement returned by `sqlp_parse` and not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn sqlp_free_statement(statement: *mut SqlpStatement) {
+    if !statement.is_null() {
+        drop(Box::from_raw(statement));
+    }
+}
+
+/// Releases a string returned by `sqlp_to_sql`
+///
+/// # Safety
+///
+/// `s` must be null or a string returned by `sqlp_to_sql` and not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn sqlp_free_string(s: *mut c_char) {
+    free_message(s);
+}
+
+/// Releases the message of an error filled in by `sqlp_parse` and clears the error, so
+/// that freeing it twice is harmless
+///
+/// # Safety
+///
+/// `err` must be null or point to a `SqlpError` filled in by `sqlp_parse` or cleared by
+/// this function.
+#[no_mangle]
+pub unsafe extern "C" fn sqlp_free_error(err: *mut SqlpError) {
+    if let Some(err) = err.as_mut() {
+        free_message(err.message);
+        err.message = ptr::null_mut();
+        err.code = ptr::null();
+        err.line = 0;
+        err.column = 0;
+    }
+}
+
+/// Runs `f`, returning `None` if it panics, so that no panic unwinds into C
+fn catch<T>(f: impl FnOnce() -> T) -> Option<T> {
+    panic::catch_unwind(AssertUnwindSafe(f)).ok()
+}
+
+unsafe fn free_message(message: *mut c_char) {
+    if !message.is_null() {
+        drop(CString::from_raw(message));
+    }
+}
+
+fn error(e: &ParseError) -> SqlpError {
+    let span = e.span();
+    SqlpError {
+        code: code(e.kind()).as_ptr(),
+        message: message(&e.to_string()),
+        line: span.map_or(0, |span| span.start.line),
+        column: span.map_or(0, |span| span.start.column),
+    }
+}
+
+fn invalid_input(text: &str) -> SqlpError {
+    SqlpError {
+        code: code(ErrorKind::InvalidInput).as_ptr(),
+        message: message(text),
+        line: 0,
+        column: 0,
+    }
+}
+
+/// Copies `text` into a C string, dropping any NUL characters it contains
+fn message(text: &str) -> *mut c_char {
+    let text: String = text.chars().filter(|&c| c != '\0').collect();
+    CString::new(text).map_or(ptr::null_mut(), CString::into_raw)
+}
+
+fn code(kind: ErrorKind) -> &'static CStr {
+    match kind {
+        ErrorKind::UnexpectedToken => c"E0001",
+        ErrorKind::UnexpectedEof => c"E0002",
+        ErrorKind::InvalidCharacter => c"E0003",
+        ErrorKind::UnterminatedString => c"E0004",
+        ErrorKind::InvalidLiteral => c"E0005",
+        ErrorKind::LimitExceeded => c"E0006",
+        ErrorKind::InvalidInput => c"E0007",
+    }
+}
@@ -0,0 +1,642 @@
+use std::fmt;
+
+use crate::ast::{
+    Assignment, BinaryOperator, ColumnDef, DisplayIdent, DisplayName, Expression, InsertSource,
+    Join, JoinConstraint, JoinType, Query, SelectItem, SelectStatement, SetExpr, Statement,
+    TableConstraint, TableReference, TableWithJoins, Token, With,
+};
+use crate::options::{FormatOptions, KeywordCase};
+use crate::tokenizer::Tokenizer;
+
+/// Formats `statement` as multi-line SQL: each clause of a query starts a line, and
+/// subqueries, lists and AND/OR chains that do not fit within `options.max_line_width` are
+/// broken over indented lines. The output depends only on the tree, so formatting it again
+/// after parsing it back gives the same text.
+pub fn format_statement(statement: &Statement, options: &FormatOptions) -> String {
+    let mut formatter = Formatter {
+        options,
+        out: String::new(),
+        level: 0,
+    };
+    formatter.statement(statement);
+    match options.keyword_case {
+        KeywordCase::Upper => formatter.out,
+        // PRAGMA values are kept as written, even when they are keywords
+        KeywordCase::Lower if matches!(statement, Statement::Pragma { .. }) => formatter.out,
+        KeywordCase::Lower => lower_keywords(&formatter.out),
+    }
+}
+
+/// Represents how the items of a list are laid out after their keyword
+#[derive(Clone, Copy)]
+enum Layout {
+    /// One item per line, unless there is only one
+    Lines,
+    /// As many items per line as fit
+    Packed,
+    /// All items on the keyword's line if they fit, otherwise one per line
+    Fit,
+}
+
+struct Formatter<'a> {
+    options: &'a FormatOptions,
+    out: String,
+    /// Indentation level of the lines started by `line`
+    level: usize,
+}
+
+impl Formatter<'_> {
+    fn push(&mut self, text: &str) {
+        self.out.push_str(text);
+    }
+
+    /// Starts a new line at the current indentation
+    fn line(&mut self) {
+        self.out.push('\n');
+        let width = self.level * self.options.indent_width;
+        self.out.push_str(&" ".repeat(width));
+    }
+
+    fn indented(&mut self, write: impl FnOnce(&mut Self)) {
+        self.level += 1;
+        write(self);
+        self.level -= 1;
+    }
+
+    /// Whether `text` fits on the current line
+    fn fits(&self, text: &str) -> bool {
+        let column = self
+            .out
+            .rsplit('\n')
+            .next()
+            .map_or(0, |line| line.chars().count());
+        column + text.chars().count() <= self.options.max_line_width
+    }
+
+    /// Writes `items` after the keyword just written, separated by commas
+    fn list<T: fmt::Display>(
+        &mut self,
+        items: &[T],
+        layout: Layout,
+        render: impl Fn(&mut Self, &T),
+    ) {
+        let flat: Vec<String> = items.iter().map(ToString::to_string).collect();
+        let leading = self.options.leading_commas;
+        match layout {
+            Layout::Fit if self.fits(&format!(" {}", flat.join(", "))) => {
+                self.push(" ");
+                self.push(&flat.join(", "));
+            }
+            _ if items.len() == 1 => {
+                self.push(" ");
+                render(self, &items[0]);
+            }
+            Layout::Packed => self.indented(|f| {
+                f.push(" ");
+                for (i, item) in flat.iter().enumerate() {
+                    if i == 0 {
+                        f.push(item);
+                    } else if leading {
+                        let next = format!(", {}", item);
+                        if !f.fits(&next) {
+                            f.line();
+                        }
+                        f.push(&next);
+                    } else {
+                        f.push(",");
+                        if !f.fits(&format!(" {}", item)) {
+                            f.line();
+                        } else {
+                            f.push(" ");
+                        }
+                        f.push(item);
+                    }
+                }
+            }),
+            _ => self.indented(|f| {
+                for (i, item) in items.iter().enumerate() {
+                    f.line();
+                    if leading && i > 0 {
+                        f.push(", ");
+                    }
+                    render(f, item);
+                    if !leading && i + 1 < items.len() {
+                        f.push(",");
+                    }
+                }
+            }),
+        }
+    }
+
+    /// Writes the separator between two items of a list that is always one per line
+    fn separator(&mut self) {
+        if self.options.leading_commas {
+            self.line();
+            self.push(", ");
+        } else {
+            self.push(",");
+            self.line();
+        }
+    }
+
+    fn statement(&mut self, statement: &Statement) {
+        match statement {
+            Statement::Query(query) => self.query(query),
+            Statement::Insert {
+                table,
+                columns,
+                source,
+                on_conflict,
+                returning,
+            } => {
+                self.push(&format!("INSERT INTO {}", table));
+                if !columns.is_empty() {
+                    self.push(&format!(" ({})", idents(columns)));
+                }
+                self.line();
+                match source {
+                    InsertSource::Values(rows) => self.values(rows),
+                    InsertSource::Query(query) => self.query(query),
+                }
+                if let Some(on_conflict) = on_conflict {
+                    self.line();
+                    self.push(&on_conflict.to_string());
+                }
+                self.returning(returning);
+            }
+            Statement::Update {
+                table,
+                assignments,
+                from,
+                selection,
+                returning,
+            } => {
+                self.push("UPDATE ");
+                self.table_reference(table);
+                self.line();
+                self.push("SET");
+                self.list(assignments, Layout::Fit, Self::assignment);
+                if let Some(from) = from {
+                    self.line();
+                    self.push("FROM ");
+                    self.from(from);
+                }
+                self.clause_expression("WHERE", selection);
+                self.returning(returning);
+            }
+            Statement::Delete {
+                table,
+                using,
+                selection,
+                returning,
+            } => {
+                self.push("DELETE FROM ");
+                self.table_reference(table);
+                if let Some(using) = using {
+                    self.line();
+                    self.push("USING ");
+                    self.from(using);
+                }
+                self.clause_expression("WHERE", selection);
+                self.returning(returning);
+            }
+            Statement::CreateTable {
+                temporary,
+                if_not_exists,
+                name,
+                columns,
+                constraints,
+                query,
+            } => {
+                self.push("CREATE ");
+                if *temporary {
+                    self.push("TEMPORARY ");
+                }
+                self.push("TABLE ");
+                if *if_not_exists {
+                    self.push("IF NOT EXISTS ");
+                }
+                self.push(&name.to_string());
+                if columns.is_empty() && constraints.is_empty() {
+                    if query.is_none() {
+                        self.push(" ()");
+                    }
+                } else {
+                    self.push(" (");
+                    self.indented(|f| {
+                        let elements = columns
+                            .iter()
+                            .map(ColumnDef::to_string)
+                            .chain(constraints.iter().map(TableConstraint::to_string));
+                        for (i, element) in elements.enumerate() {
+                            if i == 0 {
+                                f.line();
+                            } else {
+                                f.separator();
+                            }
+                            f.push(&element);
+                        }
+                    });
+                    self.line();
+                    self.push(")");
+                }
+                if let Some(query) = query {
+                    self.push(" AS");
+                    self.line();
+                    self.query(query);
+                }
+            }
+            Statement::CreateView {
+                name,
+                columns,
+                query,
+                or_replace,
+                materialized,
+            } => {
+                self.push("CREATE ");
+                if *or_replace {
+                    self.push("OR REPLACE ");
+                }
+                if *materialized {
+                    self.push("MATERIALIZED ");
+                }
+                self.push(&format!("VIEW {}", name));
+                if !columns.is_empty() {
+                    self.push(&format!(" ({})", idents(columns)));
+                }
+                self.push(" AS");
+                self.line();
+                self.query(query);
+            }
+            Statement::Merge {
+                target,
+                source,
+                on,
+                clauses,
+            } => {
+                self.push("MERGE INTO ");
+                self.table_reference(target);
+                self.line();
+                self.push("USING ");
+                self.table_reference(source);
+                self.line();
+                self.push("ON ");
+                self.expression(on);
+                for clause in clauses {
+                    self.line();
+                    self.push(&clause.to_string());
+                }
+            }
+            // The remaining statements are short enough to keep on one line
+            statement => self.push(&statement.to_string()),
+        }
+    }
+
+    fn query(&mut self, query: &Query) {
+        if let Some(with) = &query.with {
+            self.with(with);
+            self.line();
+        }
+        self.set_expr(&query.body);
+        if !query.order_by.is_empty() {
+            self.line();
+            self.push("ORDER BY");
+            self.list(&query.order_by, Layout::Fit, |f, item| {
+                f.push(&item.to_string())
+            });
+        }
+        if query.with_ties {
+            // WITH TIES can only be spelled with the ANSI FETCH form
+            if let Some(offset) = &query.offset {
+                self.line();
+                self.push(&format!("OFFSET {} ROWS", offset));
+            }
+            if let Some(limit) = &query.limit {
+                self.line();
+                self.push(&format!("FETCH FIRST {} ROWS WITH TIES", limit));
+            }
+        } else {
+            if let Some(limit) = &query.limit {
+                self.line();
+                self.push(&format!("LIMIT {}", limit));
+            }
+            if let Some(offset) = &query.offset {
+                self.line();
+                self.push(&format!("OFFSET {}", offset));
+            }
+        }
+        if let Some(locking) = &query.locking {
+            self.line();
+            self.push(&locking.to_string());
+        }
+    }
+
+    fn with(&mut self, with: &With) {
+        self.push("WITH ");
+        if with.recursive {
+            self.push("RECURSIVE ");
+        }
+        for (i, cte) in with.ctes.iter().enumerate() {
+            if i > 0 {
+                self.separator();
+            }
+            self.push(&DisplayIdent(&cte.name).to_string());
+            if !cte.columns.is_empty() {
+                self.push(&format!(" ({})", idents(&cte.columns)));
+            }
+            self.push(" AS ");
+            self.subquery(&cte.query);
+        }
+    }
+
+    /// Writes a parenthesized query, on one line if it fits
+    fn subquery(&mut self, query: &Query) {
+        let flat = format!("({})", query);
+        if self.fits(&flat) {
+            self.push(&flat);
+            return;
+        }
+        self.push("(");
+        self.indented(|f| {
+            f.line();
+            f.query(query);
+        });
+        self.line();
+        self.push(")");
+    }
+
+    fn set_expr(&mut self, body: &SetExpr) {
+        match body {
+            SetExpr::Select(select) => self.select(select),
+            SetExpr::Query(query) => self.subquery(query),
+            SetExpr::SetOperation {
+                op,
+                all,
+                left,
+                right,
+            } => {
+                let precedence = body.precedence();
+                self.set_operand(left, left.precedence() < precedence);
+                self.line();
+                self.push(&op.to_string());
+                if *all {
+                    self.push(" ALL");
+                }
+                self.line();
+                self.set_operand(right, right.precedence() <= precedence);
+            }
+            SetExpr::Values(rows) => self.values(rows),
+        }
+    }
+
+    fn set_operand(&mut self, operand: &SetExpr, parenthesize: bool) {
+        if !parenthesize {
+            return self.set_expr(operand);
+        }
+        self.push("(");
+        self.indented(|f| {
+            f.line();
+            f.set_expr(operand);
+        });
+        self.line();
+        self.push(")");
+    }
+
+    fn values(&mut self, rows: &[Vec<Expression>]) {
+        let rows: Vec<String> = rows
+            .iter()
+            .map(|row| {
+                let values: Vec<String> = row.iter().map(ToString::to_string).collect();
+                format!("({})", values.join(", "))
+            })
+            .collect();
+        self.push("VALUES");
+        self.list(&rows, Layout::Fit, |f, row| f.push(row));
+    }
+
+    fn select(&mut self, select: &SelectStatement) {
+        self.push("SELECT");
+        if let Some(distinct) = &select.distinct {
+            self.push(&format!(" {}", distinct));
+        }
+        if let Some(top) = &select.top {
+            self.push(&format!(" {}", top));
+        }
+        let layout = if self.options.one_item_per_line {
+            Layout::Lines
+        } else {
+            Layout::Packed
+        };
+        self.list(&select.projection, layout, Self::select_item);
+        if let Some(from) = &select.from {
+            self.line();
+            self.push("FROM ");
+            self.from(from);
+        }
+        self.clause_expression("WHERE", &select.selection);
+        if !select.group_by.is_empty() {
+            self.line();
+            self.push("GROUP BY");
+            self.list(&select.group_by, Layout::Fit, |f, item| {
+                f.push(&item.to_string())
+            });
+        }
+        self.clause_expression("HAVING", &select.having);
+    }
+
+    fn select_item(&mut self, item: &SelectItem) {
+        match item {
+            SelectItem::Expression(expr) => self.expression(expr),
+            SelectItem::ExprWithAlias { expr, alias } => {
+                self.expression(expr);
+                self.push(&format!(" AS {}", DisplayName(alias)));
+            }
+            item => self.push(&item.to_string()),
+        }
+    }
+
+    fn assignment(&mut self, assignment: &Assignment) {
+        let column: Vec<String> = assignment
+            .column
+            .iter()
+            .map(|part| DisplayIdent(part).to_string())
+            .collect();
+        self.push(&format!("{} = ", column.join(".")));
+        self.expression(&assignment.value);
+    }
+
+    fn returning(&mut self, returning: &Option<Vec<SelectItem>>) {
+        if let Some(items) = returning {
+            self.line();
+            self.push("RETURNING");
+            self.list(items, Layout::Fit, Self::select_item);
+        }
+    }
+
+    /// Writes a clause such as WHERE on its own line, if present
+    fn clause_expression(&mut self, keyword: &str, expr: &Option<Expression>) {
+        if let Some(expr) = expr {
+            self.line();
+            self.push(&format!("{} ", keyword));
+            self.expression(expr);
+        }
+    }
+
+    /// Writes the tables of a FROM clause, starting each join on its own indented line
+    fn from(&mut self, from: &TableWithJoins) {
+        self.table_reference(&from.relation);
+        self.indented(|f| {
+            for join in &from.joins {
+                match join.join_type {
+                    JoinType::Implicit => f.separator(),
+                    _ => f.line(),
+                }
+                f.join(join);
+            }
+        });
+    }
+
+    fn join(&mut self, join: &Join) {
+        if join.constraint == JoinConstraint::Natural {
+            self.push("NATURAL ");
+        }
+        self.push(match join.join_type {
+            JoinType::Inner => "JOIN ",
+            JoinType::LeftOuter => "LEFT JOIN ",
+            JoinType::RightOuter => "RIGHT JOIN ",
+            JoinType::FullOuter => "FULL JOIN ",
+            JoinType::Cross => "CROSS JOIN ",
+            JoinType::Implicit => "",
+        });
+        self.table_reference(&join.relation);
+        match &join.constraint {
+            JoinConstraint::On(expr) => {
+                self.push(" ON ");
+                self.expression(expr);
+            }
+            JoinConstraint::Using(columns) => self.push(&format!(" USING ({})", idents(columns))),
+            JoinConstraint::Natural | JoinConstraint::None => {}
+        }
+    }
+
+    fn table_reference(&mut self, table: &TableReference) {
+        match table {
+            TableReference::Derived { subquery, alias } => {
+                self.subquery(subquery);
+                if let Some(alias) = alias {
+                    self.push(&format!(" {}", alias));
+                }
+            }
+            table => self.push(&table.to_string()),
+        }
+    }
+
+    /// Writes `expr` on the current line if it fits; otherwise breaks AND and OR chains
+    /// before each operator and lays out subqueries over several lines
+    fn expression(&mut self, expr: &Expression) {
+        let flat = expr.to_string();
+        if self.fits(&flat) {
+            return self.push(&flat);
+        }
+        match expr {
+            Expression::BinaryOperation {
+                operator: operator @ (BinaryOperator::And | BinaryOperator::Or),
+                ..
+            } => {
+                // Operators associate to the left, so the chain runs down the left operands
+                let mut operands = vec![];
+                let mut left = expr;
+                while let Expression::BinaryOperation {
+                    left_operand,
+                    operator: op,
+                    right_operand,
+                } = left
+                {
+                    if op != operator {
+                        break;
+                    }
+                    operands.push(&**right_operand);
+                    left = left_operand;
+                }
+                let precedence = operator.precedence();
+                self.operand(left, left.precedence() < precedence);
+                self.indented(|f| {
+                    for operand in operands.into_iter().rev() {
+                        f.line();
+                        f.push(&format!("{} ", operator));
+                        f.operand(operand, right_parenthesized(operand, precedence));
+                    }
+                });
+            }
+            Expression::BinaryOperation {
+                left_operand,
+                operator,
+                right_operand,
+            } => {
+                self.operand(left_operand, operator.parenthesizes_left(left_operand));
+                self.push(&format!(" {} ", operator));
+                self.operand(
+                    right_operand,
+                    right_parenthesized(right_operand, operator.precedence()),
+                );
+            }
+            Expression::Subquery(query) => self.subquery(query),
+            _ => self.push(&flat),
+        }
+    }
+
+    fn operand(&mut self, operand: &Expression, parenthesize: bool) {
+        if !parenthesize {
+            return self.expression(operand);
+        }
+        let flat = format!("({})", operand);
+        if self.fits(&flat) {
+            return self.push(&flat);
+        }
+        self.push("(");
+        self.indented(|f| {
+            f.line();
+            f.expression(operand);
+        });
+        self.line();
+        self.push(")");
+    }
+}
+
+/// Whether the right operand of an operator binding at `precedence` needs parentheses,
+/// following `Expression`'s `Display`
+fn right_parenthesized(operand: &Expression, precedence: u8) -> bool {
+    match operand {
+        Expression::UnaryOperation { .. } => operand.precedence() < precedence,
+        _ => operand.precedence() <= precedence,
+    }
+}
+
+fn idents(names: &[String]) -> String {
+    let names: Vec<String> = names
+        .iter()
+        .map(|name| DisplayIdent(name).to_string())
+        .collect();
+    names.join(", ")
+}
+
+/// Lower-cases the keywords of `sql`, leaving names, literals and layout untouched
+fn lower_keywords(sql: &str) -> String {
+    let mut tokenizer = Tokenizer::new(sql);
+    let mut out = String::with_capacity(sql.len());
+    let mut copied = 0;
+    loop {
+        match tokenizer.next() {
+            Some(Token::Keyword(_)) => {
+                let span = tokenizer.span();
+                out.push_str(&sql[copied..span.start.offset]);
+                out.push_str(&sql[span.start.offset..span.end.offset].to_lowercase());
+                copied = span.end.offset;
+            }
+            Some(Token::Eof) | None => break,
+            Some(_) => {}
+        }
+    }
+    out.push_str(&sql[copied..]);
+    out
+}
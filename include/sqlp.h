@@ -0,0 +1,149 @@
+/*
+ * C interface of rust_sql_parser, built with the `ffi` feature.
+ *
+ * Ownership: a pointer returned as non-const belongs to the caller and must be released
+ * with the matching sqlp_free_ function. A const pointer is borrowed from the argument it
+ * came from and stays valid until that argument is freed. Every function accepts NULL,
+ * and no Rust panic unwinds into the caller.
+ */
+
+#ifndef SQLP_H
+#define SQLP_H
+
+/* Generated by cbindgen from ffi.rs; do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * The kind of a statement; `Invalid` is returned for a null statement
+ */
+typedef enum SqlpStatementKind {
+  SQLP_STATEMENT_KIND_INVALID,
+  SQLP_STATEMENT_KIND_QUERY,
+  SQLP_STATEMENT_KIND_INSERT,
+  SQLP_STATEMENT_KIND_UPDATE,
+  SQLP_STATEMENT_KIND_DELETE,
+  SQLP_STATEMENT_KIND_CREATE_TABLE,
+  SQLP_STATEMENT_KIND_CREATE_VIEW,
+  SQLP_STATEMENT_KIND_CREATE_INDEX,
+  SQLP_STATEMENT_KIND_ALTER_TABLE,
+  SQLP_STATEMENT_KIND_DROP,
+  SQLP_STATEMENT_KIND_TRUNCATE,
+  /**
+   * Any of BEGIN, COMMIT, ROLLBACK, SAVEPOINT and RELEASE
+   */
+  SQLP_STATEMENT_KIND_TRANSACTION,
+  SQLP_STATEMENT_KIND_SHOW,
+  SQLP_STATEMENT_KIND_DESCRIBE,
+  SQLP_STATEMENT_KIND_PRAGMA,
+  SQLP_STATEMENT_KIND_MERGE,
+} SqlpStatementKind;
+
+/**
+ * A parsed statement, created by `sqlp_parse` and released with `sqlp_free_statement`
+ */
+typedef struct SqlpStatement SqlpStatement;
+
+/**
+ * Describes why `sqlp_parse` failed. `line` and `column` count from 1 and are 0 when the
+ * error has no position.
+ */
+typedef struct SqlpError {
+  /**
+   * The stable code of the error kind, such as `"E0001"`; a static string that must not
+   * be freed
+   */
+  const char *code;
+  /**
+   * The error message, owned by the error until `sqlp_free_error`
+   */
+  char *message;
+  uintptr_t line;
+  uintptr_t column;
+} SqlpError;
+
+/**
+ * Parses `sql`, a NUL-terminated UTF-8 string holding one statement optionally followed
+ * by a semicolon. Returns the statement, or null on failure after filling in `out_err`
+ * if it is not null; the caller then owns the error's message. Expressions nested more
+ * than 100 levels deep are rejected with the limit error `"E0006"`.
+ *
+ * # Safety
+ *
+ * `sql` must be null or point to a NUL-terminated string, and `out_err` must be null or
+ * point to writable memory for a `SqlpError`.
+ */
+SqlpStatement *sqlp_parse(const char *sql, SqlpError *out_err);
+
+/**
+ * Returns the kind of `statement`
+ *
+ * # Safety
+ *
+ * `statement` must be null or a statement returned by `sqlp_parse` and not yet freed.
+ */
+SqlpStatementKind sqlp_statement_kind(const SqlpStatement *statement);
+
+/**
+ * Returns how many tables `statement` reads or writes, as listed by `sqlp_table_name`
+ *
+ * # Safety
+ *
+ * `statement` must be null or a statement returned by `sqlp_parse` and not yet freed.
+ */
+uintptr_t sqlp_table_count(const SqlpStatement *statement);
+
+/**
+ * Returns the name of the `index`th table `statement` references, in order of first
+ * appearance, or null when `index` is out of range. The name is borrowed from the
+ * statement and stays valid until it is freed.
+ *
+ * # Safety
+ *
+ * `statement` must be null or a statement returned by `sqlp_parse` and not yet freed.
+ */
+const char *sqlp_table_name(const SqlpStatement *statement, uintptr_t index);
+
+/**
+ * Returns `statement` printed as SQL in canonical form, to be released with
+ * `sqlp_free_string`, or null if `statement` is null
+ *
+ * # Safety
+ *
+ * `statement` must be null or a statement returned by `sqlp_parse` and not yet freed.
+ */
+char *sqlp_to_sql(const SqlpStatement *statement);
+
+/**
+ * Releases a statement returned by `sqlp_parse`, along with its table names
+ *
+ * # Safety
+ *
+ * `statement` must be null or a statement returned by `sqlp_parse` and not yet freed.
+ */
+void sqlp_free_statement(SqlpStatement *statement);
+
+/**
+ * Releases a string returned by `sqlp_to_sql`
+ *
+ * # Safety
+ *
+ * `s` must be null or a string returned by `sqlp_to_sql` and not yet freed.
+ */
+void sqlp_free_string(char *s);
+
+/**
+ * Releases the message of an error filled in by `sqlp_parse` and clears the error, so
+ * that freeing it twice is harmless
+ *
+ * # Safety
+ *
+ * `err` must be null or point to a `SqlpError` filled in by `sqlp_parse` or cleared by
+ * this function.
+ */
+void sqlp_free_error(SqlpError *err);
+
+#endif /* SQLP_H */
@@ -0,0 +1,267 @@
+use std::ops::Range;
+
+use crate::ast::Statement;
+use crate::error::ParseError;
+use crate::options::ParserOptions;
+use crate::source_map::SourceMap;
+use crate::stream::{parse_piece, Splitter};
+use crate::tokenizer::Location;
+
+/// Identifies a statement of a `ParsedScript` across edits
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, PartialOrd, Ord)]
+pub struct StatementId(u64);
+
+/// Represents a `;`-separated script that is kept parsed as it is edited, for editors that
+/// reparse on every keystroke. The script is split into statements the way
+/// `StatementStream` splits its input, and each statement is parsed on its own, so an edit
+/// only reparses the statements it touches, along with those after it until the statement
+/// boundaries line up with the old ones again. The result is always the same as parsing the
+/// edited script from scratch, apart from the ids.
+///
+/// ```
+/// use rust_sql_parser::incremental::ParsedScript;
+///
+/// let mut script = ParsedScript::new("SELECT 1; SELECT 2; SELECT 3;");
+/// let second = script.ids().nth(1).unwrap();
+/// let changed = script.apply_edit(17..18, "x + 1");
+/// assert_eq!(changed, [second]);
+/// assert_eq!(script.statement(second).unwrap().to_string(), "SELECT x + 1");
+/// ```
+pub struct ParsedScript {
+    map: SourceMap,
+    options: ParserOptions,
+    pieces: Vec<Piece>,
+    next_id: u64,
+    /// The statements reported by the last edit
+    changed: Vec<StatementId>,
+}
+
+/// Represents the text after one statement up to and including the semicolon ending the
+/// next. Together the pieces cover the whole script.
+struct Piece {
+    id: StatementId,
+    range: Range<usize>,
+    /// Whether the piece ends with a semicolon; only the last one may not
+    terminated: bool,
+    /// The length of the whitespace and comments before the statement
+    leading: usize,
+    /// The number the statement was parsed as, which appears in its error messages
+    number: usize,
+    /// The statement, with error spans relative to the piece, or `None` if the piece holds
+    /// none
+    result: Option<Result<Statement, ParseError>>,
+}
+
+impl ParsedScript {
+    pub fn new(source: &str) -> Self {
+        Self::new_with_options(source, ParserOptions::default())
+    }
+
+    pub fn new_with_options(source: &str, options: ParserOptions) -> Self {
+        let mut script = ParsedScript {
+            map: SourceMap::new(""),
+            options,
+            pieces: vec![],
+            next_id: 0,
+            changed: vec![],
+        };
+        script.apply_edit(0..0, source);
+        script.changed.clear();
+        script
+    }
+
+    /// Replaces the bytes of the script in `range` with `replacement` and reparses the
+    /// statements affected. Returns the statements that were reparsed or removed; an id
+    /// that is no longer in `ids` was removed. Statements that replace edited ones take over
+    /// their ids in order. Statements after the edit keep their ids, and are only reported
+    /// if their error messages change because statements were added or removed before them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds or does not fall on character boundaries.
+    pub fn apply_edit(&mut self, range: Range<usize>, replacement: &str) -> &[StatementId] {
+        self.changed.clear();
+        self.map.replace_range(range.clone(), replacement);
+        let inserted_end = range.start + replacement.len();
+
+        // An unterminated last piece goes on with any text added after it
+        let mut first = self
+            .pieces
+            .partition_point(|piece| piece.range.end <= range.start);
+        if first == self.pieces.len() && first > 0 && !self.pieces[first - 1].terminated {
+            first -= 1;
+        }
+        let mut number = self.pieces[..first]
+            .iter()
+            .rev()
+            .find_map(|piece| piece.result.as_ref().map(|_| piece.number + 1))
+            .unwrap_or(1);
+
+        // Split from the first piece touched until a piece ends where an old one after the
+        // edit started, as everything from there on is unchanged
+        let start = self
+            .pieces
+            .get(first)
+            .map_or(range.start, |piece| piece.range.start);
+        let mut last = first;
+        let mut reused = false;
+        let mut pieces = vec![];
+        let mut splitter = Splitter::default();
+        let mut piece_start = start;
+        let source = self.map.source().as_bytes();
+        for offset in start..source.len() {
+            let terminated = splitter.scan(source[offset]);
+            let end = offset + 1;
+            if !terminated && end < source.len() {
+                continue;
+            }
+            let text = std::mem::take(&mut splitter.statement);
+            let piece = self.parse(piece_start..end, terminated, text, number);
+            if piece.result.is_some() {
+                number += 1;
+            }
+            pieces.push(piece);
+            piece_start = end;
+
+            if end >= inserted_end {
+                let moved = |piece: &Piece| piece.range.start + inserted_end - range.end;
+                while last < self.pieces.len()
+                    && (self.pieces[last].range.start < range.end
+                        || moved(&self.pieces[last]) < end)
+                {
+                    last += 1;
+                }
+                if last < self.pieces.len() && moved(&self.pieces[last]) == end {
+                    reused = true;
+                    break;
+                }
+            }
+        }
+        if !reused {
+            last = self.pieces.len();
+        }
+
+        let mut old_ids = self.pieces[first..last].iter().map(|piece| piece.id);
+        for piece in &mut pieces {
+            piece.id = old_ids.next().unwrap_or_else(|| {
+                self.next_id += 1;
+                StatementId(self.next_id)
+            });
+            if piece.result.is_some() {
+                self.changed.push(piece.id);
+            }
+        }
+        let new_ids: Vec<StatementId> = pieces.iter().map(|piece| piece.id).collect();
+        for piece in &self.pieces[first..last] {
+            if piece.result.is_some() && !new_ids.contains(&piece.id) {
+                self.changed.push(piece.id);
+            }
+        }
+        let count = pieces.len();
+        self.pieces.splice(first..last, pieces);
+
+        // Move the statements after the edit, renumbering them until the numbers agree
+        let mut renumber = true;
+        for i in first + count..self.pieces.len() {
+            let piece = &mut self.pieces[i];
+            piece.range = piece.range.start + inserted_end - range.end
+                ..piece.range.end + inserted_end - range.end;
+            if !renumber || piece.result.is_none() {
+                continue;
+            }
+            if piece.number == number {
+                renumber = false;
+                continue;
+            }
+            piece.number = number;
+            number += 1;
+            if let Some(Err(_)) = piece.result {
+                let (span, terminated, id) = (piece.range.clone(), piece.terminated, piece.id);
+                let mut splitter = Splitter::default();
+                for &byte in &self.map.source().as_bytes()[span.clone()] {
+                    splitter.scan(byte);
+                }
+                let mut piece = self.parse(span, terminated, splitter.statement, number - 1);
+                piece.id = id;
+                self.changed.push(id);
+                self.pieces[i] = piece;
+            }
+        }
+        &self.changed
+    }
+
+    /// Parses the piece of the script in `range`, whose text as read by a `Splitter` is
+    /// `text`
+    fn parse(&self, range: Range<usize>, terminated: bool, text: Vec<u8>, number: usize) -> Piece {
+        let leading = text
+            .iter()
+            .take_while(|byte| byte.is_ascii_whitespace())
+            .count();
+        Piece {
+            id: StatementId(0),
+            range,
+            terminated,
+            leading,
+            number,
+            result: parse_piece(text, &self.options, number, Location::default()),
+        }
+    }
+
+    fn piece(&self, id: StatementId) -> Option<&Piece> {
+        self.pieces
+            .iter()
+            .find(|piece| piece.id == id && piece.result.is_some())
+    }
+
+    /// Returns the text of the script
+    pub fn source(&self) -> &str {
+        self.map.source()
+    }
+
+    /// Returns the line index of the script, to convert between offsets and positions
+    pub fn source_map(&self) -> &SourceMap {
+        &self.map
+    }
+
+    /// Returns the ids of the statements, in order
+    pub fn ids(&self) -> impl Iterator<Item = StatementId> + '_ {
+        self.pieces
+            .iter()
+            .filter(|piece| piece.result.is_some())
+            .map(|piece| piece.id)
+    }
+
+    /// Returns the bytes of the script the statement covers, from its first token up to and
+    /// including the semicolon ending it, if any
+    pub fn span(&self, id: StatementId) -> Option<Range<usize>> {
+        self.piece(id)
+            .map(|piece| piece.range.start + piece.leading..piece.range.end)
+    }
+
+    /// Returns the statement, or `None` if it did not parse or is not in the script
+    pub fn statement(&self, id: StatementId) -> Option<&Statement> {
+        self.piece(id)?.result.as_ref()?.as_ref().ok()
+    }
+
+    /// Returns why the statement did not parse, located in the whole script, or `None` if
+    /// it parsed or is not in the script
+    pub fn error(&self, id: StatementId) -> Option<ParseError> {
+        let piece = self.piece(id)?;
+        match piece.result.as_ref()? {
+            Ok(_) => None,
+            Err(e) => Some(e.clone().offset_by(self.map.location(piece.range.start))),
+        }
+    }
+
+    /// Returns the statements that parsed, in order
+    pub fn statements(&self) -> impl Iterator<Item = &Statement> + '_ {
+        self.pieces
+            .iter()
+            .filter_map(|piece| piece.result.as_ref()?.as_ref().ok())
+    }
+
+    /// Returns the errors of the statements that did not parse, in order
+    pub fn errors(&self) -> impl Iterator<Item = ParseError> + '_ {
+        self.ids().filter_map(|id| self.error(id))
+    }
+}
@@ -0,0 +1,146 @@
+//! Filtering of `serde_json` records with SQL conditions, enabled by the `json` feature
+
+use std::collections::HashMap;
+use std::ops::ControlFlow;
+
+use serde_json::{Map, Value as Json};
+
+use crate::ast::{Expression, Ident, Query};
+use crate::error::{CompileError, EvalError};
+use crate::eval::Value;
+use crate::options::PredicateOptions;
+use crate::visitor::{walk_expression, Visitor};
+
+impl Expression {
+    /// Compiles this expression into a filter over JSON objects, as a WHERE clause would
+    /// apply it: a row passes when the condition is TRUE, and fails when it is FALSE or
+    /// NULL. Missing fields read as NULL.
+    pub fn compile_predicate(
+        &self,
+    ) -> Result<impl Fn(&Map<String, Json>) -> Result<bool, EvalError>, CompileError> {
+        self.compile_predicate_with_options(PredicateOptions::default())
+    }
+
+    /// Compiles this expression into a filter over JSON objects, configured by `options`.
+    /// A qualified name such as `a.b` reads the key `"a.b"` if the row has one, or the field
+    /// `b` of the nested object `a` otherwise. Function calls, subqueries and placeholders
+    /// are rejected when compiling.
+    pub fn compile_predicate_with_options(
+        &self,
+        options: PredicateOptions,
+    ) -> Result<impl Fn(&Map<String, Json>) -> Result<bool, EvalError>, CompileError> {
+        let mut checker = FieldCollector::default();
+        if let ControlFlow::Break(err) = checker.visit_expression(self) {
+            return Err(err);
+        }
+        let fields = checker.fields;
+        let expr = self.clone();
+
+        Ok(move |row: &Map<String, Json>| {
+            let mut values = HashMap::new();
+            for field in &fields {
+                let value = match lookup(row, field) {
+                    Some(json) => from_json(field, json)?,
+                    None if options.reject_unknown_fields => {
+                        return Err(EvalError::UnknownIdentifier(field.clone()))
+                    }
+                    None => Value::Null,
+                };
+                values.insert(field.as_str(), value);
+            }
+
+            match expr.evaluate(&|name| values.get(name).cloned())? {
+                Value::Boolean(b) => Ok(b),
+                Value::Null => Ok(false),
+                value => Err(EvalError::TypeMismatch(format!(
+                    "condition evaluated to {}, not a boolean",
+                    value
+                ))),
+            }
+        })
+    }
+}
+
+/// Finds `field` in `row`, trying the literal key before following dots into nested objects
+fn lookup<'a>(row: &'a Map<String, Json>, field: &str) -> Option<&'a Json> {
+    if let Some(json) = row.get(field) {
+        return Some(json);
+    }
+    let mut parts = field.split('.');
+    let mut json = row.get(parts.next()?)?;
+    for part in parts {
+        json = json.as_object()?.get(part)?;
+    }
+    Some(json)
+}
+
+fn from_json(field: &str, json: &Json) -> Result<Value, EvalError> {
+    match json {
+        Json::Null => Ok(Value::Null),
+        Json::Bool(b) => Ok(Value::Boolean(*b)),
+        Json::Number(n) => match n.as_i64() {
+            Some(n) => Ok(Value::Integer(n)),
+            None => n.as_f64().map(Value::Float).ok_or_else(|| {
+                EvalError::TypeMismatch(format!("field {} holds an unsupported number", field))
+            }),
+        },
+        Json::String(s) => Ok(Value::String(s.clone())),
+        Json::Array(_) | Json::Object(_) => Err(EvalError::TypeMismatch(format!(
+            "field {} holds {}, not a scalar",
+            field,
+            if json.is_array() {
+                "an array"
+            } else {
+                "an object"
+            }
+        ))),
+    }
+}
+
+/// Collects the field names an expression reads, rejecting what a row filter cannot evaluate
+#[derive(Default)]
+struct FieldCollector {
+    fields: Vec<String>,
+}
+
+impl FieldCollector {
+    fn add(&mut self, field: String) {
+        if !self.fields.contains(&field) {
+            self.fields.push(field);
+        }
+    }
+}
+
+impl Visitor for FieldCollector {
+    type Break = CompileError;
+
+    fn visit_query(&mut self, _query: &Query) -> ControlFlow<CompileError> {
+        ControlFlow::Break(CompileError::Unsupported("subquery".into()))
+    }
+
+    fn visit_expression(&mut self, expr: &Expression) -> ControlFlow<CompileError> {
+        match expr {
+            Expression::Function { name, .. } => {
+                ControlFlow::Break(CompileError::Unsupported(format!("function call {}", name)))
+            }
+            Expression::Placeholder { text, .. } => {
+                ControlFlow::Break(CompileError::Unsupported(format!("placeholder {}", text)))
+            }
+            Expression::Wildcard => {
+                ControlFlow::Break(CompileError::Unsupported("wildcard".into()))
+            }
+            _ => walk_expression(self, expr),
+        }
+    }
+
+    fn visit_identifier(&mut self, ident: &Ident) -> ControlFlow<CompileError> {
+        self.add(ident.value.to_string());
+        ControlFlow::Continue(())
+    }
+
+    fn visit_compound_identifier(&mut self, parts: &[Ident]) -> ControlFlow<CompileError> {
+        let parts: Vec<&str> = parts.iter().map(|part| part.value.as_str()).collect();
+        self.add(parts.join("."));
+        ControlFlow::Continue(())
+    }
+}
@@ -1,5 +1,147 @@
-pub mod tokenizer;
-pub mod ast;
-pub mod error;
-pub mod parser;
-
+//! A SQL parser built around a Pratt parser for expressions.
+//!
+//! Most uses only need the functions at the root of the crate: [`parse_statement`] for a
+//! single statement, [`parse_statements`] for a `;`-separated script, [`parse_expression`]
+//! for an expression on its own and [`tokenize`] or [`tokenize_with_spans`] for the tokens.
+//! `Expression` and `Statement` also implement `FromStr`, so `str::parse` works as well:
+//!
+//! ```
+//! use rust_sql_parser::ast::Statement;
+//!
+//! let statement: Statement = "SELECT a FROM t WHERE b > 1".parse().unwrap();
+//! assert_eq!(statement.to_string(), "SELECT a FROM t WHERE b > 1");
+//! ```
+//!
+//! [`parser::PrattParser`] gives more control, such as parsing with [`options::ParserOptions`]
+//! or recovering from errors.
+
+// Parse errors carry their span and the chain of constructs being parsed, which makes them
+// larger than Clippy likes; they are only built once parsing has failed
+#![allow(clippy::result_large_err)]
+
+use std::str::FromStr;
+
+use crate::ast::{Expression, Statement, Token};
+use crate::error::ParseError;
+use crate::parser::PrattParser;
+use crate::tokenizer::{Span, Tokenizer};
+
+pub mod tokenizer;
+pub mod ast;
+pub mod dialect;
+pub mod error;
+pub mod parser;
+pub mod options;
+
+pub mod visitor;
+pub mod optimizer;
+pub mod eval;
+pub mod builder;
+pub mod analysis;
+pub mod params;
+pub mod normalize;
+pub mod tree;
+pub mod metrics;
+pub mod rename;
+pub mod lineage;
+pub mod completion;
+pub mod formatter;
+pub mod stream;
+pub mod incremental;
+pub mod source_map;
+
+#[cfg(feature = "sqlparser-compat")]
+pub mod compat;
+
+#[cfg(feature = "json")]
+pub mod json;
+
+#[cfg(feature = "arbitrary")]
+pub mod arbitrary;
+
+/// Parses an expression that makes up the whole of `sql`
+///
+/// ```
+/// let expr = rust_sql_parser::parse_expression("a + 1 > b").unwrap();
+/// assert_eq!(expr.to_string(), "a + 1 > b");
+///
+/// assert!(rust_sql_parser::parse_expression("a b").is_err());
+/// ```
+pub fn parse_expression(sql: &str) -> Result<Expression, ParseError> {
+    PrattParser::new(sql).parse_complete_expression()
+}
+
+/// Parses a single statement, optionally followed by a semicolon, that makes up the whole
+/// of `sql`
+///
+/// ```
+/// let statement = rust_sql_parser::parse_statement("DELETE FROM t WHERE a = 1;").unwrap();
+/// assert_eq!(statement.to_string(), "DELETE FROM t WHERE a = 1");
+///
+/// assert!(rust_sql_parser::parse_statement("SELECT 1; SELECT 2").is_err());
+/// ```
+pub fn parse_statement(sql: &str) -> Result<Statement, ParseError> {
+    PrattParser::new(sql).parse_statement()
+}
+
+/// Parses every statement of a `;`-separated script, stopping at the first error
+///
+/// ```
+/// let statements = rust_sql_parser::parse_statements("SELECT 1; SELECT 2;").unwrap();
+/// assert_eq!(statements.len(), 2);
+/// ```
+pub fn parse_statements(sql: &str) -> Result<Vec<Statement>, ParseError> {
+    PrattParser::parse_statements(sql)
+}
+
+/// Splits `sql` into tokens, ending with `Token::Eof`, or returns the first lexical error
+///
+/// ```
+/// use rust_sql_parser::ast::Token;
+///
+/// let tokens = rust_sql_parser::tokenize("a = 1").unwrap();
+/// assert_eq!(tokens.len(), 4);
+/// assert_eq!(tokens.last(), Some(&Token::Eof));
+///
+/// assert!(rust_sql_parser::tokenize("'unterminated").is_err());
+/// ```
+pub fn tokenize(sql: &str) -> Result<Vec<Token>, ParseError> {
+    Tokenizer::new(sql).tokenize_string()
+}
+
+/// Splits `sql` into tokens like `tokenize`, pairing each with the stretch of input it covers
+///
+/// ```
+/// use rust_sql_parser::ast::Token;
+///
+/// let tokens = rust_sql_parser::tokenize_with_spans("a =\n  1").unwrap();
+/// let (token, span) = &tokens[2];
+/// assert_eq!(*token, Token::Number(1));
+/// assert_eq!((span.start.line, span.start.column), (2, 3));
+/// assert_eq!((span.start.offset, span.end.offset), (6, 7));
+/// ```
+pub fn tokenize_with_spans(sql: &str) -> Result<Vec<(Token, Span)>, ParseError> {
+    Tokenizer::new(sql).tokenize_with_spans()
+}
+
+impl FromStr for Expression {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_expression(s)
+    }
+}
+
+impl FromStr for Statement {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_statement(s)
+    }
+}
+
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+#[cfg(feature = "ffi")]
+pub mod ffi;
@@ -0,0 +1,323 @@
+use std::ops::ControlFlow;
+
+use crate::analysis::ColumnRef;
+use crate::ast::{
+    Cte, Expression, Ident, ObjectName, Query, SelectItem, SelectStatement, SetExpr, TableAlias,
+    TableReference, TableWithJoins,
+};
+use crate::visitor::Visitor;
+
+/// Represents one output column of a query and the source columns that feed it
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct OutputColumn {
+    /// The alias, the column name for a plain reference, or the expression as written
+    pub name: String,
+    /// Columns of real tables where they can be resolved; otherwise the reference as
+    /// written, with the qualifier it was given
+    pub sources: Vec<ColumnRef>,
+    /// Set for `*` or `t.*` over a table, whose columns cannot be listed without the schema.
+    /// The sources then hold a single `*` column of that table.
+    pub unresolved: bool,
+}
+
+impl Query {
+    /// Returns the lineage of each output column, looking through the CTEs of this query
+    /// and any derived tables one level deep. For a set operation, the names come from the
+    /// first branch and the sources of all branches are merged column by column.
+    pub fn output_lineage(&self) -> Vec<OutputColumn> {
+        query_lineage(self, &[], true)
+    }
+}
+
+impl SelectStatement {
+    /// Returns the lineage of each output column, resolving table aliases and looking through
+    /// derived tables one level deep. A bare column is attributed to the only table in the
+    /// FROM clause, or left unqualified when there are several. Subqueries in expressions
+    /// are not followed.
+    pub fn output_lineage(&self) -> Vec<OutputColumn> {
+        select_lineage(self, &[], true)
+    }
+}
+
+/// Represents a relation in a FROM clause and what its columns resolve to
+struct Relation {
+    /// The qualifiers that refer to the relation, such as its alias
+    names: Vec<String>,
+    kind: RelationKind,
+}
+
+enum RelationKind {
+    Table(String),
+    /// A derived table or CTE whose own lineage is known
+    Derived(Vec<OutputColumn>),
+}
+
+fn query_lineage<'a>(query: &'a Query, ctes: &[&'a Cte], descend: bool) -> Vec<OutputColumn> {
+    let mut ctes = ctes.to_vec();
+    if let Some(with) = &query.with {
+        ctes.extend(&with.ctes);
+    }
+    set_expr_lineage(&query.body, &ctes, descend)
+}
+
+fn set_expr_lineage(body: &SetExpr, ctes: &[&Cte], descend: bool) -> Vec<OutputColumn> {
+    match body {
+        SetExpr::Select(select) => select_lineage(select, ctes, descend),
+        SetExpr::Query(query) => query_lineage(query, ctes, descend),
+        SetExpr::SetOperation { left, right, .. } => {
+            let mut columns = set_expr_lineage(left, ctes, descend);
+            let right = set_expr_lineage(right, ctes, descend);
+            for (column, other) in columns.iter_mut().zip(right) {
+                for source in other.sources {
+                    if !column.sources.contains(&source) {
+                        column.sources.push(source);
+                    }
+                }
+                column.unresolved |= other.unresolved;
+            }
+            columns
+        }
+        SetExpr::Values(_) => vec![],
+    }
+}
+
+fn select_lineage(select: &SelectStatement, ctes: &[&Cte], descend: bool) -> Vec<OutputColumn> {
+    let relations = match &select.from {
+        Some(from) => relations(from, ctes, descend),
+        None => vec![],
+    };
+
+    let mut columns = vec![];
+    for item in &select.projection {
+        match item {
+            SelectItem::Expression(expr) => columns.push(OutputColumn {
+                name: output_name(expr),
+                sources: sources(expr, &relations),
+                unresolved: false,
+            }),
+            SelectItem::ExprWithAlias { expr, alias } => columns.push(OutputColumn {
+                name: alias.value.to_string(),
+                sources: sources(expr, &relations),
+                unresolved: false,
+            }),
+            SelectItem::Wildcard => {
+                for relation in &relations {
+                    expand_wildcard(relation, &mut columns);
+                }
+            }
+            SelectItem::QualifiedWildcard(qualifier) => {
+                let qualifier = qualifier.join(".");
+                match find_relation(&relations, &qualifier) {
+                    Some(relation) => expand_wildcard(relation, &mut columns),
+                    None => columns.push(unresolved(qualifier)),
+                }
+            }
+        }
+    }
+    columns
+}
+
+fn relations(from: &TableWithJoins, ctes: &[&Cte], descend: bool) -> Vec<Relation> {
+    let tables = std::iter::once(&from.relation).chain(from.joins.iter().map(|j| &j.relation));
+    tables
+        .filter_map(|table| relation(table, ctes, descend))
+        .collect()
+}
+
+fn relation(table: &TableReference, ctes: &[&Cte], descend: bool) -> Option<Relation> {
+    match table {
+        TableReference::Table { name, alias } => {
+            let names = match alias {
+                Some(alias) => vec![alias.name.value.to_string()],
+                None => qualifier_names(name),
+            };
+            let cte = match name.0.as_slice() {
+                [ident] if descend => find_cte(ctes, ident),
+                _ => None,
+            };
+            let kind = match cte {
+                Some(cte) => {
+                    let mut columns = query_lineage(&cte.query, &[], false);
+                    rename_columns(&mut columns, cte.columns.iter().map(String::as_str));
+                    if let Some(alias) = alias {
+                        rename_columns(&mut columns, alias_columns(alias));
+                    }
+                    RelationKind::Derived(columns)
+                }
+                None => RelationKind::Table(name.to_string()),
+            };
+            Some(Relation { names, kind })
+        }
+        TableReference::Derived {
+            subquery,
+            alias: Some(alias),
+        } => {
+            let kind = if descend {
+                let mut columns = query_lineage(subquery, ctes, false);
+                rename_columns(&mut columns, alias_columns(alias));
+                RelationKind::Derived(columns)
+            } else {
+                RelationKind::Table(alias.name.value.to_string())
+            };
+            Some(Relation {
+                names: vec![alias.name.value.to_string()],
+                kind,
+            })
+        }
+        TableReference::Function {
+            alias: Some(TableAlias { name, .. }),
+            ..
+        } => Some(Relation {
+            names: vec![name.value.to_string()],
+            kind: RelationKind::Table(name.value.to_string()),
+        }),
+        _ => None,
+    }
+}
+
+/// Returns the qualifiers an unaliased table answers to: its full name and its last part
+fn qualifier_names(name: &ObjectName) -> Vec<String> {
+    let mut names = vec![name.to_string()];
+    if let [.., last] = name.0.as_slice() {
+        if name.0.len() > 1 {
+            names.push(last.value.to_string());
+        }
+    }
+    names
+}
+
+fn find_cte<'a>(ctes: &[&'a Cte], ident: &Ident) -> Option<&'a Cte> {
+    ctes.iter()
+        .rev()
+        .copied()
+        .find(|cte| match ident.quote_style {
+            Some(_) => cte.name == ident.value,
+            None => cte.name.eq_ignore_ascii_case(&ident.value),
+        })
+}
+
+fn find_relation<'a>(relations: &'a [Relation], qualifier: &str) -> Option<&'a Relation> {
+    relations.iter().find(|relation| {
+        relation
+            .names
+            .iter()
+            .any(|name| name.eq_ignore_ascii_case(qualifier))
+    })
+}
+
+/// Applies the column list of a CTE or alias, such as `t (x, y)`, to the derived columns
+fn rename_columns<'a>(columns: &mut [OutputColumn], names: impl IntoIterator<Item = &'a str>) {
+    for (column, name) in columns.iter_mut().zip(names) {
+        column.name = name.to_string();
+    }
+}
+
+fn alias_columns(alias: &TableAlias) -> impl Iterator<Item = &str> {
+    alias.columns.iter().map(|column| column.value.as_str())
+}
+
+fn expand_wildcard(relation: &Relation, columns: &mut Vec<OutputColumn>) {
+    match &relation.kind {
+        RelationKind::Derived(derived) => columns.extend(derived.iter().cloned()),
+        RelationKind::Table(table) => columns.push(unresolved(table.clone())),
+    }
+}
+
+fn unresolved(table: String) -> OutputColumn {
+    OutputColumn {
+        name: format!("{}.*", table),
+        sources: vec![ColumnRef {
+            table: Some(table),
+            column: "*".to_string(),
+        }],
+        unresolved: true,
+    }
+}
+
+fn output_name(expr: &Expression) -> String {
+    match expr {
+        Expression::Identifier(ident) => ident.value.to_string(),
+        Expression::CompoundIdentifier(parts) => parts
+            .last()
+            .map(|part| part.value.to_string())
+            .unwrap_or_default(),
+        _ => expr.to_string(),
+    }
+}
+
+/// Resolves every column `expr` references against the relations of the FROM clause
+fn sources(expr: &Expression, relations: &[Relation]) -> Vec<ColumnRef> {
+    let mut collector = ReferenceCollector::default();
+    let _ = collector.visit_expression(expr);
+
+    let mut sources = vec![];
+    for (qualifier, column) in collector.references {
+        let relation = match &qualifier {
+            Some(qualifier) => find_relation(relations, qualifier),
+            None if relations.len() == 1 => relations.first(),
+            None => None,
+        };
+        let resolved = match relation.map(|relation| &relation.kind) {
+            Some(RelationKind::Table(table)) => vec![ColumnRef {
+                table: Some(table.clone()),
+                column,
+            }],
+            Some(RelationKind::Derived(columns)) => {
+                match columns
+                    .iter()
+                    .find(|derived| derived.name.eq_ignore_ascii_case(&column))
+                {
+                    Some(derived) => derived.sources.clone(),
+                    // The column may come from a `*` the derived table could not expand
+                    None => columns
+                        .iter()
+                        .filter(|derived| derived.unresolved)
+                        .flat_map(|derived| &derived.sources)
+                        .map(|source| ColumnRef {
+                            table: source.table.clone(),
+                            column: column.clone(),
+                        })
+                        .collect(),
+                }
+            }
+            None => vec![ColumnRef {
+                table: qualifier,
+                column,
+            }],
+        };
+        for source in resolved {
+            if !sources.contains(&source) {
+                sources.push(source);
+            }
+        }
+    }
+    sources
+}
+
+/// Collects column references as qualifier and column name, without entering subqueries
+#[derive(Default)]
+struct ReferenceCollector {
+    references: Vec<(Option<String>, String)>,
+}
+
+impl Visitor for ReferenceCollector {
+    type Break = ();
+
+    fn visit_query(&mut self, _query: &Query) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+
+    fn visit_identifier(&mut self, ident: &Ident) -> ControlFlow<()> {
+        self.references.push((None, ident.value.to_string()));
+        ControlFlow::Continue(())
+    }
+
+    fn visit_compound_identifier(&mut self, parts: &[Ident]) -> ControlFlow<()> {
+        if let Some((column, qualifier)) = parts.split_last() {
+            let qualifier: Vec<&str> = qualifier.iter().map(|part| part.value.as_str()).collect();
+            self.references
+                .push((Some(qualifier.join(".")), column.value.to_string()));
+        }
+        ControlFlow::Continue(())
+    }
+}
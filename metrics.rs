@@ -0,0 +1,206 @@
+use std::ops::ControlFlow;
+
+use crate::ast::{Expression, Query, SelectStatement, Statement, TableReference};
+use crate::error::ComplexityError;
+use crate::visitor::{
+    push_operands, walk_query, walk_select, walk_statement, walk_table_reference, Visitor,
+};
+
+/// Represents the size of a statement's AST
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct Metrics {
+    /// Statements, queries, SELECTs, table references and expressions
+    pub node_count: usize,
+    /// How deeply those nodes nest; a lone statement has depth 1
+    pub max_depth: usize,
+    /// Joins in all FROM clauses, including comma-separated tables
+    pub join_count: usize,
+    /// Queries nested in the statement, counting CTEs and derived tables but not the query
+    /// of a SELECT statement itself
+    pub subquery_count: usize,
+}
+
+/// Configures `check_limits`; a limit of `None` is not checked
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct Limits {
+    pub max_nodes: Option<usize>,
+    pub max_depth: Option<usize>,
+    pub max_joins: Option<usize>,
+    pub max_subqueries: Option<usize>,
+}
+
+impl Limits {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_max_nodes(mut self, max: usize) -> Self {
+        self.max_nodes = Some(max);
+        self
+    }
+
+    pub fn with_max_depth(mut self, max: usize) -> Self {
+        self.max_depth = Some(max);
+        self
+    }
+
+    pub fn with_max_joins(mut self, max: usize) -> Self {
+        self.max_joins = Some(max);
+        self
+    }
+
+    pub fn with_max_subqueries(mut self, max: usize) -> Self {
+        self.max_subqueries = Some(max);
+        self
+    }
+}
+
+impl Statement {
+    pub fn metrics(&self) -> Metrics {
+        let mut collector = MetricsCollector::new(self, Limits::default());
+        let _ = collector.visit_statement(self);
+        collector.metrics
+    }
+}
+
+/// Returns an error naming the first limit `statement` exceeds, stopping the walk as soon as
+/// a limit is crossed. Expressions are walked with a heap stack, so however long an operator
+/// chain is it cannot exhaust the stack; queries nested in one another are walked a call per
+/// level, which `max_depth` bounds.
+pub fn check_limits(statement: &Statement, limits: &Limits) -> Result<(), ComplexityError> {
+    let mut collector = MetricsCollector::new(statement, limits.clone());
+    match collector.visit_statement(statement) {
+        ControlFlow::Continue(()) => Ok(()),
+        ControlFlow::Break(err) => Err(err),
+    }
+}
+
+struct MetricsCollector<'a> {
+    metrics: Metrics,
+    limits: Limits,
+    depth: usize,
+    /// The query of a SELECT statement, which is not counted as a subquery
+    top_query: Option<&'a Query>,
+}
+
+impl<'a> MetricsCollector<'a> {
+    fn new(statement: &'a Statement, limits: Limits) -> Self {
+        let top_query = match statement {
+            Statement::Query(query) => Some(&**query),
+            _ => None,
+        };
+        MetricsCollector {
+            metrics: Metrics::default(),
+            limits,
+            depth: 0,
+            top_query,
+        }
+    }
+
+    /// Counts a node one level below the current one, then walks into it with `walk`
+    fn enter(
+        &mut self,
+        walk: impl FnOnce(&mut Self) -> ControlFlow<ComplexityError>,
+    ) -> ControlFlow<ComplexityError> {
+        self.depth += 1;
+        self.metrics.node_count += 1;
+        self.metrics.max_depth = self.metrics.max_depth.max(self.depth);
+        self.check()?;
+        let result = walk(self);
+        self.depth -= 1;
+        result
+    }
+
+    fn add_joins(&mut self, joins: usize) -> ControlFlow<ComplexityError> {
+        self.metrics.join_count += joins;
+        self.check()
+    }
+
+    fn check(&self) -> ControlFlow<ComplexityError> {
+        let metrics = &self.metrics;
+        let exceeded = |count: usize, limit: Option<usize>| limit.filter(|limit| count > *limit);
+
+        if let Some(limit) = exceeded(metrics.node_count, self.limits.max_nodes) {
+            return ControlFlow::Break(ComplexityError::TooManyNodes(format!(
+                "statement has more than {} nodes",
+                limit
+            )));
+        }
+        if let Some(limit) = exceeded(metrics.max_depth, self.limits.max_depth) {
+            return ControlFlow::Break(ComplexityError::TooDeep(format!(
+                "statement nests deeper than {} levels",
+                limit
+            )));
+        }
+        if let Some(limit) = exceeded(metrics.join_count, self.limits.max_joins) {
+            return ControlFlow::Break(ComplexityError::TooManyJoins(format!(
+                "statement has more than {} joins",
+                limit
+            )));
+        }
+        if let Some(limit) = exceeded(metrics.subquery_count, self.limits.max_subqueries) {
+            return ControlFlow::Break(ComplexityError::TooManySubqueries(format!(
+                "statement has more than {} subqueries",
+                limit
+            )));
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+impl Visitor for MetricsCollector<'_> {
+    type Break = ComplexityError;
+
+    fn visit_statement(&mut self, statement: &Statement) -> ControlFlow<ComplexityError> {
+        match statement {
+            Statement::Update {
+                from: Some(from), ..
+            } => self.add_joins(from.joins.len())?,
+            Statement::Delete {
+                using: Some(using), ..
+            } => self.add_joins(using.joins.len())?,
+            _ => {}
+        }
+        self.enter(|this| walk_statement(this, statement))
+    }
+
+    fn visit_query(&mut self, query: &Query) -> ControlFlow<ComplexityError> {
+        if !self.top_query.is_some_and(|top| std::ptr::eq(top, query)) {
+            self.metrics.subquery_count += 1;
+        }
+        self.enter(|this| walk_query(this, query))
+    }
+
+    fn visit_select(&mut self, select: &SelectStatement) -> ControlFlow<ComplexityError> {
+        if let Some(from) = &select.from {
+            self.add_joins(from.joins.len())?;
+        }
+        self.enter(|this| walk_select(this, select))
+    }
+
+    fn visit_table_reference(&mut self, table: &TableReference) -> ControlFlow<ComplexityError> {
+        self.enter(|this| walk_table_reference(this, table))
+    }
+
+    fn visit_expression(&mut self, expr: &Expression) -> ControlFlow<ComplexityError> {
+        // Counted with a heap stack rather than a call per level, since a generated chain of
+        // many thousands of `OR` terms nests that deep on its left
+        let outer = self.depth;
+        let mut stack = vec![(expr, outer + 1)];
+        let mut operands = vec![];
+        while let Some((expr, depth)) = stack.pop() {
+            self.depth = depth;
+            self.metrics.node_count += 1;
+            self.metrics.max_depth = self.metrics.max_depth.max(depth);
+            self.check()?;
+            if let Expression::Subquery(query) = expr {
+                self.visit_query(query)?;
+            }
+            push_operands(expr, &mut operands);
+            stack.extend(operands.drain(..).map(|operand| (operand, depth + 1)));
+        }
+        self.depth = outer;
+        ControlFlow::Continue(())
+    }
+}
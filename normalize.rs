@@ -0,0 +1,235 @@
+use std::ops::ControlFlow;
+
+use crate::ast::{
+    DataType, Expression, Ident, ObjectName, Query, SelectItem, SelectStatement, SetExpr,
+    Statement, TableReference,
+};
+use crate::options::{EqualityOptions, IdentifierCase, NormalizeOptions};
+use crate::visitor::{
+    walk_expression_mut, walk_query_mut, walk_select_mut, walk_table_reference_mut, VisitorMut,
+};
+
+impl Statement {
+    /// Rewrites this statement into a canonical form so that equivalent statements compare
+    /// equal: unquoted identifiers, aliases, function names and type names are folded per
+    /// `options`, and redundant table qualifiers can be dropped.
+    /// Column lists such as `INSERT INTO t (a, b)` do not record whether they were quoted and
+    /// are left as parsed.
+    pub fn normalize(&mut self, options: NormalizeOptions) {
+        let mut normalizer = Normalizer {
+            options,
+            scopes: vec![],
+        };
+        let _ = normalizer.visit_statement_mut(self);
+    }
+}
+
+/// Returns whether `a` and `b` are the same statement once formatting is set aside. Keyword
+/// case, whitespace and parentheses are never stored in the AST; on top of that, unquoted
+/// identifiers are folded per `options` and parenthesized queries that only group, as in
+/// `(SELECT 1) UNION (SELECT 2)`, are unwrapped. Parentheses that change precedence are
+/// reflected in the tree, so `(a + b) * c` and `a + b * c` stay different.
+pub fn semantically_equal(a: &Statement, b: &Statement, options: &EqualityOptions) -> bool {
+    let canonical = |statement: &Statement| {
+        let mut statement = statement.clone();
+        statement.normalize(
+            NormalizeOptions::new()
+                .with_identifier_case(options.identifier_case)
+                .with_qualifiers_stripped(options.strip_qualifiers),
+        );
+        let _ = GroupingStripper.visit_statement_mut(&mut statement);
+        statement
+    };
+    canonical(a) == canonical(b)
+}
+
+struct Normalizer {
+    options: NormalizeOptions,
+    /// For each query being walked, the qualifiers that name its only table
+    scopes: Vec<Vec<Vec<String>>>,
+}
+
+impl Normalizer {
+    fn fold(&self, ident: &mut Ident) {
+        if ident.quote_style.is_none() {
+            ident.value = fold_case(self.options.identifier_case, &ident.value).into();
+        }
+    }
+
+    /// Folds the name of a user-defined type; built-in types already print canonically
+    fn fold_data_type(&self, data_type: &mut DataType) {
+        match data_type {
+            DataType::Custom(name) => {
+                for part in &mut name.0 {
+                    self.fold(part);
+                }
+            }
+            DataType::Array(element) => self.fold_data_type(element),
+            _ => {}
+        }
+    }
+
+    /// Returns the qualifiers that may be dropped inside `query`: the alias of its only
+    /// table, or the table's name with and without its schema
+    fn redundant_qualifiers(&self, query: &Query) -> Vec<Vec<String>> {
+        let select = match &query.body {
+            SetExpr::Select(select) if self.options.strip_qualifiers => select,
+            _ => return vec![],
+        };
+        let relation = match &select.from {
+            Some(from) if from.joins.is_empty() => &from.relation,
+            _ => return vec![],
+        };
+
+        match relation {
+            TableReference::Table {
+                alias: Some(alias), ..
+            }
+            | TableReference::Derived {
+                alias: Some(alias), ..
+            }
+            | TableReference::Function {
+                alias: Some(alias), ..
+            } => {
+                let mut name = alias.name.clone();
+                self.fold(&mut name);
+                vec![vec![name.value.into()]]
+            }
+            TableReference::Table { name, alias: None } => {
+                let mut name = name.clone();
+                for part in &mut name.0 {
+                    self.fold(part);
+                }
+                let parts: Vec<String> = name.0.into_iter().map(|part| part.value.into()).collect();
+                let table = parts[parts.len() - 1..].to_vec();
+                if parts.len() > 1 {
+                    vec![parts, table]
+                } else {
+                    vec![table]
+                }
+            }
+            _ => vec![],
+        }
+    }
+}
+
+impl VisitorMut for Normalizer {
+    type Break = ();
+
+    fn visit_query_mut(&mut self, query: &mut Query) -> ControlFlow<()> {
+        let scope = self.redundant_qualifiers(query);
+        self.scopes.push(scope);
+        let result = walk_query_mut(self, query);
+        self.scopes.pop();
+        result
+    }
+
+    fn visit_expression_mut(&mut self, expr: &mut Expression) -> ControlFlow<()> {
+        match expr {
+            Expression::Function { name, .. } => {
+                *name = fold_case(self.options.identifier_case, name);
+            }
+            Expression::Cast { data_type, .. } => self.fold_data_type(data_type),
+            Expression::CompoundIdentifier(parts) => {
+                for part in parts.iter_mut() {
+                    self.fold(part);
+                }
+                let (column, qualifier) = parts.split_last().expect("qualified name has parts");
+                let qualifier: Vec<String> = qualifier
+                    .iter()
+                    .map(|part| part.value.to_string())
+                    .collect();
+                let redundant = self
+                    .scopes
+                    .last()
+                    .is_some_and(|scope| scope.contains(&qualifier));
+                if redundant {
+                    *expr = Expression::Identifier(column.clone());
+                }
+                return ControlFlow::Continue(());
+            }
+            _ => {}
+        }
+        walk_expression_mut(self, expr)
+    }
+
+    fn visit_select_mut(&mut self, select: &mut SelectStatement) -> ControlFlow<()> {
+        for item in &mut select.projection {
+            if let SelectItem::ExprWithAlias { alias, .. } = item {
+                self.fold(alias);
+            }
+        }
+        walk_select_mut(self, select)
+    }
+
+    fn visit_table_reference_mut(&mut self, table: &mut TableReference) -> ControlFlow<()> {
+        let (TableReference::Table { alias, .. }
+        | TableReference::Derived { alias, .. }
+        | TableReference::Function { alias, .. }) = table;
+        if let Some(alias) = alias {
+            self.fold(&mut alias.name);
+            for column in &mut alias.columns {
+                self.fold(column);
+            }
+        }
+        walk_table_reference_mut(self, table)
+    }
+
+    fn visit_table_name_mut(&mut self, name: &mut ObjectName) -> ControlFlow<()> {
+        for part in &mut name.0 {
+            self.fold(part);
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn visit_identifier_mut(&mut self, ident: &mut Ident) -> ControlFlow<()> {
+        self.fold(ident);
+        ControlFlow::Continue(())
+    }
+}
+
+fn fold_case(case: IdentifierCase, name: &str) -> String {
+    match case {
+        IdentifierCase::Preserve => name.to_string(),
+        IdentifierCase::Lower => name.to_lowercase(),
+        IdentifierCase::Upper => name.to_uppercase(),
+    }
+}
+
+/// Unwraps parenthesized queries that carry nothing but a body
+struct GroupingStripper;
+
+impl GroupingStripper {
+    fn strip(body: &mut SetExpr) {
+        match body {
+            SetExpr::Query(query) if is_bare(query) => {
+                let inner = std::mem::replace(&mut query.body, SetExpr::Values(vec![]));
+                *body = inner;
+                Self::strip(body);
+            }
+            SetExpr::SetOperation { left, right, .. } => {
+                Self::strip(left);
+                Self::strip(right);
+            }
+            _ => {}
+        }
+    }
+}
+
+impl VisitorMut for GroupingStripper {
+    type Break = ();
+
+    fn visit_query_mut(&mut self, query: &mut Query) -> ControlFlow<()> {
+        Self::strip(&mut query.body);
+        walk_query_mut(self, query)
+    }
+}
+
+fn is_bare(query: &Query) -> bool {
+    query.with.is_none()
+        && query.order_by.is_empty()
+        && query.limit.is_none()
+        && query.offset.is_none()
+        && !query.with_ties
+        && query.locking.is_none()
+}
@@ -0,0 +1,377 @@
+use std::ops::ControlFlow;
+
+use crate::ast::{BinaryOperator, Expression, UnaryOperator, Value};
+use crate::visitor::VisitorMut;
+
+/// Represents a literal operand while folding; integers are widened so negation and
+/// overflow can be checked before the result is turned back into an expression
+#[derive(Debug, PartialEq)]
+enum Constant {
+    Integer(i128),
+    String(String),
+    Boolean(bool),
+    Null,
+}
+
+impl Expression {
+    /// Returns a copy of this expression with every literal operation evaluated, so
+    /// `2 + 3 * 4` becomes `14`, `'a' || 'b'` becomes `'ab'`, `NULL IS NULL` becomes `TRUE`
+    /// and `TRUE AND x` becomes `x`. Function arguments, IN lists, LIKE operands and
+    /// subqueries are folded too. Any operation that would overflow or divide by zero is
+    /// left as it is.
+    pub fn fold_constants(&self) -> Expression {
+        fold(self, false)
+    }
+
+    /// Returns a copy of this expression with boolean noise removed. On top of
+    /// `fold_constants`, which already folds comparisons between literals, the rules are:
+    ///
+    /// - `TRUE AND x` and `FALSE OR x` become `x`
+    /// - `FALSE AND x` and `TRUE OR x` become the literal, which holds under three-valued
+    ///   logic because `NULL AND FALSE` is FALSE and `NULL OR TRUE` is TRUE
+    /// - `NOT NOT x` becomes `x`, as negating NULL twice gives NULL again
+    ///
+    /// `NULL AND x` and `NULL OR x` are kept, since their result depends on `x`. Dropping an
+    /// operand also drops any error evaluating it would raise, such as dividing by zero.
+    pub fn simplify(&self) -> Expression {
+        fold(self, true)
+    }
+}
+
+/// Represents a step of the walk in `fold`: entering a node pushes its operands, and
+/// leaving it builds the folded node from theirs
+enum Step<'a> {
+    Enter(&'a Expression),
+    Leave(&'a Expression),
+}
+
+/// Represents a folded operand. A negation is kept apart from its operand until its
+/// parent is built, so that `NOT NOT x` can give back `x` without taking it out of a node.
+enum Folded {
+    Expression(Expression),
+    Not(Expression),
+}
+
+impl Folded {
+    /// Reads a literal operand; a negation left apart is never one, as negating a literal
+    /// folds
+    fn constant(&self) -> Option<Constant> {
+        match self {
+            Folded::Expression(expr) => constant(expr),
+            Folded::Not(_) => None,
+        }
+    }
+
+    fn into_expression(self) -> Expression {
+        match self {
+            Folded::Expression(expr) => expr,
+            Folded::Not(operand) => Expression::UnaryOperation {
+                operator: UnaryOperator::Not,
+                operand: Box::new(operand),
+            },
+        }
+    }
+}
+
+/// Folds `expr` bottom up with a heap stack, so that a long generated chain of `AND` or
+/// `OR` terms cannot run out of call stack
+fn fold(expr: &Expression, simplify: bool) -> Expression {
+    let mut steps = vec![Step::Enter(expr)];
+    let mut folded: Vec<Folded> = vec![];
+    while let Some(step) = steps.pop() {
+        match step {
+            Step::Enter(expr) => match expr {
+                Expression::BinaryOperation {
+                    left_operand,
+                    right_operand,
+                    ..
+                } => {
+                    steps.push(Step::Leave(expr));
+                    steps.push(Step::Enter(right_operand));
+                    steps.push(Step::Enter(left_operand));
+                }
+                Expression::UnaryOperation { operand, .. }
+                | Expression::Cast { expr: operand, .. }
+                | Expression::IsNull { expr: operand, .. } => {
+                    steps.push(Step::Leave(expr));
+                    steps.push(Step::Enter(operand));
+                }
+                Expression::Function { args, .. } => {
+                    steps.push(Step::Leave(expr));
+                    steps.extend(args.iter().rev().map(Step::Enter));
+                }
+                Expression::InList {
+                    expr: operand,
+                    list,
+                    ..
+                } => {
+                    steps.push(Step::Leave(expr));
+                    steps.extend(list.iter().rev().map(Step::Enter));
+                    steps.push(Step::Enter(operand));
+                }
+                Expression::Like {
+                    expr: operand,
+                    pattern,
+                    ..
+                } => {
+                    steps.push(Step::Leave(expr));
+                    steps.push(Step::Enter(pattern));
+                    steps.push(Step::Enter(operand));
+                }
+                Expression::Subquery(query) => {
+                    let mut query = query.clone();
+                    let _ = Folder { simplify }.visit_query_mut(&mut query);
+                    folded.push(Folded::Expression(Expression::Subquery(query)));
+                }
+                Expression::Value(_)
+                | Expression::Placeholder { .. }
+                | Expression::Identifier(_)
+                | Expression::CompoundIdentifier(_)
+                | Expression::Wildcard => folded.push(Folded::Expression(expr.clone())),
+            },
+            Step::Leave(expr) => {
+                let node = leave(expr, &mut folded, simplify);
+                folded.push(node);
+            }
+        }
+    }
+    folded
+        .pop()
+        .expect("the walk leaves the folded expression")
+        .into_expression()
+}
+
+/// Builds the folded form of `expr` from its operands, which are on top of `folded`
+fn leave(expr: &Expression, folded: &mut Vec<Folded>, simplify: bool) -> Folded {
+    let mut operand = || {
+        folded
+            .pop()
+            .expect("operands are folded before their node")
+            .into_expression()
+    };
+    let node = match expr {
+        Expression::BinaryOperation {
+            operator: operator @ (BinaryOperator::And | BinaryOperator::Or),
+            ..
+        } => {
+            let right = folded.pop().expect("operands are folded before their node");
+            let left = folded.pop().expect("operands are folded before their node");
+            return fold_logical(left, operator, right);
+        }
+        Expression::BinaryOperation { operator, .. } => {
+            let right = operand();
+            fold_binary(operand(), operator, right)
+        }
+        Expression::UnaryOperation {
+            operator: UnaryOperator::Not,
+            ..
+        } => {
+            return match folded.pop().expect("operands are folded before their node") {
+                Folded::Not(operand) if simplify => Folded::Expression(operand),
+                operand => {
+                    let operand = operand.into_expression();
+                    if constant(&operand).is_some() {
+                        Folded::Expression(fold_unary(&UnaryOperator::Not, operand))
+                    } else {
+                        Folded::Not(operand)
+                    }
+                }
+            };
+        }
+        Expression::UnaryOperation { operator, .. } => fold_unary(operator, operand()),
+        Expression::Cast { data_type, .. } => Expression::Cast {
+            expr: Box::new(operand()),
+            data_type: data_type.clone(),
+        },
+        Expression::IsNull { negated, .. } => {
+            let operand = operand();
+            match constant(&operand) {
+                Some(c) => Expression::Value(Value::Boolean((c == Constant::Null) != *negated)),
+                None => Expression::IsNull {
+                    expr: Box::new(operand),
+                    negated: *negated,
+                },
+            }
+        }
+        Expression::Like { negated, .. } => {
+            let pattern = operand();
+            Expression::Like {
+                expr: Box::new(operand()),
+                pattern: Box::new(pattern),
+                negated: *negated,
+            }
+        }
+        Expression::Function { name, args } => Expression::Function {
+            name: name.clone(),
+            args: operands(folded, args.len()),
+        },
+        Expression::InList { list, negated, .. } => {
+            let list = operands(folded, list.len());
+            Expression::InList {
+                expr: Box::new(
+                    folded
+                        .pop()
+                        .expect("operands are folded before their node")
+                        .into_expression(),
+                ),
+                list,
+                negated: *negated,
+            }
+        }
+        _ => unreachable!("only nodes with operands are left"),
+    };
+    Folded::Expression(node)
+}
+
+/// Takes the last `count` folded operands, in order
+fn operands(folded: &mut Vec<Folded>, count: usize) -> Vec<Expression> {
+    folded
+        .split_off(folded.len() - count)
+        .into_iter()
+        .map(Folded::into_expression)
+        .collect()
+}
+
+/// Folds the expressions of a subquery in place
+struct Folder {
+    simplify: bool,
+}
+
+impl VisitorMut for Folder {
+    type Break = ();
+
+    fn visit_expression_mut(&mut self, expr: &mut Expression) -> ControlFlow<()> {
+        *expr = fold(expr, self.simplify);
+        ControlFlow::Continue(())
+    }
+}
+
+fn fold_unary(operator: &UnaryOperator, operand: Expression) -> Expression {
+    let folded = match (operator, constant(&operand)) {
+        (_, Some(Constant::Null)) => Some(Expression::Value(Value::Null)),
+        (UnaryOperator::Not, Some(Constant::Boolean(b))) => {
+            Some(Expression::Value(Value::Boolean(!b)))
+        }
+        (UnaryOperator::Minus, Some(Constant::Integer(n))) => integer(-n),
+        (UnaryOperator::Plus, Some(Constant::Integer(n))) => integer(n),
+        _ => None,
+    };
+
+    folded.unwrap_or_else(|| Expression::UnaryOperation {
+        operator: operator.clone(),
+        operand: Box::new(operand),
+    })
+}
+
+fn fold_binary(left: Expression, operator: &BinaryOperator, right: Expression) -> Expression {
+    let folded = match (constant(&left), constant(&right)) {
+        (Some(l), Some(r)) => fold_operation(l, operator, r),
+        _ => None,
+    };
+
+    folded.unwrap_or_else(|| Expression::BinaryOperation {
+        left_operand: Box::new(left),
+        operator: operator.clone(),
+        right_operand: Box::new(right),
+    })
+}
+
+/// Folds AND and OR, which can simplify even when only one side is a literal. The operand
+/// that is kept is passed through as it is, so a negation in it can still be cancelled.
+fn fold_logical(left: Folded, operator: &BinaryOperator, right: Folded) -> Folded {
+    let (absorbing, identity) = match operator {
+        BinaryOperator::And => (false, true),
+        _ => (true, false),
+    };
+
+    match (left.constant(), right.constant()) {
+        (Some(Constant::Boolean(b)), _) | (_, Some(Constant::Boolean(b))) if b == absorbing => {
+            Folded::Expression(Expression::Value(Value::Boolean(absorbing)))
+        }
+        (Some(Constant::Boolean(b)), _) if b == identity => right,
+        (_, Some(Constant::Boolean(b))) if b == identity => left,
+        (Some(Constant::Null), Some(Constant::Null)) => {
+            Folded::Expression(Expression::Value(Value::Null))
+        }
+        _ => Folded::Expression(Expression::BinaryOperation {
+            left_operand: Box::new(left.into_expression()),
+            operator: operator.clone(),
+            right_operand: Box::new(right.into_expression()),
+        }),
+    }
+}
+
+/// Applies an arithmetic or comparison operator to two literals, or returns `None` when
+/// the result cannot be computed safely
+fn fold_operation(
+    left: Constant,
+    operator: &BinaryOperator,
+    right: Constant,
+) -> Option<Expression> {
+    match (left, right) {
+        (Constant::Null, _) | (_, Constant::Null) => Some(Expression::Value(Value::Null)),
+        (Constant::Integer(l), Constant::Integer(r)) => match operator {
+            BinaryOperator::Plus => integer(l.checked_add(r)?),
+            BinaryOperator::Minus => integer(l.checked_sub(r)?),
+            BinaryOperator::Multiply => integer(l.checked_mul(r)?),
+            BinaryOperator::Divide => integer(l.checked_div(r)?),
+            BinaryOperator::Equal => Some(Expression::Value(Value::Boolean(l == r))),
+            BinaryOperator::NotEqual => Some(Expression::Value(Value::Boolean(l != r))),
+            BinaryOperator::LessThan => Some(Expression::Value(Value::Boolean(l < r))),
+            BinaryOperator::LessThanOrEqual => Some(Expression::Value(Value::Boolean(l <= r))),
+            BinaryOperator::GreaterThan => Some(Expression::Value(Value::Boolean(l > r))),
+            BinaryOperator::GreaterThanOrEqual => Some(Expression::Value(Value::Boolean(l >= r))),
+            BinaryOperator::And | BinaryOperator::Or | BinaryOperator::Concat => None,
+        },
+        (Constant::String(l), Constant::String(r)) if *operator == BinaryOperator::Concat => {
+            Some(Expression::Value(Value::SingleQuotedString(l + &r)))
+        }
+        // String ordering depends on the collation, so only equality is folded
+        (l @ (Constant::String(_) | Constant::Boolean(_)), r) => match operator {
+            BinaryOperator::Equal if same_kind(&l, &r) => {
+                Some(Expression::Value(Value::Boolean(l == r)))
+            }
+            BinaryOperator::NotEqual if same_kind(&l, &r) => {
+                Some(Expression::Value(Value::Boolean(l != r)))
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn same_kind(left: &Constant, right: &Constant) -> bool {
+    std::mem::discriminant(left) == std::mem::discriminant(right)
+}
+
+/// Reads a literal operand, treating `-n` as a negative integer
+fn constant(expr: &Expression) -> Option<Constant> {
+    match expr {
+        Expression::Value(Value::Number(n)) => Some(Constant::Integer(i128::from(*n))),
+        Expression::Value(Value::SingleQuotedString(s)) => Some(Constant::String(s.clone())),
+        Expression::Value(Value::Boolean(b)) => Some(Constant::Boolean(*b)),
+        Expression::Value(Value::Null) => Some(Constant::Null),
+        Expression::UnaryOperation {
+            operator: UnaryOperator::Minus,
+            operand,
+        } => match **operand {
+            Expression::Value(Value::Number(n)) => Some(Constant::Integer(-i128::from(n))),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Builds the literal for `n`, or returns `None` if it does not fit in a `Number`
+fn integer(n: i128) -> Option<Expression> {
+    let magnitude = u64::try_from(n.unsigned_abs()).ok()?;
+    let number = Expression::Value(Value::Number(magnitude));
+    if n < 0 {
+        Some(Expression::UnaryOperation {
+            operator: UnaryOperator::Minus,
+            operand: Box::new(number),
+        })
+    } else {
+        Some(number)
+    }
+}
@@ -0,0 +1,226 @@
+use crate::dialect::Dialect;
+
+/// Represents how unquoted identifiers are normalized
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum IdentifierCase {
+    /// Keep identifiers as written
+    #[default]
+    Preserve,
+    Lower,
+    Upper,
+}
+
+/// Configures a `PrattParser`; the defaults match `PrattParser::new`
+#[derive(Debug, PartialEq, Clone)]
+#[non_exhaustive]
+pub struct ParserOptions {
+    pub dialect: Dialect,
+    /// Deepest allowed nesting of expressions and parenthesized queries, or `None` for no limit
+    pub max_expression_depth: Option<usize>,
+    /// Accept a `,` before the end of a list, as in `SELECT a, b, FROM t`
+    pub allow_trailing_commas: bool,
+    /// Reject `a < b < c`, which parses as `(a < b) < c` otherwise
+    pub reject_chained_comparisons: bool,
+    /// Keep parsing a script after an error instead of stopping at the first one
+    pub recover_from_errors: bool,
+    pub identifier_case: IdentifierCase,
+}
+
+impl Default for ParserOptions {
+    fn default() -> Self {
+        ParserOptions {
+            dialect: Dialect::Generic,
+            max_expression_depth: None,
+            allow_trailing_commas: false,
+            reject_chained_comparisons: false,
+            recover_from_errors: false,
+            identifier_case: IdentifierCase::Preserve,
+        }
+    }
+}
+
+impl ParserOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_dialect(mut self, dialect: Dialect) -> Self {
+        self.dialect = dialect;
+        self
+    }
+
+    pub fn with_max_expression_depth(mut self, depth: usize) -> Self {
+        self.max_expression_depth = Some(depth);
+        self
+    }
+
+    pub fn with_trailing_commas(mut self, allow: bool) -> Self {
+        self.allow_trailing_commas = allow;
+        self
+    }
+
+    pub fn with_chained_comparisons_rejected(mut self, reject: bool) -> Self {
+        self.reject_chained_comparisons = reject;
+        self
+    }
+
+    pub fn with_error_recovery(mut self, recover: bool) -> Self {
+        self.recover_from_errors = recover;
+        self
+    }
+
+    pub fn with_identifier_case(mut self, case: IdentifierCase) -> Self {
+        self.identifier_case = case;
+        self
+    }
+}
+
+/// Configures `Statement::normalize`
+#[derive(Debug, PartialEq, Clone, Default)]
+#[non_exhaustive]
+pub struct NormalizeOptions {
+    /// How unquoted identifiers are folded; quoted identifiers are never changed
+    pub identifier_case: IdentifierCase,
+    /// Drop a column's table qualifier when the query reads from that table alone
+    pub strip_qualifiers: bool,
+}
+
+impl NormalizeOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_identifier_case(mut self, case: IdentifierCase) -> Self {
+        self.identifier_case = case;
+        self
+    }
+
+    pub fn with_qualifiers_stripped(mut self, strip: bool) -> Self {
+        self.strip_qualifiers = strip;
+        self
+    }
+}
+
+/// Configures `normalize::semantically_equal`
+#[derive(Debug, PartialEq, Clone)]
+#[non_exhaustive]
+pub struct EqualityOptions {
+    /// How unquoted identifiers are folded before comparing; `Preserve` makes the comparison
+    /// case-sensitive
+    pub identifier_case: IdentifierCase,
+    /// Treat `t.a` and `a` as equal when the query reads from `t` alone
+    pub strip_qualifiers: bool,
+}
+
+impl Default for EqualityOptions {
+    fn default() -> Self {
+        EqualityOptions {
+            identifier_case: IdentifierCase::Lower,
+            strip_qualifiers: false,
+        }
+    }
+}
+
+impl EqualityOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_identifier_case(mut self, case: IdentifierCase) -> Self {
+        self.identifier_case = case;
+        self
+    }
+
+    pub fn with_qualifiers_stripped(mut self, strip: bool) -> Self {
+        self.strip_qualifiers = strip;
+        self
+    }
+}
+
+/// Configures `Expression::compile_predicate_with_options`
+#[derive(Debug, PartialEq, Clone, Default)]
+#[non_exhaustive]
+pub struct PredicateOptions {
+    /// Fail with `EvalError::UnknownIdentifier` when a row lacks a referenced field, instead
+    /// of reading the field as NULL
+    pub reject_unknown_fields: bool,
+}
+
+impl PredicateOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_unknown_fields_rejected(mut self, reject: bool) -> Self {
+        self.reject_unknown_fields = reject;
+        self
+    }
+}
+
+/// Represents how the formatter writes keywords
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum KeywordCase {
+    #[default]
+    Upper,
+    Lower,
+}
+
+/// Configures `formatter::format_statement`
+#[derive(Debug, PartialEq, Clone)]
+#[non_exhaustive]
+pub struct FormatOptions {
+    /// Spaces per level of indentation
+    pub indent_width: usize,
+    /// How keywords are written; type names always keep their upper-case spelling
+    pub keyword_case: KeywordCase,
+    /// Put each select item on its own line, instead of packing as many as fit on a line
+    pub one_item_per_line: bool,
+    /// Start the continuation lines of a list with its comma instead of ending lines with it
+    pub leading_commas: bool,
+    /// The width beyond which subqueries, lists and boolean chains are broken over several
+    /// lines; a single name or literal longer than this is left as it is
+    pub max_line_width: usize,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        FormatOptions {
+            indent_width: 2,
+            keyword_case: KeywordCase::Upper,
+            one_item_per_line: true,
+            leading_commas: false,
+            max_line_width: 80,
+        }
+    }
+}
+
+impl FormatOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_indent_width(mut self, width: usize) -> Self {
+        self.indent_width = width;
+        self
+    }
+
+    pub fn with_keyword_case(mut self, case: KeywordCase) -> Self {
+        self.keyword_case = case;
+        self
+    }
+
+    pub fn with_one_item_per_line(mut self, one_per_line: bool) -> Self {
+        self.one_item_per_line = one_per_line;
+        self
+    }
+
+    pub fn with_leading_commas(mut self, leading: bool) -> Self {
+        self.leading_commas = leading;
+        self
+    }
+
+    pub fn with_max_line_width(mut self, width: usize) -> Self {
+        self.max_line_width = width;
+        self
+    }
+}
@@ -0,0 +1,184 @@
+use std::collections::HashMap;
+use std::ops::ControlFlow;
+
+use crate::ast::{self, DataType, Expression, Float, Statement, UnaryOperator};
+use crate::error::BindError;
+use crate::eval::Value;
+use crate::visitor::{walk_expression, walk_expression_mut, Visitor, VisitorMut};
+
+/// Represents a parameter marker found in a statement
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Placeholder {
+    /// The marker as written: `?`, `$1` or `:name`
+    pub text: String,
+    /// Zero-based position among the statement's placeholders in source order
+    pub position: usize,
+}
+
+impl Placeholder {
+    /// Returns `n` for a `$n` placeholder
+    pub fn number(&self) -> Option<usize> {
+        number(&self.text)
+    }
+
+    /// Returns the name of a `:name` placeholder
+    pub fn name(&self) -> Option<&str> {
+        self.text.strip_prefix(':')
+    }
+}
+
+impl Statement {
+    /// Returns every placeholder in source order; a marker used twice is listed twice. The
+    /// order comes from the ordinals the parser gave the placeholders, since the visitor
+    /// reaches some clauses, such as OFFSET before LIMIT, out of order.
+    pub fn parameters(&self) -> Vec<Placeholder> {
+        let mut collector = PlaceholderCollector::default();
+        let _ = collector.visit_statement(self);
+        collector.placeholders.sort_by_key(|p| p.position);
+        collector.placeholders
+    }
+
+    /// Returns a copy of this statement with each `?` or `$n` placeholder replaced by the
+    /// literal for its value. `?` markers take the values in the order they are written
+    /// while `$n` takes the n-th value, so a number used twice consumes a single value. The
+    /// two styles cannot be mixed.
+    pub fn bind(&self, values: &[Value]) -> Result<Statement, BindError> {
+        let parameters = self.parameters();
+        if let Some(named) = parameters.iter().find(|p| p.name().is_some()) {
+            return Err(BindError::InvalidPlaceholder(format!(
+                "{} needs a named value",
+                named.text
+            )));
+        }
+
+        let anonymous = parameters.iter().filter(|p| p.text == "?").count();
+        let mut highest = 0;
+        for parameter in parameters.iter().filter(|p| p.text != "?") {
+            match parameter.number() {
+                Some(0) | None => {
+                    return Err(BindError::InvalidPlaceholder(format!(
+                        "{} is not a valid parameter number",
+                        parameter.text
+                    )))
+                }
+                Some(n) => highest = highest.max(n),
+            }
+        }
+        if anonymous > 0 && highest > 0 {
+            return Err(BindError::InvalidPlaceholder(
+                "cannot mix ? and $n placeholders".to_string(),
+            ));
+        }
+
+        let expected = anonymous.max(highest);
+        if values.len() != expected {
+            return Err(BindError::CountMismatch(format!(
+                "statement expects {} values, got {}",
+                expected,
+                values.len()
+            )));
+        }
+
+        // Without $n markers every placeholder is a `?`, so its ordinal is its index
+        self.bind_with(|placeholder, ordinal| {
+            let index = number(placeholder).map_or(ordinal, |n| n - 1);
+            values
+                .get(index)
+                .map(literal)
+                .ok_or_else(|| BindError::MissingValue(placeholder.to_string()))
+        })
+    }
+
+    /// Returns a copy of this statement with each `:name` placeholder replaced by the
+    /// literal for the value stored under `name`
+    pub fn bind_named(&self, values: &HashMap<String, Value>) -> Result<Statement, BindError> {
+        self.bind_with(|placeholder, _| match placeholder.strip_prefix(':') {
+            Some(name) => values
+                .get(name)
+                .map(literal)
+                .ok_or_else(|| BindError::MissingValue(placeholder.to_string())),
+            None => Err(BindError::InvalidPlaceholder(format!(
+                "{} needs a positional value",
+                placeholder
+            ))),
+        })
+    }
+
+    fn bind_with<F>(&self, literal_for: F) -> Result<Statement, BindError>
+    where
+        F: FnMut(&str, usize) -> Result<Expression, BindError>,
+    {
+        let mut statement = self.clone();
+        match (Binder { literal_for }).visit_statement_mut(&mut statement) {
+            ControlFlow::Continue(()) => Ok(statement),
+            ControlFlow::Break(err) => Err(err),
+        }
+    }
+}
+
+fn number(placeholder: &str) -> Option<usize> {
+    placeholder.strip_prefix('$')?.parse().ok()
+}
+
+/// Builds the literal expression for `value`; infinite and NaN floats have no literal of
+/// their own and become a cast string such as `'NaN'::FLOAT`
+fn literal(value: &Value) -> Expression {
+    match value {
+        Value::Integer(n) if *n < 0 => Expression::UnaryOperation {
+            operator: UnaryOperator::Minus,
+            operand: Box::new(Expression::Value(ast::Value::Number(n.unsigned_abs()))),
+        },
+        Value::Integer(n) => Expression::Value(ast::Value::Number(n.unsigned_abs())),
+        Value::Float(x) if x.is_finite() => Expression::Value(ast::Value::Float(Float(*x))),
+        Value::Float(x) => Expression::Cast {
+            expr: Box::new(Expression::Value(ast::Value::SingleQuotedString(
+                x.to_string(),
+            ))),
+            data_type: DataType::Float,
+        },
+        Value::String(s) => Expression::Value(ast::Value::SingleQuotedString(s.clone())),
+        Value::Boolean(b) => Expression::Value(ast::Value::Boolean(*b)),
+        Value::Null => Expression::Value(ast::Value::Null),
+    }
+}
+
+#[derive(Default)]
+struct PlaceholderCollector {
+    placeholders: Vec<Placeholder>,
+}
+
+impl Visitor for PlaceholderCollector {
+    type Break = ();
+
+    fn visit_expression(&mut self, expr: &Expression) -> ControlFlow<()> {
+        if let Expression::Placeholder { text, ordinal } = expr {
+            self.placeholders.push(Placeholder {
+                text: text.clone(),
+                position: *ordinal,
+            });
+        }
+        walk_expression(self, expr)
+    }
+}
+
+struct Binder<F> {
+    literal_for: F,
+}
+
+impl<F> VisitorMut for Binder<F>
+where
+    F: FnMut(&str, usize) -> Result<Expression, BindError>,
+{
+    type Break = BindError;
+
+    fn visit_expression_mut(&mut self, expr: &mut Expression) -> ControlFlow<BindError> {
+        if let Expression::Placeholder { text, ordinal } = expr {
+            match (self.literal_for)(text, *ordinal) {
+                Ok(literal) => *expr = literal,
+                Err(err) => return ControlFlow::Break(err),
+            }
+            return ControlFlow::Continue(());
+        }
+        walk_expression_mut(self, expr)
+    }
+}
@@ -1,111 +1,3030 @@
-use crate::ast::{Expression, Token, BinaryOperator};
-use crate::error::ParseError;
-use crate::tokenizer::Tokenizer;
-
-pub struct PrattParser {
-    tokenizer: Tokenizer,
-    current_token: Option<Token>,
-}
-
-impl PrattParser {
-    pub fn new(input: &str) -> Self {
-        let mut tokenizer = Tokenizer::new(input);
-        let first_token = tokenizer.next();
-        PrattParser {
-            tokenizer,
-            current_token: first_token,
-        }
-    }
-
-    pub fn parse(&mut self) -> Result<Expression, ParseError> {
-        self.parse_expression(0)
-    }
-
-    fn advance(&mut self) -> Result<(), ParseError> {
-        self.current_token = self.tokenizer.next();
-        Ok(())
-    }
-
-    fn parse_expression(&mut self, precedence: u8) -> Result<Expression, ParseError> {
-        let mut left = self.parse_primary()?;
-
-        while let Some(token) = &self.current_token {
-            let token_precedence = self.get_precedence(token);
-
-            if token_precedence <= precedence {
-                break;
-            }
-
-            let op = self.current_token.clone();
-            self.advance()?;
-            let right = self.parse_expression(token_precedence)?;
-
-            match op {
-                Some(Token::Plus) => {
-                    left = Expression::BinaryOperation {
-                        left_operand: Box::new(left),
-                        operator: BinaryOperator::Plus,
-                        right_operand: Box::new(right),
-                    };
-                }
-                Some(Token::Minus) => {
-                    left = Expression::BinaryOperation {
-                        left_operand: Box::new(left),
-                        operator: BinaryOperator::Minus,
-                        right_operand: Box::new(right),
-                    };
-                }
-                Some(Token::Multiply) => {
-                    left = Expression::BinaryOperation {
-                        left_operand: Box::new(left),
-                        operator: BinaryOperator::Multiply,
-                        right_operand: Box::new(right),
-                    };
-                }
-                Some(Token::Divide) => {
-                    left = Expression::BinaryOperation {
-                        left_operand: Box::new(left),
-                        operator: BinaryOperator::Divide,
-                        right_operand: Box::new(right),
-                    };
-                }
-                _ => return Err(ParseError::InvalidInput("Unexpected operator".into())),
-            }
-        }
-
-        Ok(left)
-    }
-
-    fn parse_primary(&mut self) -> Result<Expression, ParseError> {
-        match self.current_token.clone() {
-            Some(Token::Number(n)) => {
-                self.advance()?;
-                Ok(Expression::Number(n))
-            }
-            Some(Token::Identifier(s)) => {
-                self.advance()?;
-                Ok(Expression::Identifier(s))
-            }
-            Some(Token::LeftParentheses) => {
-                self.advance()?;
-                let expr = self.parse_expression(0)?;
-                if let Some(Token::RightParentheses) = self.current_token {
-                    self.advance()?;
-                    Ok(expr)
-                } else {
-                    Err(ParseError::InvalidInput("Expected closing parenthesis".into()))
-                }
-            }
-            Some(t) => Err(ParseError::InvalidInput(format!("Unexpected token: {:?}", t))),
-            None => Err(ParseError::InvalidInput("Unexpected end of input".into())),
-        }
-    }
-
-    fn get_precedence(&self, token: &Token) -> u8 {
-        match token {
-            Token::Plus | Token::Minus => 1,
-            Token::Multiply | Token::Divide => 2,
-            _ => 0,
-        }
-    }
-}
+use crate::ast::{
+    AlterTableOperation, Assignment, BinaryOperator, ColumnDef, ColumnOption, ConflictTarget, Cte,
+    DataType, Distinct, Expression, Float, GroupByExpr, Ident, InsertSource, IsolationLevel, Join,
+    JoinConstraint, JoinType, Keyword, LockStrength, LockingClause, MergeAction, MergeClause, Name,
+    ObjectName, ObjectType, OnConflict, OnConflictAction, OrderByItem, PragmaValue, Query,
+    ReferentialAction, SelectItem, SelectStatement, SetExpr, SetOperator, ShowFilter, Statement,
+    TableAlias, TableConstraint, TableReference, TableWithJoins, Token, TokenKind, Top,
+    UnaryOperator, Value, WaitPolicy, With,
+};
+use crate::dialect::Dialect;
+use crate::error::{describe, ParseContext, ParseError, ParseErrors, Problem};
+use crate::options::{IdentifierCase, ParserOptions};
+use crate::tokenizer::Tokenizer;
+
+/// Binding power of prefix NOT: tighter than comparisons, so `NOT a = b` negates `a` alone
+/// and negating a comparison takes parentheses, `NOT (a = b)`; looser than `||` and
+/// arithmetic, so `NOT a + 1` negates the sum
+const NOT_PRECEDENCE: u8 = 5;
+
+/// Binding power of comparisons, and of IN, LIKE and IS, which compare too
+const COMPARISON_PRECEDENCE: u8 = 4;
+
+/// Binding power of prefix `-` and `+`: as tight as multiplication
+const UNARY_SIGN_PRECEDENCE: u8 = 8;
+
+/// Tokens `get_precedence` gives a binding power
+const INFIX_OPERATORS: &[Token] = &[
+    Token::Keyword(Keyword::Or),
+    Token::Keyword(Keyword::And),
+    Token::Equal,
+    Token::NotEqual,
+    Token::LessThan,
+    Token::LessThanOrEqual,
+    Token::GreaterThan,
+    Token::GreaterThanOrEqual,
+    Token::Keyword(Keyword::In),
+    Token::Keyword(Keyword::Like),
+    Token::Keyword(Keyword::Is),
+    Token::Concat,
+    Token::Plus,
+    Token::Minus,
+    Token::Multiply,
+    Token::Divide,
+    Token::DoubleColon,
+];
+
+/// Leading keywords of the statements `parse_statement` understands
+const SUPPORTED_STATEMENTS: &[&str] = &[
+    "SELECT",
+    "WITH",
+    "VALUES",
+    "INSERT",
+    "UPDATE",
+    "DELETE",
+    "CREATE",
+    "ALTER",
+    "DROP",
+    "TRUNCATE",
+    "BEGIN",
+    "START",
+    "COMMIT",
+    "ROLLBACK",
+    "SAVEPOINT",
+    "RELEASE",
+    "SHOW",
+    "DESCRIBE",
+    "PRAGMA",
+    "MERGE",
+];
+
+pub struct PrattParser {
+    tokenizer: Tokenizer,
+    current_token: Option<Token>,
+    options: ParserOptions,
+    /// Current nesting of expressions and queries, checked against
+    /// `options.max_expression_depth`
+    depth: usize,
+    /// Placeholders parsed so far in the current statement, which is the next one's ordinal
+    placeholders: usize,
+    /// The constructs being parsed, outermost first; left as they were when an error occurs,
+    /// so it can be recorded in the error
+    parsing: Vec<ParseContext>,
+    /// What was tried and not found at the current token, for the error message
+    alternatives: Vec<String>,
+    /// The kinds of token that would have been accepted at the current token, for
+    /// completion and errors
+    expected_tokens: Vec<TokenKind>,
+}
+
+impl PrattParser {
+    pub fn new(input: &str) -> Self {
+        Self::new_with_options(input, ParserOptions::default())
+    }
+
+    /// Creates a parser that also accepts the syntax specific to `dialect`
+    pub fn new_with_dialect(input: &str, dialect: Dialect) -> Self {
+        Self::new_with_options(input, ParserOptions::new().with_dialect(dialect))
+    }
+
+    pub fn new_with_options(input: &str, options: ParserOptions) -> Self {
+        let mut tokenizer = if options.recover_from_errors {
+            Tokenizer::new_recovering(input)
+        } else {
+            Tokenizer::new(input)
+        };
+        let first_token = tokenizer.next();
+        PrattParser {
+            tokenizer,
+            current_token: first_token,
+            options,
+            depth: 0,
+            placeholders: 0,
+            parsing: vec![],
+            alternatives: vec![],
+            expected_tokens: vec![],
+        }
+    }
+
+    /// Starts over on `input` with the same options, reusing the buffers of the tokenizer
+    /// and parser. Parsing afterwards gives the same results as a new parser would, so a
+    /// parser can be kept per thread for parsing many short inputs.
+    pub fn reset(&mut self, input: &str) {
+        self.tokenizer.reset(input);
+        self.current_token = self.tokenizer.next();
+        self.depth = 0;
+        self.placeholders = 0;
+        self.parsing.clear();
+        self.alternatives.clear();
+        self.expected_tokens.clear();
+    }
+
+    pub fn parse(&mut self) -> Result<Expression, ParseError> {
+        let result = self.parse_expression(0);
+        self.locate(result)
+    }
+
+    /// Parses an expression that makes up the whole input, unlike `parse`, which stops at
+    /// the first token that cannot continue the expression
+    pub fn parse_complete_expression(&mut self) -> Result<Expression, ParseError> {
+        let result = self
+            .parse_expression(0)
+            .and_then(|expr| match self.current_token.clone() {
+                Some(Token::Eof) | None => Ok(expr),
+                Some(t) => Err(ParseError::new(Problem::UnexpectedToken {
+                    found: t,
+                    position: "after end of expression".into(),
+                })),
+            });
+        self.locate(result)
+    }
+
+    /// Parses a single SQL statement, optionally terminated by a semicolon
+    pub fn parse_statement(&mut self) -> Result<Statement, ParseError> {
+        let result = self.parse_single_statement();
+        self.locate(result)
+    }
+
+    fn parse_single_statement(&mut self) -> Result<Statement, ParseError> {
+        let statement = self.parse_statement_body()?;
+
+        self.consume_token(&Token::Semicolon)?;
+
+        match self.current_token.clone() {
+            Some(Token::Eof) | None => Ok(statement),
+            Some(t) => Err(ParseError::new(Problem::UnexpectedToken {
+                found: t,
+                position: "after end of statement".into(),
+            })),
+        }
+    }
+
+    /// Parses every statement of a `;`-separated script, skipping empty statements
+    pub fn parse_statements(input: &str) -> Result<Vec<Statement>, ParseError> {
+        let (statements, errors) =
+            Self::parse_statements_with_options(input, ParserOptions::default());
+        match errors.into_iter().next() {
+            Some(e) => Err(e),
+            None => Ok(statements),
+        }
+    }
+
+    /// Parses a script like `parse_statements`, but on an error skips to the next `;`
+    /// and carries on, returning every statement that parsed and every error hit
+    pub fn parse_statements_recovering(input: &str) -> (Vec<Statement>, ParseErrors) {
+        Self::parse_statements_with_options(input, ParserOptions::new().with_error_recovery(true))
+    }
+
+    /// Parses a script, stopping at the first error unless `options.recover_from_errors` is
+    /// set. When recovering, lexical errors are skipped over too, and the errors come back in
+    /// source order. A statement with a lexical error is not returned, and a syntax error
+    /// after one in the same statement is taken to be caused by it and left out, as is a
+    /// second error at the same place.
+    pub fn parse_statements_with_options(
+        input: &str,
+        options: ParserOptions,
+    ) -> (Vec<Statement>, ParseErrors) {
+        let mut parser = PrattParser::new_with_options(input, options);
+        let recover = parser.options.recover_from_errors;
+        let mut statements = vec![];
+        let mut errors = vec![];
+        let mut number = 1;
+        loop {
+            let start = parser.tokenizer.span().start.offset;
+            let result = match parser.parse_script_statement(number) {
+                // A recovering tokenizer does not stop early; its errors are gathered below
+                result if recover => result.map_err(|e| parser.annotate(e)),
+                result => parser.locate(result),
+            };
+            match result {
+                Ok(Some(statement)) => {
+                    // Text was dropped from a statement with a lexical error, so it did
+                    // not really parse
+                    let end = parser.tokenizer.span().start.offset;
+                    if !parser.has_lexical_error(start, end) {
+                        statements.push(statement);
+                    }
+                }
+                Ok(None) => break,
+                Err(e) => {
+                    let offset = e.span().map_or(usize::MAX, |span| span.start.offset);
+                    if !parser.has_lexical_error(start, offset) {
+                        errors.push(e);
+                    }
+                    if !recover || parser.at_end() {
+                        break;
+                    }
+                    parser.skip_to_statement_end();
+                }
+            }
+            number += 1;
+        }
+
+        if recover {
+            errors.extend(parser.tokenizer.errors().iter().cloned());
+            errors.sort_by_key(|e| e.span().map_or(usize::MAX, |span| span.start.offset));
+            errors.dedup_by(|a, b| a.span().is_some() && a.span() == b.span());
+        }
+        (statements, ParseErrors(errors))
+    }
+
+    /// Parses `input`, the `number`th statement of a script up to and including its
+    /// semicolon, on its own. Returns `None` if it holds only semicolons and whitespace.
+    pub(crate) fn parse_script_piece(
+        input: &str,
+        options: ParserOptions,
+        number: usize,
+    ) -> Result<Option<Statement>, ParseError> {
+        let mut parser = PrattParser::new_with_options(input, options.with_error_recovery(false));
+        let result = parser.parse_script_statement(number);
+        parser.locate(result)
+    }
+
+    /// Parses `input` as the start of a script and returns the tokens that could come next,
+    /// or `None` if it does not parse up to its end
+    pub(crate) fn expected_at_end(input: &str, options: ParserOptions) -> Option<Vec<TokenKind>> {
+        let mut parser = PrattParser::new_with_options(input, options);
+        if parser.tokenizer.error().is_some() {
+            return None;
+        }
+        let mut number = 1;
+        loop {
+            match parser.parse_script_statement(number) {
+                Ok(Some(_)) => number += 1,
+                Ok(None) => break,
+                Err(_) if parser.at_end() => break,
+                Err(_) => return None,
+            }
+        }
+        Some(parser.expected_tokens)
+    }
+
+    /// Whether a recovering tokenizer skipped over a lexical error between the byte offsets
+    /// `start` and `end`
+    fn has_lexical_error(&self, start: usize, end: usize) -> bool {
+        self.options.recover_from_errors
+            && self.tokenizer.errors().iter().any(|e| {
+                e.span()
+                    .is_some_and(|span| (start..=end).contains(&span.start.offset))
+            })
+    }
+
+    /// Parses the next statement of a script, or returns `None` once only semicolons remain
+    fn parse_script_statement(&mut self, number: usize) -> Result<Option<Statement>, ParseError> {
+        self.parsing.clear();
+        let mut at_start = number == 1;
+        while self.consume_token(&Token::Semicolon)? {
+            at_start = true;
+        }
+        if matches!(self.current_token, Some(Token::Eof) | None) {
+            // Only a statement that has been ended can be followed by another
+            if at_start {
+                self.expect_statement();
+            }
+            return Ok(None);
+        }
+
+        let statement = self
+            .parse_statement_body()
+            .map_err(|e| e.in_statement(number))?;
+
+        match self.current_token.clone() {
+            Some(Token::Semicolon | Token::Eof) | None => Ok(Some(statement)),
+            Some(t) => {
+                self.expect_alternative(describe(&Token::Semicolon));
+                Err(ParseError::new(Problem::UnexpectedToken {
+                    found: t,
+                    position: format!("after end of statement {}", number),
+                }))
+            }
+        }
+    }
+
+    /// Locates an error at the current token, where parsing stopped. Once the tokens run out
+    /// after a lexical error, that error is reported instead, even if the truncated input
+    /// happened to parse.
+    fn locate<T>(&self, result: Result<T, ParseError>) -> Result<T, ParseError> {
+        match self.tokenizer.error() {
+            Some(lexical) if self.at_end() => Err(lexical.clone()),
+            _ => result.map_err(|e| self.annotate(e)),
+        }
+    }
+
+    /// Runs `parse` with `context` pushed onto the stack of constructs being parsed. On an
+    /// error the stack is left as it is, so the error can record the full chain.
+    fn in_context<T>(
+        &mut self,
+        context: ParseContext,
+        parse: impl FnOnce(&mut Self) -> Result<T, ParseError>,
+    ) -> Result<T, ParseError> {
+        self.parsing.push(context);
+        let result = parse(self)?;
+        self.parsing.pop();
+        Ok(result)
+    }
+
+    /// Records where parsing stopped in `e`: the current token, the constructs being parsed
+    /// and what would have been accepted instead
+    fn annotate(&self, e: ParseError) -> ParseError {
+        e.with_span(self.tokenizer.span())
+            .while_parsing(&self.parsing)
+            .with_alternatives(&self.alternatives)
+            .with_expected_tokens(&self.expected_tokens)
+    }
+
+    fn at_end(&self) -> bool {
+        matches!(self.current_token, Some(Token::Eof) | None)
+    }
+
+    /// Skips tokens up to the next `;`; string literals are single tokens, so their semicolons are safe
+    fn skip_to_statement_end(&mut self) {
+        while !matches!(
+            self.current_token,
+            Some(Token::Semicolon | Token::Eof) | None
+        ) {
+            self.current_token = self.tokenizer.next();
+        }
+        self.alternatives.clear();
+    }
+
+    /// Parses one statement, leaving any terminating `;` unconsumed
+    fn parse_statement_body(&mut self) -> Result<Statement, ParseError> {
+        self.placeholders = 0;
+        let statement = match self.current_token.clone() {
+            Some(
+                Token::Keyword(Keyword::Select | Keyword::With | Keyword::Values)
+                | Token::LeftParentheses,
+            ) => Statement::Query(Box::new(self.parse_query()?)),
+            Some(Token::Keyword(Keyword::Insert)) => {
+                self.in_context(ParseContext::Insert, Self::parse_insert)?
+            }
+            Some(Token::Keyword(Keyword::Update)) => {
+                self.in_context(ParseContext::Update, Self::parse_update)?
+            }
+            Some(Token::Keyword(Keyword::Delete)) => {
+                self.in_context(ParseContext::Delete, Self::parse_delete)?
+            }
+            Some(Token::Keyword(Keyword::Create)) => self.parse_create()?,
+            Some(Token::Keyword(Keyword::Alter)) => self.parse_alter()?,
+            Some(Token::Keyword(Keyword::Drop)) => self.parse_drop()?,
+            Some(Token::Keyword(Keyword::Truncate)) => self.parse_truncate()?,
+            Some(Token::Keyword(
+                keyword @ (Keyword::Begin
+                | Keyword::Start
+                | Keyword::Commit
+                | Keyword::Rollback
+                | Keyword::Savepoint
+                | Keyword::Release),
+            )) => self.parse_transaction_statement(keyword)?,
+            Some(Token::Keyword(Keyword::Show)) => self.parse_show()?,
+            Some(Token::Keyword(Keyword::Pragma)) => self.parse_pragma()?,
+            Some(Token::Keyword(Keyword::Merge)) => self.parse_merge()?,
+            Some(Token::Keyword(Keyword::Describe | Keyword::Desc)) => self.parse_describe()?,
+            Some(Token::Eof) | None => {
+                self.expect_statement();
+                return Err(ParseError::new(Problem::UnexpectedEndOfInput {
+                    what: format!(
+                        "a statement starting with {}",
+                        SUPPORTED_STATEMENTS.join(", ")
+                    ),
+                }));
+            }
+            Some(t) => {
+                return Err(ParseError::new(Problem::ExpectedKeyword {
+                    keywords: SUPPORTED_STATEMENTS
+                        .iter()
+                        .filter_map(|name| name.parse().ok())
+                        .collect(),
+                    position: "to start a statement".into(),
+                    found: t,
+                }))
+            }
+        };
+        Ok(statement)
+    }
+
+    /// Parses `INSERT INTO table [(column, ...)] {VALUES (...), ... | query}`
+    fn parse_insert(&mut self) -> Result<Statement, ParseError> {
+        self.advance()?; // Skip INSERT
+        self.expect_keyword(Keyword::Into, "INSERT")?;
+        let table = self.parse_object_name("table name after INSERT INTO")?;
+
+        // A parenthesized query is the source, not a column list
+        let query_follows = matches!(
+            self.tokenizer.peek_token(),
+            Some(Token::Keyword(
+                Keyword::Select | Keyword::With | Keyword::Values
+            ))
+        );
+        let mut columns = vec![];
+        if !query_follows && self.current_token == Some(Token::LeftParentheses) {
+            columns = self.parse_column_list("INSERT column list")?;
+        }
+
+        let source = if self.consume_keyword(Keyword::Values)? {
+            InsertSource::Values(self.parse_values_rows()?)
+        } else if self.starts_query() {
+            InsertSource::Query(Box::new(self.parse_query()?))
+        } else {
+            return Err(ParseError::new(Problem::ExpectedKeyword {
+                keywords: vec![Keyword::Values, Keyword::Select, Keyword::With],
+                position: format!("after INSERT INTO {}", table),
+                found: self.found(),
+            }));
+        };
+
+        let on_conflict = if self.consume_keyword(Keyword::On)? {
+            self.expect_keyword(Keyword::Conflict, "ON")?;
+            Some(self.parse_on_conflict()?)
+        } else {
+            None
+        };
+
+        let returning = self.parse_returning()?;
+
+        Ok(Statement::Insert {
+            table,
+            columns,
+            source,
+            on_conflict,
+            returning,
+        })
+    }
+
+    /// Parses `MERGE INTO target USING source ON condition WHEN [NOT] MATCHED ... THEN action ...`
+    fn parse_merge(&mut self) -> Result<Statement, ParseError> {
+        self.advance()?; // Skip MERGE
+        self.expect_keyword(Keyword::Into, "MERGE")?;
+        let target = self.parse_table_reference()?;
+        self.expect_keyword(Keyword::Using, "MERGE INTO target")?;
+        let source = self.parse_table_reference()?;
+        self.expect_keyword(Keyword::On, "MERGE ... USING source")?;
+        let on = self.parse_clause_expression("ON")?;
+
+        let mut clauses = vec![];
+        while self.consume_keyword(Keyword::When)? {
+            clauses.push(self.parse_merge_clause()?);
+        }
+        if clauses.is_empty() {
+            return Err(ParseError::new(Problem::ExpectedKeyword {
+                keywords: vec![Keyword::When],
+                position: "after MERGE ... ON condition".into(),
+                found: self.found(),
+            }));
+        }
+
+        Ok(Statement::Merge {
+            target,
+            source,
+            on,
+            clauses,
+        })
+    }
+
+    /// Parses `[NOT] MATCHED [AND condition] THEN action` after WHEN
+    fn parse_merge_clause(&mut self) -> Result<MergeClause, ParseError> {
+        let matched = !self.consume_keyword(Keyword::Not)?;
+        let when = if matched { "WHEN" } else { "WHEN NOT" };
+        self.expect_keyword(Keyword::Matched, when)?;
+        let predicate = if self.consume_keyword(Keyword::And)? {
+            Some(self.parse_clause_expression(&format!("{} MATCHED AND", when))?)
+        } else {
+            None
+        };
+        self.expect_keyword(Keyword::Then, &format!("{} MATCHED", when))?;
+
+        let action = match self.current_token.clone() {
+            Some(Token::Keyword(Keyword::Update)) if matched => {
+                self.advance()?;
+                self.expect_keyword(Keyword::Set, "THEN UPDATE")?;
+                let assignments = self.parse_comma_separated("SET", Self::parse_assignment)?;
+                MergeAction::Update { assignments }
+            }
+            Some(Token::Keyword(Keyword::Delete)) if matched => {
+                self.advance()?;
+                MergeAction::Delete
+            }
+            Some(Token::Keyword(Keyword::Insert)) if !matched => {
+                self.advance()?;
+                let columns = if self.current_token == Some(Token::LeftParentheses) {
+                    self.parse_column_list("MERGE INSERT column list")?
+                } else {
+                    vec![]
+                };
+                self.expect_keyword(Keyword::Values, "THEN INSERT")?;
+                let mut rows = self.parse_values_rows()?;
+                if rows.len() != 1 {
+                    return Err(ParseError::new(Problem::InvalidInput {
+                        message: "MERGE INSERT takes exactly one VALUES row".into(),
+                    }));
+                }
+                MergeAction::Insert {
+                    columns,
+                    values: rows.remove(0),
+                }
+            }
+            Some(Token::Keyword(Keyword::Do)) => {
+                self.advance()?;
+                self.expect_keyword(Keyword::Nothing, "THEN DO")?;
+                MergeAction::DoNothing
+            }
+            t => {
+                let (keywords, position) = if matched {
+                    (
+                        vec![Keyword::Update, Keyword::Delete, Keyword::Do],
+                        "after WHEN MATCHED THEN",
+                    )
+                } else {
+                    (
+                        vec![Keyword::Insert, Keyword::Do],
+                        "after WHEN NOT MATCHED THEN",
+                    )
+                };
+                return Err(ParseError::new(Problem::ExpectedKeyword {
+                    keywords,
+                    position: position.into(),
+                    found: t.unwrap_or(Token::Eof),
+                }));
+            }
+        };
+
+        Ok(MergeClause {
+            matched,
+            predicate,
+            action,
+        })
+    }
+
+    /// Parses `UPDATE table [alias] SET column = expr, ... [FROM ...] [WHERE ...]`
+    fn parse_update(&mut self) -> Result<Statement, ParseError> {
+        self.advance()?; // Skip UPDATE
+        let table = self.parse_table_reference()?;
+        self.expect_keyword(Keyword::Set, "UPDATE table")?;
+        let assignments = self.in_context(ParseContext::Assignments, |parser| {
+            parser.parse_comma_separated("SET", Self::parse_assignment)
+        })?;
+
+        let from = if self.consume_keyword(Keyword::From)? {
+            Some(self.parse_table_with_joins()?)
+        } else {
+            None
+        };
+        let selection = self.parse_where_clause()?;
+        let returning = self.parse_returning()?;
+
+        Ok(Statement::Update {
+            table,
+            assignments,
+            from,
+            selection,
+            returning,
+        })
+    }
+
+    /// Parses `DELETE FROM table [alias] [USING ...] [WHERE ...]`
+    fn parse_delete(&mut self) -> Result<Statement, ParseError> {
+        self.advance()?; // Skip DELETE
+        self.expect_keyword(Keyword::From, "DELETE")?;
+        let table = self.parse_table_reference()?;
+
+        let using = if self.consume_keyword(Keyword::Using)? {
+            Some(self.parse_table_with_joins()?)
+        } else {
+            None
+        };
+        let selection = self.parse_where_clause()?;
+        let returning = self.parse_returning()?;
+
+        Ok(Statement::Delete {
+            table,
+            using,
+            selection,
+            returning,
+        })
+    }
+
+    /// Parses `CREATE TABLE ...`, `CREATE [UNIQUE] INDEX ...` or `CREATE ... VIEW ...`
+    fn parse_create(&mut self) -> Result<Statement, ParseError> {
+        self.advance()?; // Skip CREATE
+        if self.consume_keyword(Keyword::Unique)? {
+            self.expect_keyword(Keyword::Index, "CREATE UNIQUE")?;
+            return self.parse_create_index(true);
+        }
+        if self.consume_keyword(Keyword::Index)? {
+            return self.parse_create_index(false);
+        }
+
+        // OR here is part of OR REPLACE, not the logical operator
+        let or_replace = if self.consume_keyword(Keyword::Or)? {
+            self.expect_keyword(Keyword::Replace, "CREATE OR")?;
+            true
+        } else {
+            false
+        };
+        let materialized = self.consume_keyword(Keyword::Materialized)?;
+        if or_replace || materialized || self.current_token == Some(Token::Keyword(Keyword::View)) {
+            let after = if materialized {
+                "MATERIALIZED"
+            } else {
+                "CREATE OR REPLACE"
+            };
+            self.expect_keyword(Keyword::View, after)?;
+            return self.parse_create_view(or_replace, materialized);
+        }
+
+        let temporary =
+            self.consume_keyword(Keyword::Temp)? || self.consume_keyword(Keyword::Temporary)?;
+        if !self.consume_keyword(Keyword::Table)? {
+            return Err(ParseError::new(Problem::ExpectedKeyword {
+                keywords: vec![Keyword::Table, Keyword::Index, Keyword::View],
+                position: "after CREATE".into(),
+                found: self.found(),
+            }));
+        }
+        self.in_context(ParseContext::CreateTable, |parser| {
+            parser.parse_create_table(temporary)
+        })
+    }
+
+    /// Parses `CREATE [OR REPLACE] [MATERIALIZED] VIEW name [(column, ...)] AS query`
+    fn parse_create_view(
+        &mut self,
+        or_replace: bool,
+        materialized: bool,
+    ) -> Result<Statement, ParseError> {
+        let name = self.parse_object_name("view name after CREATE VIEW")?;
+        let columns = if self.current_token == Some(Token::LeftParentheses) {
+            self.parse_column_list("CREATE VIEW column list")?
+        } else {
+            vec![]
+        };
+        self.expect_keyword(Keyword::As, &format!("CREATE VIEW {}", name))?;
+        let query = Box::new(self.parse_query()?);
+
+        Ok(Statement::CreateView {
+            name,
+            columns,
+            query,
+            or_replace,
+            materialized,
+        })
+    }
+
+    /// Parses `CREATE [UNIQUE] INDEX [IF NOT EXISTS] name ON table (key [ASC | DESC], ...)`
+    fn parse_create_index(&mut self, unique: bool) -> Result<Statement, ParseError> {
+        let if_not_exists = self.parse_if_not_exists("CREATE INDEX")?;
+        let name = self.parse_identifier("index name after CREATE INDEX")?;
+        self.expect_keyword(Keyword::On, &format!("CREATE INDEX {}", name))?;
+        let table =
+            self.parse_object_name(&format!("table name after CREATE INDEX {} ON", name))?;
+
+        self.expect_token(
+            &Token::LeftParentheses,
+            &format!("and a column list after ON {}", table),
+        )?;
+        let columns = self.parse_comma_separated("index column list", Self::parse_order_by_item)?;
+        self.expect_token(&Token::RightParentheses, "to close index column list")?;
+
+        Ok(Statement::CreateIndex {
+            name,
+            table,
+            columns,
+            unique,
+            if_not_exists,
+        })
+    }
+
+    /// Parses `CREATE [TEMP | TEMPORARY] TABLE [IF NOT EXISTS] name {(column type, ...) | [(column, ...)] AS query}`
+    fn parse_create_table(&mut self, temporary: bool) -> Result<Statement, ParseError> {
+        let if_not_exists = self.parse_if_not_exists("CREATE TABLE")?;
+        let name = self.parse_object_name("table name after CREATE TABLE")?;
+
+        let mut columns = vec![];
+        let mut constraints = vec![];
+        if self.consume_token(&Token::LeftParentheses)? {
+            self.in_context(ParseContext::CreateTableColumns, |parser| {
+                let mut context = "CREATE TABLE";
+                loop {
+                    if parser.starts_table_constraint() {
+                        constraints.push(parser.parse_table_constraint()?);
+                    } else {
+                        columns.push(parser.parse_column_def(context)?);
+                    }
+                    if !parser.consume_token(&Token::Comma)?
+                        || parser.options.allow_trailing_commas
+                            && parser.current_token == Some(Token::RightParentheses)
+                    {
+                        break;
+                    }
+                    context = "',' in CREATE TABLE";
+                }
+                parser.expect_token(
+                    &Token::RightParentheses,
+                    &format!("after column definitions of {}", name),
+                )
+            })?;
+        } else if self.current_token != Some(Token::Keyword(Keyword::As)) {
+            return Err(match self.current_token.clone() {
+                Some(Token::Eof) | None => ParseError::new(Problem::UnexpectedEndOfInput {
+                    what: format!(
+                        "'(' and a column list or AS query after CREATE TABLE {}",
+                        name
+                    ),
+                }),
+                Some(t) => ParseError::new(Problem::ExpectedToken {
+                    expected: vec![TokenKind::LeftParentheses, TokenKind::Keyword(Keyword::As)],
+                    position: format!("after CREATE TABLE {}", name),
+                    found: t,
+                }),
+            });
+        }
+
+        let query = if self.consume_keyword(Keyword::As)? {
+            let has_definitions = !constraints.is_empty()
+                || columns
+                    .iter()
+                    .any(|c| c.data_type != DataType::Unspecified || !c.options.is_empty());
+            if has_definitions {
+                return Err(ParseError::new(Problem::InvalidInput {
+                    message: format!(
+                        "CREATE TABLE {} AS takes only column names, not types or constraints",
+                        name
+                    ),
+                }));
+            }
+            Some(Box::new(self.parse_query()?))
+        } else {
+            if let Some(column) = columns
+                .iter()
+                .find(|c| c.data_type == DataType::Unspecified)
+            {
+                return Err(ParseError::new(Problem::ExpectedType {
+                    what: format!("data type for column {}", column.name),
+                    found: self.found(),
+                }));
+            }
+            None
+        };
+
+        Ok(Statement::CreateTable {
+            temporary,
+            if_not_exists,
+            name,
+            columns,
+            constraints,
+            query,
+        })
+    }
+
+    /// Parses `ALTER TABLE [IF EXISTS] name {ADD | DROP | RENAME} ...`
+    fn parse_alter(&mut self) -> Result<Statement, ParseError> {
+        self.advance()?; // Skip ALTER
+        self.expect_keyword(Keyword::Table, "ALTER")?;
+        let if_exists = self.parse_if_exists("ALTER TABLE")?;
+        let name = self.parse_object_name("table name after ALTER TABLE")?;
+        let operation = self.parse_alter_table_operation(&name.to_string())?;
+        Ok(Statement::AlterTable {
+            name,
+            if_exists,
+            operation,
+        })
+    }
+
+    fn parse_alter_table_operation(
+        &mut self,
+        table: &str,
+    ) -> Result<AlterTableOperation, ParseError> {
+        if self.consume_keyword(Keyword::Add)? {
+            self.consume_keyword(Keyword::Column)?;
+            let if_not_exists = self.parse_if_not_exists("ADD COLUMN")?;
+            let column_def = self.parse_column_def("ADD COLUMN")?;
+            if column_def.data_type == DataType::Unspecified {
+                return Err(ParseError::new(Problem::ExpectedType {
+                    what: format!("data type for column {}", column_def.name),
+                    found: self.found(),
+                }));
+            }
+            Ok(AlterTableOperation::AddColumn {
+                if_not_exists,
+                column_def,
+            })
+        } else if self.consume_keyword(Keyword::Drop)? {
+            self.consume_keyword(Keyword::Column)?;
+            let if_exists = self.parse_if_exists("DROP COLUMN")?;
+            let column_name = self.parse_identifier("column name after DROP COLUMN")?;
+            let cascade = self.consume_keyword(Keyword::Cascade)?;
+            Ok(AlterTableOperation::DropColumn {
+                if_exists,
+                column_name,
+                cascade,
+            })
+        } else if self.consume_keyword(Keyword::Rename)? {
+            if self.consume_keyword(Keyword::To)? {
+                let new_name = self.parse_identifier("table name after RENAME TO")?;
+                return Ok(AlterTableOperation::RenameTable { new_name });
+            }
+            self.consume_keyword(Keyword::Column)?;
+            let old_name = self.parse_identifier("column name after RENAME COLUMN")?;
+            self.expect_keyword(Keyword::To, &format!("RENAME COLUMN {}", old_name))?;
+            let new_name = self.parse_identifier(&format!("new name for column {}", old_name))?;
+            Ok(AlterTableOperation::RenameColumn { old_name, new_name })
+        } else {
+            Err(ParseError::new(Problem::ExpectedKeyword {
+                keywords: vec![Keyword::Add, Keyword::Drop, Keyword::Rename],
+                position: format!("after ALTER TABLE {}", table),
+                found: self.found(),
+            }))
+        }
+    }
+
+    /// Parses `DROP {TABLE | INDEX | VIEW} [IF EXISTS] name, ... [ON table] [CASCADE | RESTRICT]`
+    fn parse_drop(&mut self) -> Result<Statement, ParseError> {
+        self.advance()?; // Skip DROP
+        let object_type = if self.consume_keyword(Keyword::Table)? {
+            ObjectType::Table
+        } else if self.consume_keyword(Keyword::Index)? {
+            ObjectType::Index
+        } else if self.consume_keyword(Keyword::View)? {
+            ObjectType::View
+        } else {
+            return Err(ParseError::new(Problem::ExpectedKeyword {
+                keywords: vec![Keyword::Table, Keyword::Index, Keyword::View],
+                position: "after DROP".into(),
+                found: self.found(),
+            }));
+        };
+
+        let clause = format!("DROP {}", object_type);
+        let if_exists = self.parse_if_exists(&clause)?;
+        let names = self.parse_comma_separated(&clause, |parser, context| {
+            parser.parse_object_name(&format!("name after {}", context))
+        })?;
+
+        // MySQL names the table an index belongs to
+        let table = if object_type == ObjectType::Index && self.consume_keyword(Keyword::On)? {
+            Some(self.parse_object_name("table name after DROP INDEX ... ON")?)
+        } else {
+            None
+        };
+
+        let cascade = if self.consume_keyword(Keyword::Cascade)? {
+            Some(true)
+        } else if self.consume_keyword(Keyword::Restrict)? {
+            Some(false)
+        } else {
+            None
+        };
+
+        Ok(Statement::Drop {
+            object_type,
+            if_exists,
+            names,
+            table,
+            cascade,
+        })
+    }
+
+    /// Parses `TRUNCATE [TABLE] name, ... [RESTART | CONTINUE IDENTITY] [CASCADE | RESTRICT]`
+    fn parse_truncate(&mut self) -> Result<Statement, ParseError> {
+        self.advance()?; // Skip TRUNCATE
+        self.consume_keyword(Keyword::Table)?;
+        let tables = self.parse_comma_separated("TRUNCATE", |parser, context| {
+            parser.parse_object_name(&format!("table name after {}", context))
+        })?;
+
+        let restart_identity = if self.consume_keyword(Keyword::Restart)? {
+            self.expect_keyword(Keyword::Identity, "RESTART")?;
+            Some(true)
+        } else if self.consume_keyword(Keyword::Continue)? {
+            self.expect_keyword(Keyword::Identity, "CONTINUE")?;
+            Some(false)
+        } else {
+            None
+        };
+
+        let cascade = if self.consume_keyword(Keyword::Cascade)? {
+            Some(true)
+        } else if self.consume_keyword(Keyword::Restrict)? {
+            Some(false)
+        } else {
+            None
+        };
+
+        Ok(Statement::Truncate {
+            tables,
+            restart_identity,
+            cascade,
+        })
+    }
+
+    /// Parses BEGIN, START TRANSACTION, COMMIT, ROLLBACK, SAVEPOINT and RELEASE SAVEPOINT
+    fn parse_transaction_statement(&mut self, keyword: Keyword) -> Result<Statement, ParseError> {
+        self.advance()?; // Skip the leading keyword
+
+        match keyword {
+            Keyword::Begin | Keyword::Start => {
+                if keyword == Keyword::Start {
+                    self.expect_keyword(Keyword::Transaction, "START")?;
+                } else if !self.consume_keyword(Keyword::Transaction)? {
+                    self.consume_keyword(Keyword::Work)?;
+                }
+                let isolation_level = if self.consume_keyword(Keyword::Isolation)? {
+                    self.expect_keyword(Keyword::Level, "ISOLATION")?;
+                    Some(self.parse_isolation_level()?)
+                } else {
+                    None
+                };
+                Ok(Statement::StartTransaction { isolation_level })
+            }
+            Keyword::Commit => {
+                self.consume_transaction_noise()?;
+                Ok(Statement::Commit)
+            }
+            Keyword::Rollback => {
+                self.consume_transaction_noise()?;
+                let savepoint = if self.consume_keyword(Keyword::To)? {
+                    self.consume_keyword(Keyword::Savepoint)?;
+                    Some(self.parse_identifier("savepoint name after ROLLBACK TO")?)
+                } else {
+                    None
+                };
+                Ok(Statement::Rollback { savepoint })
+            }
+            Keyword::Savepoint => {
+                let name = self.parse_identifier("savepoint name after SAVEPOINT")?;
+                Ok(Statement::Savepoint { name })
+            }
+            Keyword::Release => {
+                self.consume_keyword(Keyword::Savepoint)?;
+                let name = self.parse_identifier("savepoint name after RELEASE")?;
+                Ok(Statement::ReleaseSavepoint { name })
+            }
+            _ => unreachable!("dispatched on a transaction keyword"),
+        }
+    }
+
+    /// Consumes the optional WORK or TRANSACTION after COMMIT and ROLLBACK
+    fn consume_transaction_noise(&mut self) -> Result<(), ParseError> {
+        if !self.consume_keyword(Keyword::Work)? {
+            self.consume_keyword(Keyword::Transaction)?;
+        }
+        Ok(())
+    }
+
+    fn parse_isolation_level(&mut self) -> Result<IsolationLevel, ParseError> {
+        if self.consume_keyword(Keyword::Serializable)? {
+            Ok(IsolationLevel::Serializable)
+        } else if self.consume_keyword(Keyword::Repeatable)? {
+            self.expect_keyword(Keyword::Read, "REPEATABLE")?;
+            Ok(IsolationLevel::RepeatableRead)
+        } else if self.consume_keyword(Keyword::Read)? {
+            if self.consume_keyword(Keyword::Committed)? {
+                Ok(IsolationLevel::ReadCommitted)
+            } else if self.consume_keyword(Keyword::Uncommitted)? {
+                Ok(IsolationLevel::ReadUncommitted)
+            } else {
+                Err(ParseError::new(Problem::ExpectedKeyword {
+                    keywords: vec![Keyword::Committed, Keyword::Uncommitted],
+                    position: "after READ".into(),
+                    found: self.found(),
+                }))
+            }
+        } else {
+            Err(ParseError::new(Problem::ExpectedKeyword {
+                keywords: vec![Keyword::Read, Keyword::Repeatable, Keyword::Serializable],
+                position: "after ISOLATION LEVEL".into(),
+                found: self.found(),
+            }))
+        }
+    }
+
+    /// Parses `SHOW [FULL] object [{FROM | IN} name [{FROM | IN} database]] [LIKE 'pattern' | WHERE expr]`,
+    /// where the object may take several words, as in `SHOW TRANSACTION ISOLATION LEVEL`, and
+    /// `SHOW CREATE kind name` names the object whose definition to show
+    fn parse_show(&mut self) -> Result<Statement, ParseError> {
+        self.advance()?; // Skip SHOW
+        let full = self.consume_keyword(Keyword::Full)?;
+        let mut words = vec![];
+        let mut name = None;
+        if self.consume_keyword(Keyword::Create)? {
+            words.push(Keyword::Create.to_string());
+            words.push(self.parse_show_word("object type after SHOW CREATE")?);
+            let context = format!("name after SHOW {}", words.join(" "));
+            name = Some(self.parse_object_name(&context)?);
+        } else {
+            words.push(self.parse_show_word("what to show after SHOW")?);
+            while match &self.current_token {
+                Some(Token::Identifier(_)) => true,
+                Some(Token::Keyword(keyword)) => !matches!(
+                    keyword,
+                    Keyword::From | Keyword::In | Keyword::Like | Keyword::Where
+                ),
+                _ => false,
+            } {
+                words.push(self.parse_show_word("what to show after SHOW")?);
+            }
+        }
+        let object = words.join(" ");
+
+        let mut from: Option<ObjectName> = None;
+        while self.consume_keyword(Keyword::From)? || self.consume_keyword(Keyword::In)? {
+            let name = self.parse_object_name(&format!("name after SHOW {} FROM", object))?;
+            // MySQL's `SHOW COLUMNS FROM t FROM db` is the same as `FROM db.t`
+            from = Some(match from {
+                Some(ObjectName(inner)) => ObjectName(name.0.into_iter().chain(inner).collect()),
+                None => name,
+            });
+        }
+
+        let filter = if self.consume_keyword(Keyword::Like)? {
+            match self.current_token.clone() {
+                Some(Token::String(pattern)) => {
+                    self.advance()?;
+                    Some(ShowFilter::Like(pattern))
+                }
+                t => {
+                    return Err(ParseError::new(Problem::ExpectedToken {
+                        expected: vec![TokenKind::String],
+                        position: "pattern after LIKE".into(),
+                        found: t.unwrap_or(Token::Eof),
+                    }))
+                }
+            }
+        } else if self.consume_keyword(Keyword::Where)? {
+            Some(ShowFilter::Where(self.parse_clause_expression("WHERE")?))
+        } else {
+            None
+        };
+
+        Ok(Statement::Show {
+            full,
+            object,
+            name,
+            from,
+            filter,
+        })
+    }
+
+    /// Parses a word of what a SHOW statement shows, keeping identifiers as written
+    fn parse_show_word(&mut self, context: &str) -> Result<String, ParseError> {
+        let word = match self.current_token.clone() {
+            Some(Token::Identifier(name)) => name.to_string(),
+            Some(Token::Keyword(keyword)) => keyword.to_string(),
+            Some(Token::Eof) | None => {
+                return Err(ParseError::new(Problem::UnexpectedEndOfInput {
+                    what: context.into(),
+                }))
+            }
+            Some(t) => {
+                return Err(ParseError::new(Problem::ExpectedIdentifier {
+                    what: context.into(),
+                    found: t,
+                }))
+            }
+        };
+        self.advance()?;
+        Ok(word)
+    }
+
+    /// Parses `{DESCRIBE | DESC} table`
+    fn parse_describe(&mut self) -> Result<Statement, ParseError> {
+        self.advance()?; // Skip DESCRIBE
+        let table = self.parse_object_name("table name after DESCRIBE")?;
+        Ok(Statement::Describe { table })
+    }
+
+    /// Parses SQLite's `PRAGMA name`, `PRAGMA name = value` or `PRAGMA name(value)`
+    fn parse_pragma(&mut self) -> Result<Statement, ParseError> {
+        self.advance()?; // Skip PRAGMA
+        let name = self.parse_object_name("pragma name after PRAGMA")?;
+        let (value, call) = if self.consume_token(&Token::Equal)? {
+            (Some(self.parse_pragma_value(&name)?), false)
+        } else if self.consume_token(&Token::LeftParentheses)? {
+            let value = self.parse_pragma_value(&name)?;
+            self.expect_token(&Token::RightParentheses, "after pragma value")?;
+            (Some(value), true)
+        } else {
+            (None, false)
+        };
+        Ok(Statement::Pragma { name, value, call })
+    }
+
+    fn parse_pragma_value(&mut self, name: &ObjectName) -> Result<PragmaValue, ParseError> {
+        // A sign is checked for directly so that a bad value is not reported as a missing `-`
+        let negative = self.current_token == Some(Token::Minus);
+        if negative {
+            self.advance()?;
+        }
+        let value = match self.current_token.clone() {
+            Some(Token::Number(n)) => {
+                let n = i64::try_from(n).map_err(|_| {
+                    ParseError::new(Problem::InvalidInput {
+                        message: format!("pragma value {} is out of range", n),
+                    })
+                })?;
+                PragmaValue::Number(if negative { -n } else { n })
+            }
+            Some(Token::Float(x)) => PragmaValue::Float(Float(if negative { -x.0 } else { x.0 })),
+            Some(Token::String(s)) if !negative => PragmaValue::String(s),
+            Some(Token::Identifier(s) | Token::QuotedIdentifier(s)) if !negative => {
+                PragmaValue::Identifier(s.to_string())
+            }
+            // Values such as ON, OFF, TRUE and FULL are keywords here
+            Some(Token::Keyword(keyword)) if !negative => {
+                PragmaValue::Identifier(keyword.to_string())
+            }
+            Some(Token::Eof) | None => {
+                return Err(ParseError::new(Problem::UnexpectedEndOfInput {
+                    what: format!("a value for pragma {}", name),
+                }))
+            }
+            Some(t) => {
+                return Err(ParseError::new(Problem::UnexpectedToken {
+                    found: t,
+                    position: format!("as value for pragma {}", name),
+                }))
+            }
+        };
+        self.advance()?;
+        Ok(value)
+    }
+
+    /// Parses an optional `IF EXISTS`
+    fn parse_if_exists(&mut self, statement: &str) -> Result<bool, ParseError> {
+        if !self.consume_keyword(Keyword::If)? {
+            return Ok(false);
+        }
+        self.expect_keyword(Keyword::Exists, &format!("IF in {}", statement))?;
+        Ok(true)
+    }
+
+    /// Parses an optional `IF NOT EXISTS`
+    fn parse_if_not_exists(&mut self, statement: &str) -> Result<bool, ParseError> {
+        if !self.consume_keyword(Keyword::If)? {
+            return Ok(false);
+        }
+        let after = format!("IF in {}", statement);
+        if !self.consume_keyword(Keyword::Not)? {
+            return Err(ParseError::new(Problem::ExpectedKeyword {
+                keywords: vec![Keyword::Not],
+                position: format!("after {}", after),
+                found: self.found(),
+            }));
+        }
+        self.expect_keyword(Keyword::Exists, "IF NOT")?;
+        Ok(true)
+    }
+
+    /// Returns true if the current CREATE TABLE element is a constraint rather than a column
+    fn starts_table_constraint(&self) -> bool {
+        matches!(
+            self.current_token,
+            Some(Token::Keyword(
+                Keyword::Constraint
+                    | Keyword::Primary
+                    | Keyword::Unique
+                    | Keyword::Foreign
+                    | Keyword::Check
+            ))
+        )
+    }
+
+    /// Parses `[CONSTRAINT name] {PRIMARY KEY | UNIQUE | FOREIGN KEY | CHECK} ...`
+    fn parse_table_constraint(&mut self) -> Result<TableConstraint, ParseError> {
+        let name = if self.consume_keyword(Keyword::Constraint)? {
+            Some(self.parse_identifier("constraint name after CONSTRAINT")?)
+        } else {
+            None
+        };
+
+        if self.consume_keyword(Keyword::Primary)? {
+            self.expect_keyword(Keyword::Key, "PRIMARY")?;
+            let columns = self.parse_column_list("PRIMARY KEY")?;
+            Ok(TableConstraint::PrimaryKey { name, columns })
+        } else if self.consume_keyword(Keyword::Unique)? {
+            let columns = self.parse_column_list("UNIQUE")?;
+            Ok(TableConstraint::Unique { name, columns })
+        } else if self.consume_keyword(Keyword::Foreign)? {
+            self.expect_keyword(Keyword::Key, "FOREIGN")?;
+            let columns = self.parse_column_list("FOREIGN KEY")?;
+            self.expect_keyword(Keyword::References, "FOREIGN KEY (...)")?;
+            let foreign_table = self.parse_object_name("table name after REFERENCES")?;
+            let referred_columns = if self.current_token == Some(Token::LeftParentheses) {
+                self.parse_column_list("REFERENCES")?
+            } else {
+                vec![]
+            };
+
+            let mut on_delete = None;
+            let mut on_update = None;
+            while self.consume_keyword(Keyword::On)? {
+                if self.consume_keyword(Keyword::Delete)? {
+                    on_delete = Some(self.parse_referential_action("ON DELETE")?);
+                } else if self.consume_keyword(Keyword::Update)? {
+                    on_update = Some(self.parse_referential_action("ON UPDATE")?);
+                } else {
+                    return Err(ParseError::new(Problem::ExpectedKeyword {
+                        keywords: vec![Keyword::Delete, Keyword::Update],
+                        position: "after ON in FOREIGN KEY".into(),
+                        found: self.found(),
+                    }));
+                }
+            }
+
+            Ok(TableConstraint::ForeignKey {
+                name,
+                columns,
+                foreign_table,
+                referred_columns,
+                on_delete,
+                on_update,
+            })
+        } else if self.consume_keyword(Keyword::Check)? {
+            self.expect_token(&Token::LeftParentheses, "after CHECK")?;
+            let expr = self.parse_clause_expression("CHECK (")?;
+            self.expect_token(&Token::RightParentheses, "to close CHECK")?;
+            Ok(TableConstraint::Check { name, expr })
+        } else {
+            Err(ParseError::new(Problem::ExpectedKeyword {
+                keywords: vec![
+                    Keyword::Primary,
+                    Keyword::Unique,
+                    Keyword::Foreign,
+                    Keyword::Check,
+                ],
+                position: "after CONSTRAINT".into(),
+                found: self.found(),
+            }))
+        }
+    }
+
+    /// Parses `CASCADE`, `SET NULL`, `SET DEFAULT`, `RESTRICT` or `NO ACTION`
+    fn parse_referential_action(&mut self, clause: &str) -> Result<ReferentialAction, ParseError> {
+        if self.consume_keyword(Keyword::Cascade)? {
+            Ok(ReferentialAction::Cascade)
+        } else if self.consume_keyword(Keyword::Restrict)? {
+            Ok(ReferentialAction::Restrict)
+        } else if self.consume_keyword(Keyword::Set)? {
+            if self.consume_keyword(Keyword::Null)? {
+                Ok(ReferentialAction::SetNull)
+            } else {
+                self.expect_keyword(Keyword::Default, &format!("{} SET", clause))?;
+                Ok(ReferentialAction::SetDefault)
+            }
+        } else if self.consume_keyword(Keyword::No)? {
+            self.expect_keyword(Keyword::Action, &format!("{} NO", clause))?;
+            Ok(ReferentialAction::NoAction)
+        } else {
+            Err(ParseError::new(Problem::ExpectedKeyword {
+                keywords: vec![
+                    Keyword::Cascade,
+                    Keyword::Set,
+                    Keyword::Restrict,
+                    Keyword::No,
+                ],
+                position: format!("after {}", clause),
+                found: self.found(),
+            }))
+        }
+    }
+
+    fn parse_column_def(&mut self, context: &str) -> Result<ColumnDef, ParseError> {
+        if self.current_token == Some(Token::RightParentheses) {
+            return Err(ParseError::new(Problem::ExpectedToken {
+                expected: vec![TokenKind::Identifier],
+                position: format!("for a column definition after {}", context),
+                found: Token::RightParentheses,
+            }));
+        }
+        let name = self.parse_name(&format!("column name after {}", context))?;
+        let data_type = match self.current_token {
+            // A bare name, only valid in the column list of CREATE TABLE ... AS
+            Some(Token::Comma | Token::RightParentheses) => DataType::Unspecified,
+            _ => self.parse_data_type_after(&format!("column {}", name))?,
+        };
+
+        let mut options = vec![];
+        while let Some(option) = self.parse_column_option(&name)? {
+            options.push(option);
+        }
+
+        Ok(ColumnDef {
+            name,
+            data_type,
+            options,
+        })
+    }
+
+    /// Parses one column option, in any order and without checking for conflicts
+    fn parse_column_option(&mut self, column: &str) -> Result<Option<ColumnOption>, ParseError> {
+        let option = if self.consume_keyword(Keyword::Not)? {
+            self.expect_keyword(Keyword::Null, &format!("NOT in column {}", column))?;
+            ColumnOption::NotNull
+        } else if self.consume_keyword(Keyword::Null)? {
+            ColumnOption::Null
+        } else if self.consume_keyword(Keyword::Default)? {
+            ColumnOption::Default(self.in_context(ParseContext::ColumnDefault, |parser| {
+                parser.parse_clause_expression(&format!("DEFAULT in column {}", column))
+            })?)
+        } else if self.consume_keyword(Keyword::Primary)? {
+            self.expect_keyword(Keyword::Key, &format!("PRIMARY in column {}", column))?;
+            ColumnOption::PrimaryKey
+        } else if self.consume_keyword(Keyword::Unique)? {
+            ColumnOption::Unique
+        } else {
+            return Ok(None);
+        };
+        Ok(Some(option))
+    }
+
+    /// Parses a data type such as `INT`, `VARCHAR(255)`, `DOUBLE PRECISION` or `INT[]`;
+    /// names it does not know are kept as `DataType::Custom`
+    pub fn parse_data_type(&mut self) -> Result<DataType, ParseError> {
+        let result = self.parse_data_type_after("the start of input");
+        self.locate(result)
+    }
+
+    fn parse_data_type_after(&mut self, context: &str) -> Result<DataType, ParseError> {
+        let name = match self.current_token.clone() {
+            Some(Token::Identifier(name)) => name.to_uppercase(),
+            Some(Token::QuotedIdentifier(_)) => String::new(),
+            Some(Token::Keyword(keyword)) if !keyword.is_reserved() => String::new(),
+            Some(Token::Eof) | None => {
+                // Type names are read as identifiers
+                self.expect_tokens(&[TokenKind::Identifier]);
+                return Err(ParseError::new(Problem::UnexpectedEndOfInput {
+                    what: format!("a data type after {}", context),
+                }));
+            }
+            Some(t) => {
+                return Err(ParseError::new(Problem::ExpectedType {
+                    what: format!("data type after {}", context),
+                    found: t,
+                }))
+            }
+        };
+
+        let mut data_type = match name.as_str() {
+            "INT" | "INTEGER" => DataType::Int,
+            "BIGINT" => DataType::BigInt,
+            "SMALLINT" => DataType::SmallInt,
+            "VARCHAR" => DataType::Varchar(None),
+            "CHAR" | "CHARACTER" => DataType::Char(None),
+            "TEXT" => DataType::Text,
+            "BOOLEAN" | "BOOL" => DataType::Boolean,
+            "FLOAT" | "REAL" => DataType::Float,
+            "DOUBLE" => DataType::Double,
+            "DECIMAL" | "NUMERIC" => DataType::Decimal(None, None),
+            "DATE" => DataType::Date,
+            "TIMESTAMP" => DataType::Timestamp,
+            _ => {
+                let name = self.parse_object_name(&format!("data type after {}", context))?;
+                return self.parse_array_suffix(DataType::Custom(name));
+            }
+        };
+        self.advance()?;
+
+        match data_type {
+            DataType::Char(_) if self.is_identifier("VARYING") => {
+                self.advance()?;
+                data_type = DataType::Varchar(self.parse_type_length("VARCHAR")?);
+            }
+            DataType::Varchar(_) => data_type = DataType::Varchar(self.parse_type_length(&name)?),
+            DataType::Char(_) => data_type = DataType::Char(self.parse_type_length(&name)?),
+            DataType::Decimal(..) => {
+                let (precision, scale) = self.parse_type_precision_scale(&name)?;
+                data_type = DataType::Decimal(precision, scale);
+            }
+            DataType::Double if self.is_identifier("PRECISION") => self.advance()?,
+            DataType::Timestamp => {
+                if self.current_token == Some(Token::Keyword(Keyword::With)) {
+                    self.advance()?;
+                    self.expect_time_zone("TIMESTAMP WITH")?;
+                    data_type = DataType::TimestampWithTimeZone;
+                } else if self.is_identifier("WITHOUT") {
+                    self.advance()?;
+                    self.expect_time_zone("TIMESTAMP WITHOUT")?;
+                }
+            }
+            _ => {}
+        }
+        self.parse_array_suffix(data_type)
+    }
+
+    /// Consumes the `TIME ZONE` that ends `TIMESTAMP WITH TIME ZONE`
+    fn expect_time_zone(&mut self, context: &str) -> Result<(), ParseError> {
+        for word in ["TIME", "ZONE"] {
+            if !self.is_identifier(word) {
+                return Err(ParseError::new(Problem::ExpectedType {
+                    what: format!("{} after {}", word, context),
+                    found: self.found(),
+                }));
+            }
+            self.advance()?;
+        }
+        Ok(())
+    }
+
+    /// Wraps `element` in `DataType::Array` once for every `[]` that follows
+    fn parse_array_suffix(&mut self, mut element: DataType) -> Result<DataType, ParseError> {
+        while self.consume_token(&Token::LeftBracket)? {
+            self.expect_type_token(&Token::RightBracket, &format!("']' after {}[", element))?;
+            element = DataType::Array(Box::new(element));
+        }
+        Ok(element)
+    }
+
+    /// Consumes `expected` inside a data type, reporting a malformed type otherwise
+    fn expect_type_token(&mut self, expected: &Token, context: &str) -> Result<(), ParseError> {
+        if self.consume_token(expected)? {
+            return Ok(());
+        }
+        Err(ParseError::new(Problem::ExpectedType {
+            what: context.to_string(),
+            found: self.found(),
+        }))
+    }
+
+    /// Parses an optional `(n)` length after a character type
+    fn parse_type_length(&mut self, type_name: &str) -> Result<Option<u64>, ParseError> {
+        if !self.consume_token(&Token::LeftParentheses)? {
+            return Ok(None);
+        }
+        let length = self.parse_type_parameter(&format!("length for {}", type_name))?;
+        self.expect_type_token(
+            &Token::RightParentheses,
+            &format!("')' after {} length", type_name),
+        )?;
+        Ok(Some(length))
+    }
+
+    /// Parses an optional `(p [, s])` after a numeric type
+    fn parse_type_precision_scale(
+        &mut self,
+        type_name: &str,
+    ) -> Result<(Option<u64>, Option<u64>), ParseError> {
+        if !self.consume_token(&Token::LeftParentheses)? {
+            return Ok((None, None));
+        }
+        let precision = self.parse_type_parameter(&format!("precision for {}", type_name))?;
+        let scale = if self.consume_token(&Token::Comma)? {
+            Some(self.parse_type_parameter(&format!("scale for {}", type_name))?)
+        } else {
+            None
+        };
+        self.expect_type_token(
+            &Token::RightParentheses,
+            &format!("')' after {} precision", type_name),
+        )?;
+        Ok((Some(precision), scale))
+    }
+
+    fn parse_type_parameter(&mut self, context: &str) -> Result<u64, ParseError> {
+        match self.current_token.clone() {
+            Some(Token::Number(n)) => {
+                self.advance()?;
+                Ok(n)
+            }
+            Some(Token::Eof) | None => Err(ParseError::new(Problem::UnexpectedEndOfInput {
+                what: context.to_string(),
+            })),
+            Some(t) => Err(ParseError::new(Problem::ExpectedType {
+                what: context.to_string(),
+                found: t,
+            })),
+        }
+    }
+
+    /// Parses an optional `RETURNING item, ...` list after a DML statement
+    fn parse_returning(&mut self) -> Result<Option<Vec<SelectItem>>, ParseError> {
+        if !self.consume_keyword(Keyword::Returning)? {
+            return Ok(None);
+        }
+        Ok(Some(self.parse_comma_separated(
+            "RETURNING",
+            Self::parse_select_item,
+        )?))
+    }
+
+    /// Parses `[(column, ...) | ON CONSTRAINT name] DO {NOTHING | UPDATE SET ... [WHERE ...]}`
+    fn parse_on_conflict(&mut self) -> Result<OnConflict, ParseError> {
+        let target = if self.current_token == Some(Token::LeftParentheses) {
+            Some(ConflictTarget::Columns(
+                self.parse_column_list("ON CONFLICT target")?,
+            ))
+        } else if self.consume_keyword(Keyword::On)? {
+            self.expect_keyword(Keyword::Constraint, "ON CONFLICT ON")?;
+            Some(ConflictTarget::OnConstraint(
+                self.parse_identifier("constraint name after ON CONSTRAINT")?,
+            ))
+        } else {
+            None
+        };
+
+        self.expect_keyword(Keyword::Do, "ON CONFLICT")?;
+        let action = if self.consume_keyword(Keyword::Nothing)? {
+            OnConflictAction::DoNothing
+        } else if self.consume_keyword(Keyword::Update)? {
+            self.expect_keyword(Keyword::Set, "DO UPDATE")?;
+            let assignments = self.parse_comma_separated("SET", Self::parse_assignment)?;
+            let selection = self.parse_where_clause()?;
+            OnConflictAction::DoUpdate {
+                assignments,
+                selection,
+            }
+        } else {
+            return Err(ParseError::new(Problem::ExpectedKeyword {
+                keywords: vec![Keyword::Nothing, Keyword::Update],
+                position: "after DO".into(),
+                found: self.found(),
+            }));
+        };
+
+        Ok(OnConflict { target, action })
+    }
+
+    /// Parses `column = expr`, where the column may be qualified
+    fn parse_assignment(&mut self, context: &str) -> Result<Assignment, ParseError> {
+        if context != "SET" {
+            if let Some(
+                t @ (Token::Semicolon
+                | Token::Eof
+                | Token::Keyword(
+                    Keyword::From | Keyword::Where | Keyword::Returning | Keyword::When,
+                )),
+            ) = self.current_token.clone()
+            {
+                self.expect_tokens(&[TokenKind::Identifier]);
+                return Err(ParseError::new(Problem::ExpectedIdentifier {
+                    what: format!("column name after {}", context),
+                    found: t,
+                }));
+            }
+        }
+
+        let mut column = vec![self.parse_identifier(&format!("column name after {}", context))?];
+        while self.consume_token(&Token::Dot)? {
+            column.push(self.parse_identifier("column name after '.'")?);
+        }
+
+        self.expect_token(&Token::Equal, &format!("after {}", column.join(".")))?;
+        let value = self.parse_clause_expression(&format!("{} =", column.join(".")))?;
+        Ok(Assignment { column, value })
+    }
+
+    /// Parses a query: set operations over SELECTs, then ORDER BY and row limits for the whole
+    /// Parses a query; queries nested in parentheses count towards the expression depth
+    fn parse_query(&mut self) -> Result<Query, ParseError> {
+        self.nested(Self::parse_query_body)
+    }
+
+    fn parse_query_body(&mut self) -> Result<Query, ParseError> {
+        let with = if self.consume_keyword(Keyword::With)? {
+            Some(self.parse_with()?)
+        } else {
+            None
+        };
+        let body = self.parse_set_expr(0)?;
+        let order_by = self.in_context(ParseContext::OrderByClause, Self::parse_order_by)?;
+        let (limit, offset, with_ties) =
+            self.in_context(ParseContext::LimitClause, Self::parse_limit_offset)?;
+        let locking = if self.consume_keyword(Keyword::For)? {
+            Some(self.parse_locking_clause()?)
+        } else {
+            None
+        };
+
+        Ok(Query {
+            with,
+            body,
+            order_by,
+            limit,
+            offset,
+            with_ties,
+            locking,
+        })
+    }
+
+    /// Parses `{UPDATE | NO KEY UPDATE | SHARE | KEY SHARE} [OF table, ...] [NOWAIT | SKIP LOCKED]` after FOR
+    fn parse_locking_clause(&mut self) -> Result<LockingClause, ParseError> {
+        let strength = if self.consume_keyword(Keyword::Update)? {
+            LockStrength::Update
+        } else if self.consume_keyword(Keyword::Share)? {
+            LockStrength::Share
+        } else if self.consume_keyword(Keyword::No)? {
+            self.expect_keyword(Keyword::Key, "FOR NO")?;
+            self.expect_keyword(Keyword::Update, "FOR NO KEY")?;
+            LockStrength::NoKeyUpdate
+        } else if self.consume_keyword(Keyword::Key)? {
+            self.expect_keyword(Keyword::Share, "FOR KEY")?;
+            LockStrength::KeyShare
+        } else {
+            return Err(ParseError::new(Problem::ExpectedKeyword {
+                keywords: vec![Keyword::Update, Keyword::No, Keyword::Share, Keyword::Key],
+                position: "after FOR".into(),
+                found: self.found(),
+            }));
+        };
+
+        let of = if self.consume_keyword(Keyword::Of)? {
+            self.parse_comma_separated("FOR ... OF", |parser, context| {
+                parser.parse_object_name(&format!("table name after {}", context))
+            })?
+        } else {
+            vec![]
+        };
+
+        let wait = if self.consume_keyword(Keyword::Nowait)? {
+            Some(WaitPolicy::Nowait)
+        } else if self.consume_keyword(Keyword::Skip)? {
+            self.expect_keyword(Keyword::Locked, "SKIP")?;
+            Some(WaitPolicy::SkipLocked)
+        } else {
+            None
+        };
+
+        Ok(LockingClause { strength, of, wait })
+    }
+
+    /// Parses `[RECURSIVE] name [(column, ...)] AS (query), ...` after WITH
+    fn parse_with(&mut self) -> Result<With, ParseError> {
+        let recursive = self.consume_keyword(Keyword::Recursive)?;
+        let ctes = self.parse_comma_separated("WITH", Self::parse_cte)?;
+        Ok(With { recursive, ctes })
+    }
+
+    fn parse_cte(&mut self, context: &str) -> Result<Cte, ParseError> {
+        let name =
+            self.parse_identifier(&format!("common table expression name after {}", context))?;
+
+        // `name (SELECT ...)` is a CTE body missing its AS, not a column list
+        let body_follows = matches!(
+            self.tokenizer.peek_token(),
+            Some(Token::Keyword(
+                Keyword::Select | Keyword::With | Keyword::Values
+            ))
+        );
+        let mut columns = vec![];
+        if !body_follows && self.current_token == Some(Token::LeftParentheses) {
+            columns = self.parse_column_list("CTE column list")?;
+        }
+
+        self.expect_keyword(Keyword::As, &format!("common table expression {}", name))?;
+        self.expect_token(&Token::LeftParentheses, &format!("after {} AS", name))?;
+        let query = Box::new(self.parse_query()?);
+        self.expect_token(&Token::RightParentheses, &format!("to close {}", name))?;
+
+        Ok(Cte {
+            name,
+            columns,
+            query,
+        })
+    }
+
+    /// Parses set operations left-associatively, with INTERSECT binding tighter than UNION and EXCEPT
+    fn parse_set_expr(&mut self, precedence: u8) -> Result<SetExpr, ParseError> {
+        let mut left = self.parse_set_operand()?;
+
+        loop {
+            let (op, op_precedence) = match self.current_token {
+                Some(Token::Keyword(Keyword::Union)) => (SetOperator::Union, 1),
+                Some(Token::Keyword(Keyword::Except)) => (SetOperator::Except, 1),
+                Some(Token::Keyword(Keyword::Intersect)) => (SetOperator::Intersect, 2),
+                _ => {
+                    self.expect_tokens(&[
+                        TokenKind::Keyword(Keyword::Union),
+                        TokenKind::Keyword(Keyword::Intersect),
+                        TokenKind::Keyword(Keyword::Except),
+                    ]);
+                    break;
+                }
+            };
+            if op_precedence <= precedence {
+                break;
+            }
+            self.advance()?;
+
+            let all = if self.consume_keyword(Keyword::All)? {
+                true
+            } else {
+                self.consume_keyword(Keyword::Distinct)?;
+                false
+            };
+            let right = self.parse_set_expr(op_precedence)?;
+            left = SetExpr::SetOperation {
+                op,
+                all,
+                left: Box::new(left),
+                right: Box::new(right),
+            };
+        }
+
+        Ok(left)
+    }
+
+    /// Parses a SELECT or a parenthesized query
+    fn parse_set_operand(&mut self) -> Result<SetExpr, ParseError> {
+        match self.current_token.clone() {
+            Some(Token::Keyword(Keyword::Select)) => {
+                let select = self.in_context(ParseContext::Select, Self::parse_select)?;
+                Ok(SetExpr::Select(Box::new(select)))
+            }
+            Some(Token::Keyword(Keyword::Values)) => {
+                self.advance()?;
+                Ok(SetExpr::Values(self.parse_values_rows()?))
+            }
+            Some(Token::LeftParentheses) => {
+                self.advance()?;
+                let query = self.parse_query()?;
+                self.expect_token(&Token::RightParentheses, "to close subquery")?;
+                Ok(SetExpr::Query(Box::new(query)))
+            }
+            Some(Token::Eof) | None => {
+                self.expect_tokens(&[
+                    TokenKind::Keyword(Keyword::Select),
+                    TokenKind::Keyword(Keyword::Values),
+                    TokenKind::LeftParentheses,
+                ]);
+                Err(ParseError::new(Problem::UnexpectedEndOfInput {
+                    what: "SELECT, VALUES or '(' to start a query".into(),
+                }))
+            }
+            Some(t) => Err(ParseError::new(Problem::ExpectedToken {
+                expected: vec![
+                    TokenKind::Keyword(Keyword::Select),
+                    TokenKind::Keyword(Keyword::Values),
+                    TokenKind::LeftParentheses,
+                ],
+                position: "to start a query".into(),
+                found: t,
+            })),
+        }
+    }
+
+    /// Parses the comma-separated row constructors that follow VALUES
+    fn parse_values_rows(&mut self) -> Result<Vec<Vec<Expression>>, ParseError> {
+        self.in_context(ParseContext::ValuesRows, |parser| {
+            parser.parse_comma_separated("VALUES", Self::parse_values_row)
+        })
+    }
+
+    /// Parses one parenthesized row of a VALUES list
+    fn parse_values_row(&mut self, context: &str) -> Result<Vec<Expression>, ParseError> {
+        self.expect_token(
+            &Token::LeftParentheses,
+            &format!("to start a row after {}", context),
+        )?;
+        if self.current_token == Some(Token::RightParentheses) {
+            return Err(ParseError::new(Problem::ExpectedExpression {
+                what: "at least one value in VALUES row".into(),
+                found: Token::RightParentheses,
+            }));
+        }
+        let row = self.parse_comma_separated("VALUES row", Self::parse_clause_expression)?;
+        self.expect_token(&Token::RightParentheses, "to close VALUES row")?;
+        Ok(row)
+    }
+
+    fn parse_select(&mut self) -> Result<SelectStatement, ParseError> {
+        self.advance()?; // Skip SELECT
+
+        let distinct = self.parse_distinct()?;
+        let top = if self.options.dialect.supports_top() && self.is_identifier("TOP") {
+            self.advance()?;
+            Some(self.parse_top()?)
+        } else {
+            None
+        };
+        let projection = self.in_context(ParseContext::SelectList, |parser| {
+            parser.parse_comma_separated("SELECT", Self::parse_select_item)
+        })?;
+
+        let from = if self.consume_keyword(Keyword::From)? {
+            Some(self.in_context(ParseContext::FromClause, Self::parse_table_with_joins)?)
+        } else {
+            None
+        };
+
+        let selection = self.parse_where_clause()?;
+        let group_by = self.in_context(ParseContext::GroupByClause, Self::parse_group_by)?;
+        let having = if self.consume_keyword(Keyword::Having)? {
+            Some(self.in_context(ParseContext::HavingClause, |parser| {
+                parser.parse_clause_expression("HAVING")
+            })?)
+        } else {
+            None
+        };
+
+        Ok(SelectStatement {
+            distinct,
+            top,
+            projection,
+            from,
+            selection,
+            group_by,
+            having,
+        })
+    }
+
+    /// Parses `n | (expr) [PERCENT] [WITH TIES]` after TOP
+    fn parse_top(&mut self) -> Result<Top, ParseError> {
+        // Without parentheses only a single term is allowed, so `TOP 10 * FROM` is not a product
+        let quantity = match self.current_token.clone() {
+            Some(Token::LeftParentheses) => {
+                self.advance()?;
+                let quantity = self.parse_clause_expression("TOP (")?;
+                self.expect_token(&Token::RightParentheses, "after TOP quantity")?;
+                quantity
+            }
+            Some(Token::Number(n)) => {
+                self.advance()?;
+                Expression::Value(Value::Number(n))
+            }
+            Some(Token::Placeholder(p)) => {
+                self.advance()?;
+                self.placeholder(p)
+            }
+            t => {
+                return Err(ParseError::new(Problem::ExpectedNumber {
+                    what: "row count or (expression) after TOP".into(),
+                    found: t.unwrap_or(Token::Eof),
+                }))
+            }
+        };
+
+        let percent = self.is_identifier("PERCENT");
+        if percent {
+            self.advance()?;
+        }
+        let with_ties = self.current_token == Some(Token::Keyword(Keyword::With))
+            && self.tokenizer.peek_token() == Some(Token::Keyword(Keyword::Ties));
+        if with_ties {
+            self.advance()?; // Skip WITH
+            self.advance()?; // Skip TIES
+        }
+
+        Ok(Top {
+            quantity,
+            percent,
+            with_ties,
+        })
+    }
+
+    /// Returns true if the current token is the unreserved word `word`
+    fn is_identifier(&self, word: &str) -> bool {
+        matches!(&self.current_token, Some(Token::Identifier(name)) if name.eq_ignore_ascii_case(word))
+    }
+
+    /// Parses `LIMIT n [OFFSET m]` or the ANSI `[OFFSET m ROWS] [FETCH FIRST n ROWS ONLY]` form
+    /// into `(limit, offset, with_ties)`
+    fn parse_limit_offset(
+        &mut self,
+    ) -> Result<(Option<Expression>, Option<Expression>, bool), ParseError> {
+        if self.consume_keyword(Keyword::Limit)? {
+            let limit = self.parse_clause_expression("LIMIT")?;
+            let offset = if self.consume_keyword(Keyword::Offset)? {
+                let offset = self.parse_clause_expression("OFFSET")?;
+                if self.consume_row_or_rows()? {
+                    return Err(ParseError::new(Problem::InvalidInput {
+                        message:
+                            "OFFSET ... ROWS cannot be combined with LIMIT; use FETCH FIRST instead"
+                                .into(),
+                    }));
+                }
+                Some(offset)
+            } else {
+                None
+            };
+            if self.current_token == Some(Token::Keyword(Keyword::Fetch)) {
+                return Err(ParseError::new(Problem::UnexpectedToken {
+                    found: Token::Keyword(Keyword::Fetch),
+                    position: "after LIMIT; FETCH cannot be combined with LIMIT".into(),
+                }));
+            }
+            return Ok((Some(limit), offset, false));
+        }
+
+        let mut offset = None;
+        if self.consume_keyword(Keyword::Offset)? {
+            offset = Some(self.parse_clause_expression("OFFSET")?);
+            let ansi = self.consume_row_or_rows()?;
+            if self.consume_keyword(Keyword::Limit)? {
+                if ansi {
+                    return Err(ParseError::new(Problem::InvalidInput {
+                        message:
+                            "LIMIT cannot be combined with OFFSET ... ROWS; use FETCH FIRST instead"
+                                .into(),
+                    }));
+                }
+                let limit = self.parse_clause_expression("LIMIT")?;
+                return Ok((Some(limit), offset, false));
+            }
+        }
+
+        if !self.consume_keyword(Keyword::Fetch)? {
+            return Ok((None, offset, false));
+        }
+
+        if !self.consume_keyword(Keyword::First)? && !self.consume_keyword(Keyword::Next)? {
+            return Err(ParseError::new(Problem::ExpectedKeyword {
+                keywords: vec![Keyword::First, Keyword::Next],
+                position: "after FETCH".into(),
+                found: self.found(),
+            }));
+        }
+
+        // The row count may be omitted, in which case it defaults to one row
+        let limit = if self.consume_row_or_rows()? {
+            Expression::Value(Value::Number(1))
+        } else {
+            let limit = self.parse_clause_expression("FETCH FIRST")?;
+            if !self.consume_row_or_rows()? {
+                return Err(ParseError::new(Problem::ExpectedKeyword {
+                    keywords: vec![Keyword::Row, Keyword::Rows],
+                    position: "after FETCH count".into(),
+                    found: self.found(),
+                }));
+            }
+            limit
+        };
+
+        let with_ties = if self.consume_keyword(Keyword::Only)? {
+            false
+        } else if self.consume_keyword(Keyword::With)? {
+            self.expect_keyword(Keyword::Ties, "WITH")?;
+            true
+        } else {
+            return Err(ParseError::new(Problem::ExpectedKeyword {
+                keywords: vec![Keyword::Only, Keyword::With],
+                position: "after FETCH".into(),
+                found: self.found(),
+            }));
+        };
+
+        Ok((Some(limit), offset, with_ties))
+    }
+
+    /// Consumes the ROW or ROWS noise word if present
+    fn consume_row_or_rows(&mut self) -> Result<bool, ParseError> {
+        Ok(self.consume_keyword(Keyword::Row)? || self.consume_keyword(Keyword::Rows)?)
+    }
+
+    /// Parses an optional `GROUP BY item, ...` clause
+    fn parse_group_by(&mut self) -> Result<Vec<GroupByExpr>, ParseError> {
+        if !self.consume_keyword(Keyword::Group)? {
+            return Ok(vec![]);
+        }
+
+        self.expect_keyword(Keyword::By, "GROUP")?;
+        self.parse_comma_separated("GROUP BY", Self::parse_group_by_item)
+    }
+
+    fn parse_group_by_item(&mut self, context: &str) -> Result<GroupByExpr, ParseError> {
+        if self.consume_keyword(Keyword::Rollup)? {
+            return Ok(GroupByExpr::Rollup(self.parse_grouping_sets("ROLLUP")?));
+        }
+        if self.consume_keyword(Keyword::Cube)? {
+            return Ok(GroupByExpr::Cube(self.parse_grouping_sets("CUBE")?));
+        }
+        if self.consume_keyword(Keyword::Grouping)? {
+            self.expect_keyword(Keyword::Sets, "GROUPING")?;
+            return Ok(GroupByExpr::GroupingSets(
+                self.parse_grouping_sets("GROUPING SETS")?,
+            ));
+        }
+
+        Ok(GroupByExpr::Expression(
+            self.parse_clause_expression(context)?,
+        ))
+    }
+
+    /// Parses the parenthesized element list of ROLLUP, CUBE or GROUPING SETS
+    fn parse_grouping_sets(&mut self, clause: &str) -> Result<Vec<Vec<Expression>>, ParseError> {
+        self.expect_token(&Token::LeftParentheses, &format!("after {}", clause))?;
+        let sets = self.parse_comma_separated(clause, Self::parse_grouping_set)?;
+        self.expect_token(&Token::RightParentheses, &format!("to close {}", clause))?;
+        Ok(sets)
+    }
+
+    /// Parses a grouping element: `expr`, `(expr, ...)` or the empty set `()`
+    fn parse_grouping_set(&mut self, context: &str) -> Result<Vec<Expression>, ParseError> {
+        if !self.consume_token(&Token::LeftParentheses)? {
+            return Ok(vec![self.parse_clause_expression(context)?]);
+        }
+        if self.consume_token(&Token::RightParentheses)? {
+            return Ok(vec![]);
+        }
+
+        let mut set = self.parse_comma_separated("grouping set", Self::parse_clause_expression)?;
+        self.expect_token(&Token::RightParentheses, "to close grouping set")?;
+
+        // A lone parenthesized expression may continue as an operand, as in `(a + b) * 2`
+        if set.len() == 1 {
+            let operand = set.pop().unwrap();
+            set.push(self.parse_infix(operand, 0)?);
+        }
+        Ok(set)
+    }
+
+    /// Parses an optional `ORDER BY item [ASC | DESC], ...` clause
+    fn parse_order_by(&mut self) -> Result<Vec<OrderByItem>, ParseError> {
+        if !self.consume_keyword(Keyword::Order)? {
+            return Ok(vec![]);
+        }
+
+        self.expect_keyword(Keyword::By, "ORDER")?;
+        self.parse_comma_separated("ORDER BY", Self::parse_order_by_item)
+    }
+
+    fn parse_order_by_item(&mut self, context: &str) -> Result<OrderByItem, ParseError> {
+        let expr = self.parse_clause_expression(context)?;
+
+        let asc = if self.consume_keyword(Keyword::Asc)? {
+            Some(true)
+        } else if self.consume_keyword(Keyword::Desc)? {
+            Some(false)
+        } else {
+            None
+        };
+
+        let nulls_first = if self.consume_keyword(Keyword::Nulls)? {
+            if self.consume_keyword(Keyword::First)? {
+                Some(true)
+            } else if self.consume_keyword(Keyword::Last)? {
+                Some(false)
+            } else {
+                return Err(ParseError::new(Problem::ExpectedKeyword {
+                    keywords: vec![Keyword::First, Keyword::Last],
+                    position: "after NULLS".into(),
+                    found: self.found(),
+                }));
+            }
+        } else {
+            None
+        };
+
+        if let Some(Token::Keyword(direction @ (Keyword::Asc | Keyword::Desc))) =
+            &self.current_token
+        {
+            return Err(ParseError::new(Problem::UnexpectedToken {
+                found: Token::Keyword(direction.clone()),
+                position: "after NULLS FIRST/LAST; the direction must come first".into(),
+            }));
+        }
+
+        Ok(OrderByItem {
+            expr,
+            asc,
+            nulls_first,
+        })
+    }
+
+    /// Parses one or more comma-separated items, passing each item the clause it belongs to
+    fn parse_comma_separated<T>(
+        &mut self,
+        clause: &str,
+        mut parse_item: impl FnMut(&mut Self, &str) -> Result<T, ParseError>,
+    ) -> Result<Vec<T>, ParseError> {
+        let mut items = vec![parse_item(self, clause)?];
+        let next_context = format!("',' in {}", clause);
+        while self.consume_token(&Token::Comma)? {
+            if self.options.allow_trailing_commas && self.at_list_end() {
+                break;
+            }
+            items.push(parse_item(self, &next_context)?);
+        }
+        Ok(items)
+    }
+
+    /// Returns true if the current token cannot continue a comma-separated list
+    fn at_list_end(&self) -> bool {
+        match &self.current_token {
+            Some(Token::RightParentheses | Token::Semicolon | Token::Eof) | None => true,
+            Some(Token::Keyword(keyword)) => keyword.is_reserved() && !starts_expression(keyword),
+            _ => false,
+        }
+    }
+
+    /// Returns true if the current token can begin a query, and otherwise notes the tokens
+    /// that could
+    fn starts_query(&mut self) -> bool {
+        let starts = matches!(
+            self.current_token,
+            Some(
+                Token::Keyword(Keyword::Select | Keyword::With | Keyword::Values)
+                    | Token::LeftParentheses
+            )
+        );
+        if !starts {
+            self.expect_tokens(&[
+                TokenKind::Keyword(Keyword::Select),
+                TokenKind::Keyword(Keyword::With),
+                TokenKind::Keyword(Keyword::Values),
+                TokenKind::LeftParentheses,
+            ]);
+        }
+        starts
+    }
+
+    /// Parses an optional `ALL`, `DISTINCT` or `DISTINCT ON (expr, ...)` quantifier
+    fn parse_distinct(&mut self) -> Result<Option<Distinct>, ParseError> {
+        if self.consume_keyword(Keyword::All)? {
+            return Ok(Some(Distinct::All));
+        }
+        if !self.consume_keyword(Keyword::Distinct)? {
+            return Ok(None);
+        }
+        if !self.consume_keyword(Keyword::On)? {
+            return Ok(Some(Distinct::Distinct));
+        }
+
+        self.expect_token(&Token::LeftParentheses, "after DISTINCT ON")?;
+        let exprs = self.parse_comma_separated("DISTINCT ON", Self::parse_clause_expression)?;
+        self.expect_token(&Token::RightParentheses, "to close DISTINCT ON")?;
+        Ok(Some(Distinct::DistinctOn(exprs)))
+    }
+
+    /// Parses an optional `WHERE <condition>` clause
+    fn parse_where_clause(&mut self) -> Result<Option<Expression>, ParseError> {
+        if self.consume_keyword(Keyword::Where)? {
+            let selection = self.in_context(ParseContext::WhereClause, |parser| {
+                parser.parse_clause_expression("WHERE")
+            })?;
+            Ok(Some(selection))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Parses the expression that must follow a clause keyword, naming the clause if it is missing
+    fn parse_clause_expression(&mut self, clause: &str) -> Result<Expression, ParseError> {
+        match self.current_token.clone() {
+            Some(Token::Eof) | None => {
+                self.expect_expression();
+                Err(ParseError::new(Problem::UnexpectedEndOfInput {
+                    what: format!("expression after {}", clause),
+                }))
+            }
+            Some(Token::Semicolon) => Err(ParseError::new(Problem::UnexpectedEndOfInput {
+                what: format!("expression after {}", clause),
+            })),
+            Some(Token::Keyword(keyword))
+                if keyword.is_reserved() && !starts_expression(&keyword) =>
+            {
+                Err(ParseError::new(Problem::ExpectedExpression {
+                    what: format!("expression after {}", clause),
+                    found: Token::Keyword(keyword),
+                }))
+            }
+            Some(t @ (Token::RightParentheses | Token::Comma)) => {
+                Err(ParseError::new(Problem::ExpectedExpression {
+                    what: format!("expression after {}", clause),
+                    found: t,
+                }))
+            }
+            _ => self.parse_expression(0),
+        }
+    }
+
+    fn parse_select_item(&mut self, context: &str) -> Result<SelectItem, ParseError> {
+        match self.current_token.clone() {
+            Some(Token::Eof) | None => {
+                self.expect_tokens(&[TokenKind::Multiply]);
+                self.expect_expression();
+                Err(ParseError::new(Problem::UnexpectedEndOfInput {
+                    what: format!("projection item after {}", context),
+                }))
+            }
+            Some(t @ (Token::Semicolon | Token::Keyword(Keyword::From))) => {
+                Err(ParseError::new(Problem::ExpectedExpression {
+                    what: format!("projection item after {}", context),
+                    found: t,
+                }))
+            }
+            Some(Token::Multiply) => {
+                self.advance()?;
+                self.expect_wildcard_end("*")?;
+                Ok(SelectItem::Wildcard)
+            }
+            Some(Token::Identifier(_) | Token::QuotedIdentifier(_))
+                if self.is_qualified_wildcard() =>
+            {
+                let mut qualifier = vec![];
+                while let Some(Token::Identifier(name) | Token::QuotedIdentifier(name)) =
+                    self.current_token.clone()
+                {
+                    qualifier.push(name.to_string());
+                    self.advance()?; // Skip the name
+                    self.advance()?; // Skip the dot
+                }
+                self.advance()?; // Skip *
+                self.expect_wildcard_end(&format!("{}.*", qualifier.join(".")))?;
+                Ok(SelectItem::QualifiedWildcard(qualifier))
+            }
+            _ => {
+                let expr = self.parse_expression(0)?;
+                match self.parse_optional_alias()? {
+                    Some(alias) => Ok(SelectItem::ExprWithAlias { expr, alias }),
+                    None => Ok(SelectItem::Expression(expr)),
+                }
+            }
+        }
+    }
+
+    /// Parses `AS alias` or a bare identifier alias; keywords are never taken as bare aliases
+    fn parse_optional_alias(&mut self) -> Result<Option<Ident>, ParseError> {
+        if self.consume_keyword(Keyword::As)? {
+            return Ok(Some(self.parse_ident("alias after AS")?));
+        }
+
+        match self.current_token.clone() {
+            Some(Token::Identifier(_) | Token::QuotedIdentifier(_)) => {
+                Ok(Some(self.parse_ident("alias")?))
+            }
+            Some(Token::Keyword(keyword)) if !keyword.is_reserved() => {
+                Ok(Some(self.parse_ident("alias")?))
+            }
+            _ => {
+                self.expect_tokens(&[TokenKind::Identifier]);
+                Ok(None)
+            }
+        }
+    }
+
+    /// Looks ahead for `name.name.*` starting at the current identifier
+    fn is_qualified_wildcard(&self) -> bool {
+        let mut n = 0;
+        while self.tokenizer.peek_nth_token(n) == Some(Token::Dot) {
+            match self.tokenizer.peek_nth_token(n + 1) {
+                Some(Token::Identifier(_) | Token::QuotedIdentifier(_)) => n += 2,
+                Some(Token::Multiply) => return true,
+                _ => return false,
+            }
+        }
+        false
+    }
+
+    /// Rejects a wildcard that is followed by an operator, as in `a.* + 1`
+    fn expect_wildcard_end(&self, wildcard: &str) -> Result<(), ParseError> {
+        match &self.current_token {
+            Some(token) if self.get_precedence(token) > 0 => {
+                Err(ParseError::new(Problem::InvalidInput {
+                    message: format!(
+                        "wildcard {} cannot be used as an operand of {}",
+                        wildcard,
+                        describe(token)
+                    ),
+                }))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Parses a table factor followed by any number of joins
+    fn parse_table_with_joins(&mut self) -> Result<TableWithJoins, ParseError> {
+        let relation = self.parse_table_reference()?;
+
+        let mut joins = vec![];
+        loop {
+            let natural = self.consume_keyword(Keyword::Natural)?;
+            let join_type = match self.parse_join_type()? {
+                Some(JoinType::Cross | JoinType::Implicit) if natural => {
+                    return Err(ParseError::new(Problem::ExpectedKeyword {
+                        keywords: vec![Keyword::Join],
+                        position: "after NATURAL; a natural join cannot be a cross join".into(),
+                        found: self.found(),
+                    }))
+                }
+                Some(join_type) => join_type,
+                None if natural => {
+                    return Err(ParseError::new(Problem::ExpectedKeyword {
+                        keywords: vec![Keyword::Join],
+                        position: "after NATURAL".into(),
+                        found: self.found(),
+                    }))
+                }
+                None => break,
+            };
+
+            let relation = self.parse_table_reference()?;
+            let constraint = match join_type {
+                _ if natural => {
+                    if let Some(Token::Keyword(keyword @ (Keyword::On | Keyword::Using))) =
+                        self.current_token.clone()
+                    {
+                        return Err(ParseError::new(Problem::UnexpectedToken {
+                            found: Token::Keyword(keyword),
+                            position:
+                                "after NATURAL JOIN; natural joins take no explicit condition"
+                                    .into(),
+                        }));
+                    }
+                    JoinConstraint::Natural
+                }
+                JoinType::Cross | JoinType::Implicit => {
+                    if self.current_token == Some(Token::Keyword(Keyword::On)) {
+                        return Err(ParseError::new(Problem::UnexpectedToken {
+                            found: Token::Keyword(Keyword::On),
+                            position: "after CROSS JOIN or ','; cross joins take no join condition"
+                                .into(),
+                        }));
+                    }
+                    JoinConstraint::None
+                }
+                _ => self.in_context(ParseContext::JoinCondition, Self::parse_join_constraint)?,
+            };
+            joins.push(Join {
+                relation,
+                join_type,
+                constraint,
+            });
+        }
+
+        Ok(TableWithJoins { relation, joins })
+    }
+
+    /// Parses the keywords introducing a join, if any
+    fn parse_join_type(&mut self) -> Result<Option<JoinType>, ParseError> {
+        let (join_type, keyword) = match self.current_token {
+            Some(Token::Comma) => {
+                self.advance()?;
+                if self.options.allow_trailing_commas && self.at_list_end() {
+                    return Ok(None);
+                }
+                return Ok(Some(JoinType::Implicit));
+            }
+            Some(Token::Keyword(Keyword::Join)) => {
+                self.advance()?;
+                return Ok(Some(JoinType::Inner));
+            }
+            Some(Token::Keyword(Keyword::Cross)) => (JoinType::Cross, Keyword::Cross),
+            Some(Token::Keyword(Keyword::Inner)) => (JoinType::Inner, Keyword::Inner),
+            Some(Token::Keyword(Keyword::Left)) => (JoinType::LeftOuter, Keyword::Left),
+            Some(Token::Keyword(Keyword::Right)) => (JoinType::RightOuter, Keyword::Right),
+            Some(Token::Keyword(Keyword::Full)) => (JoinType::FullOuter, Keyword::Full),
+            _ => {
+                self.expect_tokens(&[
+                    TokenKind::Comma,
+                    TokenKind::Keyword(Keyword::Join),
+                    TokenKind::Keyword(Keyword::Cross),
+                    TokenKind::Keyword(Keyword::Inner),
+                    TokenKind::Keyword(Keyword::Left),
+                    TokenKind::Keyword(Keyword::Right),
+                    TokenKind::Keyword(Keyword::Full),
+                ]);
+                return Ok(None);
+            }
+        };
+        self.advance()?;
+
+        let outer = matches!(
+            join_type,
+            JoinType::LeftOuter | JoinType::RightOuter | JoinType::FullOuter
+        );
+        if outer && self.consume_keyword(Keyword::Outer)? {
+            self.expect_keyword(Keyword::Join, "OUTER")?;
+        } else if !self.consume_keyword(Keyword::Join)? {
+            // Join keywords are reserved, so they are never read as table aliases
+            return Err(ParseError::new(Problem::ExpectedKeyword {
+                keywords: vec![Keyword::Join],
+                position: format!(
+                    "after {} ({} is reserved; quote it to use it as an alias)",
+                    keyword, keyword
+                ),
+                found: self.found(),
+            }));
+        }
+
+        Ok(Some(join_type))
+    }
+
+    /// Parses the mandatory `ON condition` or `USING (column, ...)` of a qualified join
+    fn parse_join_constraint(&mut self) -> Result<JoinConstraint, ParseError> {
+        if self.consume_keyword(Keyword::Using)? {
+            return Ok(JoinConstraint::Using(self.parse_column_list("USING")?));
+        }
+
+        self.expect_keyword(Keyword::On, "joined table (or USING)")?;
+        Ok(JoinConstraint::On(self.parse_clause_expression("ON")?))
+    }
+
+    fn parse_table_reference(&mut self) -> Result<TableReference, ParseError> {
+        if self.consume_token(&Token::LeftParentheses)? {
+            if !self.starts_query() {
+                return Err(ParseError::new(Problem::ExpectedKeyword {
+                    keywords: vec![Keyword::Select, Keyword::With, Keyword::Values],
+                    position: "to start a derived table after '('".into(),
+                    found: self.found(),
+                }));
+            }
+            let subquery = self.in_context(ParseContext::Subquery, |parser| {
+                let query = parser.parse_query()?;
+                parser.expect_token(&Token::RightParentheses, "to close derived table")?;
+                Ok(Box::new(query))
+            })?;
+            let alias = self.parse_optional_table_alias()?;
+            return Ok(TableReference::Derived { subquery, alias });
+        }
+
+        let name = self.parse_object_name("table name")?;
+
+        if self.current_token == Some(Token::LeftParentheses) {
+            let args = self.parse_function_args(&name.to_string())?;
+            let alias = self.parse_optional_table_alias()?;
+            return Ok(TableReference::Function { name, args, alias });
+        }
+
+        let alias = self.parse_optional_table_alias()?;
+        Ok(TableReference::Table { name, alias })
+    }
+
+    /// Parses an optional `[AS] alias [(column, ...)]` after a table factor
+    fn parse_optional_table_alias(&mut self) -> Result<Option<TableAlias>, ParseError> {
+        let name = match self.parse_optional_alias()? {
+            Some(name) => name,
+            None => return Ok(None),
+        };
+
+        let mut columns = vec![];
+        if self.current_token == Some(Token::LeftParentheses) {
+            columns = self.parse_ident_list("alias column list")?;
+        }
+
+        Ok(Some(TableAlias { name, columns }))
+    }
+
+    /// Parses a parenthesized, non-empty list of plain column names, rejecting expressions
+    fn parse_column_list(&mut self, clause: &str) -> Result<Vec<String>, ParseError> {
+        let columns = self.parse_ident_list(clause)?;
+        Ok(columns
+            .into_iter()
+            .map(|column| column.value.into())
+            .collect())
+    }
+
+    /// Parses a column list like `parse_column_list`, keeping how each name was quoted
+    fn parse_ident_list(&mut self, clause: &str) -> Result<Vec<Ident>, ParseError> {
+        self.expect_token(&Token::LeftParentheses, &format!("to start {}", clause))?;
+        let columns = self.parse_comma_separated(clause, |parser, context| {
+            let column = parser.parse_ident(&format!("column name after {}", context))?;
+            match parser.current_token.clone() {
+                Some(Token::Comma | Token::RightParentheses) => Ok(column),
+                Some(Token::Eof) | None => Err(ParseError::new(Problem::ExpectedToken {
+                    expected: vec![TokenKind::Comma, TokenKind::RightParentheses],
+                    position: format!("after column {} in {}", column, clause),
+                    found: Token::Eof,
+                })),
+                // Anything else makes the column an expression, such as `a + 1`
+                Some(t) => Err(ParseError::new(Problem::ExpectedIdentifier {
+                    what: format!("column name in {} (expressions are not allowed)", clause),
+                    found: t,
+                })),
+            }
+        })?;
+        self.expect_token(&Token::RightParentheses, &format!("to close {}", clause))?;
+        Ok(columns)
+    }
+
+    /// Parses a possibly qualified name such as `schema.table`
+    fn parse_object_name(&mut self, context: &str) -> Result<ObjectName, ParseError> {
+        let mut name = ObjectName(vec![self.parse_ident(context)?]);
+        while self.consume_token(&Token::Dot)? {
+            let part = self.parse_ident(&format!("name after '.' in {}", name))?;
+            name.0.push(part);
+        }
+        Ok(name)
+    }
+
+    /// Parses one part of an object name, keeping its quote style
+    fn parse_ident(&mut self, context: &str) -> Result<Ident, ParseError> {
+        let quote_style = match self.current_token {
+            Some(Token::QuotedIdentifier(ref value)) if value.is_empty() => {
+                return Err(ParseError::new(Problem::ExpectedIdentifier {
+                    what: format!("{} (quoted identifiers cannot be empty)", context),
+                    found: Token::QuotedIdentifier(Name::default()),
+                }))
+            }
+            Some(Token::QuotedIdentifier(_)) => Some('"'),
+            _ => None,
+        };
+        let value = self.parse_name(context)?;
+        Ok(Ident { value, quote_style })
+    }
+
+    /// Consumes an identifier, describing what it names in the error otherwise
+    fn parse_identifier(&mut self, context: &str) -> Result<String, ParseError> {
+        self.parse_name(context).map(String::from)
+    }
+
+    /// Consumes an identifier like `parse_identifier`, sharing its text with other
+    /// occurrences of the name
+    fn parse_name(&mut self, context: &str) -> Result<Name, ParseError> {
+        match self.current_token.clone() {
+            Some(Token::QuotedIdentifier(name)) => {
+                self.advance()?;
+                Ok(name)
+            }
+            Some(Token::Identifier(name)) => {
+                self.advance()?;
+                Ok(match self.options.identifier_case {
+                    IdentifierCase::Preserve => name,
+                    IdentifierCase::Lower => self.tokenizer.intern(&name.to_lowercase()),
+                    IdentifierCase::Upper => self.tokenizer.intern(&name.to_uppercase()),
+                })
+            }
+            // Non-reserved keywords fold to lower case, as unquoted names do in Postgres
+            Some(Token::Keyword(keyword)) if !keyword.is_reserved() => {
+                self.advance()?;
+                let name = match self.options.identifier_case {
+                    IdentifierCase::Upper => keyword.to_string(),
+                    _ => keyword.to_string().to_lowercase(),
+                };
+                Ok(self.tokenizer.intern(&name))
+            }
+            Some(Token::Eof) | None => {
+                self.expect_tokens(&[TokenKind::Identifier]);
+                Err(ParseError::new(Problem::UnexpectedEndOfInput {
+                    what: context.to_string(),
+                }))
+            }
+            Some(t) => Err(ParseError::new(Problem::ExpectedIdentifier {
+                what: context.to_string(),
+                found: t,
+            })),
+        }
+    }
+
+    /// Consumes `expected`, saying where it was needed in the error otherwise, as in
+    /// `to close IN list`
+    fn expect_token(&mut self, expected: &Token, position: &str) -> Result<(), ParseError> {
+        if self.consume_token(expected)? {
+            return Ok(());
+        }
+
+        match self.current_token.clone() {
+            Some(Token::Eof) | None => Err(ParseError::new(Problem::UnexpectedEndOfInput {
+                what: format!("{} {}", describe(expected), position),
+            })),
+            Some(t) => Err(ParseError::new(Problem::ExpectedToken {
+                expected: vec![expected.kind()],
+                position: position.to_string(),
+                found: t,
+            })),
+        }
+    }
+
+    /// Consumes `keyword`, which must follow `after`
+    fn expect_keyword(&mut self, keyword: Keyword, after: &str) -> Result<(), ParseError> {
+        if self.consume_keyword(keyword.clone())? {
+            return Ok(());
+        }
+
+        Err(ParseError::new(Problem::ExpectedKeyword {
+            keywords: vec![keyword],
+            position: format!("after {}", after),
+            found: self.found(),
+        }))
+    }
+
+    /// Consumes the current token if it is the given keyword
+    fn consume_keyword(&mut self, keyword: Keyword) -> Result<bool, ParseError> {
+        self.consume_token(&Token::Keyword(keyword))
+    }
+
+    /// Consumes the current token if it equals `expected`
+    fn consume_token(&mut self, expected: &Token) -> Result<bool, ParseError> {
+        if self.current_token.as_ref() == Some(expected) {
+            self.advance()?;
+            Ok(true)
+        } else {
+            self.expect_alternative(describe(expected));
+            self.expect_tokens(&[expected.kind()]);
+            Ok(false)
+        }
+    }
+
+    /// Notes that each of `tokens` would have been accepted at the current token, without
+    /// listing them in an error message
+    fn expect_tokens(&mut self, tokens: &[TokenKind]) {
+        for token in tokens {
+            if !self.expected_tokens.contains(token) {
+                self.expected_tokens.push(token.clone());
+            }
+        }
+    }
+
+    /// Notes that an expression could start at the current token
+    fn expect_expression(&mut self) {
+        self.expect_tokens(&[
+            TokenKind::Identifier,
+            TokenKind::LeftParentheses,
+            TokenKind::Minus,
+            TokenKind::Plus,
+            TokenKind::Keyword(Keyword::Not),
+            TokenKind::Keyword(Keyword::True),
+            TokenKind::Keyword(Keyword::False),
+            TokenKind::Keyword(Keyword::Null),
+        ]);
+    }
+
+    /// Notes that a statement could start at the current token
+    fn expect_statement(&mut self) {
+        let keywords = SUPPORTED_STATEMENTS
+            .iter()
+            .filter_map(|name| name.parse().ok());
+        let mut tokens: Vec<TokenKind> = keywords.map(TokenKind::Keyword).collect();
+        tokens.push(TokenKind::LeftParentheses);
+        self.expect_tokens(&tokens);
+    }
+
+    /// Notes that `alternative` would have been accepted at the current token
+    fn expect_alternative(&mut self, alternative: String) {
+        if !self.alternatives.contains(&alternative) {
+            self.alternatives.push(alternative);
+        }
+    }
+
+    /// Returns the current token for an error message
+    fn found(&self) -> Token {
+        self.current_token.clone().unwrap_or(Token::Eof)
+    }
+
+    fn advance(&mut self) -> Result<(), ParseError> {
+        self.current_token = self.tokenizer.next();
+        self.alternatives.clear();
+        self.expected_tokens.clear();
+        Ok(())
+    }
+
+    fn parse_expression(&mut self, precedence: u8) -> Result<Expression, ParseError> {
+        self.nested(|parser| {
+            let left = parser.parse_primary()?;
+            parser.parse_infix(left, precedence)
+        })
+    }
+
+    /// Runs `parse` one level deeper, failing once `options.max_expression_depth` is reached
+    fn nested<T>(
+        &mut self,
+        parse: impl FnOnce(&mut Self) -> Result<T, ParseError>,
+    ) -> Result<T, ParseError> {
+        if let Some(max) = self.options.max_expression_depth {
+            if self.depth >= max {
+                return Err(ParseError::new(Problem::ExpressionTooDeep {
+                    max_depth: max,
+                }));
+            }
+        }
+        self.depth += 1;
+        let result = parse(self);
+        self.depth -= 1;
+        result
+    }
+
+    /// Applies infix operators binding tighter than `precedence` to an already parsed operand
+    fn parse_infix(
+        &mut self,
+        mut left: Expression,
+        precedence: u8,
+    ) -> Result<Expression, ParseError> {
+        let mut after_comparison = false;
+        while let Some(token) = &self.current_token {
+            let token_precedence = self.get_precedence(token);
+
+            if token_precedence <= precedence {
+                self.expect_alternative("an operator".into());
+                let mut operators: Vec<TokenKind> = INFIX_OPERATORS
+                    .iter()
+                    .filter(|operator| self.get_precedence(operator) > precedence)
+                    .map(Token::kind)
+                    .collect();
+                // NOT is only an operator before IN or LIKE, which `get_precedence` looks
+                // ahead for
+                if COMPARISON_PRECEDENCE > precedence {
+                    operators.push(TokenKind::Keyword(Keyword::Not));
+                }
+                self.expect_tokens(&operators);
+                break;
+            }
+
+            let op = self.current_token.clone();
+            self.advance()?;
+
+            if op == Some(Token::DoubleColon) {
+                let data_type = self.parse_data_type_after("'::'")?;
+                left = Expression::Cast {
+                    expr: Box::new(left),
+                    data_type,
+                };
+                after_comparison = false;
+                continue;
+            }
+
+            if let Some(Token::Keyword(
+                keyword @ (Keyword::In | Keyword::Like | Keyword::Is | Keyword::Not),
+            )) = op
+            {
+                left = self.parse_predicate(left, keyword)?;
+                after_comparison = false;
+                continue;
+            }
+
+            let right = self.parse_expression(token_precedence)?;
+
+            let operator = match op {
+                Some(Token::Plus) => BinaryOperator::Plus,
+                Some(Token::Minus) => BinaryOperator::Minus,
+                Some(Token::Multiply) => BinaryOperator::Multiply,
+                Some(Token::Divide) => BinaryOperator::Divide,
+                Some(Token::Equal) => BinaryOperator::Equal,
+                Some(Token::NotEqual) => BinaryOperator::NotEqual,
+                Some(Token::LessThan) => BinaryOperator::LessThan,
+                Some(Token::LessThanOrEqual) => BinaryOperator::LessThanOrEqual,
+                Some(Token::GreaterThan) => BinaryOperator::GreaterThan,
+                Some(Token::GreaterThanOrEqual) => BinaryOperator::GreaterThanOrEqual,
+                Some(Token::Keyword(Keyword::And)) => BinaryOperator::And,
+                Some(Token::Keyword(Keyword::Or)) => BinaryOperator::Or,
+                Some(Token::Concat) => BinaryOperator::Concat,
+                _ => {
+                    return Err(ParseError::new(Problem::InvalidInput {
+                        message: "Unexpected operator".into(),
+                    }))
+                }
+            };
+
+            let is_comparison = operator.is_comparison();
+            if is_comparison && after_comparison && self.options.reject_chained_comparisons {
+                return Err(ParseError::new(Problem::InvalidInput {
+                    message: format!(
+                        "chained comparison with '{}'; use AND or parentheses",
+                        operator
+                    ),
+                }));
+            }
+            after_comparison = is_comparison;
+
+            left = Expression::BinaryOperation {
+                left_operand: Box::new(left),
+                operator,
+                right_operand: Box::new(right),
+            };
+        }
+
+        Ok(left)
+    }
+
+    /// Parses the rest of `expr [NOT] IN (list)`, `expr [NOT] LIKE pattern` or
+    /// `expr IS [NOT] NULL` after the keyword that follows `expr`
+    fn parse_predicate(
+        &mut self,
+        expr: Expression,
+        keyword: Keyword,
+    ) -> Result<Expression, ParseError> {
+        let expr = Box::new(expr);
+        // get_precedence only lets NOT through when IN or LIKE follows, or the input ends
+        let (keyword, negated) = match keyword {
+            Keyword::Not if self.consume_keyword(Keyword::In)? => (Keyword::In, true),
+            Keyword::Not if self.consume_keyword(Keyword::Like)? => (Keyword::Like, true),
+            Keyword::Not => {
+                return Err(ParseError::new(Problem::ExpectedKeyword {
+                    keywords: vec![Keyword::In, Keyword::Like],
+                    position: "after NOT".into(),
+                    found: self.found(),
+                }))
+            }
+            keyword => (keyword, false),
+        };
+        match keyword {
+            Keyword::In => {
+                self.expect_token(&Token::LeftParentheses, "after IN")?;
+                let list =
+                    self.parse_comma_separated("IN list", |parser, _| parser.parse_expression(0))?;
+                self.expect_token(&Token::RightParentheses, "to close IN list")?;
+                Ok(Expression::InList {
+                    expr,
+                    list,
+                    negated,
+                })
+            }
+            Keyword::Like => Ok(Expression::Like {
+                expr,
+                pattern: Box::new(self.parse_expression(COMPARISON_PRECEDENCE)?),
+                negated,
+            }),
+            _ => {
+                let negated = self.consume_keyword(Keyword::Not)?;
+                self.expect_keyword(Keyword::Null, "IS")?;
+                Ok(Expression::IsNull { expr, negated })
+            }
+        }
+    }
+
+    /// Builds the expression for a placeholder token, numbering it by its position so that
+    /// `?` markers bind in the order they are written
+    fn placeholder(&mut self, text: String) -> Expression {
+        let ordinal = self.placeholders;
+        self.placeholders += 1;
+        Expression::Placeholder { text, ordinal }
+    }
+
+    /// Parses what follows a leading name: a function call, `a.b.c`, or the bare identifier
+    fn parse_identifier_expression(&mut self, ident: Ident) -> Result<Expression, ParseError> {
+        if self.current_token == Some(Token::LeftParentheses) {
+            return self.parse_function_call(ident.value.into());
+        }
+        if self.current_token != Some(Token::Dot) {
+            return Ok(Expression::Identifier(ident));
+        }
+
+        let mut parts = vec![ident];
+        while self.consume_token(&Token::Dot)? {
+            parts.push(self.parse_ident("identifier after '.'")?);
+        }
+        Ok(Expression::CompoundIdentifier(parts))
+    }
+
+    fn parse_primary(&mut self) -> Result<Expression, ParseError> {
+        match self.current_token.clone() {
+            Some(Token::Number(n)) => {
+                self.advance()?;
+                Ok(Expression::Value(Value::Number(n)))
+            }
+            Some(Token::Float(x)) => {
+                self.advance()?;
+                Ok(Expression::Value(Value::Float(x)))
+            }
+            Some(Token::String(s)) => {
+                self.advance()?;
+                Ok(Expression::Value(Value::SingleQuotedString(s)))
+            }
+            Some(Token::Placeholder(p)) => {
+                self.advance()?;
+                Ok(self.placeholder(p))
+            }
+            Some(Token::Keyword(Keyword::Not)) => {
+                self.advance()?;
+                let operand = self.parse_expression(NOT_PRECEDENCE)?;
+                Ok(Expression::UnaryOperation {
+                    operator: UnaryOperator::Not,
+                    operand: Box::new(operand),
+                })
+            }
+            Some(Token::Minus) => {
+                self.advance()?;
+                let operand = self.parse_expression(UNARY_SIGN_PRECEDENCE)?;
+                Ok(Expression::UnaryOperation {
+                    operator: UnaryOperator::Minus,
+                    operand: Box::new(operand),
+                })
+            }
+            Some(Token::Plus) => {
+                self.advance()?;
+                let operand = self.parse_expression(UNARY_SIGN_PRECEDENCE)?;
+                Ok(Expression::UnaryOperation {
+                    operator: UnaryOperator::Plus,
+                    operand: Box::new(operand),
+                })
+            }
+            Some(Token::Keyword(Keyword::True)) => {
+                self.advance()?;
+                Ok(Expression::Value(Value::Boolean(true)))
+            }
+            Some(Token::Keyword(Keyword::False)) => {
+                self.advance()?;
+                Ok(Expression::Value(Value::Boolean(false)))
+            }
+            Some(Token::Keyword(Keyword::Null)) => {
+                self.advance()?;
+                Ok(Expression::Value(Value::Null))
+            }
+            Some(Token::Identifier(_) | Token::QuotedIdentifier(_)) => {
+                let ident = self.parse_ident("identifier")?;
+                self.parse_identifier_expression(ident)
+            }
+            Some(Token::Keyword(keyword)) if !keyword.is_reserved() => {
+                let ident = self.parse_ident("identifier")?;
+                self.parse_identifier_expression(ident)
+            }
+            Some(Token::LeftParentheses) => {
+                self.advance()?;
+                if let Some(Token::Keyword(Keyword::Select | Keyword::With | Keyword::Values)) =
+                    self.current_token
+                {
+                    let query = self.in_context(ParseContext::Subquery, |parser| {
+                        let query = parser.parse_query()?;
+                        parser.expect_token(&Token::RightParentheses, "after subquery")?;
+                        Ok(query)
+                    })?;
+                    return Ok(Expression::Subquery(Box::new(query)));
+                }
+                self.expect_tokens(&[
+                    TokenKind::Keyword(Keyword::Select),
+                    TokenKind::Keyword(Keyword::With),
+                    TokenKind::Keyword(Keyword::Values),
+                ]);
+                let expr = self.parse_expression(0)?;
+                if self.consume_token(&Token::RightParentheses)? {
+                    Ok(expr)
+                } else {
+                    Err(ParseError::new(Problem::ExpectedToken {
+                        expected: vec![TokenKind::RightParentheses],
+                        position: "to close parenthesized expression".into(),
+                        found: self.found(),
+                    }))
+                }
+            }
+            Some(Token::Eof) | None => {
+                self.expect_expression();
+                Err(ParseError::new(Problem::UnexpectedEndOfInput {
+                    what: "an expression".into(),
+                }))
+            }
+            Some(t) => Err(ParseError::new(Problem::UnexpectedToken {
+                found: t,
+                position: "at the start of an expression".into(),
+            })),
+        }
+    }
+
+    fn parse_function_call(&mut self, name: String) -> Result<Expression, ParseError> {
+        let args = self.in_context(ParseContext::FunctionArgs, |parser| {
+            parser.parse_function_args(&name)
+        })?;
+        Ok(Expression::Function { name, args })
+    }
+
+    /// Parses the parenthesized argument list of the function `name`
+    fn parse_function_args(&mut self, name: &str) -> Result<Vec<Expression>, ParseError> {
+        self.advance()?; // Skip (
+
+        let mut args = vec![];
+        if self.current_token == Some(Token::Multiply) {
+            self.advance()?;
+            args.push(Expression::Wildcard);
+            self.expect_token(
+                &Token::RightParentheses,
+                &format!("after * in arguments of {}", name),
+            )?;
+        } else if !self.consume_token(&Token::RightParentheses)? {
+            self.expect_tokens(&[TokenKind::Multiply]);
+            args = self.parse_comma_separated("function arguments", |parser, _| {
+                parser.parse_expression(0)
+            })?;
+            self.expect_token(
+                &Token::RightParentheses,
+                &format!("to close arguments of {}", name),
+            )?;
+        }
+
+        Ok(args)
+    }
+
+    fn get_precedence(&self, token: &Token) -> u8 {
+        match token {
+            Token::Keyword(Keyword::Or) => 1,
+            Token::Keyword(Keyword::And) => 2,
+            Token::Equal
+            | Token::NotEqual
+            | Token::LessThan
+            | Token::LessThanOrEqual
+            | Token::GreaterThan
+            | Token::GreaterThanOrEqual
+            | Token::Keyword(Keyword::In | Keyword::Like | Keyword::Is) => 4,
+            // After an operand, NOT can only begin NOT IN or NOT LIKE; elsewhere, as in
+            // `DEFAULT 0 NOT NULL`, it ends the expression. At the end of the input it is
+            // taken as the start of either, so that what may follow can be completed.
+            Token::Keyword(Keyword::Not)
+                if matches!(
+                    self.tokenizer.peek_token(),
+                    Some(Token::Keyword(Keyword::In | Keyword::Like) | Token::Eof) | None
+                ) =>
+            {
+                4
+            }
+            // Like PostgreSQL, `'a' || 1 + 2` concatenates the sum
+            Token::Concat => 6,
+            Token::Plus | Token::Minus => 7,
+            Token::Multiply | Token::Divide => 8,
+            Token::DoubleColon => 9,
+            _ => 0,
+        }
+    }
+}
+
+/// Returns true for keywords that may begin an expression
+fn starts_expression(keyword: &Keyword) -> bool {
+    matches!(
+        keyword,
+        Keyword::Not | Keyword::True | Keyword::False | Keyword::Null
+    )
+}
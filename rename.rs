@@ -0,0 +1,499 @@
+use std::ops::ControlFlow;
+
+use crate::ast::{
+    Assignment, ConflictTarget, Cte, Expression, Ident, JoinConstraint, ObjectName,
+    OnConflictAction, Query, SelectItem, SelectStatement, SetExpr, Statement, TableReference,
+    TableWithJoins,
+};
+use crate::visitor::{
+    walk_cte_mut, walk_query_mut, walk_select_mut, walk_statement_mut, walk_table_reference_mut,
+    VisitorMut,
+};
+
+impl Statement {
+    /// Renames the table `from` to `to` everywhere it is referenced, including qualified
+    /// column references such as `from.a`. Dotted names such as `"s.t"` must match all
+    /// parts. A CTE of the same name shadows the table inside its scope, and a qualifier
+    /// that names a table alias or derived table is left alone.
+    pub fn rename_table(&mut self, from: &str, to: &str) {
+        let mut renamer = Renamer::new(
+            Some(ObjectName::from(from)),
+            Rename::Table(ObjectName::from(to)),
+        );
+        let _ = renamer.visit_statement_mut(self);
+    }
+
+    /// Renames the column `from` to `to`. With a `table`, only columns of that table are
+    /// renamed: qualified references whose qualifier resolves to it, through an alias or
+    /// not, and bare references in a query that reads from it. Without one, every column
+    /// named `from` is renamed. INSERT column lists and SET targets are renamed as well;
+    /// bare references to a select alias of the same name cannot be told apart from columns.
+    ///
+    /// With a `table`, a derived table or CTE that selects the column keeps its output name
+    /// through an alias, as in `(SELECT to AS from FROM table)`, so the query around it
+    /// still reads. A `USING` list naming the column is renamed when the table is on both
+    /// sides of the join; when it is on one side only, the join becomes an `ON` condition
+    /// comparing the columns of both sides.
+    pub fn rename_column(&mut self, table: Option<&str>, from: &str, to: &str) {
+        let mut renamer = Renamer::new(
+            table.map(ObjectName::from),
+            Rename::Column {
+                from: Ident::new(from),
+                to: to.to_string(),
+            },
+        );
+        let _ = renamer.visit_statement_mut(self);
+    }
+}
+
+enum Rename {
+    Table(ObjectName),
+    Column { from: Ident, to: String },
+}
+
+/// Represents a name a query's FROM clause makes available as a column qualifier
+struct Binding {
+    qualifier: Vec<Ident>,
+    /// Whether the qualifier refers to the table being tracked
+    is_target: bool,
+    /// Whether the qualifier is an alias rather than the table's own name
+    is_alias: bool,
+}
+
+struct Renamer {
+    /// The table whose references are renamed, or whose columns are
+    table: Option<ObjectName>,
+    rename: Rename,
+    /// CTE names visible at the current point of the walk
+    ctes: Vec<String>,
+    /// For each query or DML statement being walked, the qualifiers its FROM clause binds
+    scopes: Vec<Vec<Binding>>,
+    /// Set on the way into a derived table or CTE whose columns the enclosing query refers
+    /// to by their output names
+    keep_output_names: bool,
+}
+
+impl Renamer {
+    fn new(table: Option<ObjectName>, rename: Rename) -> Self {
+        Renamer {
+            table,
+            rename,
+            ctes: vec![],
+            scopes: vec![],
+            keep_output_names: false,
+        }
+    }
+
+    fn is_target(&self, name: &ObjectName) -> bool {
+        let is_cte = match name.0.as_slice() {
+            [ident] => self
+                .ctes
+                .iter()
+                .any(|cte| ident_eq(ident, &Ident::new(cte.as_str()))),
+            _ => false,
+        };
+        !is_cte
+            && self
+                .table
+                .as_ref()
+                .is_some_and(|table| name_eq(&name.0, &table.0))
+    }
+
+    fn bind_table(&self, table: &TableReference, bindings: &mut Vec<Binding>) {
+        match table {
+            TableReference::Table {
+                name,
+                alias: Some(alias),
+            } => bindings.push(Binding {
+                qualifier: vec![alias.name.clone()],
+                is_target: self.is_target(name),
+                is_alias: true,
+            }),
+            TableReference::Table { name, alias: None } => {
+                let is_target = self.is_target(name);
+                bindings.push(Binding {
+                    qualifier: name.0.clone(),
+                    is_target,
+                    is_alias: false,
+                });
+                if name.0.len() > 1 {
+                    bindings.push(Binding {
+                        qualifier: name.0[name.0.len() - 1..].to_vec(),
+                        is_target,
+                        is_alias: false,
+                    });
+                }
+            }
+            TableReference::Derived {
+                alias: Some(alias), ..
+            }
+            | TableReference::Function {
+                alias: Some(alias), ..
+            } => bindings.push(Binding {
+                qualifier: vec![alias.name.clone()],
+                is_target: false,
+                is_alias: true,
+            }),
+            _ => {}
+        }
+    }
+
+    fn bind_from(&self, from: &TableWithJoins, bindings: &mut Vec<Binding>) {
+        self.bind_table(&from.relation, bindings);
+        for join in &from.joins {
+            self.bind_table(&join.relation, bindings);
+        }
+    }
+
+    fn bind_select(&self, select: &SelectStatement) -> Vec<Binding> {
+        let mut bindings = vec![];
+        if let Some(from) = &select.from {
+            self.bind_from(from, &mut bindings);
+        }
+        bindings
+    }
+
+    /// Finds what `qualifier` refers to, searching the innermost scope first so that
+    /// correlated subqueries see the tables of the queries around them
+    fn resolve(&self, qualifier: &[Ident]) -> Option<&Binding> {
+        self.scopes.iter().rev().find_map(|scope| {
+            scope
+                .iter()
+                .find(|binding| name_eq(&binding.qualifier, qualifier))
+        })
+    }
+
+    /// Whether a bare column reference at this point may belong to the tracked table
+    fn in_target_scope(&self) -> bool {
+        self.table.is_none()
+            || self
+                .scopes
+                .last()
+                .is_some_and(|scope| scope.iter().any(|binding| binding.is_target))
+    }
+
+    fn rename_column_name(&self, column: &mut String) {
+        if let Rename::Column { from, to } = &self.rename {
+            if ident_eq(&Ident::new(column.as_str()), from) {
+                *column = to.clone();
+            }
+        }
+    }
+
+    /// Renames the column in the USING lists of `from`, or turns a USING join into an ON
+    /// join when the tracked table is on one side only. The ON condition is walked
+    /// afterwards, which renames the tracked table's side of it.
+    fn rename_using(&self, from: &mut TableWithJoins) {
+        let Rename::Column { from: column, .. } = &self.rename else {
+            return;
+        };
+        let mut left = vec![];
+        self.bind_table(&from.relation, &mut left);
+        for (i, join) in from.joins.iter_mut().enumerate() {
+            let mut right = vec![];
+            self.bind_table(&join.relation, &mut right);
+            let is_target = |bindings: &[Binding]| bindings.iter().any(|b| b.is_target);
+            let (left_is_target, right_is_target) = (is_target(&left), is_target(&right));
+            match &mut join.constraint {
+                JoinConstraint::Using(columns)
+                    if !columns
+                        .iter()
+                        .any(|name| ident_eq(&Ident::new(name.as_str()), column)) => {}
+                JoinConstraint::Using(columns)
+                    if self.table.is_none() || (left_is_target && right_is_target) =>
+                {
+                    for name in columns.iter_mut() {
+                        self.rename_column_name(name);
+                    }
+                }
+                JoinConstraint::Using(columns) if left_is_target || right_is_target => {
+                    // The left side is qualified by the tracked table, or by the only table
+                    // there; with several, there is no telling which one has the column
+                    let left_binding = if left_is_target {
+                        left.iter().find(|binding| binding.is_target)
+                    } else if i == 0 {
+                        left.first()
+                    } else {
+                        None
+                    };
+                    if let (Some(l), Some(r)) = (left_binding, right.first()) {
+                        let condition = columns
+                            .iter()
+                            .map(|name| {
+                                qualified(&l.qualifier, name).eq(qualified(&r.qualifier, name))
+                            })
+                            .reduce(Expression::and)
+                            .expect("USING lists are not empty");
+                        join.constraint = JoinConstraint::On(condition);
+                    }
+                }
+                _ => {}
+            }
+            left.extend(right);
+        }
+    }
+
+    fn rename_assignments(&self, assignments: &mut [Assignment]) {
+        for assignment in assignments {
+            if let Some(column) = assignment.column.last_mut() {
+                self.rename_column_name(column);
+            }
+        }
+    }
+
+    /// Renames the columns written by an INSERT or UPDATE of `table`
+    fn rename_target_columns(&self, statement: &mut Statement) {
+        match statement {
+            Statement::Insert {
+                table,
+                columns,
+                on_conflict,
+                ..
+            } => {
+                if self.table.is_some() && !self.is_target(table) {
+                    return;
+                }
+                for column in columns {
+                    self.rename_column_name(column);
+                }
+                if let Some(on_conflict) = on_conflict {
+                    if let Some(ConflictTarget::Columns(columns)) = &mut on_conflict.target {
+                        for column in columns {
+                            self.rename_column_name(column);
+                        }
+                    }
+                    if let OnConflictAction::DoUpdate { assignments, .. } = &mut on_conflict.action
+                    {
+                        self.rename_assignments(assignments);
+                    }
+                }
+            }
+            Statement::Update {
+                table, assignments, ..
+            } => {
+                let is_target = match table {
+                    TableReference::Table { name, .. } => self.is_target(name),
+                    _ => false,
+                };
+                if self.table.is_none() || is_target {
+                    self.rename_assignments(assignments);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl VisitorMut for Renamer {
+    type Break = ();
+
+    fn visit_statement_mut(&mut self, statement: &mut Statement) -> ControlFlow<()> {
+        let mut bindings = vec![];
+        match &*statement {
+            Statement::Insert { table, .. } => {
+                let table = TableReference::Table {
+                    name: table.clone(),
+                    alias: None,
+                };
+                self.bind_table(&table, &mut bindings);
+            }
+            Statement::Update { table, from, .. } => {
+                self.bind_table(table, &mut bindings);
+                if let Some(from) = from {
+                    self.bind_from(from, &mut bindings);
+                }
+            }
+            Statement::Delete { table, using, .. } => {
+                self.bind_table(table, &mut bindings);
+                if let Some(using) = using {
+                    self.bind_from(using, &mut bindings);
+                }
+            }
+            _ => {}
+        }
+        self.rename_target_columns(statement);
+        if let Statement::Update {
+            from: Some(from), ..
+        }
+        | Statement::Delete {
+            using: Some(from), ..
+        } = statement
+        {
+            self.rename_using(from);
+        }
+
+        self.scopes.push(bindings);
+        let result = walk_statement_mut(self, statement);
+        self.scopes.pop();
+        result
+    }
+
+    fn visit_query_mut(&mut self, query: &mut Query) -> ControlFlow<()> {
+        let output_names = match std::mem::take(&mut self.keep_output_names) {
+            true => leftmost_select(&mut query.body).map(|select| output_names(select)),
+            false => None,
+        };
+        let scope = self.ctes.len();
+        let cte_names = query.with.iter().flat_map(|with| &with.ctes);
+        // ORDER BY sees the tables of the query body, which sees all of the query's CTEs
+        self.ctes.extend(cte_names.map(|cte| cte.name.clone()));
+        let bindings = match &query.body {
+            SetExpr::Select(select) => self.bind_select(select),
+            _ => vec![],
+        };
+        self.ctes.truncate(scope);
+        // A recursive CTE can refer to itself and to the CTEs after it
+        if let Some(with) = query.with.as_ref().filter(|with| with.recursive) {
+            self.ctes
+                .extend(with.ctes.iter().map(|cte| cte.name.clone()));
+        }
+
+        self.scopes.push(bindings);
+        let result = walk_query_mut(self, query);
+        self.scopes.pop();
+        self.ctes.truncate(scope);
+        if let (Some(names), Some(select)) = (output_names, leftmost_select(&mut query.body)) {
+            keep_output_names(select, names);
+        }
+        result
+    }
+
+    fn visit_cte_mut(&mut self, cte: &mut Cte) -> ControlFlow<()> {
+        // A column list names the CTE's columns, whatever its query calls them
+        self.keep_output_names = self.table.is_some() && cte.columns.is_empty();
+        walk_cte_mut(self, cte)?;
+        // Only the CTEs after this one and the query body can see its name
+        self.ctes.push(cte.name.clone());
+        ControlFlow::Continue(())
+    }
+
+    fn visit_select_mut(&mut self, select: &mut SelectStatement) -> ControlFlow<()> {
+        // Each branch of a set operation has its own FROM clause
+        let bindings = self.bind_select(select);
+        if let Some(from) = &mut select.from {
+            self.rename_using(from);
+        }
+        self.scopes.push(bindings);
+        let result = walk_select_mut(self, select);
+        self.scopes.pop();
+        result
+    }
+
+    fn visit_table_reference_mut(&mut self, table: &mut TableReference) -> ControlFlow<()> {
+        if let TableReference::Derived { alias, .. } = table {
+            self.keep_output_names = self.table.is_some()
+                && alias.as_ref().is_some_and(|alias| alias.columns.is_empty());
+        }
+        walk_table_reference_mut(self, table)
+    }
+
+    fn visit_table_name_mut(&mut self, name: &mut ObjectName) -> ControlFlow<()> {
+        if let Rename::Table(to) = &self.rename {
+            if self.is_target(name) {
+                *name = to.clone();
+            }
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn visit_identifier_mut(&mut self, ident: &mut Ident) -> ControlFlow<()> {
+        if let Rename::Column { from, to } = &self.rename {
+            if ident_eq(ident, from) && self.in_target_scope() {
+                *ident = Ident::new(to.as_str());
+            }
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn visit_compound_identifier_mut(&mut self, parts: &mut Vec<Ident>) -> ControlFlow<()> {
+        let (column, qualifier) = match parts.split_last_mut() {
+            Some(split) => split,
+            None => return ControlFlow::Continue(()),
+        };
+        let binding = self.resolve(qualifier);
+        let is_target = binding.is_some_and(|binding| binding.is_target);
+
+        match &self.rename {
+            Rename::Table(to) => {
+                if is_target && !binding.is_some_and(|binding| binding.is_alias) {
+                    let table = self.table.as_ref().expect("renaming a table tracks it");
+                    // A qualifier written with the schema keeps it; a bare table name stays bare
+                    let new_qualifier = if qualifier.len() == table.0.len() {
+                        to.0.clone()
+                    } else {
+                        to.0[to.0.len() - 1..].to_vec()
+                    };
+                    let column = column.clone();
+                    *parts = new_qualifier;
+                    parts.push(column);
+                }
+            }
+            Rename::Column { from, to } => {
+                if ident_eq(column, from) && (self.table.is_none() || is_target) {
+                    *column = Ident::new(to.as_str());
+                }
+            }
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+/// Returns the SELECT that names the columns of `body`: the first branch of a set operation
+fn leftmost_select(body: &mut SetExpr) -> Option<&mut SelectStatement> {
+    match body {
+        SetExpr::Select(select) => Some(select),
+        SetExpr::SetOperation { left, .. } => leftmost_select(left),
+        SetExpr::Query(query) => leftmost_select(&mut query.body),
+        SetExpr::Values(_) => None,
+    }
+}
+
+/// Returns the name each unaliased column reference in the projection is output under
+fn output_names(select: &SelectStatement) -> Vec<Option<Ident>> {
+    select
+        .projection
+        .iter()
+        .map(|item| match item {
+            SelectItem::Expression(Expression::Identifier(ident)) => Some(ident.clone()),
+            SelectItem::Expression(Expression::CompoundIdentifier(parts)) => parts.last().cloned(),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Aliases each column reference that was renamed back to the name it was output under
+fn keep_output_names(select: &mut SelectStatement, names: Vec<Option<Ident>>) {
+    let renamed = output_names(select);
+    let projection = std::mem::take(&mut select.projection);
+    select.projection = projection
+        .into_iter()
+        .zip(names.into_iter().zip(renamed))
+        .map(|item| match item {
+            (SelectItem::Expression(expr), (Some(before), Some(after))) if before != after => {
+                SelectItem::ExprWithAlias {
+                    expr,
+                    alias: before,
+                }
+            }
+            (item, _) => item,
+        })
+        .collect();
+}
+
+fn qualified(qualifier: &[Ident], column: &str) -> Expression {
+    let mut parts = qualifier.to_vec();
+    parts.push(Ident::new(column));
+    Expression::CompoundIdentifier(parts)
+}
+
+/// Compares identifiers the way the database would: case-insensitively unless either is quoted
+fn ident_eq(a: &Ident, b: &Ident) -> bool {
+    match (a.quote_style, b.quote_style) {
+        (None, None) => a.value.eq_ignore_ascii_case(&b.value),
+        _ => a.value == b.value,
+    }
+}
+
+fn name_eq(a: &[Ident], b: &[Ident]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).all(|(a, b)| ident_eq(a, b))
+}
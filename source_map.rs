@@ -0,0 +1,165 @@
+use std::ops::Range;
+
+use crate::tokenizer::Location;
+
+/// Converts between the byte offsets spans carry and line and column positions in one
+/// input, using an index of where its lines start that is built once.
+///
+/// Lines end at `\n`, and the text after the last one is a line of its own even when it is
+/// empty, so the end of the input always has a position. A `\r` before the `\n` belongs to
+/// its line, as the tokenizer counts it. Lines and columns count from 1, as in `Location`.
+/// Columns count characters, or UTF-16 code units in the `_utf16` methods; an LSP
+/// `Position` takes the latter with both numbers counting from 0 instead.
+///
+/// ```
+/// use rust_sql_parser::source_map::SourceMap;
+///
+/// let map = SourceMap::new("SELECT 'é😀'\r\nFROM t");
+/// assert_eq!(map.offset_to_line_col(14), (1, 11));
+/// assert_eq!(map.offset_to_line_col_utf16(14), (1, 12));
+/// assert_eq!(map.offset_to_line_col(17), (2, 1));
+/// // The end of the input, on a last line without a newline
+/// assert_eq!(map.offset_to_line_col(23), (2, 7));
+/// assert_eq!(map.line_col_to_offset(2, 7), Some(23));
+/// assert_eq!(map.line_col_utf16_to_offset(1, 12), Some(14));
+/// // Past the end of a line, and inside the surrogate pair of 😀
+/// assert_eq!(map.line_col_to_offset(1, 14), None);
+/// assert_eq!(map.line_col_utf16_to_offset(1, 11), None);
+/// assert_eq!(map.line(1), Some("SELECT 'é😀'"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceMap {
+    source: String,
+    /// The byte offset where each line starts
+    line_starts: Vec<usize>,
+}
+
+impl SourceMap {
+    pub fn new(source: &str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(source.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        SourceMap {
+            source: source.to_string(),
+            line_starts,
+        }
+    }
+
+    /// Returns the text the map was built from
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Returns the number of lines, which is one more than the number of `\n`s
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// Returns the text of `line` without the `\n` or `\r\n` ending it, or `None` if there
+    /// is no such line
+    pub fn line(&self, line: usize) -> Option<&str> {
+        let range = self.line_range(line)?;
+        let text = &self.source[range.clone()];
+        if range.end < self.source.len() {
+            return Some(text.strip_suffix('\r').unwrap_or(text));
+        }
+        Some(text)
+    }
+
+    /// Returns the line and character column of byte `offset`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `offset` is past the end of the input or not on a character boundary.
+    pub fn offset_to_line_col(&self, offset: usize) -> (u32, u32) {
+        let (line, before) = self.locate(offset);
+        (line as u32, before.chars().count() as u32 + 1)
+    }
+
+    /// Returns the line and UTF-16 column of byte `offset`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `offset` is past the end of the input or not on a character boundary.
+    pub fn offset_to_line_col_utf16(&self, offset: usize) -> (u32, u32) {
+        let (line, before) = self.locate(offset);
+        (line as u32, before.encode_utf16().count() as u32 + 1)
+    }
+
+    /// Returns the byte offset of character `column` of `line`, or `None` if the line does
+    /// not exist or the column is past its end. The column just past the last character,
+    /// before any line ending, is its end.
+    pub fn line_col_to_offset(&self, line: u32, column: u32) -> Option<usize> {
+        self.column_offset(line, column, |_| 1)
+    }
+
+    /// Returns the byte offset of UTF-16 `column` of `line`, or `None` if the line does not
+    /// exist, the column is past its end or it falls inside a surrogate pair
+    pub fn line_col_utf16_to_offset(&self, line: u32, column: u32) -> Option<usize> {
+        self.column_offset(line, column, char::len_utf16)
+    }
+
+    /// Returns the location of byte `offset`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `offset` is past the end of the input or not on a character boundary.
+    pub fn location(&self, offset: usize) -> Location {
+        let (line, column) = self.offset_to_line_col(offset);
+        Location {
+            line: line as usize,
+            column: column as usize,
+            offset,
+        }
+    }
+
+    /// Replaces the bytes in `range` with `replacement`, updating only the lines that start
+    /// in or after it
+    pub(crate) fn replace_range(&mut self, range: Range<usize>, replacement: &str) {
+        self.source.replace_range(range.clone(), replacement);
+        let from = self
+            .line_starts
+            .partition_point(|&start| start <= range.start);
+        let to = self
+            .line_starts
+            .partition_point(|&start| start <= range.end);
+        let added: Vec<usize> = replacement
+            .match_indices('\n')
+            .map(|(i, _)| range.start + i + 1)
+            .collect();
+        let count = added.len();
+        self.line_starts.splice(from..to, added);
+        for start in &mut self.line_starts[from + count..] {
+            *start = *start - range.end + range.start + replacement.len();
+        }
+    }
+
+    /// Returns the line of byte `offset` and the text of the line before it
+    fn locate(&self, offset: usize) -> (usize, &str) {
+        let line = self.line_starts.partition_point(|&start| start <= offset);
+        (line, &self.source[self.line_starts[line - 1]..offset])
+    }
+
+    /// Returns the bytes of `line`, up to but not including the `\n` ending it
+    fn line_range(&self, line: usize) -> Option<Range<usize>> {
+        let start = *self.line_starts.get(line.checked_sub(1)?)?;
+        let end = self
+            .line_starts
+            .get(line)
+            .map_or(self.source.len(), |&next| next - 1);
+        Some(start..end)
+    }
+
+    /// Finds `column` of `line`, where each character takes up `width` columns
+    fn column_offset(&self, line: u32, column: u32, width: fn(char) -> usize) -> Option<usize> {
+        let range = self.line_range(line as usize)?;
+        let mut current = 1;
+        for (i, ch) in self.source[range.clone()].char_indices() {
+            if current == column as usize {
+                return Some(range.start + i);
+            }
+            current += width(ch);
+        }
+        (current == column as usize).then_some(range.end)
+    }
+}
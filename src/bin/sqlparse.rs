@@ -0,0 +1,175 @@
+//! Tokenizes or parses SQL for debugging, e.g. `sqlparse --tokens "SELECT 1+2"`.
+//!
+//! The SQL is taken from the arguments, from a file given with `-f`, or from stdin. Parse
+//! failures are printed as rendered diagnostics and exit with status 1.
+
+use std::env;
+use std::fs;
+use std::io::{self, Read};
+use std::process;
+
+use rust_sql_parser::ast::{Statement, Token};
+use rust_sql_parser::parser::PrattParser;
+use rust_sql_parser::tokenizer::Tokenizer;
+
+const USAGE: &str =
+    "usage: sqlparse [--tokens | --ast | --tree | --json | --check] [-f <file> | <sql>...]
+
+Reads SQL from the arguments, from a file, or from stdin when neither is given.
+
+  --tokens  print the tokens one per line with their spans
+  --ast     print the statements as Rust debug output (the default)
+  --tree    print the statements as S-expression trees
+  --json    print the statements as JSON (needs the serde and json features)
+  --check   only report whether each statement parses";
+
+#[derive(Clone, Copy, PartialEq)]
+enum Mode {
+    Tokens,
+    Ast,
+    Tree,
+    Json,
+    Check,
+}
+
+fn main() {
+    let mut mode = Mode::Ast;
+    let mut file = None;
+    let mut words = vec![];
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--tokens" => mode = Mode::Tokens,
+            "--ast" => mode = Mode::Ast,
+            "--tree" => mode = Mode::Tree,
+            "--json" => mode = Mode::Json,
+            "--check" => mode = Mode::Check,
+            "-f" | "--file" => match args.next() {
+                Some(path) => file = Some(path),
+                None => usage(),
+            },
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return;
+            }
+            _ if arg.starts_with("--") => usage(),
+            _ => words.push(arg),
+        }
+    }
+
+    let sql = match (file, words.is_empty()) {
+        (Some(path), true) => fs::read_to_string(&path).unwrap_or_else(|err| {
+            eprintln!("sqlparse: cannot read {}: {}", path, err);
+            process::exit(2);
+        }),
+        (Some(_), false) => usage(),
+        (None, false) => words.join(" "),
+        (None, true) => {
+            let mut sql = String::new();
+            if let Err(err) = io::stdin().read_to_string(&mut sql) {
+                eprintln!("sqlparse: cannot read stdin: {}", err);
+                process::exit(2);
+            }
+            sql
+        }
+    };
+
+    let ok = match mode {
+        Mode::Tokens => print_tokens(&sql),
+        Mode::Check => check(&sql),
+        _ => print_statements(&sql, mode),
+    };
+    if !ok {
+        process::exit(1);
+    }
+}
+
+fn usage() -> ! {
+    eprintln!("{}", USAGE);
+    process::exit(2);
+}
+
+/// Prints each token with its span as `line:column-line:column`, up to the first lexical error
+fn print_tokens(sql: &str) -> bool {
+    let mut tokenizer = Tokenizer::new(sql);
+    loop {
+        let token = tokenizer.next().unwrap_or(Token::Eof);
+        let span = tokenizer.span();
+        println!(
+            "{}:{}-{}:{}\t{:?}",
+            span.start.line, span.start.column, span.end.line, span.end.column, token
+        );
+        if token == Token::Eof {
+            break;
+        }
+    }
+    match tokenizer.error() {
+        Some(err) => {
+            eprintln!("{}", err.render(sql));
+            false
+        }
+        None => true,
+    }
+}
+
+fn print_statements(sql: &str, mode: Mode) -> bool {
+    let statements = match PrattParser::parse_statements(sql) {
+        Ok(statements) => statements,
+        Err(err) => {
+            eprintln!("{}", err.render(sql));
+            return false;
+        }
+    };
+    match mode {
+        Mode::Tree => {
+            for statement in &statements {
+                println!("{}", statement.to_tree_string());
+            }
+        }
+        Mode::Json => return print_json(&statements),
+        _ => {
+            for statement in &statements {
+                println!("{:#?}", statement);
+            }
+        }
+    }
+    true
+}
+
+#[cfg(all(feature = "serde", feature = "json"))]
+fn print_json(statements: &[Statement]) -> bool {
+    match serde_json::to_string_pretty(statements) {
+        Ok(json) => {
+            println!("{}", json);
+            true
+        }
+        Err(err) => {
+            eprintln!("sqlparse: {}", err);
+            false
+        }
+    }
+}
+
+#[cfg(not(all(feature = "serde", feature = "json")))]
+fn print_json(_statements: &[Statement]) -> bool {
+    eprintln!("sqlparse: --json needs the serde and json features");
+    process::exit(2);
+}
+
+/// Parses every statement, carrying on past errors, and prints a line for each statement
+/// that parsed followed by the diagnostics of those that did not
+fn check(sql: &str) -> bool {
+    let (statements, errors) = PrattParser::parse_statements_recovering(sql);
+    for statement in &statements {
+        println!("ok: {}", statement);
+    }
+    for err in &errors {
+        println!("{}", err.render(sql));
+    }
+    println!(
+        "{} statement(s) ok, {} error(s)",
+        statements.len(),
+        errors.len()
+    );
+    errors.is_empty()
+}
@@ -0,0 +1,223 @@
+use std::io::{self, BufRead};
+
+use crate::ast::Statement;
+use crate::error::{ParseError, Problem};
+use crate::options::ParserOptions;
+use crate::parser::PrattParser;
+use crate::tokenizer::{Location, Span};
+
+/// Parses the statements of a `;`-separated script one at a time as they are read, so that
+/// a dump of any size can be processed while holding only the statement being parsed.
+///
+/// The input is split at each `;` outside string literals, quoted identifiers and `--` or
+/// `/* */` comments, and each piece is parsed on its own. Comments are blanked out before
+/// parsing, as the tokenizer does not accept them. Error spans refer to the whole input,
+/// except that columns after a non-ASCII character in a comment on the same line are off
+/// by the extra bytes it took.
+///
+/// An error ends only the statement it occurs in: the next call to `next` carries on with
+/// the following statement, whatever `ParserOptions::recover_from_errors` says. A failure
+/// to read ends the stream after it has been returned as an `InvalidInput` error.
+///
+/// ```
+/// use std::io::Cursor;
+/// use rust_sql_parser::stream::StatementStream;
+///
+/// let dump = Cursor::new("INSERT INTO t VALUES (';'); -- done;\nSELECT a FROM t;");
+/// let statements: Vec<_> = StatementStream::new(dump).collect();
+/// assert_eq!(statements.len(), 2);
+/// assert!(statements.iter().all(Result::is_ok));
+/// ```
+pub struct StatementStream<R> {
+    reader: R,
+    options: ParserOptions,
+    splitter: Splitter,
+    /// Where the statement being read starts in the input
+    start: Location,
+    /// The number of the next statement, counting from 1, for error messages
+    number: usize,
+    done: bool,
+}
+
+impl<R: BufRead> StatementStream<R> {
+    pub fn new(reader: R) -> Self {
+        Self::new_with_options(reader, ParserOptions::default())
+    }
+
+    pub fn new_with_options(reader: R, options: ParserOptions) -> Self {
+        StatementStream {
+            reader,
+            options,
+            splitter: Splitter::default(),
+            start: Location::default(),
+            number: 1,
+            done: false,
+        }
+    }
+
+    /// Reads up to and including the next statement-ending semicolon, returning whether
+    /// one was found before the end of the input
+    fn read_statement(&mut self) -> io::Result<bool> {
+        loop {
+            let buffer = match self.reader.fill_buf() {
+                Ok(buffer) => buffer,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            };
+            if buffer.is_empty() {
+                return Ok(false);
+            }
+            let used = buffer
+                .iter()
+                .position(|&byte| self.splitter.scan(byte))
+                .map(|i| i + 1);
+            match used {
+                Some(used) => {
+                    self.reader.consume(used);
+                    return Ok(true);
+                }
+                None => {
+                    let used = buffer.len();
+                    self.reader.consume(used);
+                }
+            }
+        }
+    }
+
+    /// Parses the statement read last, returning `None` if it was empty
+    fn parse(&mut self, text: Vec<u8>) -> Option<Result<Statement, ParseError>> {
+        let start = std::mem::replace(&mut self.start, self.splitter.location);
+        let result = parse_piece(text, &self.options, self.number, start)?;
+        self.number += 1;
+        Some(result)
+    }
+}
+
+impl<R: BufRead> Iterator for StatementStream<R> {
+    type Item = Result<Statement, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            match self.read_statement() {
+                Ok(ended) => self.done = !ended,
+                Err(e) => {
+                    self.done = true;
+                    let location = self.splitter.location;
+                    return Some(Err(ParseError::new(Problem::InvalidInput {
+                        message: format!("failed to read input: {}", e),
+                    })
+                    .with_span(Span {
+                        start: location,
+                        end: location,
+                    })));
+                }
+            }
+            let text = std::mem::take(&mut self.splitter.statement);
+            if let Some(result) = self.parse(text) {
+                return Some(result);
+            }
+        }
+        None
+    }
+}
+
+/// Where the splitter is in the SQL, carried from one read to the next
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+enum State {
+    #[default]
+    Code,
+    /// Inside text quoted with the given character; a doubled quote closes and reopens it
+    Quoted(u8),
+    LineComment,
+    BlockComment,
+}
+
+/// Parses `text`, the `number`th statement of a script as read by a `Splitter`, with error
+/// spans moved to follow `origin`, where it starts. Returns `None` if it holds no statement.
+pub(crate) fn parse_piece(
+    text: Vec<u8>,
+    options: &ParserOptions,
+    number: usize,
+    origin: Location,
+) -> Option<Result<Statement, ParseError>> {
+    match String::from_utf8(text) {
+        Ok(text) => PrattParser::parse_script_piece(&text, options.clone(), number)
+            .map_err(|e| e.offset_by(origin))
+            .transpose(),
+        Err(_) => Some(Err(ParseError::new(Problem::InvalidInput {
+            message: format!("statement {} is not valid UTF-8", number),
+        })
+        .with_span(Span {
+            start: origin,
+            end: origin,
+        }))),
+    }
+}
+
+/// Finds where statements end, one byte at a time. Statements always end in plain code,
+/// so a new splitter can start at any statement's first byte.
+#[derive(Default)]
+pub(crate) struct Splitter {
+    /// The statement read so far, with comments blanked out
+    pub(crate) statement: Vec<u8>,
+    state: State,
+    /// The byte before the current one, to recognize `--`, `/*` and `*/`; cleared where
+    /// it cannot start one of them
+    previous: u8,
+    /// Where the next byte is in the input
+    location: Location,
+}
+
+impl Splitter {
+    /// Adds `byte` to the statement, returning whether it is the semicolon ending it
+    pub(crate) fn scan(&mut self, byte: u8) -> bool {
+        self.location.offset += 1;
+        if byte == b'\n' {
+            self.location.line += 1;
+            self.location.column = 1;
+        } else if byte & 0xC0 != 0x80 {
+            // Continuation bytes of a UTF-8 character do not start a new column
+            self.location.column += 1;
+        }
+
+        let previous = std::mem::replace(&mut self.previous, byte);
+        let in_comment = matches!(self.state, State::LineComment | State::BlockComment);
+        match self.state {
+            State::Code => match byte {
+                b';' => {
+                    self.statement.push(byte);
+                    self.previous = 0;
+                    return true;
+                }
+                b'\'' | b'"' => self.state = State::Quoted(byte),
+                b'-' if previous == b'-' => self.start_comment(State::LineComment),
+                b'*' if previous == b'/' => self.start_comment(State::BlockComment),
+                _ => {}
+            },
+            State::Quoted(quote) if byte == quote => self.state = State::Code,
+            State::LineComment if byte == b'\n' => self.state = State::Code,
+            State::BlockComment if previous == b'*' && byte == b'/' => {
+                self.state = State::Code;
+                self.previous = 0;
+            }
+            _ => {}
+        }
+
+        let blank = in_comment || matches!(self.state, State::LineComment | State::BlockComment);
+        self.statement.push(match byte {
+            b'\n' => b'\n',
+            _ if blank => b' ',
+            _ => byte,
+        });
+        false
+    }
+
+    /// Enters a comment whose first character has already been added to the statement
+    fn start_comment(&mut self, state: State) {
+        self.state = state;
+        self.previous = 0;
+        if let Some(last) = self.statement.last_mut() {
+            *last = b' ';
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use rust_sql_parser::ast::{Ident, SelectItem, SetExpr, Statement, TableReference};
+use rust_sql_parser::parse_statement;
+
+fn quoted(value: &str) -> Ident {
+    Ident {
+        value: value.into(),
+        quote_style: Some('"'),
+    }
+}
+
+#[test]
+fn quoted_aliases_keep_their_quotes() {
+    let sql = "SELECT a AS \"Foo\", b AS bar FROM t AS \"T\" (\"X\", y)";
+    let statement = parse_statement(sql).unwrap();
+    assert_eq!(statement.to_string(), sql);
+
+    let Statement::Query(query) = statement else {
+        panic!("not a query");
+    };
+    let SetExpr::Select(select) = &query.body else {
+        panic!("not a select");
+    };
+    assert!(matches!(
+        &select.projection[0],
+        SelectItem::ExprWithAlias { alias, .. } if *alias == quoted("Foo")
+    ));
+    assert!(matches!(
+        &select.projection[1],
+        SelectItem::ExprWithAlias { alias, .. } if *alias == Ident::new("bar")
+    ));
+    let Some(TableReference::Table {
+        alias: Some(alias), ..
+    }) = select.from.as_ref().map(|from| &from.relation)
+    else {
+        panic!("no table alias");
+    };
+    assert_eq!(alias.name, quoted("T"));
+    assert_eq!(alias.columns, [quoted("X"), Ident::new("y")]);
+}
+
+#[test]
+fn aliases_that_are_not_names_are_rejected() {
+    for (sql, column) in [("SELECT a AS 1 FROM t", 13), ("SELECT a FROM t AS 1", 20)] {
+        let e = parse_statement(sql).unwrap_err();
+        assert_eq!(e.message(), "alias after AS, found 1", "{}", sql);
+        assert_eq!(e.span().unwrap().start.column, column, "{}", sql);
+    }
+    assert_eq!(
+        parse_statement("SELECT a AS \"\" FROM t")
+            .unwrap_err()
+            .message(),
+        "alias after AS (quoted identifiers cannot be empty), found \"\""
+    );
+}
@@ -0,0 +1,142 @@
+use rust_sql_parser::ast::{
+    AlterTableOperation, ColumnOption, DataType, Expression, Ident, Keyword, ObjectName, Statement,
+    Value,
+};
+use rust_sql_parser::error::Problem;
+use rust_sql_parser::parse_statement;
+
+/// Returns the operation of an ALTER TABLE statement on `users`
+fn operation(sql: &str) -> AlterTableOperation {
+    match parse_statement(sql).unwrap_or_else(|e| panic!("{}: {}", sql, e)) {
+        Statement::AlterTable {
+            name,
+            if_exists: false,
+            operation,
+        } => {
+            assert_eq!(name, ObjectName(vec![Ident::new("users")]));
+            operation
+        }
+        statement => panic!("not an ALTER TABLE: {}", statement),
+    }
+}
+
+#[test]
+fn add_column() {
+    for sql in [
+        "ALTER TABLE users ADD COLUMN age INT NOT NULL DEFAULT 0",
+        "ALTER TABLE users ADD age INT NOT NULL DEFAULT 0",
+    ] {
+        match operation(sql) {
+            AlterTableOperation::AddColumn {
+                if_not_exists: false,
+                column_def,
+            } => {
+                assert_eq!(&*column_def.name, "age");
+                assert_eq!(column_def.data_type, DataType::Int);
+                assert_eq!(
+                    column_def.options,
+                    [
+                        ColumnOption::NotNull,
+                        ColumnOption::Default(Expression::Value(Value::Number(0))),
+                    ]
+                );
+            }
+            operation => panic!("{}: {:?}", sql, operation),
+        }
+    }
+    assert!(matches!(
+        operation("ALTER TABLE users ADD COLUMN IF NOT EXISTS age INT"),
+        AlterTableOperation::AddColumn {
+            if_not_exists: true,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn drop_column() {
+    for (sql, if_exists, cascade) in [
+        ("ALTER TABLE users DROP COLUMN legacy_flag", false, false),
+        ("ALTER TABLE users DROP legacy_flag CASCADE", false, true),
+        (
+            "ALTER TABLE users DROP COLUMN IF EXISTS legacy_flag",
+            true,
+            false,
+        ),
+    ] {
+        assert_eq!(
+            operation(sql),
+            AlterTableOperation::DropColumn {
+                if_exists,
+                column_name: "legacy_flag".into(),
+                cascade,
+            },
+            "{}",
+            sql
+        );
+    }
+}
+
+#[test]
+fn rename_column_and_table() {
+    for sql in [
+        "ALTER TABLE users RENAME COLUMN name TO full_name",
+        "ALTER TABLE users RENAME name TO full_name",
+    ] {
+        assert_eq!(
+            operation(sql),
+            AlterTableOperation::RenameColumn {
+                old_name: "name".into(),
+                new_name: "full_name".into(),
+            },
+            "{}",
+            sql
+        );
+    }
+    assert_eq!(
+        operation("ALTER TABLE users RENAME TO people"),
+        AlterTableOperation::RenameTable {
+            new_name: "people".into(),
+        }
+    );
+    assert!(matches!(
+        parse_statement("ALTER TABLE IF EXISTS users RENAME TO people").unwrap(),
+        Statement::AlterTable {
+            if_exists: true,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn unknown_operations_list_the_supported_ones() {
+    let e = parse_statement("ALTER TABLE users MODIFY a INT").unwrap_err();
+    match &e.problem {
+        Problem::ExpectedKeyword { keywords, .. } => {
+            assert_eq!(keywords, &[Keyword::Add, Keyword::Drop, Keyword::Rename])
+        }
+        problem => panic!("{:?}", problem),
+    }
+    assert!(e
+        .message()
+        .starts_with("ADD, DROP or RENAME after ALTER TABLE users, found MODIFY"));
+    assert_eq!(e.span().unwrap().start.column, 19);
+}
+
+#[test]
+fn incomplete_operations_are_errors() {
+    for (sql, message) in [
+        (
+            "ALTER TABLE users ADD COLUMN",
+            "expected column name after ADD COLUMN",
+        ),
+        (
+            "ALTER TABLE users RENAME COLUMN a",
+            "TO after RENAME COLUMN a, found end of input",
+        ),
+        ("ALTER users", "TABLE after ALTER, found users"),
+    ] {
+        let e = parse_statement(sql).unwrap_err();
+        assert_eq!(e.message(), message, "{}", sql);
+    }
+}
@@ -0,0 +1,96 @@
+use rust_sql_parser::analysis::ColumnRef;
+use rust_sql_parser::parse_statement;
+
+fn tables(sql: &str) -> Vec<String> {
+    parse_statement(sql)
+        .unwrap()
+        .referenced_tables()
+        .iter()
+        .map(|name| name.to_string())
+        .collect()
+}
+
+fn column(table: Option<&str>, column: &str) -> ColumnRef {
+    ColumnRef {
+        table: table.map(String::from),
+        column: column.into(),
+    }
+}
+
+#[test]
+fn cte_shadowing_a_real_table_is_not_reported() {
+    let sql = "WITH orders AS (SELECT id FROM orders WHERE open = TRUE) \
+               SELECT o.id FROM orders AS o JOIN customers AS c ON o.id = c.order_id";
+    // The CTE reads the real table, which its own name does not yet shadow
+    assert_eq!(tables(sql), ["orders", "customers"]);
+
+    let sql = "WITH orders AS (SELECT id FROM archive) SELECT id FROM orders";
+    assert_eq!(tables(sql), ["archive"]);
+}
+
+#[test]
+fn cte_names_do_not_leak_out_of_their_query() {
+    let sql = "SELECT a FROM (WITH x AS (SELECT a FROM t) SELECT a FROM x) AS s JOIN x ON TRUE";
+    assert_eq!(tables(sql), ["t", "x"]);
+}
+
+#[test]
+fn quoted_names_only_match_ctes_exactly() {
+    let sql = "WITH x AS (SELECT 1) SELECT a FROM \"X\", X";
+    assert_eq!(tables(sql), ["\"X\""]);
+}
+
+#[test]
+fn subqueries_joins_and_dml_targets() {
+    assert_eq!(
+        tables("SELECT a FROM t WHERE a > (SELECT MAX(b) FROM u) UNION SELECT c FROM v"),
+        ["t", "u", "v"]
+    );
+    assert_eq!(
+        tables("SELECT * FROM a LEFT JOIN b ON a.id = b.id CROSS JOIN c"),
+        ["a", "b", "c"]
+    );
+    assert_eq!(tables("INSERT INTO t SELECT a FROM u"), ["t", "u"]);
+    assert_eq!(tables("UPDATE t SET a = 1 WHERE b = 2"), ["t"]);
+    assert_eq!(tables("DELETE FROM s.t WHERE a = 1"), ["s.t"]);
+}
+
+#[test]
+fn tables_are_deduplicated_in_order_of_first_appearance() {
+    assert_eq!(
+        tables("SELECT * FROM b JOIN a ON TRUE JOIN b AS b2 ON TRUE"),
+        ["b", "a"]
+    );
+}
+
+#[test]
+fn columns_are_deduplicated_and_qualified_as_written() {
+    let statement = parse_statement("SELECT a, t.b, a FROM t WHERE t.b > 1 AND s.t.c = 2").unwrap();
+    assert_eq!(
+        statement.referenced_columns(),
+        [
+            column(None, "a"),
+            column(Some("t"), "b"),
+            column(Some("s.t"), "c")
+        ]
+    );
+}
+
+#[test]
+fn dml_columns() {
+    let statement = parse_statement("INSERT INTO t (a, b) VALUES (1, c)").unwrap();
+    assert_eq!(
+        statement.referenced_columns(),
+        [
+            column(Some("t"), "a"),
+            column(Some("t"), "b"),
+            column(None, "c")
+        ]
+    );
+
+    let statement = parse_statement("UPDATE t SET a = b WHERE c = 1").unwrap();
+    assert_eq!(
+        statement.referenced_columns(),
+        [column(None, "a"), column(None, "b"), column(None, "c")]
+    );
+}
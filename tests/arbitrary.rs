@@ -0,0 +1,49 @@
+#![cfg(feature = "arbitrary")]
+
+use arbitrary::{Arbitrary, Unstructured};
+use rust_sql_parser::ast::{Expression, Statement};
+use rust_sql_parser::{parse_expression, parse_statement};
+
+const CASES: u64 = 2_000;
+
+/// Returns `len` bytes from a xorshift generator, so that a failure reproduces from its seed
+fn bytes(seed: u64, len: usize) -> Vec<u8> {
+    let mut state = seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1;
+    (0..len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as u8
+        })
+        .collect()
+}
+
+/// Generates a tree of type `T` from each seed and checks that printing it and parsing the
+/// text back gives the same tree
+fn round_trips<T>(parse: impl Fn(&str) -> Result<T, rust_sql_parser::error::ParseError>)
+where
+    T: for<'a> Arbitrary<'a> + PartialEq + std::fmt::Debug + std::fmt::Display,
+{
+    for seed in 1..=CASES {
+        let data = bytes(seed, 64 + (seed as usize % 512));
+        let Ok(tree) = T::arbitrary(&mut Unstructured::new(&data)) else {
+            continue;
+        };
+        let sql = tree.to_string();
+        match parse(&sql) {
+            Ok(parsed) => assert_eq!(parsed, tree, "seed {}: {}", seed, sql),
+            Err(e) => panic!("seed {}: {} does not parse: {}", seed, sql, e),
+        }
+    }
+}
+
+#[test]
+fn generated_expressions_round_trip() {
+    round_trips::<Expression>(parse_expression);
+}
+
+#[test]
+fn generated_statements_round_trip() {
+    round_trips::<Statement>(parse_statement);
+}
@@ -0,0 +1,76 @@
+use rust_sql_parser::ast::{DataType, Statement};
+use rust_sql_parser::builder::{col, func, lit, null, SelectBuilder};
+use rust_sql_parser::{parse_expression, parse_statement};
+
+fn parsed(sql: &str) -> Statement {
+    parse_statement(sql).unwrap_or_else(|e| panic!("{}: {}", sql, e))
+}
+
+#[test]
+fn expressions_match_the_parser() {
+    for (built, sql) in [
+        (col("a").eq(lit(1)), "a = 1"),
+        (col("t.a").not_eq(lit("x")), "t.a <> 'x'"),
+        (
+            col("a").lt(lit(1)).or(col("b").gt_eq(lit(2.5))),
+            "a < 1 OR b >= 2.5",
+        ),
+        (
+            col("a").lt_eq(lit(1)).and(col("b").gt(lit(true))),
+            "a <= 1 AND b > TRUE",
+        ),
+        ((col("a") + lit(1)) * -col("b"), "(a + 1) * -b"),
+        (col("a") - (col("b") - col("c")), "a - (b - c)"),
+        (col("a") / lit(2) - lit(1), "a / 2 - 1"),
+        (!col("a").eq(null()), "NOT (a = NULL)"),
+        (col("a").concat(lit("b")), "a || 'b'"),
+        (col("a").cast(DataType::Varchar(Some(10))), "a::VARCHAR(10)"),
+        (func("coalesce", vec![col("a"), lit(0)]), "coalesce(a, 0)"),
+        (col("s.t.a"), "s.t.a"),
+    ] {
+        assert_eq!(built, parse_expression(sql).unwrap(), "{}", sql);
+        assert_eq!(built.to_string(), sql);
+    }
+}
+
+#[test]
+fn queries_match_the_parser() {
+    let built = SelectBuilder::new()
+        .column(col("a"))
+        .from("t")
+        .build_statement();
+    assert_eq!(built, parsed("SELECT a FROM t"));
+
+    let built = SelectBuilder::new()
+        .distinct()
+        .column(col("u.name"))
+        .column_as(func("count", vec![col("o.id")]), "orders")
+        .from("users")
+        .join("orders", col("o.user_id").eq(col("u.id")))
+        .left_join("s.notes", col("n.user_id").eq(col("u.id")))
+        .filter(col("u.active").eq(lit(true)))
+        .filter(col("o.total").gt(lit(100)))
+        .group_by(col("u.name"))
+        .having(func("count", vec![col("o.id")]).gt(lit(1)))
+        .order_by_desc(col("orders"))
+        .order_by(col("u.name"))
+        .limit(10)
+        .offset(20)
+        .build_statement();
+    let sql = "SELECT DISTINCT u.name, count(o.id) AS orders FROM users \
+               JOIN orders ON o.user_id = u.id LEFT JOIN s.notes ON n.user_id = u.id \
+               WHERE u.active = TRUE AND o.total > 100 GROUP BY u.name \
+               HAVING count(o.id) > 1 ORDER BY orders DESC, u.name LIMIT 10 OFFSET 20";
+    assert_eq!(built, parsed(sql));
+    assert_eq!(built.to_string(), sql);
+
+    let built = SelectBuilder::new()
+        .wildcard()
+        .from("t")
+        .order_by_asc(col("a"))
+        .build();
+    assert_eq!(
+        Statement::Query(Box::new(built)),
+        parsed("SELECT * FROM t ORDER BY a ASC")
+    );
+}
@@ -0,0 +1,152 @@
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+/// Runs `sqlparse` with `args`, writing `stdin` to it
+fn sqlparse(args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_sqlparse"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8(output.stderr.clone()).unwrap()
+}
+
+const SCRIPT: &str = "SELECT a FROM t;\nDELETE FROM u WHERE b = 1";
+
+#[test]
+fn arguments_files_and_stdin_give_the_same_output() {
+    let from_args = sqlparse(&["--tree", SCRIPT], "");
+    assert_eq!(from_args.status.code(), Some(0), "{}", stderr(&from_args));
+    assert_eq!(
+        stdout(&from_args),
+        "(query (select (items (ident a)) (from (table t))))\n\
+         (delete (table u) (where (= (ident b) 1)))\n"
+    );
+
+    let from_stdin = sqlparse(&["--tree"], SCRIPT);
+    assert_eq!(stdout(&from_stdin), stdout(&from_args));
+
+    let path = std::env::temp_dir().join(format!("sqlparse-{}.sql", std::process::id()));
+    std::fs::write(&path, SCRIPT).unwrap();
+    let from_file = sqlparse(&["--tree", "-f", path.to_str().unwrap()], "");
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(stdout(&from_file), stdout(&from_args));
+
+    // Words are joined, so SQL need not be quoted as one argument
+    let from_words = sqlparse(&["--tree", "SELECT", "a", "FROM", "t"], "");
+    assert_eq!(
+        stdout(&from_words),
+        "(query (select (items (ident a)) (from (table t))))\n"
+    );
+}
+
+#[test]
+fn tokens_are_printed_with_their_spans() {
+    let output = sqlparse(&["--tokens", "SELECT 1+2"], "");
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        stdout(&output),
+        "1:1-1:7\tKeyword(Select)\n\
+         1:8-1:9\tNumber(1)\n\
+         1:9-1:10\tPlus\n\
+         1:10-1:11\tNumber(2)\n\
+         1:11-1:11\tEof\n"
+    );
+
+    let output = sqlparse(&["--tokens", "SELECT 'open"], "");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(
+        stderr(&output).starts_with("error: Unterminated string"),
+        "{}",
+        stderr(&output)
+    );
+}
+
+#[test]
+fn statements_are_debug_printed_by_default() {
+    let output = sqlparse(&["SELECT 1"], "");
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(output.stdout, sqlparse(&["--ast", "SELECT 1"], "").stdout);
+    assert!(stdout(&output).starts_with("Query("), "{}", stdout(&output));
+}
+
+#[test]
+fn parse_failures_print_a_diagnostic_and_exit_1() {
+    let output = sqlparse(&["--tree"], "SELECT 1;\nDELETE t");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout(&output), "");
+    assert_eq!(
+        stderr(&output),
+        "error: Expected keyword: FROM after DELETE, found t, while parsing DELETE in \
+         statement 2\n --> line 2, column 8\n  |\n2 | DELETE t\n  |        ^\n"
+    );
+}
+
+#[test]
+fn check_reports_each_statement() {
+    let output = sqlparse(&["--check", "SELECT 1; DELETE t; SELECT 2"], "");
+    assert_eq!(output.status.code(), Some(1));
+    let out = stdout(&output);
+    let lines: Vec<&str> = out.lines().collect();
+    assert_eq!(lines[..2], ["ok: SELECT 1", "ok: SELECT 2"]);
+    assert!(
+        lines[2].starts_with("error: Expected keyword: FROM"),
+        "{}",
+        out
+    );
+    assert_eq!(lines.last(), Some(&"2 statement(s) ok, 1 error(s)"));
+
+    let output = sqlparse(&["--check", "SELECT 1; SELECT 2"], "");
+    assert_eq!(output.status.code(), Some(0));
+    assert!(stdout(&output).ends_with("2 statement(s) ok, 0 error(s)\n"));
+}
+
+#[test]
+fn bad_usage_exits_2() {
+    for args in [&["--bogus"][..], &["-f"], &["-f", "script.sql", "SELECT 1"]] {
+        let output = sqlparse(args, "");
+        assert_eq!(output.status.code(), Some(2), "{:?}", args);
+        assert!(stderr(&output).starts_with("usage: sqlparse"), "{:?}", args);
+    }
+
+    let output = sqlparse(&["-f", "/nonexistent/script.sql"], "");
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).starts_with("sqlparse: cannot read /nonexistent/script.sql"));
+
+    let output = sqlparse(&["--help"], "");
+    assert_eq!(output.status.code(), Some(0));
+    assert!(stdout(&output).starts_with("usage: sqlparse"));
+}
+
+#[cfg(all(feature = "serde", feature = "json"))]
+#[test]
+fn json_prints_the_statements() {
+    let output = sqlparse(&["--json", "SELECT a FROM t"], "");
+    assert_eq!(output.status.code(), Some(0));
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(json[0].get("Query").is_some(), "{}", json);
+}
+
+#[cfg(not(all(feature = "serde", feature = "json")))]
+#[test]
+fn json_needs_its_features() {
+    let output = sqlparse(&["--json", "SELECT a FROM t"], "");
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).contains("needs the serde and json features"));
+}
@@ -0,0 +1,285 @@
+#![cfg(feature = "sqlparser-compat")]
+
+use rust_sql_parser::error::ConversionError;
+use rust_sql_parser::{parse_expression, parse_statement};
+use sqlparser::ast::{
+    BinaryOperator, CastKind, DataType, Expr, Function, FunctionArg, FunctionArgExpr,
+    FunctionArgumentList, FunctionArguments, GroupByExpr, Ident, Join, JoinConstraint,
+    JoinOperator, ObjectName, Query, Select, SelectItem, SetExpr, Statement, TableAlias,
+    TableFactor, TableWithJoins, UnaryOperator, Value,
+};
+use sqlparser::dialect::GenericDialect;
+use sqlparser::parser::Parser;
+
+fn expr(sql: &str) -> Expr {
+    Expr::try_from(parse_expression(sql).unwrap()).unwrap()
+}
+
+fn id(name: &str) -> Box<Expr> {
+    Box::new(Expr::Identifier(Ident::new(name)))
+}
+
+fn number(n: &str) -> Box<Expr> {
+    Box::new(Expr::Value(Value::Number(n.into(), false)))
+}
+
+fn table(name: &str, alias: Option<&str>) -> TableFactor {
+    TableFactor::Table {
+        name: ObjectName(vec![Ident::new(name)]),
+        alias: alias.map(|alias| TableAlias {
+            name: Ident::new(alias),
+            columns: vec![],
+        }),
+        args: None,
+        with_hints: vec![],
+        version: None,
+        with_ordinality: false,
+        partitions: vec![],
+    }
+}
+
+fn select(projection: Vec<SelectItem>, from: Vec<TableWithJoins>) -> Select {
+    Select {
+        distinct: None,
+        top: None,
+        top_before_distinct: false,
+        projection,
+        into: None,
+        from,
+        lateral_views: vec![],
+        prewhere: None,
+        selection: None,
+        group_by: GroupByExpr::Expressions(vec![], vec![]),
+        cluster_by: vec![],
+        distribute_by: vec![],
+        sort_by: vec![],
+        having: None,
+        named_window: vec![],
+        qualify: None,
+        window_before_qualify: false,
+        value_table_mode: None,
+        connect_by: None,
+    }
+}
+
+fn query(body: SetExpr) -> Query {
+    Query {
+        with: None,
+        body: Box::new(body),
+        order_by: None,
+        limit: None,
+        limit_by: vec![],
+        offset: None,
+        fetch: None,
+        locks: vec![],
+        for_clause: None,
+        settings: None,
+        format_clause: None,
+    }
+}
+
+#[test]
+fn operators_keep_their_grouping() {
+    assert_eq!(
+        expr("(a + 1) * -b"),
+        Expr::BinaryOp {
+            left: Box::new(Expr::Nested(Box::new(Expr::BinaryOp {
+                left: id("a"),
+                op: BinaryOperator::Plus,
+                right: number("1"),
+            }))),
+            op: BinaryOperator::Multiply,
+            right: Box::new(Expr::UnaryOp {
+                op: UnaryOperator::Minus,
+                expr: id("b"),
+            }),
+        }
+    );
+    // Redundant parentheses are not kept by the parser, so none come back
+    assert_eq!(expr("((a))"), *id("a"));
+    assert_eq!(
+        expr("a - (b - c)"),
+        Expr::BinaryOp {
+            left: id("a"),
+            op: BinaryOperator::Minus,
+            right: Box::new(Expr::Nested(Box::new(Expr::BinaryOp {
+                left: id("b"),
+                op: BinaryOperator::Minus,
+                right: id("c"),
+            }))),
+        }
+    );
+}
+
+#[test]
+fn predicates_casts_and_functions() {
+    assert_eq!(
+        expr("x NOT IN (1, 2)"),
+        Expr::InList {
+            expr: id("x"),
+            list: vec![*number("1"), *number("2")],
+            negated: true,
+        }
+    );
+    assert_eq!(expr("x IS NOT NULL"), Expr::IsNotNull(id("x")));
+    assert_eq!(
+        expr("name LIKE 'a%'"),
+        Expr::Like {
+            negated: false,
+            any: false,
+            expr: id("name"),
+            pattern: Box::new(Expr::Value(Value::SingleQuotedString("a%".into()))),
+            escape_char: None,
+        }
+    );
+    assert_eq!(
+        expr("a::VARCHAR(10)"),
+        Expr::Cast {
+            kind: CastKind::DoubleColon,
+            expr: id("a"),
+            data_type: DataType::Varchar(Some(sqlparser::ast::CharacterLength::IntegerLength {
+                length: 10,
+                unit: None
+            })),
+            format: None,
+        }
+    );
+    assert_eq!(
+        expr("count(*)"),
+        Expr::Function(Function {
+            name: ObjectName(vec![Ident::new("count")]),
+            parameters: FunctionArguments::None,
+            args: FunctionArguments::List(FunctionArgumentList {
+                duplicate_treatment: None,
+                args: vec![FunctionArg::Unnamed(FunctionArgExpr::Wildcard)],
+                clauses: vec![],
+            }),
+            filter: None,
+            null_treatment: None,
+            over: None,
+            within_group: vec![],
+        })
+    );
+    assert_eq!(
+        expr("\"Mixed\".b = $1"),
+        Expr::BinaryOp {
+            left: Box::new(Expr::CompoundIdentifier(vec![
+                Ident::with_quote('"', "Mixed"),
+                Ident::new("b"),
+            ])),
+            op: BinaryOperator::Eq,
+            right: Box::new(Expr::Value(Value::Placeholder("$1".into()))),
+        }
+    );
+}
+
+#[test]
+fn selects_and_table_references() {
+    let statement = parse_statement("SELECT a AS x, t.* FROM t AS u JOIN v USING (id), w").unwrap();
+    let mut expected = select(
+        vec![
+            SelectItem::ExprWithAlias {
+                expr: *id("a"),
+                alias: Ident::new("x"),
+            },
+            SelectItem::QualifiedWildcard(ObjectName(vec![Ident::new("t")]), Default::default()),
+        ],
+        vec![
+            TableWithJoins {
+                relation: table("t", Some("u")),
+                joins: vec![Join {
+                    relation: table("v", None),
+                    global: false,
+                    join_operator: JoinOperator::Inner(JoinConstraint::Using(vec![Ident::new(
+                        "id",
+                    )])),
+                }],
+            },
+            TableWithJoins {
+                relation: table("w", None),
+                joins: vec![],
+            },
+        ],
+    );
+    assert_eq!(
+        Statement::try_from(&statement).unwrap(),
+        Statement::Query(Box::new(query(SetExpr::Select(Box::new(expected.clone())))))
+    );
+
+    let statement = parse_statement("SELECT * FROM (SELECT 1) AS d").unwrap();
+    expected = select(
+        vec![SelectItem::Wildcard(Default::default())],
+        vec![TableWithJoins {
+            relation: TableFactor::Derived {
+                lateral: false,
+                subquery: Box::new(query(SetExpr::Select(Box::new(select(
+                    vec![SelectItem::UnnamedExpr(*number("1"))],
+                    vec![],
+                ))))),
+                alias: Some(TableAlias {
+                    name: Ident::new("d"),
+                    columns: vec![],
+                }),
+            },
+            joins: vec![],
+        }],
+    );
+    assert_eq!(
+        Query::try_from(&statement_query(&statement)).unwrap(),
+        query(SetExpr::Select(Box::new(expected)))
+    );
+}
+
+fn statement_query(statement: &rust_sql_parser::ast::Statement) -> rust_sql_parser::ast::Query {
+    match statement {
+        rust_sql_parser::ast::Statement::Query(query) => (**query).clone(),
+        _ => panic!("not a query: {}", statement),
+    }
+}
+
+/// The conversion agrees with `sqlparser`'s own reading of the printed statement
+#[test]
+fn agrees_with_sqlparser() {
+    for sql in [
+        "SELECT DISTINCT a, b + 1 AS c FROM t WHERE a > 1 AND NOT b GROUP BY a HAVING COUNT(*) > 1 \
+         ORDER BY a DESC NULLS LAST LIMIT 10 OFFSET 5",
+        "WITH x (a) AS (SELECT 1) SELECT a FROM x",
+        "SELECT a FROM t UNION ALL (SELECT b FROM u INTERSECT SELECT c FROM v) EXCEPT SELECT d FROM w",
+        "SELECT a FROM t LEFT JOIN u ON t.id = u.id CROSS JOIN v NATURAL JOIN w",
+        "SELECT a FROM generate_series(1, 10) AS g (a)",
+        "SELECT (SELECT MAX(b) FROM u), a::INT, 'x' || a FROM t FOR UPDATE OF t NOWAIT",
+        "SELECT a FROM t ORDER BY a OFFSET 2 ROWS FETCH FIRST 3 ROWS WITH TIES",
+        "VALUES (1, 'a'), (2, NULL)",
+        "INSERT INTO t (a, b) VALUES (1, 2) ON CONFLICT (a) DO UPDATE SET b = 3 RETURNING a",
+        "INSERT INTO t SELECT a FROM u",
+        "UPDATE t SET a = a + 1 FROM u WHERE t.id = u.id",
+        "DELETE FROM t USING u WHERE t.id = u.id RETURNING *",
+    ] {
+        let statement = parse_statement(sql).unwrap();
+        let printed = statement.to_string();
+        let expected = Parser::parse_sql(&GenericDialect {}, &printed)
+            .unwrap()
+            .remove(0);
+        assert_eq!(Statement::try_from(&statement).unwrap(), expected, "{}", sql);
+    }
+}
+
+#[test]
+fn unsupported_constructs_are_errors() {
+    for sql in [
+        "CREATE TABLE t (a INT)",
+        "SELECT a FROM t FOR KEY SHARE",
+        "SELECT a FROM t FOR UPDATE OF t, u",
+    ] {
+        let statement = parse_statement(sql).unwrap();
+        assert!(
+            matches!(
+                Statement::try_from(&statement),
+                Err(ConversionError::Unsupported(_))
+            ),
+            "{}",
+            sql
+        );
+    }
+    assert!(Expr::try_from(rust_sql_parser::ast::Expression::Wildcard).is_err());
+}
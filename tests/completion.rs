@@ -0,0 +1,140 @@
+use rust_sql_parser::ast::{Keyword, Token};
+use rust_sql_parser::completion::{completions, CompletionResult};
+use rust_sql_parser::tokenize_with_spans;
+
+const CORPUS: &[&str] = &[
+    "SELECT name FROM users WHERE id = 1",
+    "SELECT u.name, COUNT(*) AS n FROM users u JOIN orders o ON o.user_id = u.id GROUP BY u.name HAVING COUNT(*) > 1 ORDER BY n DESC LIMIT 10",
+    "SELECT a FROM t UNION ALL SELECT b FROM u EXCEPT SELECT c FROM v",
+    "WITH recent AS (SELECT * FROM orders WHERE placed > 5) SELECT id FROM recent",
+    "SELECT a FROM t WHERE b IN (1, 2) AND c IS NOT NULL AND d NOT LIKE 'x%' OR NOT e",
+    "SELECT (SELECT MAX(x) FROM s), a::INT, 'it''s' || b FROM t LEFT JOIN s USING (id)",
+    "INSERT INTO t (a, b) VALUES (1, 'é'), (2, NULL) ON CONFLICT DO NOTHING",
+    "UPDATE t SET a = a + 1, b = $1 WHERE c < ?",
+    "DELETE FROM t WHERE a >= 3;",
+    "CREATE TABLE t (id INT PRIMARY KEY, name VARCHAR(20) NOT NULL DEFAULT 'x')",
+    "CREATE INDEX idx ON t (a)",
+    "ALTER TABLE t ADD COLUMN c INT",
+    "DROP VIEW IF EXISTS v CASCADE",
+    "SELECT 1; SELECT \"quoted name\" FROM \"t\"",
+];
+
+/// Whether `result` offers `token` as something that may be typed
+fn offers(result: &CompletionResult, token: &Token) -> bool {
+    match token {
+        Token::Keyword(keyword) => result.keywords.contains(keyword),
+        Token::Identifier(_) | Token::QuotedIdentifier(_) => result.identifier_expected,
+        Token::String(_)
+        | Token::Number(_)
+        | Token::Float(_)
+        | Token::Placeholder(_)
+        | Token::Eof => true,
+        symbol => result.punctuation.contains(&symbol.to_string()),
+    }
+}
+
+#[test]
+fn every_truncation_of_the_corpus() {
+    for sql in CORPUS {
+        for cursor in 0..=sql.len() {
+            let result = completions(sql, cursor);
+            assert!(result.start <= cursor, "{:?} at {}", sql, cursor);
+            let end = (result.start..=cursor)
+                .rev()
+                .find(|&i| sql.is_char_boundary(i))
+                .unwrap();
+            let word = &sql[result.start..end];
+            for keyword in &result.keywords {
+                let name = keyword.to_string();
+                assert!(
+                    name.to_lowercase().starts_with(&word.to_lowercase()),
+                    "{:?} at {}: {} does not complete {:?}",
+                    sql,
+                    cursor,
+                    name,
+                    word
+                );
+            }
+            if !word.is_empty() {
+                assert!(result.punctuation.is_empty(), "{:?} at {}", sql, cursor);
+            }
+        }
+
+        // The token actually written next is among the suggestions, both before it and at
+        // the end of a keyword
+        for (token, span) in tokenize_with_spans(sql).unwrap() {
+            let (start, end) = (span.start.offset, span.end.offset);
+            let result = completions(sql, start);
+            if result.start < start {
+                // Right after a word, as in `u.name`, that word is taken to be unfinished
+                continue;
+            }
+            assert!(
+                offers(&result, &token),
+                "{:?} at {}: {:?} not offered in {:?}",
+                sql,
+                start,
+                token,
+                result
+            );
+            if let Token::Keyword(keyword) = &token {
+                let result = completions(sql, end);
+                assert!(
+                    result.keywords.contains(keyword),
+                    "{:?} at {}: {:?}",
+                    sql,
+                    end,
+                    result
+                );
+                assert_eq!(result.start, start);
+            }
+        }
+    }
+}
+
+#[test]
+fn after_a_table_reference() {
+    let sql = "SELECT name FROM users ";
+    let result = completions(sql, sql.len());
+    for keyword in [
+        Keyword::Union,
+        Keyword::Intersect,
+        Keyword::Except,
+        Keyword::Where,
+        Keyword::Join,
+    ] {
+        assert!(result.keywords.contains(&keyword), "{:?} missing", keyword);
+    }
+    // An alias may follow
+    assert!(result.identifier_expected);
+
+    let sql = "SELECT name FROM users u ";
+    let result = completions(sql, sql.len());
+    assert!(result.keywords.contains(&Keyword::Union));
+    assert!(!result.identifier_expected);
+}
+
+#[test]
+fn after_an_operator() {
+    let sql = "SELECT a FROM t WHERE a = ";
+    let result = completions(sql, sql.len());
+    assert!(result.identifier_expected);
+    assert!(result.keywords.contains(&Keyword::Not));
+    assert!(!result.keywords.contains(&Keyword::Union));
+}
+
+#[test]
+fn partial_keywords() {
+    let sql = "SELECT a FROM t WH";
+    let result = completions(sql, sql.len());
+    assert_eq!(result.keywords, [Keyword::Where]);
+    assert_eq!(result.start, 16);
+}
+
+#[test]
+fn inside_a_string_nothing_is_offered() {
+    let sql = "SELECT 'abc";
+    let result = completions(sql, sql.len());
+    assert!(result.keywords.is_empty() && result.punctuation.is_empty());
+    assert!(!result.identifier_expected);
+}
@@ -0,0 +1,327 @@
+use rust_sql_parser::ast::{
+    ColumnDef, ColumnOption, DataType, Expression, Ident, ObjectName, ReferentialAction, Statement,
+    TableConstraint, Value,
+};
+use rust_sql_parser::parse_statement;
+
+/// Returns the column definitions of a CREATE TABLE statement
+fn columns(sql: &str) -> Vec<ColumnDef> {
+    match parse_statement(sql).unwrap_or_else(|e| panic!("{}: {}", sql, e)) {
+        Statement::CreateTable { columns, .. } => columns,
+        statement => panic!("not a CREATE TABLE: {}", statement),
+    }
+}
+
+/// Returns the message and column of the error parsing `sql`
+fn error(sql: &str) -> (String, usize) {
+    let e = parse_statement(sql).unwrap_err();
+    (
+        e.message(),
+        e.span().expect("errors are located").start.column,
+    )
+}
+
+#[test]
+fn columns_and_types() {
+    let sql = "CREATE TABLE users (id INT, name VARCHAR(255), created TIMESTAMP)";
+    match parse_statement(sql).unwrap() {
+        Statement::CreateTable {
+            temporary: false,
+            if_not_exists: false,
+            name,
+            columns,
+            constraints,
+            query: None,
+        } => {
+            assert_eq!(name, ObjectName(vec![Ident::new("users")]));
+            assert!(constraints.is_empty());
+            let columns: Vec<(&str, &DataType)> = columns
+                .iter()
+                .map(|column| (&*column.name, &column.data_type))
+                .collect();
+            assert_eq!(
+                columns,
+                [
+                    ("id", &DataType::Int),
+                    ("name", &DataType::Varchar(Some(255))),
+                    ("created", &DataType::Timestamp),
+                ]
+            );
+        }
+        statement => panic!("{}", statement),
+    }
+}
+
+#[test]
+fn every_data_type() {
+    let columns = columns(
+        "CREATE TABLE t (a INT, b BIGINT, c SMALLINT, d VARCHAR(10), e CHAR(3), f TEXT, \
+         g BOOLEAN, h FLOAT, i DOUBLE, j DECIMAL(10, 2), k DATE, l TIMESTAMP)",
+    );
+    let types: Vec<DataType> = columns.into_iter().map(|column| column.data_type).collect();
+    assert_eq!(
+        types,
+        [
+            DataType::Int,
+            DataType::BigInt,
+            DataType::SmallInt,
+            DataType::Varchar(Some(10)),
+            DataType::Char(Some(3)),
+            DataType::Text,
+            DataType::Boolean,
+            DataType::Float,
+            DataType::Double,
+            DataType::Decimal(Some(10), Some(2)),
+            DataType::Date,
+            DataType::Timestamp,
+        ]
+    );
+}
+
+#[test]
+fn a_missing_column_list_is_an_error() {
+    assert_eq!(
+        error("CREATE TABLE t"),
+        (
+            "expected '(' and a column list or AS query after CREATE TABLE t, \
+             expected one of: '.', '('"
+                .to_string(),
+            15
+        )
+    );
+    assert_eq!(
+        error("CREATE TABLE t ()"),
+        (
+            "identifier for a column definition after CREATE TABLE, found ')'".to_string(),
+            17
+        )
+    );
+}
+
+#[test]
+fn malformed_type_parameters_are_errors() {
+    for (sql, message, column) in [
+        (
+            "CREATE TABLE t (a VARCHAR())",
+            "length for VARCHAR, found ')'",
+            27,
+        ),
+        (
+            "CREATE TABLE t (a VARCHAR(x))",
+            "length for VARCHAR, found x",
+            27,
+        ),
+        (
+            "CREATE TABLE t (a DECIMAL(10,))",
+            "scale for DECIMAL, found ')'",
+            30,
+        ),
+    ] {
+        assert_eq!(error(sql), (message.to_string(), column), "{}", sql);
+    }
+}
+
+#[test]
+fn a_stray_comma_before_the_closing_parenthesis_is_an_error() {
+    assert_eq!(
+        error("CREATE TABLE t (a INT,)"),
+        (
+            "identifier for a column definition after ',' in CREATE TABLE, found ')'".to_string(),
+            23
+        )
+    );
+}
+
+#[test]
+fn column_options_in_any_order() {
+    let columns = columns(
+        "CREATE TABLE t (id INT PRIMARY KEY, name TEXT NOT NULL DEFAULT 'anon', \
+         email VARCHAR(100) UNIQUE NOT NULL, note TEXT DEFAULT '' NULL)",
+    );
+    let options: Vec<&[ColumnOption]> = columns.iter().map(|c| &c.options[..]).collect();
+    assert_eq!(
+        options,
+        [
+            &[ColumnOption::PrimaryKey][..],
+            &[
+                ColumnOption::NotNull,
+                ColumnOption::Default(Expression::Value(Value::SingleQuotedString("anon".into()))),
+            ],
+            &[ColumnOption::Unique, ColumnOption::NotNull],
+            &[
+                ColumnOption::Default(Expression::Value(Value::SingleQuotedString("".into()))),
+                ColumnOption::Null,
+            ],
+        ]
+    );
+}
+
+#[test]
+fn defaults_are_full_expressions() {
+    let columns =
+        columns("CREATE TABLE t (a TIMESTAMP DEFAULT NOW(), b INT DEFAULT (1 + 2) * 3, c INT)");
+    let defaults: Vec<String> = columns
+        .iter()
+        .flat_map(|column| &column.options)
+        .map(|option| match option {
+            ColumnOption::Default(expr) => expr.to_string(),
+            option => panic!("{}", option),
+        })
+        .collect();
+    assert_eq!(defaults, ["NOW()", "(1 + 2) * 3"]);
+    // The default ends where the next column starts
+    assert_eq!(&*columns[2].name, "c");
+}
+
+#[test]
+fn conflicting_options_are_kept() {
+    let columns = columns("CREATE TABLE t (a INT NULL NOT NULL, b INT UNIQUE UNIQUE)");
+    assert_eq!(
+        columns[0].options,
+        [ColumnOption::Null, ColumnOption::NotNull]
+    );
+    assert_eq!(
+        columns[1].options,
+        [ColumnOption::Unique, ColumnOption::Unique]
+    );
+}
+
+#[test]
+fn incomplete_options_are_errors() {
+    for (sql, message, column) in [
+        (
+            "CREATE TABLE t (a INT DEFAULT)",
+            "expression after DEFAULT in column a, found ')'",
+            30,
+        ),
+        (
+            "CREATE TABLE t (a INT NOT)",
+            "NULL after NOT in column a, found ')'",
+            26,
+        ),
+        (
+            "CREATE TABLE t (a INT PRIMARY)",
+            "KEY after PRIMARY in column a, found ')'",
+            30,
+        ),
+    ] {
+        assert_eq!(error(sql), (message.to_string(), column), "{}", sql);
+    }
+}
+
+/// Returns the table constraints of a CREATE TABLE statement
+fn constraints(sql: &str) -> Vec<TableConstraint> {
+    match parse_statement(sql).unwrap_or_else(|e| panic!("{}: {}", sql, e)) {
+        Statement::CreateTable { constraints, .. } => constraints,
+        statement => panic!("not a CREATE TABLE: {}", statement),
+    }
+}
+
+#[test]
+fn constraints_between_columns() {
+    let sql = "CREATE TABLE orders (a INT, PRIMARY KEY (a, b), b INT, UNIQUE (b))";
+    assert_eq!(columns(sql).len(), 2);
+    assert_eq!(
+        constraints(sql),
+        [
+            TableConstraint::PrimaryKey {
+                name: None,
+                columns: vec!["a".into(), "b".into()],
+            },
+            TableConstraint::Unique {
+                name: None,
+                columns: vec!["b".into()],
+            },
+        ]
+    );
+}
+
+#[test]
+fn composite_foreign_key() {
+    let sql = "CREATE TABLE orders (a INT, b INT, CONSTRAINT fk_user FOREIGN KEY (a, b) \
+               REFERENCES app.users (x, y) ON DELETE CASCADE ON UPDATE SET NULL)";
+    assert_eq!(
+        constraints(sql),
+        [TableConstraint::ForeignKey {
+            name: Some("fk_user".into()),
+            columns: vec!["a".into(), "b".into()],
+            foreign_table: ObjectName(vec![Ident::new("app"), Ident::new("users")]),
+            referred_columns: vec!["x".into(), "y".into()],
+            on_delete: Some(ReferentialAction::Cascade),
+            on_update: Some(ReferentialAction::SetNull),
+        }]
+    );
+}
+
+#[test]
+fn every_referential_action() {
+    for (action, parsed) in [
+        ("CASCADE", ReferentialAction::Cascade),
+        ("SET NULL", ReferentialAction::SetNull),
+        ("SET DEFAULT", ReferentialAction::SetDefault),
+        ("RESTRICT", ReferentialAction::Restrict),
+        ("NO ACTION", ReferentialAction::NoAction),
+    ] {
+        let sql = format!(
+            "CREATE TABLE t (a INT, FOREIGN KEY (a) REFERENCES u ON UPDATE {})",
+            action
+        );
+        match &constraints(&sql)[..] {
+            [TableConstraint::ForeignKey {
+                referred_columns,
+                on_delete: None,
+                on_update: Some(on_update),
+                ..
+            }] => {
+                assert!(referred_columns.is_empty());
+                assert_eq!(on_update, &parsed);
+            }
+            constraints => panic!("{}: {:?}", sql, constraints),
+        }
+    }
+}
+
+#[test]
+fn named_check_constraint() {
+    let constraints = constraints(
+        "CREATE TABLE t (price INT, CONSTRAINT positive CHECK (price > 0 AND price < 10))",
+    );
+    match &constraints[..] {
+        [TableConstraint::Check { name, expr }] => {
+            assert_eq!(name.as_deref(), Some("positive"));
+            assert_eq!(expr.to_string(), "price > 0 AND price < 10");
+        }
+        constraints => panic!("{:?}", constraints),
+    }
+}
+
+#[test]
+fn malformed_constraints_are_errors() {
+    for (sql, message, column) in [
+        (
+            "CREATE TABLE t (a INT, CONSTRAINT c x)",
+            "PRIMARY, UNIQUE, FOREIGN or CHECK after CONSTRAINT, found x, \
+             expected one of: PRIMARY, UNIQUE, FOREIGN, CHECK",
+            37,
+        ),
+        (
+            "CREATE TABLE t (a INT, CHECK a > 0)",
+            "'(' after CHECK, found a",
+            30,
+        ),
+        (
+            "CREATE TABLE t (a INT, FOREIGN KEY (a) REFERENCES u ON INSERT CASCADE)",
+            "DELETE or UPDATE after ON in FOREIGN KEY, found INSERT, \
+             expected one of: DELETE, UPDATE",
+            56,
+        ),
+        (
+            "CREATE TABLE t (a INT, FOREIGN KEY (a) REFERENCES u ON DELETE x)",
+            "CASCADE, SET, RESTRICT or NO after ON DELETE, found x, \
+             expected one of: CASCADE, RESTRICT, SET, NO",
+            63,
+        ),
+    ] {
+        assert_eq!(error(sql), (message.to_string(), column), "{}", sql);
+    }
+}
@@ -0,0 +1,111 @@
+use rust_sql_parser::ast::{DataType, Ident, ObjectName};
+use rust_sql_parser::error::Problem;
+use rust_sql_parser::parser::PrattParser;
+
+fn data_type(sql: &str) -> Result<DataType, String> {
+    PrattParser::new(sql)
+        .parse_data_type()
+        .map_err(|e| e.message())
+}
+
+fn custom(parts: &[&str]) -> DataType {
+    DataType::Custom(ObjectName(
+        parts.iter().map(|&part| Ident::new(part)).collect(),
+    ))
+}
+
+#[test]
+fn every_variant_parses_and_prints() {
+    for (sql, expected) in [
+        ("INT", DataType::Int),
+        ("BIGINT", DataType::BigInt),
+        ("SMALLINT", DataType::SmallInt),
+        ("VARCHAR", DataType::Varchar(None)),
+        ("VARCHAR(255)", DataType::Varchar(Some(255))),
+        ("CHAR", DataType::Char(None)),
+        ("CHAR(2)", DataType::Char(Some(2))),
+        ("TEXT", DataType::Text),
+        ("BOOLEAN", DataType::Boolean),
+        ("FLOAT", DataType::Float),
+        ("DOUBLE PRECISION", DataType::Double),
+        ("DECIMAL", DataType::Decimal(None, None)),
+        ("DECIMAL(10)", DataType::Decimal(Some(10), None)),
+        ("DECIMAL(10, 2)", DataType::Decimal(Some(10), Some(2))),
+        ("DATE", DataType::Date),
+        ("TIMESTAMP", DataType::Timestamp),
+        ("TIMESTAMP WITH TIME ZONE", DataType::TimestampWithTimeZone),
+        ("INT[]", DataType::Array(Box::new(DataType::Int))),
+        (
+            "VARCHAR(5)[][]",
+            DataType::Array(Box::new(DataType::Array(Box::new(DataType::Varchar(
+                Some(5),
+            ))))),
+        ),
+        ("geometry", custom(&["geometry"])),
+        (
+            "public.money[]",
+            DataType::Array(Box::new(custom(&["public", "money"]))),
+        ),
+    ] {
+        assert_eq!(data_type(sql), Ok(expected.clone()), "{}", sql);
+        assert_eq!(expected.to_string(), sql);
+    }
+    assert_eq!(DataType::Unspecified.to_string(), "");
+}
+
+#[test]
+fn other_spellings_print_in_canonical_form() {
+    for (sql, canonical) in [
+        ("integer", "INT"),
+        ("bool", "BOOLEAN"),
+        ("REAL", "FLOAT"),
+        ("DOUBLE", "DOUBLE PRECISION"),
+        ("NUMERIC(8, 3)", "DECIMAL(8, 3)"),
+        ("CHARACTER(3)", "CHAR(3)"),
+        ("CHARACTER VARYING(40)", "VARCHAR(40)"),
+        ("timestamp without time zone", "TIMESTAMP"),
+        ("timestamp with time zone", "TIMESTAMP WITH TIME ZONE"),
+    ] {
+        assert_eq!(data_type(sql).unwrap().to_string(), canonical, "{}", sql);
+    }
+}
+
+#[test]
+fn types_round_trip_through_casts() {
+    for sql in [
+        "a::DECIMAL(10, 2)",
+        "a::TIMESTAMP WITH TIME ZONE",
+        "a::INT[]",
+        "a::DOUBLE PRECISION",
+    ] {
+        let expr = rust_sql_parser::parse_expression(sql).unwrap();
+        assert_eq!(expr.to_string(), sql);
+    }
+}
+
+#[test]
+fn malformed_parameters_are_type_errors() {
+    for (sql, message) in [
+        ("VARCHAR(x)", "length for VARCHAR, found x"),
+        ("VARCHAR(10", "')' after VARCHAR length, found end of input"),
+        ("DECIMAL(10, )", "scale for DECIMAL, found ')'"),
+        (
+            "TIMESTAMP WITH ZONE",
+            "TIME after TIMESTAMP WITH, found ZONE",
+        ),
+        ("INT[", "']' after INT[, found end of input"),
+    ] {
+        let e = PrattParser::new(sql).parse_data_type().unwrap_err();
+        assert!(
+            matches!(e.problem, Problem::ExpectedType { .. }),
+            "{}: {:?}",
+            sql,
+            e
+        );
+        assert_eq!(e.message(), message, "{}", sql);
+    }
+    assert_eq!(
+        data_type("").unwrap_err(),
+        "expected a data type after the start of input"
+    );
+}
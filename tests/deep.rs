@@ -0,0 +1,160 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::ops::ControlFlow;
+
+use rust_sql_parser::ast::Ident;
+use rust_sql_parser::error::ComplexityError;
+use rust_sql_parser::metrics::{check_limits, Limits};
+use rust_sql_parser::visitor::{Visitor, VisitorMut};
+use rust_sql_parser::{parse_expression, parse_statement};
+
+const TERMS: usize = 100_000;
+
+/// Runs `test` on a thread with a small stack, so anything that recurses once per term
+/// overflows rather than passing on the larger main thread
+fn on_small_stack(test: impl FnOnce() + Send + 'static) {
+    std::thread::Builder::new()
+        .stack_size(2 << 20)
+        .spawn(test)
+        .unwrap()
+        .join()
+        .unwrap();
+}
+
+fn or_chain(terms: usize) -> String {
+    let mut sql = String::from("SELECT a FROM t WHERE x0 = 0");
+    for i in 1..terms {
+        sql.push_str(&format!(" OR x{} = {}", i, i));
+    }
+    sql
+}
+
+fn in_list(elements: usize) -> String {
+    let list: Vec<String> = (0..elements).map(|i| i.to_string()).collect();
+    format!("SELECT a FROM t WHERE x IN ({})", list.join(", "))
+}
+
+fn hash(value: &impl Hash) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[derive(Default)]
+struct IdentifierCounter {
+    count: usize,
+}
+
+impl Visitor for IdentifierCounter {
+    type Break = ();
+
+    fn visit_identifier(&mut self, _ident: &Ident) -> ControlFlow<()> {
+        self.count += 1;
+        ControlFlow::Continue(())
+    }
+}
+
+struct QuoteIdentifiers;
+
+impl VisitorMut for QuoteIdentifiers {
+    type Break = ();
+
+    fn visit_identifier_mut(&mut self, ident: &mut Ident) -> ControlFlow<()> {
+        ident.quote_style = Some('"');
+        ControlFlow::Continue(())
+    }
+}
+
+#[test]
+fn long_or_chain() {
+    on_small_stack(|| {
+        let sql = or_chain(TERMS);
+        let statement = parse_statement(&sql).unwrap();
+        assert_eq!(statement.to_string(), sql);
+
+        let copy = statement.clone();
+        assert_eq!(copy, statement);
+        assert_eq!(hash(&copy), hash(&statement));
+
+        let mut counter = IdentifierCounter::default();
+        let _ = counter.visit_statement(&statement);
+        assert_eq!(counter.count, TERMS + 1);
+
+        let mut quoted = statement.clone();
+        let _ = QuoteIdentifiers.visit_statement_mut(&mut quoted);
+        assert_ne!(quoted, statement);
+        assert!(quoted
+            .to_string()
+            .ends_with(&format!(" OR \"x{}\" = {}", TERMS - 1, TERMS - 1)));
+
+        let metrics = statement.metrics();
+        assert!(metrics.max_depth > TERMS);
+        assert!(check_limits(&statement, &Limits::new()).is_ok());
+        assert!(matches!(
+            check_limits(&statement, &Limits::new().with_max_depth(64)),
+            Err(ComplexityError::TooDeep(_))
+        ));
+        drop(statement);
+    });
+}
+
+#[test]
+fn long_chains_compare_and_hash_by_structure() {
+    on_small_stack(|| {
+        let sql = or_chain(TERMS);
+        let statement = parse_statement(&sql).unwrap();
+        let same = parse_statement(&sql).unwrap();
+        assert_eq!(same, statement);
+        assert_eq!(hash(&same), hash(&statement));
+
+        let last = format!(" OR x{} = {}", TERMS - 1, TERMS - 1);
+        for other in [
+            // The deepest operand, the outermost one and the outermost operator
+            sql.replacen("x0 = 0", "x0 = 1", 1),
+            sql.replace(&last, &format!(" OR x{} = 0", TERMS - 1)),
+            sql.replace(&last, &format!(" AND x{} = {}", TERMS - 1, TERMS - 1)),
+        ] {
+            let other = parse_statement(&other).unwrap();
+            assert_ne!(other, statement);
+            assert_ne!(hash(&other), hash(&statement));
+        }
+
+        // Equal subexpressions can key a map however deep they are
+        let mut seen = std::collections::HashMap::new();
+        for statement in [statement, same] {
+            *seen.entry(statement).or_insert(0) += 1;
+        }
+        assert_eq!(seen.into_values().collect::<Vec<_>>(), [2]);
+    });
+}
+
+#[test]
+fn long_chains_simplify() {
+    on_small_stack(|| {
+        let mut sql = String::from("x0 = 0");
+        for i in 1..TERMS {
+            sql.push_str(&format!(" OR x{} = {} + 0", i, i));
+        }
+        let expr = parse_expression(&format!("TRUE AND ({}) AND NOT NOT TRUE", sql)).unwrap();
+        let simplified = expr.simplify();
+        assert_eq!(simplified, expr.fold_constants());
+        assert!(simplified
+            .to_string()
+            .ends_with(&format!(" OR x{} = {}", TERMS - 1, TERMS - 1)));
+    });
+}
+
+#[test]
+fn long_in_list() {
+    on_small_stack(|| {
+        let sql = in_list(TERMS);
+        let statement = parse_statement(&sql).unwrap();
+        assert_eq!(statement.to_string(), sql);
+        assert_eq!(statement.clone(), statement);
+
+        let mut counter = IdentifierCounter::default();
+        let _ = counter.visit_statement(&statement);
+        assert_eq!(counter.count, 2);
+        assert!(statement.metrics().node_count > TERMS);
+    });
+}
@@ -0,0 +1,67 @@
+use rust_sql_parser::ast::{ObjectType, Statement};
+use rust_sql_parser::parse_statement;
+
+const ROUND_TRIPS: &[&str] = &[
+    "DROP TABLE t",
+    "DROP TABLE IF EXISTS t",
+    "DROP TABLE IF EXISTS s.t, u CASCADE",
+    "DROP TABLE t RESTRICT",
+    "DROP INDEX idx_name",
+    "DROP INDEX IF EXISTS idx_name",
+    "DROP INDEX idx ON users",
+    "DROP INDEX IF EXISTS idx ON s.users RESTRICT",
+    "DROP VIEW v1",
+    "DROP VIEW IF EXISTS v1",
+    "DROP VIEW v1, v2 CASCADE",
+    "DROP VIEW IF EXISTS v1 RESTRICT",
+];
+
+#[test]
+fn every_form_round_trips() {
+    for sql in ROUND_TRIPS {
+        let statement = parse_statement(sql).unwrap_or_else(|e| panic!("{}: {}", sql, e));
+        assert_eq!(statement.to_string(), *sql);
+        assert_eq!(parse_statement(&statement.to_string()).unwrap(), statement);
+    }
+}
+
+#[test]
+fn fields() {
+    match parse_statement("DROP VIEW IF EXISTS v1, v2 RESTRICT").unwrap() {
+        Statement::Drop {
+            object_type,
+            if_exists,
+            names,
+            table,
+            cascade,
+        } => {
+            assert_eq!(object_type, ObjectType::View);
+            assert!(if_exists);
+            assert_eq!(names.len(), 2);
+            assert_eq!(table, None);
+            assert_eq!(cascade, Some(false));
+        }
+        statement => panic!("not a DROP: {}", statement),
+    }
+    match parse_statement("DROP INDEX idx ON t").unwrap() {
+        Statement::Drop { table, cascade, .. } => {
+            assert_eq!(table.unwrap().to_string(), "t");
+            assert_eq!(cascade, None);
+        }
+        statement => panic!("not a DROP: {}", statement),
+    }
+}
+
+#[test]
+fn rejected_forms() {
+    for sql in [
+        "DROP",
+        "DROP SCHEMA s",
+        "DROP TABLE",
+        "DROP TABLE IF t",
+        "DROP TABLE t ON u",
+        "DROP VIEW v CASCADE RESTRICT",
+    ] {
+        assert!(parse_statement(sql).is_err(), "{} should not parse", sql);
+    }
+}
@@ -0,0 +1,79 @@
+use rust_sql_parser::normalize::semantically_equal;
+use rust_sql_parser::options::{EqualityOptions, IdentifierCase};
+use rust_sql_parser::parse_statement;
+
+fn equal(a: &str, b: &str, options: &EqualityOptions) -> bool {
+    semantically_equal(
+        &parse_statement(a).unwrap(),
+        &parse_statement(b).unwrap(),
+        options,
+    )
+}
+
+const EQUAL: &[(&str, &str)] = &[
+    ("SELECT a FROM t", "select A from T"),
+    ("SELECT a FROM t", "SELECT  a\n  FROM\tt"),
+    ("SELECT (a + b) * c FROM t", "SELECT ((a + b)) * (c) FROM t"),
+    ("SELECT a + b * c FROM t", "SELECT a + (b * c) FROM t"),
+    (
+        "SELECT a FROM t WHERE (x = 1)",
+        "SELECT a FROM t WHERE x = 1",
+    ),
+    ("SELECT a - b - c FROM t", "SELECT (a - b) - c FROM t"),
+    ("SELECT Count(*) FROM t", "SELECT COUNT(*) FROM t"),
+    ("(SELECT 1) UNION (SELECT 2)", "SELECT 1 UNION SELECT 2"),
+    ("SELECT \"a\" FROM t", "SELECT \"a\" FROM T"),
+];
+
+const UNEQUAL: &[(&str, &str)] = &[
+    ("SELECT (a + b) * c FROM t", "SELECT a + b * c FROM t"),
+    ("SELECT a - (b - c) FROM t", "SELECT a - b - c FROM t"),
+    ("SELECT \"A\" FROM t", "SELECT a FROM t"),
+    ("SELECT a FROM t", "SELECT b FROM t"),
+    ("SELECT a FROM t", "SELECT a FROM t WHERE TRUE"),
+    ("SELECT 1 UNION SELECT 2", "SELECT 1 UNION ALL SELECT 2"),
+    ("SELECT t.a FROM t", "SELECT a FROM t"),
+];
+
+#[test]
+fn equal_pairs() {
+    let options = EqualityOptions::new();
+    for (a, b) in EQUAL {
+        assert!(equal(a, b, &options), "{} should equal {}", a, b);
+    }
+}
+
+#[test]
+fn unequal_pairs() {
+    let options = EqualityOptions::new();
+    for (a, b) in UNEQUAL {
+        assert!(!equal(a, b, &options), "{} should not equal {}", a, b);
+    }
+}
+
+#[test]
+fn precedence_changing_parentheses_are_kept() {
+    assert!(!equal(
+        "SELECT (a + b) * c FROM t",
+        "SELECT a + b * c FROM t",
+        &EqualityOptions::new()
+    ));
+}
+
+#[test]
+fn preserve_makes_identifier_case_significant() {
+    let options = EqualityOptions::new().with_identifier_case(IdentifierCase::Preserve);
+    assert!(!equal("SELECT a FROM t", "SELECT A FROM T", &options));
+    assert!(equal("SELECT a FROM t", "select a from t", &options));
+}
+
+#[test]
+fn stripped_qualifiers() {
+    let options = EqualityOptions::new().with_qualifiers_stripped(true);
+    assert!(equal("SELECT t.a FROM t", "SELECT a FROM t", &options));
+    assert!(!equal(
+        "SELECT t.a FROM t JOIN u ON TRUE",
+        "SELECT a FROM t JOIN u ON TRUE",
+        &options
+    ));
+}
@@ -0,0 +1,237 @@
+use rust_sql_parser::ast::{Keyword, Token, TokenKind};
+use rust_sql_parser::error::{ErrorKind, ParseContext, Problem};
+use rust_sql_parser::parser::PrattParser;
+use rust_sql_parser::{parse_statement, parse_statements};
+
+fn message(sql: &str) -> String {
+    parse_statement(sql).unwrap_err().message()
+}
+
+#[test]
+fn tokens_print_as_sql() {
+    assert_eq!(Token::Keyword(Keyword::From).to_string(), "FROM");
+    assert_eq!(Token::String("it's".into()).to_string(), "'it''s'");
+    assert_eq!(Token::NotEqual.to_string(), "<>");
+    assert_eq!(Token::Eof.to_string(), "end of input");
+}
+
+#[test]
+fn found_tokens_read_as_written() {
+    assert_eq!(message("SELECT a FROM FROM"), "table name, found FROM");
+    assert_eq!(
+        message("SELECT a FROM t WHERE a = = 1"),
+        "'=' at the start of an expression"
+    );
+    assert_eq!(
+        message("INSERT INTO t VALUES 'x'"),
+        "'(' to start a row after VALUES, found 'x'"
+    );
+    assert!(message("SELECT 1 2.5").starts_with("2.5 after end of statement"));
+    assert!(message("SELECT a b c").starts_with("c after end of statement"));
+}
+
+#[test]
+fn expected_tokens_can_be_matched() {
+    let e = parse_statement("SELECT a b c").unwrap_err();
+    assert!(e.expected_tokens().contains(&TokenKind::Comma));
+    assert!(e
+        .expected_tokens()
+        .contains(&TokenKind::Keyword(Keyword::From)));
+
+    let e = parse_statement("DELETE t").unwrap_err();
+    assert_eq!(e.expected_tokens(), [TokenKind::Keyword(Keyword::From)]);
+    assert_eq!(e.message(), "FROM after DELETE, found t");
+}
+
+#[test]
+fn problems_have_typed_fields() {
+    let e = parse_statement("CREATE x").unwrap_err();
+    match &e.problem {
+        Problem::ExpectedKeyword {
+            keywords,
+            position,
+            found,
+        } => {
+            assert_eq!(keywords, &[Keyword::Table, Keyword::Index, Keyword::View]);
+            assert_eq!(position, "after CREATE");
+            assert_eq!(found, &Token::Identifier("x".into()));
+        }
+        other => panic!("{:?}", other),
+    }
+
+    let e = parse_statement("SELECT a IN (1 2").unwrap_err();
+    match &e.problem {
+        Problem::ExpectedToken { expected, .. } => {
+            assert_eq!(expected, &[TokenKind::RightParentheses])
+        }
+        other => panic!("{:?}", other),
+    }
+    assert!(e.message().starts_with("')' to close IN list, found 2"));
+}
+
+#[test]
+fn parse_errors_are_std_errors() {
+    let e: Box<dyn std::error::Error> = parse_statement("DELETE t").unwrap_err().into();
+    assert!(
+        e.to_string().contains("FROM after DELETE, found t"),
+        "{}",
+        e
+    );
+    assert!(e.source().is_none());
+}
+
+#[test]
+fn statement_number_is_its_own_field() {
+    let e = parse_statements("SELECT 1; SELECT 2; DELETE t").unwrap_err();
+    assert_eq!(e.statement(), Some(3));
+    assert_eq!(e.message(), "FROM after DELETE, found t");
+    assert!(e.to_string().contains("in statement 3"), "{}", e);
+
+    assert_eq!(parse_statement("DELETE t").unwrap_err().statement(), None);
+}
+
+#[test]
+fn running_out_of_input_is_unexpected_eof() {
+    for sql in [
+        "DELETE",
+        "SELECT a FROM",
+        "INSERT INTO t VALUES",
+        "SELECT a::",
+    ] {
+        let e = parse_statement(sql).unwrap_err();
+        assert_eq!(e.kind(), ErrorKind::UnexpectedEof, "{}: {}", sql, e);
+        assert_eq!(e.kind().code(), "E0002");
+    }
+    let e = parse_statement("DELETE t").unwrap_err();
+    assert_eq!(e.kind(), ErrorKind::UnexpectedToken);
+}
+
+/// Returns where an error starts as (line, column, offset)
+fn position(sql: &str) -> (usize, usize, usize) {
+    let start = parse_statement(sql).unwrap_err().span().unwrap().start;
+    (start.line, start.column, start.offset)
+}
+
+#[test]
+fn errors_are_located_by_line_and_column() {
+    assert_eq!(position("SELECT a FROM t WHERE a = = 1"), (1, 27, 26));
+    assert_eq!(position("SELECT a\nFROM t\nWHERE f(a b) = 1"), (3, 11, 26));
+    // At the end of the input, just past its last character
+    assert_eq!(position("SELECT a\nFROM t\nWHERE a = 1 AND"), (3, 16, 31));
+    // Columns count characters, offsets count bytes
+    assert_eq!(position("SELECT 'é', ü FROM t"), (1, 13, 13));
+
+    let e = parse_statement("SELECT a\nFROM FROM").unwrap_err();
+    assert!(e.to_string().ends_with(" at line 2, column 6"), "{}", e);
+}
+
+#[test]
+fn diagnostics_show_the_line_and_a_caret() {
+    for (sql, expected) in [
+        (
+            "SELECT a FROM t WHERE a = = 1",
+            "\
+error: Unexpected token: '=' at the start of an expression, while parsing the WHERE clause in SELECT
+ --> line 1, column 27
+  |
+1 | SELECT a FROM t WHERE a = = 1
+  |                           ^",
+        ),
+        (
+            "SELECT a\nFROM t\nWHERE a = 1 AND",
+            "\
+error: Unexpected end of input: expected an expression, while parsing the WHERE clause in SELECT
+ --> line 3, column 16
+  |
+3 | WHERE a = 1 AND
+  |                ^",
+        ),
+        (
+            "SELECT\tb, 'é', ü FROM t",
+            "\
+error: Unexpected character: 'ü'
+ --> line 1, column 16
+  |
+1 | SELECT    b, 'é', ü FROM t
+  |                   ^",
+        ),
+        (
+            "SELECT 'abc\ndef",
+            "\
+error: Unterminated string: no closing ' for string literal
+ --> line 1, column 8
+  |
+1 | SELECT 'abc
+  |        ^^^^
+2 | def
+  | ^^^",
+        ),
+    ] {
+        let rendered = parse_statement(sql).unwrap_err().render(sql);
+        assert_eq!(rendered, expected, "\n{}", rendered);
+    }
+}
+
+#[test]
+fn each_mistake_in_a_script_is_reported_in_order() {
+    let script = "SELECT 1 @ 2;\nSELECT a FROM t WHERE;\nSELECT 3;\nDELETE t;";
+    let (statements, errors) = PrattParser::parse_statements_recovering(script);
+    assert_eq!(statements.len(), 1);
+
+    let found: Vec<_> = errors
+        .iter()
+        .map(|e| (e.kind(), e.span().unwrap().start.line, e.statement()))
+        .collect();
+    assert_eq!(
+        found,
+        [
+            (ErrorKind::InvalidCharacter, 1, None),
+            (ErrorKind::UnexpectedEof, 2, Some(2)),
+            (ErrorKind::UnexpectedToken, 4, Some(4)),
+        ]
+    );
+    assert_eq!(errors.to_string().lines().count(), 3, "{}", errors);
+}
+
+#[test]
+fn errors_say_what_was_being_parsed() {
+    let e = parse_statement("SELECT a FROM t WHERE f(a b) = 1").unwrap_err();
+    assert_eq!(
+        e.parsing(),
+        [
+            ParseContext::Select,
+            ParseContext::WhereClause,
+            ParseContext::FunctionArgs
+        ]
+    );
+    // Only the innermost two are displayed
+    assert!(
+        e.to_string()
+            .contains(", while parsing function arguments in the WHERE clause at "),
+        "{}",
+        e
+    );
+
+    let e = parse_statement("CREATE TABLE t (a INT DEFAULT (1 + 2, b INT)").unwrap_err();
+    assert_eq!(
+        e.parsing(),
+        [
+            ParseContext::CreateTable,
+            ParseContext::CreateTableColumns,
+            ParseContext::ColumnDefault
+        ]
+    );
+    assert!(
+        e.to_string()
+            .contains(", while parsing a column default in the column definitions at "),
+        "{}",
+        e
+    );
+
+    // Outside any tracked construct, and for lexical errors, there is no chain
+    for sql in ["(SELECT 1", "SELECT 'open"] {
+        let e = parse_statement(sql).unwrap_err();
+        assert_eq!(e.parsing(), [], "{}", sql);
+        assert!(!e.to_string().contains("while parsing"), "{}", e);
+    }
+}
@@ -0,0 +1,71 @@
+use rust_sql_parser::error::EvalError;
+use rust_sql_parser::eval::Value;
+use rust_sql_parser::parse_expression;
+
+fn evaluate(sql: &str) -> Result<Value, EvalError> {
+    parse_expression(sql)
+        .unwrap_or_else(|e| panic!("{}: {}", sql, e))
+        .evaluate(&|_| None)
+}
+
+#[test]
+fn exact_integer_casts() {
+    for (sql, n) in [
+        ("2.0::INT", 2),
+        ("'12'::SMALLINT", 12),
+        ("32767::SMALLINT", 32767),
+        ("2147483647::INT", 2147483647),
+        ("3000000000::BIGINT", 3000000000),
+        ("TRUE::INT", 1),
+    ] {
+        assert_eq!(evaluate(sql).unwrap(), Value::Integer(n), "{}", sql);
+    }
+}
+
+#[test]
+fn lossy_casts_are_errors() {
+    for sql in ["1.9::INT", "-0.5::BIGINT", "'1.5'::INT"] {
+        assert!(
+            matches!(evaluate(sql), Err(EvalError::TypeMismatch(_))),
+            "{}",
+            sql
+        );
+    }
+}
+
+#[test]
+fn out_of_range_casts_are_errors() {
+    for sql in [
+        "32768::SMALLINT",
+        "'99999'::SMALLINT",
+        "2147483648::INT",
+        "1e30::BIGINT",
+    ] {
+        assert!(
+            matches!(evaluate(sql), Err(EvalError::Overflow(_))),
+            "{}",
+            sql
+        );
+    }
+}
+
+#[test]
+fn smallest_integer_literal() {
+    assert_eq!(
+        evaluate("-9223372036854775808").unwrap(),
+        Value::Integer(i64::MIN)
+    );
+    for sql in ["-9223372036854775809", "- -9223372036854775808"] {
+        assert!(
+            matches!(evaluate(sql), Err(EvalError::Overflow(_))),
+            "{}",
+            sql
+        );
+    }
+}
+
+#[test]
+fn errors_are_std_errors() {
+    let e: Box<dyn std::error::Error> = evaluate("1 / 0").unwrap_err().into();
+    assert!(e.to_string().starts_with("Division by zero"), "{}", e);
+}
@@ -0,0 +1,139 @@
+#![cfg(feature = "ffi")]
+
+use std::ffi::{CStr, CString};
+use std::ptr;
+
+use rust_sql_parser::ffi::*;
+
+fn empty_error() -> SqlpError {
+    SqlpError {
+        code: ptr::null(),
+        message: ptr::null_mut(),
+        line: 0,
+        column: 0,
+    }
+}
+
+/// Parses `sql` through the C interface, returning the error's code, message and position
+/// on failure
+fn parse(sql: &str) -> Result<*mut SqlpStatement, (String, String, usize, usize)> {
+    let sql = CString::new(sql).unwrap();
+    let mut err = empty_error();
+    let statement = unsafe { sqlp_parse(sql.as_ptr(), &mut err) };
+    if !statement.is_null() {
+        return Ok(statement);
+    }
+    let result = unsafe {
+        (
+            CStr::from_ptr(err.code).to_str().unwrap().to_string(),
+            CStr::from_ptr(err.message).to_str().unwrap().to_string(),
+            err.line,
+            err.column,
+        )
+    };
+    unsafe { sqlp_free_error(&mut err) };
+    assert!(err.message.is_null() && err.code.is_null());
+    unsafe { sqlp_free_error(&mut err) };
+    Err(result)
+}
+
+fn tables(statement: *const SqlpStatement) -> Vec<String> {
+    let count = unsafe { sqlp_table_count(statement) };
+    (0..count)
+        .map(|i| unsafe { CStr::from_ptr(sqlp_table_name(statement, i)) })
+        .map(|name| name.to_str().unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn parses_and_prints() {
+    let statement = parse("select a from s.t join u on t.id = u.id;").unwrap();
+    unsafe {
+        assert_eq!(sqlp_statement_kind(statement), SqlpStatementKind::Query);
+        assert_eq!(tables(statement), ["s.t", "u"]);
+        assert!(sqlp_table_name(statement, 2).is_null());
+
+        let sql = sqlp_to_sql(statement);
+        assert_eq!(
+            CStr::from_ptr(sql).to_str().unwrap(),
+            "SELECT a FROM s.t JOIN u ON t.id = u.id"
+        );
+        sqlp_free_string(sql);
+        sqlp_free_statement(statement);
+    }
+}
+
+#[test]
+fn statement_kinds() {
+    for (sql, kind) in [
+        ("INSERT INTO t VALUES (1)", SqlpStatementKind::Insert),
+        ("DELETE FROM t", SqlpStatementKind::Delete),
+        ("DROP TABLE t", SqlpStatementKind::Drop),
+        ("COMMIT", SqlpStatementKind::Transaction),
+    ] {
+        let statement = parse(sql).unwrap();
+        unsafe {
+            assert_eq!(sqlp_statement_kind(statement), kind, "{}", sql);
+            sqlp_free_statement(statement);
+        }
+    }
+}
+
+#[test]
+fn reports_errors() {
+    let (code, message, line, column) = parse("SELECT a\nFROM").unwrap_err();
+    assert_eq!(code, "E0002");
+    assert!(!message.is_empty());
+    assert_eq!((line, column), (2, 5));
+
+    let (code, ..) = parse("SELECT 'a").unwrap_err();
+    assert_eq!(code, "E0004");
+}
+
+#[test]
+fn accepts_null_pointers() {
+    let mut err = empty_error();
+    unsafe {
+        assert!(sqlp_parse(ptr::null(), &mut err).is_null());
+        assert_eq!(CStr::from_ptr(err.code).to_str().unwrap(), "E0007");
+        sqlp_free_error(&mut err);
+
+        let sql = CString::new("SELECT FROM").unwrap();
+        assert!(sqlp_parse(sql.as_ptr(), ptr::null_mut()).is_null());
+
+        assert_eq!(sqlp_statement_kind(ptr::null()), SqlpStatementKind::Invalid);
+        assert_eq!(sqlp_table_count(ptr::null()), 0);
+        assert!(sqlp_table_name(ptr::null(), 0).is_null());
+        assert!(sqlp_to_sql(ptr::null()).is_null());
+        sqlp_free_statement(ptr::null_mut());
+        sqlp_free_string(ptr::null_mut());
+        sqlp_free_error(ptr::null_mut());
+    }
+}
+
+/// Runs on a stack the size of a typical main thread, which the unoptimized parser needs
+/// at the deepest nesting allowed
+#[test]
+fn rejects_deep_nesting() {
+    std::thread::Builder::new()
+        .stack_size(8 << 20)
+        .spawn(|| {
+            let sql = format!("SELECT {}1{}", "(".repeat(10_000), ")".repeat(10_000));
+            let (code, ..) = parse(&sql).unwrap_err();
+            assert_eq!(code, "E0006");
+
+            let nested = format!("SELECT {}1{}", "(".repeat(50), ")".repeat(50));
+            unsafe { sqlp_free_statement(parse(&nested).unwrap()) };
+
+            let mut chain = String::from("SELECT a FROM t WHERE x0 = 0");
+            for i in 1..100_000 {
+                chain.push_str(&format!(" OR x{} = {}", i, i));
+            }
+            let statement = parse(&chain).unwrap();
+            assert_eq!(tables(statement), ["t"]);
+            unsafe { sqlp_free_statement(statement) };
+        })
+        .unwrap()
+        .join()
+        .unwrap();
+}
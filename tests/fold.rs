@@ -0,0 +1,79 @@
+use rust_sql_parser::eval::Value;
+use rust_sql_parser::parse_expression;
+
+fn fold(sql: &str) -> String {
+    parse_expression(sql)
+        .unwrap_or_else(|e| panic!("{}: {}", sql, e))
+        .fold_constants()
+        .to_string()
+}
+
+#[test]
+fn literal_subtrees_are_folded() {
+    for (sql, folded) in [
+        ("2 + 3 * 4", "14"),
+        ("'a' || 'b'", "'ab'"),
+        ("'a' || 'b' || 'c'", "'abc'"),
+        ("'it''s' || ' ok'", "'it''s ok'"),
+        ("1 = 1", "TRUE"),
+        ("TRUE AND x", "x"),
+        ("NULL || 'a'", "NULL"),
+    ] {
+        assert_eq!(fold(sql), folded, "{}", sql);
+    }
+}
+
+#[test]
+fn mixed_trees_keep_their_opaque_parts() {
+    for (sql, folded) in [
+        ("x + 2 * 3", "x + 6"),
+        ("name || ' ' || 'x'", "name || ' ' || 'x'"),
+        ("f(1 + 1) || ('a' || 'b')", "f(2) || 'ab'"),
+        ("$1 = 1 + 1", "$1 = 2"),
+    ] {
+        assert_eq!(fold(sql), folded, "{}", sql);
+    }
+}
+
+#[test]
+fn overflow_and_division_by_zero_are_left_unfolded() {
+    assert_eq!(fold("18446744073709551615 + 1"), "18446744073709551615 + 1");
+    assert_eq!(fold("1 / 0"), "1 / 0");
+}
+
+#[test]
+fn concatenation_binds_looser_than_arithmetic() {
+    let expr = parse_expression("'n' || 1 + 2").unwrap();
+    assert_eq!(expr.to_string(), "'n' || 1 + 2");
+    assert_eq!(
+        parse_expression("('n' || 1) + 2").unwrap().to_string(),
+        "('n' || 1) + 2"
+    );
+    assert_eq!(
+        parse_expression("a || b = 'ab'").unwrap().to_string(),
+        "a || b = 'ab'"
+    );
+}
+
+#[test]
+fn concatenation_evaluates() {
+    let row = |name: &str| match name {
+        "first" => Some(Value::String("Ada".into())),
+        "id" => Some(Value::Integer(1)),
+        _ => None,
+    };
+    let concat = parse_expression("first || ' Lovelace'").unwrap();
+    assert_eq!(
+        concat.evaluate(&row).unwrap(),
+        Value::String("Ada Lovelace".into())
+    );
+    assert!(parse_expression("first || id")
+        .unwrap()
+        .evaluate(&row)
+        .is_err());
+}
+
+#[test]
+fn a_single_bar_is_rejected() {
+    assert!(parse_expression("a | b").is_err());
+}
@@ -0,0 +1,213 @@
+use rust_sql_parser::formatter::format_statement;
+use rust_sql_parser::options::{FormatOptions, KeywordCase};
+use rust_sql_parser::parse_statement;
+
+/// Statements with nesting, long lists and chains, paired with how they format by default
+const SNAPSHOTS: &[(&str, &str)] = &[
+    (
+        "WITH active AS (SELECT id, name FROM users WHERE deleted_at IS NULL AND status IN ('active', 'trial')), totals AS (SELECT user_id, SUM(amount) AS total FROM orders GROUP BY user_id) SELECT a.name, t.total FROM active a JOIN totals t ON t.user_id = a.id ORDER BY t.total DESC LIMIT 10",
+        "WITH active AS (
+  SELECT
+    id,
+    name
+  FROM users
+  WHERE deleted_at IS NULL AND status IN ('active', 'trial')
+),
+totals AS (SELECT user_id, SUM(amount) AS total FROM orders GROUP BY user_id)
+SELECT
+  a.name,
+  t.total
+FROM active AS a
+  JOIN totals AS t ON t.user_id = a.id
+ORDER BY t.total DESC
+LIMIT 10",
+    ),
+    (
+        "SELECT a FROM t WHERE (a = 1 OR b = 2) AND NOT (c = 3 OR d = 4) AND e >= 1 AND e <= 10 AND f LIKE 'some long pattern%'",
+        "SELECT a
+FROM t
+WHERE (a = 1 OR b = 2)
+  AND NOT (c = 3 OR d = 4)
+  AND e >= 1
+  AND e <= 10
+  AND f LIKE 'some long pattern%'",
+    ),
+    (
+        "SELECT (SELECT MAX(y) FROM u WHERE u.z = t.z AND u.kind = 'primary' AND u.deleted IS NULL) AS max_y, -(-a) * (b + c) FROM t",
+        "SELECT
+  (
+    SELECT MAX(y)
+    FROM u
+    WHERE u.z = t.z AND u.kind = 'primary' AND u.deleted IS NULL
+  ) AS max_y,
+  -(-a) * (b + c)
+FROM t",
+    ),
+    (
+        "SELECT customer_id, first_name, last_name, email_address, phone_number, street_address, city, postal_code FROM customers",
+        "SELECT
+  customer_id,
+  first_name,
+  last_name,
+  email_address,
+  phone_number,
+  street_address,
+  city,
+  postal_code
+FROM customers",
+    ),
+    (
+        "SELECT a FROM t UNION ALL (SELECT b FROM u INTERSECT SELECT c FROM v) EXCEPT SELECT d FROM w ORDER BY 1",
+        "SELECT a
+FROM t
+UNION ALL
+(SELECT b FROM u INTERSECT SELECT c FROM v)
+EXCEPT
+SELECT d
+FROM w
+ORDER BY 1",
+    ),
+    (
+        "SELECT a::TEXT || 'x' || b, COUNT(*), MIN(c::INT) FROM t GROUP BY a, b HAVING COUNT(*) > 1 OR SUM(c) < 0 FOR UPDATE",
+        "SELECT
+  a::TEXT || 'x' || b,
+  COUNT(*),
+  MIN(c::INT)
+FROM t
+GROUP BY a, b
+HAVING COUNT(*) > 1 OR SUM(c) < 0
+FOR UPDATE",
+    ),
+    (
+        "SELECT * FROM a LEFT JOIN b ON a.id = b.a_id AND b.kind = 'x' CROSS JOIN c NATURAL JOIN d JOIN e USING (id, tenant_id)",
+        "SELECT *
+FROM a
+  LEFT JOIN b ON a.id = b.a_id AND b.kind = 'x'
+  CROSS JOIN c
+  NATURAL JOIN d
+  JOIN e USING (id, tenant_id)",
+    ),
+    (
+        "SELECT s.a FROM (SELECT a FROM (SELECT a FROM t WHERE a > 1) AS inner_t) AS s",
+        "SELECT s.a
+FROM (SELECT a FROM (SELECT a FROM t WHERE a > 1) AS inner_t) AS s",
+    ),
+    (
+        "INSERT INTO events (id, kind, payload, created_at) VALUES (1, 'click', 'a very long payload string', NOW()), (2, 'view', NULL, NOW()) ON CONFLICT (id) DO UPDATE SET kind = 'seen', payload = NULL",
+        "INSERT INTO events (id, kind, payload, created_at)
+VALUES
+  (1, 'click', 'a very long payload string', NOW()),
+  (2, 'view', NULL, NOW())
+ON CONFLICT (id) DO UPDATE SET kind = 'seen', payload = NULL",
+    ),
+    (
+        "UPDATE accounts SET balance = balance - 100, updated_at = NOW() WHERE id = $1 AND balance >= 100 RETURNING id, balance",
+        "UPDATE accounts
+SET balance = balance - 100, updated_at = NOW()
+WHERE id = $1 AND balance >= 100
+RETURNING id, balance",
+    ),
+    (
+        "DELETE FROM sessions WHERE expires_at < NOW() OR user_id IN (1, 2, 3) OR (revoked AND NOT pinned)",
+        "DELETE FROM sessions
+WHERE expires_at < NOW() OR user_id IN (1, 2, 3) OR revoked AND NOT pinned",
+    ),
+    (
+        "CREATE TABLE \"Order Items\" (id INT PRIMARY KEY, \"order\" INT NOT NULL, qty INT DEFAULT 1, note VARCHAR(200), PRIMARY KEY (id, \"order\"))",
+        "CREATE TABLE \"Order Items\" (
+  id INT PRIMARY KEY,
+  \"order\" INT NOT NULL,
+  qty INT DEFAULT 1,
+  note VARCHAR(200),
+  PRIMARY KEY (id, \"order\")
+)",
+    ),
+    (
+        "SELECT \"select\", \"Mixed Case\".x, 'it''s', 1.5, ?, :name FROM \"Mixed Case\" WHERE a NOT LIKE '%x' AND b IS NOT NULL",
+        "SELECT
+  \"select\",
+  \"Mixed Case\".x,
+  'it''s',
+  1.5,
+  ?,
+  :name
+FROM \"Mixed Case\"
+WHERE a NOT LIKE '%x' AND b IS NOT NULL",
+    ),
+];
+
+fn format(sql: &str, options: &FormatOptions) -> String {
+    format_statement(&parse_statement(sql).unwrap(), options)
+}
+
+/// Every combination of the layout options, at a width that breaks most lists and one that
+/// breaks none
+fn all_options() -> Vec<FormatOptions> {
+    let mut all = vec![];
+    for keyword_case in [KeywordCase::Upper, KeywordCase::Lower] {
+        for one_item_per_line in [true, false] {
+            for leading_commas in [true, false] {
+                for (indent_width, max_line_width) in [(2, 80), (4, 20), (1, 1000)] {
+                    all.push(
+                        FormatOptions::new()
+                            .with_keyword_case(keyword_case)
+                            .with_one_item_per_line(one_item_per_line)
+                            .with_leading_commas(leading_commas)
+                            .with_indent_width(indent_width)
+                            .with_max_line_width(max_line_width),
+                    );
+                }
+            }
+        }
+    }
+    all
+}
+
+#[test]
+fn snapshots() {
+    for (sql, formatted) in SNAPSHOTS {
+        assert_eq!(format(sql, &FormatOptions::new()), *formatted, "{}", sql);
+    }
+}
+
+#[test]
+fn formatting_is_idempotent_and_keeps_the_tree() {
+    for (sql, _) in SNAPSHOTS {
+        let statement = parse_statement(sql).unwrap();
+        for options in all_options() {
+            let formatted = format_statement(&statement, &options);
+            let reparsed = parse_statement(&formatted)
+                .unwrap_or_else(|e| panic!("{}\n{:?}\n{}", formatted, options, e));
+            assert_eq!(reparsed, statement, "{}\n{:?}", formatted, options);
+            assert_eq!(
+                format_statement(&reparsed, &options),
+                formatted,
+                "{:?}",
+                options
+            );
+        }
+    }
+}
+
+#[test]
+fn packed_lower_case_with_leading_commas() {
+    let options = FormatOptions::new()
+        .with_keyword_case(KeywordCase::Lower)
+        .with_one_item_per_line(false)
+        .with_leading_commas(true)
+        .with_indent_width(4)
+        .with_max_line_width(40);
+    assert_eq!(
+        format(
+            "SELECT customer_id, first_name, last_name, email_address, phone_number, \
+             street_address, city, postal_code FROM customers WHERE a::INT > 1",
+            &options
+        ),
+        "select customer_id, first_name
+    , last_name, email_address
+    , phone_number, street_address, city
+    , postal_code
+from customers
+where a::INT > 1"
+    );
+}
@@ -0,0 +1,187 @@
+use rust_sql_parser::ast::{
+    BinaryOperator, Expression, GroupByExpr, Ident, Keyword, SelectStatement, SetExpr, Statement,
+    Value,
+};
+use rust_sql_parser::error::Problem;
+use rust_sql_parser::parse_statement;
+
+/// Returns the SELECT of a plain query
+fn select(sql: &str) -> SelectStatement {
+    match parse_statement(sql).unwrap_or_else(|e| panic!("{}: {}", sql, e)) {
+        Statement::Query(query) => match query.body {
+            SetExpr::Select(select) => *select,
+            body => panic!("not a SELECT: {}", body),
+        },
+        statement => panic!("not a query: {}", statement),
+    }
+}
+
+fn column(name: &str) -> Expression {
+    Expression::Identifier(Ident::new(name))
+}
+
+#[test]
+fn columns_and_expressions() {
+    let select = select("SELECT dept, region, COUNT(*) FROM t GROUP BY dept, region");
+    assert_eq!(
+        select.group_by,
+        [
+            GroupByExpr::Expression(column("dept")),
+            GroupByExpr::Expression(column("region")),
+        ]
+    );
+
+    let select = self::select("SELECT COUNT(*) FROM t GROUP BY DATE(created_at), a + 1");
+    assert_eq!(
+        select.group_by,
+        [
+            GroupByExpr::Expression(Expression::Function {
+                name: "DATE".into(),
+                args: vec![column("created_at")],
+            }),
+            GroupByExpr::Expression(Expression::BinaryOperation {
+                left_operand: Box::new(column("a")),
+                operator: BinaryOperator::Plus,
+                right_operand: Box::new(Expression::Value(Value::Number(1))),
+            }),
+        ]
+    );
+}
+
+#[test]
+fn clauses_come_in_order() {
+    let select = select("SELECT a FROM t WHERE b = 1 GROUP BY a HAVING COUNT(*) > 1");
+    assert!(select.selection.is_some());
+    assert_eq!(select.group_by, [GroupByExpr::Expression(column("a"))]);
+    assert_eq!(select.having.unwrap().to_string(), "COUNT(*) > 1");
+
+    for (sql, column) in [
+        ("SELECT a FROM t HAVING COUNT(*) > 1 GROUP BY a", 37),
+        ("SELECT a FROM t ORDER BY a GROUP BY a", 28),
+        ("SELECT a FROM t GROUP BY a WHERE b = 1", 28),
+    ] {
+        let e = parse_statement(sql).unwrap_err();
+        assert!(
+            e.message().starts_with("GROUP after end of statement")
+                || e.message().starts_with("WHERE after end of statement"),
+            "{}: {}",
+            sql,
+            e
+        );
+        assert_eq!(e.span().unwrap().start.column, column, "{}", sql);
+    }
+}
+
+#[test]
+fn group_needs_by() {
+    let e = parse_statement("SELECT a FROM t GROUP a").unwrap_err();
+    match &e.problem {
+        Problem::ExpectedKeyword { keywords, .. } => assert_eq!(keywords, &[Keyword::By]),
+        problem => panic!("{:?}", problem),
+    }
+    assert_eq!(e.message(), "BY after GROUP, found a");
+    assert_eq!(e.span().unwrap().start.column, 23);
+}
+
+#[test]
+fn an_empty_list_is_an_error() {
+    for (sql, message) in [
+        (
+            "SELECT a FROM t GROUP BY",
+            "expected expression after GROUP BY",
+        ),
+        (
+            "SELECT a FROM t GROUP BY a,",
+            "expected expression after ',' in GROUP BY",
+        ),
+        (
+            "SELECT a FROM t GROUP BY HAVING a > 1",
+            "expression after GROUP BY, found HAVING",
+        ),
+    ] {
+        let e = parse_statement(sql).unwrap_err();
+        assert!(e.message().starts_with(message), "{}: {}", sql, e);
+    }
+}
+
+#[test]
+fn rollup_cube_and_grouping_sets() {
+    let select = select(
+        "SELECT a FROM t GROUP BY ROLLUP (region, city), CUBE (a, (b, c)), \
+         GROUPING SETS ((a), (a, b), ())",
+    );
+    assert_eq!(
+        select.group_by,
+        [
+            GroupByExpr::Rollup(vec![vec![column("region")], vec![column("city")]]),
+            GroupByExpr::Cube(vec![vec![column("a")], vec![column("b"), column("c")]]),
+            GroupByExpr::GroupingSets(vec![
+                vec![column("a")],
+                vec![column("a"), column("b")],
+                vec![],
+            ]),
+        ]
+    );
+}
+
+#[test]
+fn plain_expressions_mix_with_rollup() {
+    let select = select("SELECT a FROM t GROUP BY a, ROLLUP (b, c), d + 1");
+    assert_eq!(select.group_by.len(), 3);
+    assert_eq!(select.group_by[0], GroupByExpr::Expression(column("a")));
+    assert_eq!(
+        select.group_by[1],
+        GroupByExpr::Rollup(vec![vec![column("b")], vec![column("c")]])
+    );
+    assert_eq!(select.group_by[2].to_string(), "d + 1");
+}
+
+#[test]
+fn nested_parentheses() {
+    for (sql, printed) in [
+        // A parenthesized single expression is that expression
+        ("GROUP BY ((a))", "GROUP BY a"),
+        (
+            "GROUP BY GROUPING SETS (a, (b))",
+            "GROUP BY GROUPING SETS (a, b)",
+        ),
+        (
+            "GROUP BY GROUPING SETS (((a)))",
+            "GROUP BY GROUPING SETS (a)",
+        ),
+        (
+            "GROUP BY ROLLUP ((a + 1), (b, c))",
+            "GROUP BY ROLLUP (a + 1, (b, c))",
+        ),
+        (
+            "GROUP BY GROUPING SETS ((a, b), ((c)), ())",
+            "GROUP BY GROUPING SETS ((a, b), c, ())",
+        ),
+    ] {
+        let sql = format!("SELECT a FROM t {}", sql);
+        let statement = parse_statement(&sql).unwrap_or_else(|e| panic!("{}: {}", sql, e));
+        assert_eq!(
+            statement.to_string(),
+            format!("SELECT a FROM t {}", printed)
+        );
+        assert_eq!(parse_statement(&statement.to_string()).unwrap(), statement);
+    }
+}
+
+#[test]
+fn malformed_grouping_constructs() {
+    for (sql, message) in [
+        ("GROUP BY ROLLUP ()", "expression after ROLLUP, found ')'"),
+        ("GROUP BY CUBE (a", "expected ')' to close CUBE"),
+        ("GROUP BY GROUPING (a)", "SETS after GROUPING, found '('"),
+        // A list of expressions only groups inside ROLLUP, CUBE or GROUPING SETS
+        (
+            "GROUP BY (a, b)",
+            "')' to close parenthesized expression, found ','",
+        ),
+    ] {
+        let sql = format!("SELECT a FROM t {}", sql);
+        let e = parse_statement(&sql).unwrap_err();
+        assert!(e.message().starts_with(message), "{}: {}", sql, e);
+    }
+}
@@ -0,0 +1,85 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+use rust_sql_parser::ast::{Expression, Float, Value};
+use rust_sql_parser::{parse_expression, tokenize};
+
+fn hash(value: &impl Hash) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn expr(sql: &str) -> Expression {
+    parse_expression(sql).unwrap()
+}
+
+#[test]
+fn equal_trees_hash_equal() {
+    for sql in [
+        "a + 2 * b",
+        "f(a, 'x', NULL) IS NOT NULL",
+        "x NOT IN (1, 2.5, TRUE)",
+        "t.a::VARCHAR(10) || $1",
+        "(SELECT MAX(b) FROM u WHERE u.id = t.id) > 1",
+    ] {
+        // Spacing and redundant parentheses do not reach the tree
+        let respaced = sql.replace(' ', "  ");
+        assert_eq!(expr(sql), expr(&respaced), "{}", sql);
+        assert_eq!(hash(&expr(sql)), hash(&expr(&respaced)), "{}", sql);
+    }
+    assert_eq!(hash(&expr("((a)) + b")), hash(&expr("a + b")));
+    assert_eq!(
+        hash(&tokenize("SELECT a").unwrap()),
+        hash(&tokenize("select  a").unwrap())
+    );
+}
+
+#[test]
+fn different_trees_hash_differently() {
+    for (a, b) in [
+        ("a + b", "b + a"),
+        ("a - b - c", "a - (b - c)"),
+        ("a = 1", "a = 1.0"),
+        ("a = 1", "a = '1'"),
+        ("a", "\"a\""),
+        ("t.a", "a"),
+        ("a IS NULL", "a IS NOT NULL"),
+        ("f(a, b)", "f(b, a)"),
+    ] {
+        assert_ne!(expr(a), expr(b), "{} {}", a, b);
+        assert_ne!(hash(&expr(a)), hash(&expr(b)), "{} {}", a, b);
+    }
+}
+
+#[test]
+fn expressions_key_maps() {
+    let mut counts: HashMap<Expression, usize> = HashMap::new();
+    for sql in ["a + 1", "a  +  1", "(a + 1)", "a + 2", "a + 1.0"] {
+        *counts.entry(expr(sql)).or_default() += 1;
+    }
+    assert_eq!(counts[&expr("a + 1")], 3);
+    assert_eq!(counts.len(), 3);
+}
+
+#[test]
+fn floats_compare_and_hash_by_their_bits() {
+    let nan = Value::Float(Float(f64::NAN));
+    assert_eq!(nan, nan.clone());
+    assert_eq!(hash(&nan), hash(&nan.clone()));
+    // A NaN with other bits is a different literal
+    let other_nan = Value::Float(Float(f64::from_bits(f64::NAN.to_bits() ^ 1)));
+    assert_ne!(nan, other_nan);
+
+    let zero = Value::Float(Float(0.0));
+    let negative_zero = Value::Float(Float(-0.0));
+    assert_ne!(zero, negative_zero);
+    assert_ne!(hash(&zero), hash(&negative_zero));
+
+    assert_ne!(Value::Float(Float(1.0)), Value::Number(1));
+    assert_eq!(
+        Value::Float(Float(f64::INFINITY)),
+        Value::Float(Float(f64::INFINITY))
+    );
+}
@@ -0,0 +1,124 @@
+use rust_sql_parser::incremental::{ParsedScript, StatementId};
+
+/// Pieces of SQL that scripts and edits are built from, including ones that change how the
+/// rest of the script splits into statements
+const FRAGMENTS: &[&str] = &[
+    "SELECT 1",
+    "SELECT a FROM t WHERE b = 'x;y'",
+    "INSERT INTO t VALUES (1, 'a')",
+    "DELETE FROM t",
+    "UPDATE t SET a = a + 1",
+    ";",
+    "; ",
+    "\n",
+    " ",
+    "'",
+    "\"",
+    "--",
+    "-- note;\n",
+    "/*",
+    "*/",
+    "/* a; b */",
+    "SELECT",
+    "FROM",
+    "(",
+    ")",
+    "x",
+    "é",
+    "1 +",
+];
+
+/// A xorshift generator, so that a failure reproduces from its seed
+struct Rng(u64);
+
+impl Rng {
+    fn below(&mut self, n: usize) -> usize {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 % n as u64) as usize
+    }
+
+    fn fragments(&mut self, max: usize) -> String {
+        let count = self.below(max + 1);
+        (0..count)
+            .map(|_| FRAGMENTS[self.below(FRAGMENTS.len())])
+            .collect()
+    }
+
+    /// Returns a character boundary of `text`
+    fn boundary(&mut self, text: &str) -> usize {
+        let boundaries: Vec<usize> = text
+            .char_indices()
+            .map(|(i, _)| i)
+            .chain([text.len()])
+            .collect();
+        boundaries[self.below(boundaries.len())]
+    }
+}
+
+/// Asserts that `script` holds what parsing its source from scratch gives
+fn assert_reparsed(script: &ParsedScript, context: &str) {
+    let fresh = ParsedScript::new(script.source());
+    let spans = |script: &ParsedScript| -> Vec<_> {
+        script.ids().map(|id| script.span(id).unwrap()).collect()
+    };
+    assert_eq!(spans(script), spans(&fresh), "{}", context);
+    assert!(script.statements().eq(fresh.statements()), "{}", context);
+    let errors =
+        |script: &ParsedScript| -> Vec<String> { script.errors().map(|e| e.to_string()).collect() };
+    assert_eq!(errors(script), errors(&fresh), "{}", context);
+}
+
+/// Returns the statement as SQL, or if it did not parse the error's message and statement
+/// number, which unlike its location do not change with edits before it
+fn outcome(script: &ParsedScript, id: StatementId) -> Result<String, (String, Option<usize>)> {
+    match (script.statement(id), script.error(id)) {
+        (Some(statement), _) => Ok(statement.to_string()),
+        (None, Some(e)) => Err((e.message(), e.statement())),
+        (None, None) => Err((String::new(), None)),
+    }
+}
+
+#[test]
+fn random_edits_match_a_full_reparse() {
+    for seed in 1..=200u64 {
+        let mut rng = Rng(seed.wrapping_mul(0x9E37_79B9_7F4A_7C15));
+        let mut source = rng.fragments(12);
+        let mut script = ParsedScript::new(&source);
+        assert_reparsed(&script, &format!("seed {}: {:?}", seed, source));
+
+        for _ in 0..30 {
+            let a = rng.boundary(&source);
+            let b = rng.boundary(&source);
+            let range = a.min(b)..a.max(b);
+            let replacement = rng.fragments(3);
+            let context = format!(
+                "seed {}: {:?} with {:?} replaced by {:?}",
+                seed, source, range, replacement
+            );
+
+            let before: Vec<_> = script.ids().map(|id| (id, outcome(&script, id))).collect();
+            let changed = script.apply_edit(range.clone(), &replacement).to_vec();
+            source.replace_range(range, &replacement);
+            assert_eq!(script.source(), source, "{}", context);
+            assert_reparsed(&script, &context);
+
+            // A statement that was not reported is unchanged
+            for (id, outcome_before) in before {
+                if script.span(id).is_some() && !changed.contains(&id) {
+                    assert_eq!(outcome(&script, id), outcome_before, "{}", context);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn edits_that_only_touch_one_statement_reparse_only_it() {
+    let mut script = ParsedScript::new("SELECT 1; SELECT 2; SELECT 3;");
+    let ids: Vec<_> = script.ids().collect();
+    assert_eq!(script.apply_edit(7..8, "10"), [ids[0]]);
+    assert_eq!(script.ids().collect::<Vec<_>>(), ids);
+    assert_reparsed(&script, "one statement");
+}
@@ -0,0 +1,105 @@
+use rust_sql_parser::ast::{Ident, ObjectName, OrderByItem, Statement};
+use rust_sql_parser::parse_statement;
+
+/// Index definitions as written in migrations, each printing back unchanged
+const INDEXES: &[&str] = &[
+    "CREATE UNIQUE INDEX idx_users_email ON users (email)",
+    "CREATE INDEX idx_orders_user ON orders (user_id, created_at DESC)",
+    "CREATE INDEX IF NOT EXISTS idx_events_time ON analytics.events (occurred_at)",
+    "CREATE UNIQUE INDEX IF NOT EXISTS idx_users_lower_email ON users (LOWER(email))",
+    "CREATE INDEX idx_items_rank ON items (category ASC, score DESC NULLS LAST)",
+    "CREATE INDEX \"Idx Mixed\" ON \"My Table\" (\"Col\", a + b)",
+];
+
+#[test]
+fn index_definitions_round_trip() {
+    for sql in INDEXES {
+        let statement = parse_statement(sql).unwrap_or_else(|e| panic!("{}: {}", sql, e));
+        assert_eq!(statement.to_string(), *sql);
+        assert_eq!(parse_statement(&statement.to_string()).unwrap(), statement);
+    }
+}
+
+#[test]
+fn index_tree() {
+    let statement =
+        parse_statement("CREATE UNIQUE INDEX IF NOT EXISTS i ON app.t (a ASC, LOWER(b) DESC, c)")
+            .unwrap();
+    match statement {
+        Statement::CreateIndex {
+            name,
+            table,
+            columns,
+            unique,
+            if_not_exists,
+        } => {
+            assert_eq!(name, "i");
+            assert_eq!(table, ObjectName(vec![Ident::new("app"), Ident::new("t")]));
+            assert!(unique);
+            assert!(if_not_exists);
+            let columns: Vec<(String, Option<bool>)> = columns
+                .into_iter()
+                .map(|OrderByItem { expr, asc, .. }| (expr.to_string(), asc))
+                .collect();
+            assert_eq!(
+                columns,
+                [
+                    ("a".to_string(), Some(true)),
+                    ("LOWER(b)".to_string(), Some(false)),
+                    ("c".to_string(), None),
+                ]
+            );
+        }
+        statement => panic!("{}", statement),
+    }
+}
+
+#[test]
+fn malformed_index_definitions() {
+    for (sql, message, column) in [
+        (
+            "CREATE INDEX i t (a)",
+            "ON after CREATE INDEX i, found t",
+            16,
+        ),
+        (
+            "CREATE INDEX i ON t",
+            "expected '(' and a column list after ON t, expected one of: '.', '('",
+            20,
+        ),
+        (
+            "CREATE INDEX i ON t ()",
+            "expression after index column list, found ')'",
+            22,
+        ),
+        (
+            "CREATE INDEX ON t (a)",
+            "index name after CREATE INDEX, found ON",
+            14,
+        ),
+        ("CREATE UNIQUE x", "INDEX after CREATE UNIQUE, found x", 15),
+        (
+            "CREATE UNIQUE INDEX",
+            "expected index name after CREATE INDEX",
+            20,
+        ),
+        (
+            "CREATE INDEX IF EXISTS i ON t (a)",
+            "NOT after IF in CREATE INDEX, found EXISTS",
+            17,
+        ),
+        (
+            "CREATE INDEX IF NOT i ON t (a)",
+            "EXISTS after IF NOT, found i",
+            21,
+        ),
+    ] {
+        let e = parse_statement(sql).unwrap_err();
+        assert_eq!(
+            (e.message(), e.span().unwrap().start.column),
+            (message.to_string(), column),
+            "{}",
+            sql
+        );
+    }
+}
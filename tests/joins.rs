@@ -0,0 +1,108 @@
+use rust_sql_parser::ast::{JoinConstraint, JoinType, SetExpr, Statement, TableWithJoins};
+use rust_sql_parser::error::Problem;
+use rust_sql_parser::parse_statement;
+
+/// Returns the FROM clause of a plain SELECT
+fn from(sql: &str) -> TableWithJoins {
+    match parse_statement(sql).unwrap() {
+        Statement::Query(query) => match query.body {
+            SetExpr::Select(select) => select.from.expect("a FROM clause"),
+            body => panic!("not a SELECT: {}", body),
+        },
+        statement => panic!("not a query: {}", statement),
+    }
+}
+
+#[test]
+fn using_with_inner_and_left_joins() {
+    for (sql, join_type) in [
+        (
+            "SELECT * FROM a JOIN b USING (id, tenant_id)",
+            JoinType::Inner,
+        ),
+        (
+            "SELECT * FROM a INNER JOIN b USING (id, tenant_id)",
+            JoinType::Inner,
+        ),
+        (
+            "SELECT * FROM a LEFT JOIN b USING (id, tenant_id)",
+            JoinType::LeftOuter,
+        ),
+    ] {
+        let join = &from(sql).joins[0];
+        assert_eq!(join.join_type, join_type);
+        assert_eq!(
+            join.constraint,
+            JoinConstraint::Using(vec!["id".into(), "tenant_id".into()])
+        );
+    }
+}
+
+#[test]
+fn natural_with_inner_and_left_joins() {
+    for (sql, join_type, printed) in [
+        (
+            "SELECT * FROM a NATURAL JOIN b",
+            JoinType::Inner,
+            "SELECT * FROM a NATURAL JOIN b",
+        ),
+        (
+            "SELECT * FROM a NATURAL INNER JOIN b",
+            JoinType::Inner,
+            "SELECT * FROM a NATURAL JOIN b",
+        ),
+        (
+            "SELECT * FROM a NATURAL LEFT OUTER JOIN b",
+            JoinType::LeftOuter,
+            "SELECT * FROM a NATURAL LEFT JOIN b",
+        ),
+    ] {
+        let join = &from(sql).joins[0];
+        assert_eq!(join.join_type, join_type);
+        assert_eq!(join.constraint, JoinConstraint::Natural);
+        assert_eq!(parse_statement(sql).unwrap().to_string(), printed);
+    }
+}
+
+#[test]
+fn natural_with_an_explicit_condition_is_rejected() {
+    for sql in [
+        "SELECT * FROM a NATURAL JOIN b ON a.id = b.id",
+        "SELECT * FROM a NATURAL LEFT JOIN b USING (id)",
+    ] {
+        let e = parse_statement(sql).unwrap_err();
+        assert!(
+            e.to_string()
+                .contains("natural joins take no explicit condition"),
+            "{}",
+            e
+        );
+    }
+}
+
+#[test]
+fn using_takes_identifiers_only() {
+    for sql in [
+        "SELECT * FROM a JOIN b USING (a + 1)",
+        "SELECT * FROM a JOIN b USING (1)",
+        "SELECT * FROM a LEFT JOIN b USING (id, f(x))",
+    ] {
+        let e = parse_statement(sql).unwrap_err();
+        assert!(
+            matches!(e.problem, Problem::ExpectedIdentifier { .. }),
+            "{}: {}",
+            sql,
+            e
+        );
+    }
+}
+
+#[test]
+fn using_list_must_not_be_empty() {
+    let e = parse_statement("SELECT * FROM a JOIN b USING ()").unwrap_err();
+    assert!(
+        matches!(e.problem, Problem::ExpectedIdentifier { .. }),
+        "{}",
+        e
+    );
+}
@@ -0,0 +1,80 @@
+#![cfg(feature = "json")]
+
+use rust_sql_parser::error::EvalError;
+use rust_sql_parser::options::PredicateOptions;
+use rust_sql_parser::parse_expression;
+use serde_json::{json, Value};
+
+fn rows() -> Vec<Value> {
+    vec![
+        json!({"id": 1, "name": "alice", "age": 31, "team": "core", "address": {"city": "Oslo"}}),
+        json!({"id": 2, "name": "bob", "age": 17, "team": null}),
+        json!({"id": 3, "name": "carol", "age": 45, "team": "infra", "address": {"city": "Bergen"}}),
+        json!({"id": 4, "name": "dave", "age": 31, "team": "core"}),
+        json!({"id": 5, "name": "alfred", "team": "web"}),
+    ]
+}
+
+/// Returns the ids of the rows `condition` lets through
+fn filter(condition: &str) -> Vec<i64> {
+    let predicate = parse_expression(condition)
+        .unwrap_or_else(|e| panic!("{}: {}", condition, e))
+        .compile_predicate()
+        .unwrap_or_else(|e| panic!("{}: {}", condition, e));
+    rows()
+        .iter()
+        .map(|row| row.as_object().unwrap())
+        .filter(|row| predicate(row).unwrap_or_else(|e| panic!("{}: {}", condition, e)))
+        .map(|row| match row.get("id") {
+            Some(Value::Number(n)) => n.as_i64().unwrap(),
+            _ => panic!("row without an id"),
+        })
+        .collect()
+}
+
+#[test]
+fn comparisons_and_logic() {
+    assert_eq!(filter("age > 30"), [1, 3, 4]);
+    assert_eq!(filter("age >= 18 AND team = 'core'"), [1, 4]);
+    assert_eq!(filter("NOT (age < 40) OR name = 'bob'"), [2, 3]);
+    assert_eq!(filter("age + 10 * 2 = 51"), [1, 4]);
+}
+
+#[test]
+fn in_lists() {
+    assert_eq!(filter("team IN ('core', 'web')"), [1, 4, 5]);
+    assert_eq!(filter("id IN (2, 3)"), [2, 3]);
+    // A NULL team is neither in nor out of the list
+    assert_eq!(filter("team NOT IN ('core')"), [3, 5]);
+    assert_eq!(filter("id NOT IN (1, NULL)"), Vec::<i64>::new());
+}
+
+#[test]
+fn null_checks() {
+    assert_eq!(filter("team IS NULL"), [2]);
+    assert_eq!(filter("age IS NULL"), [5]);
+    assert_eq!(filter("team IS NOT NULL AND age IS NOT NULL"), [1, 3, 4]);
+    assert_eq!(filter("address.city IS NOT NULL"), [1, 3]);
+}
+
+#[test]
+fn like_patterns() {
+    assert_eq!(filter("name LIKE 'al%'"), [1, 5]);
+    assert_eq!(filter("name LIKE '_o%'"), [2]);
+    assert_eq!(filter("name NOT LIKE '%a%'"), [2]);
+    assert_eq!(filter("address.city LIKE '%o'"), [1]);
+    assert_eq!(filter("name LIKE 'ALICE'"), Vec::<i64>::new());
+}
+
+#[test]
+fn unknown_fields_can_be_rejected() {
+    let predicate = parse_expression("nickname IS NULL")
+        .unwrap()
+        .compile_predicate_with_options(PredicateOptions::new().with_unknown_fields_rejected(true))
+        .unwrap();
+    let row = json!({"id": 1});
+    assert!(matches!(
+        predicate(row.as_object().unwrap()),
+        Err(EvalError::UnknownIdentifier(_))
+    ));
+}
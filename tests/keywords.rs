@@ -0,0 +1,108 @@
+use rust_sql_parser::ast::{
+    Expression, Ident, ObjectName, SelectItem, SelectStatement, SetExpr, Statement, TableReference,
+};
+use rust_sql_parser::parse_statement;
+
+/// Returns the SELECT of a plain query
+fn select(sql: &str) -> SelectStatement {
+    match parse_statement(sql).unwrap_or_else(|e| panic!("{}: {}", sql, e)) {
+        Statement::Query(query) => match query.body {
+            SetExpr::Select(select) => *select,
+            body => panic!("not a SELECT: {}", body),
+        },
+        statement => panic!("not a query: {}", statement),
+    }
+}
+
+fn columns(select: &SelectStatement) -> Vec<Ident> {
+    select
+        .projection
+        .iter()
+        .map(|item| match item {
+            SelectItem::Expression(Expression::Identifier(ident)) => ident.clone(),
+            item => panic!("not a column: {}", item),
+        })
+        .collect()
+}
+
+/// Returns the message and column of the error parsing `sql`
+fn error(sql: &str) -> (String, usize) {
+    let e = parse_statement(sql).unwrap_err();
+    (
+        e.message(),
+        e.span().expect("errors are located").start.column,
+    )
+}
+
+#[test]
+fn non_reserved_keywords_are_column_names() {
+    let select = select("SELECT value, key FROM config");
+    assert_eq!(columns(&select), [Ident::new("value"), Ident::new("key")]);
+    match &select.from.unwrap().relation {
+        TableReference::Table { name, alias: None } => {
+            assert_eq!(name, &ObjectName(vec![Ident::new("config")]))
+        }
+        relation => panic!("{}", relation),
+    }
+
+    let select = self::select("SELECT text, index FROM t WHERE text = 'x' AND index > 1");
+    assert_eq!(columns(&select), [Ident::new("text"), Ident::new("index")]);
+    assert_eq!(
+        select.selection.unwrap().to_string(),
+        "text = 'x' AND index > 1"
+    );
+}
+
+#[test]
+fn non_reserved_keywords_are_column_definitions() {
+    match parse_statement("CREATE TABLE t (key INT, value TEXT, text TEXT, index INT)").unwrap() {
+        Statement::CreateTable { columns, .. } => {
+            let names: Vec<&str> = columns.iter().map(|column| &*column.name).collect();
+            assert_eq!(names, ["key", "value", "text", "index"]);
+        }
+        statement => panic!("{}", statement),
+    }
+}
+
+#[test]
+fn reserved_keywords_can_be_quoted() {
+    let select = select("SELECT \"order\" FROM t");
+    assert_eq!(
+        columns(&select),
+        [Ident {
+            value: "order".into(),
+            quote_style: Some('"'),
+        }]
+    );
+    assert_eq!(select.to_string(), "SELECT \"order\" FROM t");
+}
+
+#[test]
+fn reserved_keywords_are_not_column_names() {
+    assert_eq!(
+        error("SELECT from FROM t"),
+        (
+            "projection item after SELECT, found FROM, expected one of: ALL, DISTINCT".to_string(),
+            8
+        )
+    );
+    assert_eq!(
+        error("SELECT order FROM t"),
+        (
+            "ORDER at the start of an expression, expected one of: ALL, DISTINCT".to_string(),
+            8
+        )
+    );
+}
+
+#[test]
+fn reserved_keywords_are_not_aliases() {
+    assert_eq!(
+        error("SELECT a FROM t AS order"),
+        ("alias after AS, found ORDER".to_string(), 20)
+    );
+    assert_eq!(
+        select("SELECT a FROM t AS \"order\"").to_string(),
+        "SELECT a FROM t AS \"order\""
+    );
+}
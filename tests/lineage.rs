@@ -0,0 +1,124 @@
+use rust_sql_parser::analysis::ColumnRef;
+use rust_sql_parser::ast::{SetExpr, Statement};
+use rust_sql_parser::parse_statement;
+
+/// Returns each output column as its name, its sources written as `table.column`, and
+/// whether it is unresolved
+fn lineage(sql: &str) -> Vec<(String, Vec<String>, bool)> {
+    let query = match parse_statement(sql).unwrap_or_else(|e| panic!("{}: {}", sql, e)) {
+        Statement::Query(query) => query,
+        statement => panic!("not a query: {}", statement),
+    };
+    query
+        .output_lineage()
+        .into_iter()
+        .map(|column| {
+            let sources = column.sources.iter().map(written).collect();
+            (column.name, sources, column.unresolved)
+        })
+        .collect()
+}
+
+fn written(source: &ColumnRef) -> String {
+    match &source.table {
+        Some(table) => format!("{}.{}", table, source.column),
+        None => source.column.clone(),
+    }
+}
+
+fn resolved(name: &str, sources: &[&str]) -> (String, Vec<String>, bool) {
+    let sources = sources.iter().map(|source| source.to_string()).collect();
+    (name.to_string(), sources, false)
+}
+
+#[test]
+fn through_table_and_column_aliases() {
+    assert_eq!(
+        lineage(
+            "SELECT u.name AS customer, SUM(o.total) AS spend FROM users u \
+             JOIN orders o ON u.id = o.user_id GROUP BY u.name"
+        ),
+        [
+            resolved("customer", &["users.name"]),
+            resolved("spend", &["orders.total"]),
+        ]
+    );
+    // The select-level method agrees with the query-level one
+    let Statement::Query(query) = parse_statement("SELECT a + b AS c FROM t AS x").unwrap() else {
+        unreachable!()
+    };
+    let SetExpr::Select(select) = &query.body else {
+        unreachable!()
+    };
+    assert_eq!(select.output_lineage(), query.output_lineage());
+    assert_eq!(
+        lineage("SELECT a + x.b AS c FROM t AS x"),
+        [resolved("c", &["t.a", "t.b"])]
+    );
+}
+
+#[test]
+fn through_a_cte() {
+    assert_eq!(
+        lineage(
+            "WITH recent AS (SELECT user_id, total AS amount FROM orders WHERE placed > 1) \
+             SELECT r.user_id, r.amount * 2 AS doubled FROM recent r"
+        ),
+        [
+            resolved("user_id", &["orders.user_id"]),
+            resolved("doubled", &["orders.total"]),
+        ]
+    );
+    // A column list on the CTE renames its outputs
+    assert_eq!(
+        lineage("WITH c (k, v) AS (SELECT a, b FROM t) SELECT k, v FROM c"),
+        [resolved("k", &["t.a"]), resolved("v", &["t.b"])]
+    );
+}
+
+#[test]
+fn through_a_derived_table() {
+    assert_eq!(
+        lineage(
+            "SELECT d.region, d.n FROM \
+             (SELECT region, COUNT(*) AS n FROM sales s GROUP BY region) AS d"
+        ),
+        [resolved("region", &["sales.region"]), resolved("n", &[])]
+    );
+    assert_eq!(
+        lineage("SELECT x FROM (SELECT a FROM t) AS d (x)"),
+        [resolved("x", &["t.a"])]
+    );
+}
+
+#[test]
+fn wildcards_expand_or_stay_unresolved() {
+    assert_eq!(
+        lineage("SELECT * FROM users u JOIN (SELECT id, total FROM orders) AS o ON u.id = o.id"),
+        [
+            ("users.*".to_string(), vec!["users.*".to_string()], true),
+            resolved("id", &["orders.id"]),
+            resolved("total", &["orders.total"]),
+        ]
+    );
+    assert_eq!(
+        lineage("SELECT o.*, x.* FROM orders o, (SELECT 1 AS one) x"),
+        [
+            ("orders.*".to_string(), vec!["orders.*".to_string()], true),
+            resolved("one", &[]),
+        ]
+    );
+    // A column read from a derived table that could not expand its `*`
+    assert_eq!(
+        lineage("SELECT d.a FROM (SELECT * FROM t) AS d"),
+        [resolved("a", &["t.a"])]
+    );
+}
+
+#[test]
+fn unqualified_columns_over_several_tables_stay_unqualified() {
+    assert_eq!(
+        lineage("SELECT name, total FROM users, orders"),
+        [resolved("name", &["name"]), resolved("total", &["total"])]
+    );
+}
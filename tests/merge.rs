@@ -0,0 +1,117 @@
+use rust_sql_parser::ast::{MergeAction, MergeClause, Statement, TableReference};
+use rust_sql_parser::parse_statement;
+
+/// MERGE statements of the kind an upsert job writes, each printing back unchanged
+const STATEMENTS: &[&str] = &[
+    "MERGE INTO target USING source ON target.id = source.id \
+     WHEN MATCHED THEN UPDATE SET a = source.a \
+     WHEN NOT MATCHED THEN INSERT (id, a) VALUES (source.id, source.a)",
+    "MERGE INTO inventory AS i USING shipments AS s ON i.sku = s.sku \
+     WHEN MATCHED THEN UPDATE SET qty = i.qty + s.qty",
+    "MERGE INTO t USING (SELECT id, gone FROM u) AS s ON t.id = s.id \
+     WHEN MATCHED AND s.gone THEN DELETE WHEN NOT MATCHED THEN DO NOTHING",
+    "MERGE INTO app.customers USING staging.customers AS s ON customers.id = s.id \
+     WHEN NOT MATCHED AND s.active THEN INSERT (id, name) VALUES (s.id, s.name)",
+    "MERGE INTO t USING u ON t.id = u.id WHEN MATCHED AND u.a IS NULL THEN DELETE \
+     WHEN MATCHED THEN UPDATE SET a = u.a, b = DEFAULT_B(u.b)",
+];
+
+#[test]
+fn merge_statements_round_trip() {
+    for sql in STATEMENTS {
+        let statement = parse_statement(sql).unwrap_or_else(|e| panic!("{}: {}", sql, e));
+        assert_eq!(statement.to_string(), *sql);
+        assert_eq!(parse_statement(&statement.to_string()).unwrap(), statement);
+    }
+}
+
+#[test]
+fn merge_tree() {
+    match parse_statement(STATEMENTS[2]).unwrap() {
+        Statement::Merge {
+            target,
+            source,
+            on,
+            clauses,
+        } => {
+            assert!(matches!(target, TableReference::Table { alias: None, .. }));
+            assert!(matches!(
+                source,
+                TableReference::Derived { alias: Some(_), .. }
+            ));
+            assert_eq!(on.to_string(), "t.id = s.id");
+            assert_eq!(
+                clauses
+                    .iter()
+                    .map(
+                        |MergeClause {
+                             matched,
+                             predicate,
+                             action,
+                         }| (
+                            *matched,
+                            predicate.as_ref().map(ToString::to_string),
+                            action.clone()
+                        )
+                    )
+                    .collect::<Vec<_>>(),
+                [
+                    (true, Some("s.gone".to_string()), MergeAction::Delete),
+                    (false, None, MergeAction::DoNothing),
+                ]
+            );
+        }
+        statement => panic!("{}", statement),
+    }
+
+    match parse_statement(STATEMENTS[0]).unwrap() {
+        Statement::Merge { clauses, .. } => match &clauses[1].action {
+            MergeAction::Insert { columns, values } => {
+                assert_eq!(columns, &["id", "a"]);
+                assert_eq!(values.len(), 2);
+            }
+            action => panic!("{}", action),
+        },
+        statement => panic!("{}", statement),
+    }
+}
+
+#[test]
+fn malformed_merge_statements() {
+    for (sql, message, column) in [
+        (
+            "MERGE t USING u ON a WHEN MATCHED THEN DELETE",
+            "INTO after MERGE, found t",
+            7,
+        ),
+        (
+            "MERGE INTO t USING u WHEN MATCHED THEN DELETE",
+            "ON after MERGE ... USING source, found WHEN, expected one of: '.', AS, ON",
+            22,
+        ),
+        (
+            "MERGE INTO t USING u ON t.id = u.id",
+            "WHEN after MERGE ... ON condition, found end of input, \
+             expected one of: '.', an operator, WHEN",
+            36,
+        ),
+        (
+            "MERGE INTO t USING u ON t.id = u.id WHEN MATCHED THEN INSERT (a) VALUES (1)",
+            "UPDATE, DELETE or DO after WHEN MATCHED THEN, found INSERT",
+            55,
+        ),
+        (
+            "MERGE INTO t USING u ON t.id = u.id WHEN NOT MATCHED THEN UPDATE SET a = 1",
+            "INSERT or DO after WHEN NOT MATCHED THEN, found UPDATE",
+            59,
+        ),
+    ] {
+        let e = parse_statement(sql).unwrap_err();
+        assert_eq!(
+            (e.message(), e.span().unwrap().start.column),
+            (message.to_string(), column),
+            "{}",
+            sql
+        );
+    }
+}
@@ -0,0 +1,91 @@
+use rust_sql_parser::ast::Statement;
+use rust_sql_parser::options::{IdentifierCase, NormalizeOptions};
+use rust_sql_parser::parse_statement;
+
+fn normalized(sql: &str, options: NormalizeOptions) -> Statement {
+    let mut statement = parse_statement(sql).unwrap();
+    statement.normalize(options);
+    statement
+}
+
+fn postgres() -> NormalizeOptions {
+    NormalizeOptions::new().with_identifier_case(IdentifierCase::Lower)
+}
+
+#[test]
+fn unquoted_identifiers_fold_to_equal_asts() {
+    let a = normalized("SELECT Name FROM Users", postgres());
+    let b = normalized("select name from users", postgres());
+    assert_eq!(a, b);
+    assert_eq!(a.to_string(), "SELECT name FROM users");
+}
+
+#[test]
+fn quoted_identifiers_are_left_alone() {
+    let a = normalized("SELECT \"Name\" FROM Users", postgres());
+    let b = normalized("select name from users", postgres());
+    assert_ne!(a, b);
+    assert_eq!(a.to_string(), "SELECT \"Name\" FROM users");
+}
+
+#[test]
+fn upper_folding() {
+    let statement = normalized(
+        "SELECT name, \"x\" FROM s.users",
+        NormalizeOptions::new().with_identifier_case(IdentifierCase::Upper),
+    );
+    assert_eq!(statement.to_string(), "SELECT NAME, \"x\" FROM S.USERS");
+}
+
+#[test]
+fn preserve_changes_nothing() {
+    let sql = "SELECT Name, t.B FROM T AS t";
+    assert_eq!(
+        normalized(sql, NormalizeOptions::new()),
+        parse_statement(sql).unwrap()
+    );
+}
+
+#[test]
+fn function_and_type_names_are_folded() {
+    let statement = normalized("SELECT Upper(X), Y::MyType, Z::int FROM T", postgres());
+    assert_eq!(
+        statement.to_string(),
+        "SELECT upper(x), y::mytype, z::INT FROM t"
+    );
+}
+
+#[test]
+fn qualifiers_of_the_only_table_are_stripped() {
+    let options = postgres().with_qualifiers_stripped(true);
+    assert_eq!(
+        normalized("SELECT T.a FROM T WHERE t.b = 1", options.clone()).to_string(),
+        "SELECT a FROM t WHERE b = 1"
+    );
+    // With two tables a qualifier tells them apart
+    assert_eq!(
+        normalized("SELECT t.a FROM t JOIN u ON t.id = u.id", options).to_string(),
+        "SELECT t.a FROM t JOIN u ON t.id = u.id"
+    );
+}
+
+#[test]
+fn unquoted_aliases_are_folded() {
+    let statement = normalized(
+        "SELECT a AS Total, b AS \"Kept\" FROM T AS X (Y, \"Z\") WHERE X.y = 1",
+        postgres(),
+    );
+    assert_eq!(
+        statement.to_string(),
+        "SELECT a AS total, b AS \"Kept\" FROM t AS x (y, \"Z\") WHERE x.y = 1"
+    );
+    // The alias is folded like the qualifiers that refer to it
+    assert_eq!(
+        normalized(
+            "SELECT X.a FROM t AS X",
+            postgres().with_qualifiers_stripped(true)
+        )
+        .to_string(),
+        "SELECT a FROM t AS x"
+    );
+}
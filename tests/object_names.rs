@@ -0,0 +1,144 @@
+use rust_sql_parser::ast::{
+    Expression, Ident, ObjectName, SelectItem, SetExpr, Statement, TableConstraint, TableReference,
+};
+use rust_sql_parser::parse_statement;
+
+fn name(parts: &[&str]) -> ObjectName {
+    ObjectName(parts.iter().map(|part| Ident::new(*part)).collect())
+}
+
+fn quoted(part: &str) -> Ident {
+    Ident {
+        value: part.into(),
+        quote_style: Some('"'),
+    }
+}
+
+fn table_name(table: &TableReference) -> &ObjectName {
+    match table {
+        TableReference::Table { name, .. } => name,
+        table => panic!("not a table: {}", table),
+    }
+}
+
+/// Returns the message and column of the error parsing `sql`
+fn error(sql: &str) -> (String, usize) {
+    let e = parse_statement(sql).unwrap_err();
+    (
+        e.message(),
+        e.span().expect("errors are located").start.column,
+    )
+}
+
+#[test]
+fn dotted_names_in_expressions_and_from() {
+    let Statement::Query(query) =
+        parse_statement("SELECT analytics.events.id, events.id FROM analytics.events").unwrap()
+    else {
+        panic!("not a query")
+    };
+    let SetExpr::Select(select) = query.body else {
+        panic!("not a SELECT")
+    };
+    // In an expression every part is a compound identifier, whatever it names
+    assert_eq!(
+        select.projection,
+        [
+            SelectItem::Expression(Expression::CompoundIdentifier(
+                name(&["analytics", "events", "id"]).0
+            )),
+            SelectItem::Expression(Expression::CompoundIdentifier(name(&["events", "id"]).0)),
+        ]
+    );
+    // In FROM the parts name a table
+    assert_eq!(
+        table_name(&select.from.unwrap().relation),
+        &name(&["analytics", "events"])
+    );
+}
+
+#[test]
+fn every_statement_takes_qualified_names() {
+    let names = |sql: &str| -> Vec<ObjectName> {
+        match parse_statement(sql).unwrap_or_else(|e| panic!("{}: {}", sql, e)) {
+            Statement::Insert { table, .. } => vec![table],
+            Statement::Update { table, .. } => vec![table_name(&table).clone()],
+            Statement::CreateTable {
+                name, constraints, ..
+            } => {
+                let mut names = vec![name];
+                for constraint in constraints {
+                    if let TableConstraint::ForeignKey { foreign_table, .. } = constraint {
+                        names.push(foreign_table);
+                    }
+                }
+                names
+            }
+            Statement::Drop { names, .. } => names,
+            statement => panic!("{}", statement),
+        }
+    };
+    let three = name(&["mydb", "public", "users"]);
+    assert_eq!(
+        names("INSERT INTO mydb.public.users VALUES (1)"),
+        vec![three.clone()]
+    );
+    assert_eq!(
+        names("UPDATE mydb.public.users SET a = 1"),
+        vec![three.clone()]
+    );
+    assert_eq!(
+        names("CREATE TABLE mydb.public.users (a INT, FOREIGN KEY (a) REFERENCES s.t (b))"),
+        [three.clone(), name(&["s", "t"])]
+    );
+    assert_eq!(
+        names("DROP TABLE mydb.public.users, t"),
+        [three, name(&["t"])]
+    );
+}
+
+#[test]
+fn quoted_parts_keep_their_quotes() {
+    let Statement::Insert { table, .. } =
+        parse_statement("INSERT INTO \"My Schema\".users VALUES (1)").unwrap()
+    else {
+        panic!("not an INSERT")
+    };
+    assert_eq!(
+        table,
+        ObjectName(vec![quoted("My Schema"), Ident::new("users")])
+    );
+    assert_eq!(table.to_string(), "\"My Schema\".users");
+
+    let statement =
+        parse_statement("SELECT \"My Schema\".\"Users\".id FROM \"My Schema\".\"Users\"").unwrap();
+    assert_eq!(
+        statement.to_string(),
+        "SELECT \"My Schema\".\"Users\".id FROM \"My Schema\".\"Users\""
+    );
+}
+
+#[test]
+fn trailing_dots_and_empty_parts_are_errors() {
+    assert_eq!(
+        error("SELECT a FROM s. WHERE a = 1"),
+        ("name after '.' in s, found WHERE".to_string(), 18)
+    );
+    assert_eq!(
+        error("SELECT a FROM s..t"),
+        ("name after '.' in s, found '.'".to_string(), 17)
+    );
+    assert_eq!(
+        error("SELECT a FROM \"\".t"),
+        (
+            "table name (quoted identifiers cannot be empty), found \"\"".to_string(),
+            15
+        )
+    );
+    assert_eq!(
+        error("SELECT s..a FROM t"),
+        ("identifier after '.', found '.'".to_string(), 10)
+    );
+    let e = parse_statement("SELECT a FROM s.").unwrap_err();
+    assert_eq!(e.message(), "expected name after '.' in s");
+}
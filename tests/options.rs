@@ -0,0 +1,129 @@
+use rust_sql_parser::ast::Statement;
+use rust_sql_parser::dialect::Dialect;
+use rust_sql_parser::options::{IdentifierCase, ParserOptions};
+use rust_sql_parser::parser::PrattParser;
+
+/// Parses `sql`, giving back the error's message on failure
+fn parse(sql: &str, options: ParserOptions) -> Result<Statement, String> {
+    PrattParser::new_with_options(sql, options)
+        .parse_statement()
+        .map_err(|e| e.message())
+}
+
+#[test]
+fn dialect() {
+    let sql = "SELECT TOP 5 a FROM t";
+    assert!(parse(sql, ParserOptions::new()).is_err());
+    let statement = parse(sql, ParserOptions::new().with_dialect(Dialect::MsSql)).unwrap();
+    assert_eq!(statement.to_string(), sql);
+}
+
+#[test]
+fn max_expression_depth() {
+    let sql = "SELECT ((((((1))))))";
+    assert!(parse(sql, ParserOptions::new()).is_ok());
+    assert!(parse(sql, ParserOptions::new().with_max_expression_depth(16)).is_ok());
+    let e = parse(sql, ParserOptions::new().with_max_expression_depth(4)).unwrap_err();
+    assert!(e.contains("4"), "{}", e);
+}
+
+#[test]
+fn trailing_commas() {
+    let allowed = ParserOptions::new().with_trailing_commas(true);
+    for (sql, parsed) in [
+        ("SELECT a, b, FROM t", "SELECT a, b FROM t"),
+        (
+            "SELECT a FROM t, u, WHERE a = 1",
+            "SELECT a FROM t, u WHERE a = 1",
+        ),
+        (
+            "SELECT a FROM t ORDER BY a, b,",
+            "SELECT a FROM t ORDER BY a, b",
+        ),
+        ("SELECT f(a, b,) FROM t", "SELECT f(a, b) FROM t"),
+        (
+            "SELECT a FROM t WHERE a IN (1, 2,)",
+            "SELECT a FROM t WHERE a IN (1, 2)",
+        ),
+        (
+            "INSERT INTO t (a, b,) VALUES (1, 2,)",
+            "INSERT INTO t (a, b) VALUES (1, 2)",
+        ),
+        (
+            "CREATE TABLE t (a INT, b TEXT,)",
+            "CREATE TABLE t (a INT, b TEXT)",
+        ),
+        (
+            "CREATE TABLE t (a INT, PRIMARY KEY (a),)",
+            "CREATE TABLE t (a INT, PRIMARY KEY (a))",
+        ),
+    ] {
+        assert!(parse(sql, ParserOptions::new()).is_err(), "{}", sql);
+        match parse(sql, allowed.clone()) {
+            Ok(statement) => assert_eq!(statement.to_string(), parsed, "{}", sql),
+            Err(e) => panic!("{}: {}", sql, e),
+        }
+    }
+    // A comma still needs something before it
+    assert!(parse("SELECT f(,) FROM t", allowed.clone()).is_err());
+    assert!(parse("SELECT a FROM t, , u", allowed).is_err());
+}
+
+#[test]
+fn chained_comparisons() {
+    let rejected = ParserOptions::new().with_chained_comparisons_rejected(true);
+    let sql = "SELECT a < b < c";
+    assert_eq!(
+        parse(sql, ParserOptions::new()).unwrap().to_string(),
+        "SELECT (a < b) < c"
+    );
+    let e = parse(sql, rejected.clone()).unwrap_err();
+    assert!(e.contains("chained comparison"), "{}", e);
+
+    // What a tree with a comparison on the left prints as parses again when chains are
+    // rejected
+    for sql in [
+        "SELECT (a < b) < c",
+        "SELECT (a = b) = (c <> d)",
+        "SELECT a < b AND b < c",
+    ] {
+        let statement = parse(sql, rejected.clone()).unwrap();
+        assert_eq!(statement.to_string(), sql);
+        assert_eq!(
+            parse(&statement.to_string(), rejected.clone()).unwrap(),
+            statement
+        );
+    }
+}
+
+#[test]
+fn error_recovery() {
+    let script = "SELECT FROM; SELECT 1; SELECT 2 +;";
+    let (statements, errors) =
+        PrattParser::parse_statements_with_options(script, ParserOptions::new());
+    assert!(statements.is_empty());
+    assert_eq!(errors.len(), 1);
+
+    let (statements, errors) = PrattParser::parse_statements_with_options(
+        script,
+        ParserOptions::new().with_error_recovery(true),
+    );
+    assert_eq!(statements.len(), 1);
+    assert_eq!(errors.len(), 2);
+}
+
+#[test]
+fn identifier_case() {
+    let sql = "SELECT Name, \"Mixed\" FROM Users";
+    for (case, parsed) in [
+        (
+            IdentifierCase::Preserve,
+            "SELECT Name, \"Mixed\" FROM Users",
+        ),
+        (IdentifierCase::Lower, "SELECT name, \"Mixed\" FROM users"),
+        (IdentifierCase::Upper, "SELECT NAME, \"Mixed\" FROM USERS"),
+    ] {
+        let statement = parse(sql, ParserOptions::new().with_identifier_case(case)).unwrap();
+        assert_eq!(statement.to_string(), parsed, "{:?}", case);
+    }
+}
@@ -0,0 +1,92 @@
+use std::collections::HashMap;
+
+use rust_sql_parser::eval::Value;
+use rust_sql_parser::parse_statement;
+
+fn bind(sql: &str, values: &[Value]) -> String {
+    parse_statement(sql)
+        .unwrap_or_else(|e| panic!("{}: {}", sql, e))
+        .bind(values)
+        .unwrap_or_else(|e| panic!("{}: {}", sql, e))
+        .to_string()
+}
+
+#[test]
+fn parameters_are_listed_in_source_order() {
+    let statement = parse_statement("SELECT a FROM t WHERE b = :b OFFSET :o LIMIT :l").unwrap();
+    let names: Vec<_> = statement
+        .parameters()
+        .into_iter()
+        .map(|p| (p.text, p.position))
+        .collect();
+    assert_eq!(
+        names,
+        [
+            (":b".to_string(), 0),
+            (":o".to_string(), 1),
+            (":l".to_string(), 2)
+        ]
+    );
+}
+
+#[test]
+fn offset_before_limit_binds_in_source_order() {
+    let values = [Value::Integer(10), Value::Integer(5)];
+    assert_eq!(
+        bind("SELECT a FROM t OFFSET ? LIMIT ?", &values),
+        bind("SELECT a FROM t OFFSET 10 LIMIT 5", &[])
+    );
+    assert_eq!(
+        bind(
+            "SELECT a FROM t ORDER BY a OFFSET ? ROWS FETCH FIRST ? ROWS ONLY",
+            &values
+        ),
+        bind(
+            "SELECT a FROM t ORDER BY a OFFSET 10 ROWS FETCH FIRST 5 ROWS ONLY",
+            &[]
+        )
+    );
+}
+
+#[test]
+fn question_marks_bind_in_order() {
+    let values = [
+        Value::Integer(1),
+        Value::String("x".into()),
+        Value::Integer(-2),
+    ];
+    assert_eq!(
+        bind("SELECT ? FROM t WHERE a = ? LIMIT ?", &values),
+        "SELECT 1 FROM t WHERE a = 'x' LIMIT -2"
+    );
+}
+
+#[test]
+fn numbered_and_named_markers() {
+    let values = [Value::Integer(1), Value::Integer(2)];
+    assert_eq!(
+        bind("SELECT $2, $1, $2 FROM t", &values),
+        "SELECT 2, 1, 2 FROM t"
+    );
+
+    let statement = parse_statement("SELECT a FROM t OFFSET :o LIMIT :l").unwrap();
+    let named = HashMap::from([
+        ("o".to_string(), Value::Integer(10)),
+        ("l".to_string(), Value::Integer(5)),
+    ]);
+    assert_eq!(
+        statement.bind_named(&named).unwrap().to_string(),
+        bind("SELECT a FROM t OFFSET 10 LIMIT 5", &[])
+    );
+}
+
+#[test]
+fn ordinals_restart_with_each_statement() {
+    let statements =
+        rust_sql_parser::parse_statements("SELECT ?; SELECT a FROM t OFFSET ? LIMIT ?").unwrap();
+    let values = [Value::Integer(3), Value::Integer(4)];
+    assert_eq!(
+        statements[1].bind(&values).unwrap().to_string(),
+        bind("SELECT a FROM t OFFSET 3 LIMIT 4", &[])
+    );
+}
@@ -0,0 +1,64 @@
+use rust_sql_parser::ast::{PragmaValue, Statement};
+use rust_sql_parser::parse_statement;
+
+const ROUND_TRIPS: &[&str] = &[
+    "PRAGMA user_version",
+    "PRAGMA main.user_version",
+    "PRAGMA user_version = 7",
+    "PRAGMA cache_size = -2000",
+    "PRAGMA table_info(users)",
+    "PRAGMA main.table_info(users)",
+    "PRAGMA journal_mode = WAL",
+    "PRAGMA foreign_keys = ON",
+    "PRAGMA encoding = 'UTF-8'",
+    "PRAGMA analysis_limit(400)",
+    "PRAGMA x = 1.5",
+    "PRAGMA x = -0.25",
+];
+
+#[test]
+fn every_form_round_trips() {
+    for sql in ROUND_TRIPS {
+        let statement = parse_statement(sql).unwrap_or_else(|e| panic!("{}: {}", sql, e));
+        assert_eq!(statement.to_string(), *sql);
+        assert_eq!(parse_statement(&statement.to_string()).unwrap(), statement);
+    }
+}
+
+#[test]
+fn call_form_is_recorded() {
+    match parse_statement("PRAGMA table_info(users)").unwrap() {
+        Statement::Pragma { name, value, call } => {
+            assert_eq!(name.to_string(), "table_info");
+            assert_eq!(value, Some(PragmaValue::Identifier("users".into())));
+            assert!(call);
+        }
+        statement => panic!("not a PRAGMA: {}", statement),
+    }
+    match parse_statement("PRAGMA table_info = users").unwrap() {
+        Statement::Pragma { call, .. } => assert!(!call),
+        statement => panic!("not a PRAGMA: {}", statement),
+    }
+}
+
+#[test]
+fn float_values() {
+    match parse_statement("PRAGMA x = -1.5").unwrap() {
+        Statement::Pragma { value, .. } => {
+            assert_eq!(value, Some(PragmaValue::Float((-1.5).into())))
+        }
+        statement => panic!("not a PRAGMA: {}", statement),
+    }
+}
+
+#[test]
+fn bad_values_are_reported_as_such() {
+    for sql in ["PRAGMA x = (", "PRAGMA x = ,", "PRAGMA x(*)"] {
+        let e = parse_statement(sql).unwrap_err().to_string();
+        assert!(e.contains("value for pragma x"), "{}: {}", sql, e);
+        assert!(!e.contains("Minus"), "{}: {}", sql, e);
+    }
+    for sql in ["PRAGMA", "PRAGMA x =", "PRAGMA x(1", "PRAGMA x = -'a'"] {
+        assert!(parse_statement(sql).is_err(), "{} should not parse", sql);
+    }
+}
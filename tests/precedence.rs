@@ -0,0 +1,60 @@
+use rust_sql_parser::ast::{BinaryOperator, Expression, UnaryOperator};
+use rust_sql_parser::parse_expression;
+
+fn operator(expr: &Expression) -> &BinaryOperator {
+    match expr {
+        Expression::BinaryOperation { operator, .. } => operator,
+        expr => panic!("not a binary operation: {}", expr),
+    }
+}
+
+#[test]
+fn not_binds_tighter_than_comparisons() {
+    match &parse_expression("NOT a = b").unwrap() {
+        Expression::BinaryOperation {
+            left_operand,
+            operator,
+            ..
+        } => {
+            assert_eq!(*operator, BinaryOperator::Equal);
+            assert!(matches!(
+                **left_operand,
+                Expression::UnaryOperation {
+                    operator: UnaryOperator::Not,
+                    ..
+                }
+            ));
+        }
+        expr => panic!("not a comparison: {}", expr),
+    }
+    let negated = parse_expression("NOT (a = b)").unwrap();
+    assert!(matches!(negated, Expression::UnaryOperation { .. }));
+    assert_eq!(negated.to_string(), "NOT (a = b)");
+}
+
+#[test]
+fn comparisons_bind_tighter_than_and_and_and_than_or() {
+    let expr = parse_expression("a = 1 OR NOT b AND c < 2").unwrap();
+    assert_eq!(*operator(&expr), BinaryOperator::Or);
+    match &expr {
+        Expression::BinaryOperation {
+            left_operand,
+            right_operand,
+            ..
+        } => {
+            assert_eq!(*operator(left_operand), BinaryOperator::Equal);
+            assert_eq!(*operator(right_operand), BinaryOperator::And);
+        }
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn not_binds_looser_than_arithmetic() {
+    match &parse_expression("NOT a + 1").unwrap() {
+        Expression::UnaryOperation { operand, .. } => {
+            assert_eq!(*operator(operand), BinaryOperator::Plus)
+        }
+        expr => panic!("not a NOT: {}", expr),
+    }
+}
@@ -0,0 +1,79 @@
+use rust_sql_parser::ast::Expression;
+use rust_sql_parser::{parse_expression, parse_statement};
+
+const ROUND_TRIPS: &[&str] = &[
+    "a IN (1, 2, 3)",
+    "a NOT IN ('x')",
+    "a + 1 IN (b, c * 2)",
+    "a IS NULL",
+    "a IS NOT NULL",
+    "name LIKE 'a%'",
+    "name NOT LIKE '%b_' || suffix",
+    "NOT a IN (1)",
+    "NOT (a IN (1))",
+    "a IS NULL AND b IN (1) OR c LIKE 'x'",
+    "x = (a IS NULL)",
+    "(a IN (1))::INT",
+    "a LIKE b LIKE c",
+    "a LIKE (b LIKE c)",
+];
+
+#[test]
+fn predicates_round_trip() {
+    for sql in ROUND_TRIPS {
+        let expr = parse_expression(sql).unwrap_or_else(|e| panic!("{}: {}", sql, e));
+        assert_eq!(expr.to_string(), *sql);
+        assert_eq!(parse_expression(&expr.to_string()).unwrap(), expr);
+    }
+}
+
+#[test]
+fn fields() {
+    match &parse_expression("a NOT IN (1, 2)").unwrap() {
+        Expression::InList { list, negated, .. } => {
+            assert_eq!(list.len(), 2);
+            assert!(*negated);
+        }
+        expr => panic!("not an IN list: {}", expr),
+    }
+    match &parse_expression("a IS NOT NULL").unwrap() {
+        Expression::IsNull { negated, .. } => assert!(*negated),
+        expr => panic!("not IS NULL: {}", expr),
+    }
+    match &parse_expression("a LIKE 'x'").unwrap() {
+        Expression::Like { negated, .. } => assert!(!*negated),
+        expr => panic!("not LIKE: {}", expr),
+    }
+}
+
+#[test]
+fn not_binds_tighter_than_the_predicate() {
+    match &parse_expression("NOT a IS NULL").unwrap() {
+        Expression::IsNull { expr, .. } => {
+            assert!(matches!(**expr, Expression::UnaryOperation { .. }))
+        }
+        expr => panic!("not IS NULL: {}", expr),
+    }
+}
+
+#[test]
+fn not_null_after_a_default_still_parses() {
+    let sql = "CREATE TABLE t (a INT DEFAULT 0 NOT NULL)";
+    let statement = parse_statement(sql).unwrap();
+    assert_eq!(statement.to_string(), sql);
+}
+
+#[test]
+fn predicates_in_queries() {
+    let sql = "SELECT a FROM t WHERE b IN (1, 2) AND c IS NOT NULL AND d NOT LIKE 'x%'";
+    assert_eq!(parse_statement(sql).unwrap().to_string(), sql);
+}
+
+#[test]
+fn incomplete_predicates_are_rejected() {
+    for sql in [
+        "a IN", "a IN ()", "a IN (1", "a IN 1", "a IS", "a IS NOT", "a IS 1", "a LIKE", "a NOT",
+    ] {
+        assert!(parse_expression(sql).is_err(), "{} should not parse", sql);
+    }
+}
@@ -0,0 +1,66 @@
+use rust_sql_parser::parser::PrattParser;
+
+const SCRIPT: &str = "\
+CREATE TABLE t (a INT, b VARCHAR(10));
+INSERT INTO t VALUES (1, 'x;y') WHERE;
+SELECT a FROM t WHERE b = ';';
+UPDATE t SET a = 2 WHERE b = 'x';
+DELETE FROM WHERE a = 1;
+SELECT b FROM t ORDER BY a;
+";
+
+#[test]
+fn errors_in_two_of_six_statements() {
+    let (statements, errors) = PrattParser::parse_statements_recovering(SCRIPT);
+    assert_eq!(statements.len(), 4);
+    assert_eq!(errors.len(), 2);
+
+    let lines: Vec<usize> = errors
+        .iter()
+        .map(|e| e.span().expect("errors are located").start.line)
+        .collect();
+    assert_eq!(lines, [2, 5]);
+    let first = errors.iter().next().unwrap();
+    assert!(first.to_string().contains("statement 2"), "{}", first);
+}
+
+#[test]
+fn recovered_statements_are_those_strict_mode_parses() {
+    let (statements, _) = PrattParser::parse_statements_recovering(SCRIPT);
+    let strict: Vec<_> = SCRIPT
+        .lines()
+        .filter_map(|line| PrattParser::new(line).parse_statement().ok())
+        .collect();
+    assert_eq!(statements, strict);
+}
+
+#[test]
+fn semicolons_in_strings_do_not_end_statements() {
+    let (statements, errors) =
+        PrattParser::parse_statements_recovering("SELECT 'a;b' FROM; SELECT ';' FROM t");
+    assert_eq!(errors.len(), 1);
+    assert_eq!(statements.len(), 1);
+    assert_eq!(statements[0].to_string(), "SELECT ';' FROM t");
+}
+
+#[test]
+fn lexical_errors_are_recovered_from() {
+    let (statements, errors) =
+        PrattParser::parse_statements_recovering("SELECT 1 @ 2; SELECT 3; SELECT 'open");
+    assert_eq!(statements.len(), 1);
+    assert_eq!(errors.len(), 2);
+}
+
+#[test]
+fn strict_mode_stops_at_the_first_error() {
+    let e = PrattParser::parse_statements(SCRIPT).unwrap_err();
+    assert_eq!(e.span().unwrap().start.line, 2);
+}
+
+#[test]
+fn the_first_error_is_the_source() {
+    let (_, errors) = PrattParser::parse_statements_recovering(SCRIPT);
+    let first = errors.iter().next().unwrap().to_string();
+    let errors: Box<dyn std::error::Error> = errors.into();
+    assert_eq!(errors.source().unwrap().to_string(), first);
+}
@@ -0,0 +1,164 @@
+use rust_sql_parser::ast::Statement;
+use rust_sql_parser::parse_statement;
+
+fn rewritten(sql: &str, rewrite: impl FnOnce(&mut Statement)) -> String {
+    let mut statement = parse_statement(sql).unwrap();
+    rewrite(&mut statement);
+    statement.to_string()
+}
+
+#[test]
+fn table_name_also_used_as_a_derived_table_alias() {
+    // `users` names both the real table and the derived table; only the former changes
+    let sql = "SELECT t.id FROM users AS t JOIN (SELECT id FROM users) AS users ON t.id = users.id";
+    assert_eq!(
+        rewritten(sql, |s| s.rename_table("users", "people")),
+        "SELECT t.id FROM people AS t JOIN (SELECT id FROM people) AS users ON t.id = users.id"
+    );
+}
+
+#[test]
+fn table_renamed_in_qualifiers_on_conditions_and_order_by() {
+    let sql = "SELECT orders.id, o.total FROM orders JOIN orders AS o ON o.id = orders.id \
+               ORDER BY orders.id";
+    assert_eq!(
+        rewritten(sql, |s| s.rename_table("orders", "sales")),
+        "SELECT sales.id, o.total FROM sales JOIN sales AS o ON o.id = sales.id \
+         ORDER BY sales.id"
+    );
+}
+
+#[test]
+fn cte_shadows_the_table() {
+    let sql = "WITH orders AS (SELECT id FROM orders) SELECT orders.id FROM orders";
+    assert_eq!(
+        rewritten(sql, |s| s.rename_table("orders", "sales")),
+        "WITH orders AS (SELECT id FROM sales) SELECT orders.id FROM orders"
+    );
+}
+
+#[test]
+fn dml_targets() {
+    assert_eq!(
+        rewritten("DELETE FROM t WHERE t.a = 1", |s| s.rename_table("t", "u")),
+        "DELETE FROM u WHERE u.a = 1"
+    );
+    assert_eq!(
+        rewritten("INSERT INTO t (a) VALUES (1)", |s| s.rename_column(
+            Some("t"),
+            "a",
+            "b"
+        )),
+        "INSERT INTO t (b) VALUES (1)"
+    );
+    assert_eq!(
+        rewritten("UPDATE t SET a = a + 1 WHERE a > 0", |s| s.rename_column(
+            Some("t"),
+            "a",
+            "b"
+        )),
+        "UPDATE t SET b = b + 1 WHERE b > 0"
+    );
+}
+
+#[test]
+fn column_of_a_table_only_through_that_table() {
+    // `users.id` here is the derived table's column, while `x` is the real table
+    let sql = "SELECT x.id FROM users AS x JOIN (SELECT id FROM users) AS users ON x.id = users.id";
+    assert_eq!(
+        rewritten(sql, |s| s.rename_column(Some("users"), "id", "uid")),
+        "SELECT x.uid FROM users AS x JOIN (SELECT uid AS id FROM users) AS users \
+         ON x.uid = users.id"
+    );
+}
+
+#[test]
+fn column_of_another_table_is_left_alone() {
+    let sql = "SELECT o.id FROM orders AS o WHERE o.id > 1";
+    assert_eq!(
+        rewritten(sql, |s| s.rename_column(Some("users"), "id", "uid")),
+        sql
+    );
+}
+
+#[test]
+fn column_without_a_table() {
+    assert_eq!(
+        rewritten("SELECT a FROM t WHERE a = 1 ORDER BY a", |s| s
+            .rename_column(None, "a", "b")),
+        "SELECT b FROM t WHERE b = 1 ORDER BY b"
+    );
+}
+
+#[test]
+fn derived_tables_and_ctes_keep_their_output_names() {
+    let rename = |s: &mut Statement| s.rename_column(Some("t"), "a", "b");
+    assert_eq!(
+        rewritten("SELECT a FROM (SELECT a FROM t) AS d", rename),
+        "SELECT a FROM (SELECT b AS a FROM t) AS d"
+    );
+    assert_eq!(
+        rewritten(
+            "SELECT d.a FROM (SELECT t.a, c FROM t UNION SELECT a, c FROM t) AS d",
+            rename
+        ),
+        "SELECT d.a FROM (SELECT t.b AS a, c FROM t UNION SELECT b, c FROM t) AS d"
+    );
+    assert_eq!(
+        rewritten("WITH c AS (SELECT a FROM t) SELECT a FROM c", rename),
+        "WITH c AS (SELECT b AS a FROM t) SELECT a FROM c"
+    );
+    // A column list already names the outputs
+    assert_eq!(
+        rewritten("WITH c (a) AS (SELECT a FROM t) SELECT a FROM c", rename),
+        "WITH c (a) AS (SELECT b FROM t) SELECT a FROM c"
+    );
+    assert_eq!(
+        rewritten("SELECT x FROM (SELECT a FROM t) AS d (x)", rename),
+        "SELECT x FROM (SELECT b FROM t) AS d (x)"
+    );
+    // Without a table every `a` is renamed, outside the derived table too
+    assert_eq!(
+        rewritten("SELECT d.a FROM (SELECT a FROM t) AS d", |s| s
+            .rename_column(None, "a", "b")),
+        "SELECT d.b FROM (SELECT b FROM t) AS d"
+    );
+}
+
+#[test]
+fn using_lists() {
+    let rename = |s: &mut Statement| s.rename_column(Some("t"), "a", "b");
+    assert_eq!(
+        rewritten("SELECT * FROM t JOIN u USING (a)", rename),
+        "SELECT * FROM t JOIN u ON t.b = u.a"
+    );
+    assert_eq!(
+        rewritten("SELECT * FROM u AS x JOIN t USING (id, a)", rename),
+        "SELECT * FROM u AS x JOIN t ON x.id = t.id AND x.a = t.b"
+    );
+    // Both sides are the table, so the list still names one column
+    assert_eq!(
+        rewritten("SELECT * FROM t JOIN t AS t2 USING (a)", rename),
+        "SELECT * FROM t JOIN t AS t2 USING (b)"
+    );
+    assert_eq!(
+        rewritten("SELECT * FROM t JOIN u USING (c)", rename),
+        "SELECT * FROM t JOIN u USING (c)"
+    );
+    assert_eq!(
+        rewritten("SELECT * FROM u JOIN v USING (a)", rename),
+        "SELECT * FROM u JOIN v USING (a)"
+    );
+    assert_eq!(
+        rewritten("SELECT * FROM t JOIN u USING (a)", |s| s
+            .rename_column(None, "a", "b")),
+        "SELECT * FROM t JOIN u USING (b)"
+    );
+    assert_eq!(
+        rewritten(
+            "DELETE FROM x USING t JOIN u USING (a) WHERE x.id = t.id",
+            rename
+        ),
+        "DELETE FROM x USING t JOIN u ON t.b = u.a WHERE x.id = t.id"
+    );
+}
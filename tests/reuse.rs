@@ -0,0 +1,89 @@
+use rust_sql_parser::options::ParserOptions;
+use rust_sql_parser::parser::PrattParser;
+use rust_sql_parser::tokenizer::Tokenizer;
+
+/// Inputs that leave state behind when they fail part way: nesting depth, placeholder
+/// numbering, the constructs being parsed and the tokens that were expected
+const INPUTS: &[&str] = &[
+    "SELECT a FROM t WHERE b = ? AND c = ?",
+    "SELECT ((((((1))))))",
+    "SELECT a FROM t WHERE b = ? AND (c = ? OR",
+    "SELECT (((((((((((1)))))))))))",
+    "SELECT ?",
+    "INSERT INTO t (a VALUES (1)",
+    "SELECT a, FROM t",
+    "UPDATE t SET a = 'unterminated",
+    "",
+    "SELECT a FROM t AS",
+    "DELETE FROM t WHERE a = $2",
+    "CREATE TABLE t (a INT,",
+    "SELECT a FROM t",
+];
+
+fn options() -> ParserOptions {
+    ParserOptions::new().with_max_expression_depth(8)
+}
+
+/// Everything a parse produces, down to the error's context, alternatives and spans
+fn outcome(parser: &mut PrattParser) -> String {
+    format!("{:?}", parser.parse_statement())
+}
+
+#[test]
+fn a_reset_parser_matches_a_fresh_one() {
+    let mut reused = PrattParser::new_with_options("", options());
+    // Every order, so each input follows each failure
+    for first in INPUTS {
+        for second in INPUTS {
+            reused.reset(first);
+            let first_outcome = outcome(&mut reused);
+            assert_eq!(
+                first_outcome,
+                outcome(&mut PrattParser::new_with_options(first, options())),
+                "{:?}",
+                first
+            );
+
+            reused.reset(second);
+            assert_eq!(
+                outcome(&mut reused),
+                outcome(&mut PrattParser::new_with_options(second, options())),
+                "{:?} after {:?}",
+                second,
+                first
+            );
+        }
+    }
+}
+
+#[test]
+fn a_reset_parser_can_stop_part_way() {
+    let mut reused = PrattParser::new("SELECT a FROM t WHERE (((b");
+    let _ = reused.parse();
+    reused.reset("a + ?");
+    assert_eq!(
+        format!("{:?}", reused.parse()),
+        format!("{:?}", PrattParser::new("a + ?").parse())
+    );
+}
+
+#[test]
+fn a_reset_tokenizer_matches_a_fresh_one() {
+    let mut reused = Tokenizer::new_recovering("");
+    for input in INPUTS.iter().chain(&["'a' 'b", "\"x", "a /* b", "é ü"]) {
+        reused.reset(input);
+        let mut fresh = Tokenizer::new_recovering(input);
+        assert_eq!(
+            format!("{:?}", reused.errors()),
+            format!("{:?}", fresh.errors()),
+            "{:?}",
+            input
+        );
+        assert_eq!(
+            format!("{:?}", reused.tokenize_with_spans()),
+            format!("{:?}", fresh.tokenize_with_spans()),
+            "{:?}",
+            input
+        );
+    }
+}
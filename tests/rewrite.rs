@@ -0,0 +1,101 @@
+use std::ops::ControlFlow;
+
+use rust_sql_parser::ast::{Expression, Ident, Value};
+use rust_sql_parser::visitor::{self, VisitorMut};
+use rust_sql_parser::{parse_expression, parse_statement};
+
+/// Renames the column `old` to `new` wherever it is referred to, bare or qualified
+struct RenameColumn;
+
+impl VisitorMut for RenameColumn {
+    type Break = ();
+
+    fn visit_identifier_mut(&mut self, ident: &mut Ident) -> ControlFlow<()> {
+        if ident.value == "old" {
+            ident.value = "new".into();
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn visit_compound_identifier_mut(&mut self, parts: &mut Vec<Ident>) -> ControlFlow<()> {
+        if let Some(last) = parts.last_mut() {
+            self.visit_identifier_mut(last)?;
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+/// Replaces each `?` with the next of `values`, replacing whole expression nodes
+struct BindPlaceholders {
+    values: std::vec::IntoIter<Value>,
+}
+
+impl VisitorMut for BindPlaceholders {
+    type Break = ();
+
+    fn visit_expression_mut(&mut self, expr: &mut Expression) -> ControlFlow<()> {
+        if let Expression::Placeholder { .. } = expr {
+            if let Some(value) = self.values.next() {
+                *expr = Expression::Value(value);
+            }
+            return ControlFlow::Continue(());
+        }
+        visitor::walk_expression_mut(self, expr)
+    }
+}
+
+#[test]
+fn renames_inside_deeply_nested_expressions() {
+    let mut sql = String::from("old");
+    for i in 0..40 {
+        sql = format!("f({}, t.old) + (old * {})", sql, i);
+    }
+    let mut expr = parse_expression(&sql).unwrap();
+    let _ = RenameColumn.visit_expression_mut(&mut expr);
+    assert_eq!(expr, parse_expression(&sql.replace("old", "new")).unwrap());
+}
+
+#[test]
+fn renames_in_every_clause() {
+    for (sql, expected) in [
+        (
+            "SELECT old, t.old AS x FROM t JOIN u ON t.old = u.id \
+             WHERE old > 1 GROUP BY old HAVING COUNT(old) > 1 ORDER BY old",
+            "SELECT new, t.new AS x FROM t JOIN u ON t.new = u.id \
+             WHERE new > 1 GROUP BY new HAVING COUNT(new) > 1 ORDER BY new",
+        ),
+        (
+            "WITH w AS (SELECT old FROM t) SELECT (SELECT MAX(old) FROM w) FROM u",
+            "WITH w AS (SELECT new FROM t) SELECT (SELECT MAX(new) FROM w) FROM u",
+        ),
+        (
+            "UPDATE t SET a = old + 1 WHERE old IS NOT NULL",
+            "UPDATE t SET a = new + 1 WHERE new IS NOT NULL",
+        ),
+        (
+            "DELETE FROM t WHERE old IN (1, 2) RETURNING old",
+            "DELETE FROM t WHERE new IN (1, 2) RETURNING new",
+        ),
+        (
+            "INSERT INTO t (a) VALUES (1) ON CONFLICT (a) DO UPDATE SET a = old",
+            "INSERT INTO t (a) VALUES (1) ON CONFLICT (a) DO UPDATE SET a = new",
+        ),
+    ] {
+        let mut statement = parse_statement(sql).unwrap();
+        let _ = RenameColumn.visit_statement_mut(&mut statement);
+        assert_eq!(statement, parse_statement(expected).unwrap(), "{}", sql);
+    }
+}
+
+#[test]
+fn whole_nodes_can_be_replaced() {
+    let mut statement = parse_statement("SELECT a FROM t WHERE b = ? AND c IN (?, 3)").unwrap();
+    let mut bind = BindPlaceholders {
+        values: vec![Value::Number(1), Value::SingleQuotedString("x".into())].into_iter(),
+    };
+    let _ = bind.visit_statement_mut(&mut statement);
+    assert_eq!(
+        statement.to_string(),
+        "SELECT a FROM t WHERE b = 1 AND c IN ('x', 3)"
+    );
+}
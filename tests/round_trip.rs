@@ -0,0 +1,279 @@
+use std::collections::BTreeMap;
+use std::ops::ControlFlow;
+
+use rust_sql_parser::ast::{Cte, Expression, Ident, ObjectName, Query, SelectStatement, Statement};
+use rust_sql_parser::dialect::Dialect;
+use rust_sql_parser::parse_statement;
+use rust_sql_parser::parser::PrattParser;
+use rust_sql_parser::visitor::{self, Visitor};
+
+/// Statements that print back exactly as written
+const CANONICAL: &[&str] = &[
+    "SELECT a, b AS c FROM t",
+    "SELECT DISTINCT a FROM t WHERE a > 1 AND b <> 2 OR NOT c = 3",
+    "SELECT * FROM t ORDER BY a DESC, b ASC LIMIT 10 OFFSET 5",
+    "SELECT t.a, u.* FROM t JOIN u ON t.id = u.id",
+    "SELECT a FROM t LEFT JOIN u USING (id, tenant_id)",
+    "SELECT a FROM t NATURAL JOIN u",
+    "SELECT a FROM t CROSS JOIN u",
+    "SELECT a, COUNT(*) FROM t GROUP BY a HAVING COUNT(*) > 1",
+    "WITH x AS (SELECT a FROM t) SELECT a FROM x",
+    "SELECT a FROM t UNION ALL SELECT a FROM u",
+    "SELECT a FROM t INTERSECT SELECT a FROM u EXCEPT SELECT a FROM v",
+    "SELECT (SELECT MAX(a) FROM u) FROM t",
+    "SELECT a FROM (SELECT a FROM t) AS s",
+    "SELECT a AS \"Foo\" FROM t AS \"T\" (\"X\", y)",
+    "SELECT \"Mixed Case\", \"select\" FROM \"my table\"",
+    "SELECT a::INT, -b, +c, (a + b) * c, a - (b - c) FROM t",
+    "SELECT a FROM t FOR UPDATE",
+    "SELECT a FROM t FOR SHARE NOWAIT",
+    "VALUES (1, 2), (3, 4)",
+    "SELECT a FROM t WHERE b = $1 AND c = ? AND d = :name",
+    "SELECT 'it''s', 1.5, TRUE, FALSE, NULL FROM t",
+    "SELECT a FROM t WHERE b IN (1, 2) AND c IS NOT NULL AND d NOT LIKE 'x%'",
+    "INSERT INTO t (a, b) VALUES (1, 'x'), (2, 'y')",
+    "INSERT INTO t SELECT a, b FROM u",
+    "INSERT INTO t (a) VALUES (1) ON CONFLICT (a) DO NOTHING",
+    "INSERT INTO t (a) VALUES (1) ON CONFLICT (a) DO UPDATE SET a = 2",
+    "UPDATE t SET a = 1, b = b + 1 WHERE c = 2",
+    "DELETE FROM t WHERE a = 1",
+    "DELETE FROM t",
+    "CREATE TABLE t (id INT PRIMARY KEY, name VARCHAR(20) NOT NULL, ok BOOLEAN DEFAULT TRUE)",
+    "CREATE TABLE IF NOT EXISTS t (a INT, b INT, PRIMARY KEY (a, b))",
+    "CREATE TEMPORARY TABLE t (a INT)",
+    "CREATE VIEW v AS SELECT a FROM t",
+    "CREATE UNIQUE INDEX i ON t (a, b)",
+    "ALTER TABLE t ADD COLUMN c INT",
+    "ALTER TABLE t DROP COLUMN c",
+    "ALTER TABLE t RENAME TO u",
+    "DROP TABLE IF EXISTS t, u CASCADE",
+    "DROP INDEX i",
+    "TRUNCATE TABLE t",
+    "START TRANSACTION ISOLATION LEVEL SERIALIZABLE",
+    "COMMIT",
+    "ROLLBACK TO SAVEPOINT s",
+    "SAVEPOINT s",
+    "RELEASE SAVEPOINT s",
+    "SHOW TABLES",
+    "SHOW COLUMNS FROM users",
+    "DESCRIBE users",
+    "PRAGMA foreign_keys = ON",
+    "MERGE INTO t USING u ON t.id = u.id WHEN MATCHED THEN UPDATE SET a = u.a \
+     WHEN NOT MATCHED THEN INSERT (a) VALUES (u.a)",
+];
+
+/// Statements with their canonical form, which differs from how they were written
+const NORMALIZED: &[(&str, &str)] = &[
+    (
+        "select a from t where b = 1 order by a",
+        "SELECT a FROM t WHERE b = 1 ORDER BY a",
+    ),
+    (
+        "SELECT a FROM t t1 LEFT OUTER JOIN u AS u1 ON t1.id = u1.id",
+        "SELECT a FROM t AS t1 LEFT JOIN u AS u1 ON t1.id = u1.id",
+    ),
+    (
+        "SELECT t.a FROM t INNER JOIN u ON t.id = u.id",
+        "SELECT t.a FROM t JOIN u ON t.id = u.id",
+    ),
+    ("SELECT ((a + b)) * (c) FROM t", "SELECT (a + b) * c FROM t"),
+    (
+        "SELECT a FROM t WHERE (a = 1)",
+        "SELECT a FROM t WHERE a = 1",
+    ),
+    ("BEGIN", "START TRANSACTION"),
+];
+
+fn round_trip(statement: &Statement) {
+    let printed = statement.to_string();
+    let reparsed = parse_statement(&printed)
+        .unwrap_or_else(|e| panic!("{} does not parse back: {}", printed, e));
+    assert_eq!(&reparsed, statement, "{}", printed);
+}
+
+#[test]
+fn canonical_statements_print_back_unchanged() {
+    for sql in CANONICAL {
+        let statement = parse_statement(sql).unwrap_or_else(|e| panic!("{}: {}", sql, e));
+        assert_eq!(statement.to_string(), *sql);
+        round_trip(&statement);
+    }
+}
+
+#[test]
+fn other_spellings_print_in_canonical_form() {
+    for (sql, canonical) in NORMALIZED {
+        let statement = parse_statement(sql).unwrap_or_else(|e| panic!("{}: {}", sql, e));
+        assert_eq!(statement.to_string(), *canonical);
+        round_trip(&statement);
+    }
+}
+
+#[test]
+fn dialect_syntax_round_trips_in_its_dialect() {
+    let sql = "SELECT TOP 5 a FROM t";
+    let statement = PrattParser::new_with_dialect(sql, Dialect::MsSql)
+        .parse_statement()
+        .unwrap();
+    assert_eq!(statement.to_string(), sql);
+}
+
+/// Counts each kind of node it is called for, by the name of its variant
+#[derive(Default)]
+struct Counter {
+    counts: BTreeMap<String, usize>,
+}
+
+impl Counter {
+    fn count(&mut self, kind: impl Into<String>) {
+        *self.counts.entry(kind.into()).or_default() += 1;
+    }
+
+    /// Counts `node` by the name of its enum variant, such as `Statement::Insert`
+    fn count_variant(&mut self, of: &str, node: &impl std::fmt::Debug) {
+        let debug = format!("{:?}", node);
+        let variant = debug
+            .split(|ch: char| !ch.is_alphanumeric())
+            .next()
+            .unwrap_or_default();
+        self.count(format!("{}::{}", of, variant));
+    }
+}
+
+impl Visitor for Counter {
+    type Break = ();
+
+    fn visit_statement(&mut self, statement: &Statement) -> ControlFlow<()> {
+        self.count_variant("Statement", statement);
+        visitor::walk_statement(self, statement)
+    }
+
+    fn visit_query(&mut self, query: &Query) -> ControlFlow<()> {
+        self.count("Query");
+        visitor::walk_query(self, query)
+    }
+
+    fn visit_cte(&mut self, cte: &Cte) -> ControlFlow<()> {
+        self.count("Cte");
+        visitor::walk_cte(self, cte)
+    }
+
+    fn visit_select(&mut self, select: &SelectStatement) -> ControlFlow<()> {
+        self.count("Select");
+        visitor::walk_select(self, select)
+    }
+
+    fn visit_expression(&mut self, expr: &Expression) -> ControlFlow<()> {
+        self.count_variant("Expression", expr);
+        visitor::walk_expression(self, expr)
+    }
+
+    fn visit_table_name(&mut self, _name: &ObjectName) -> ControlFlow<()> {
+        self.count("table name");
+        ControlFlow::Continue(())
+    }
+
+    fn visit_identifier(&mut self, _ident: &Ident) -> ControlFlow<()> {
+        self.count("identifier");
+        ControlFlow::Continue(())
+    }
+
+    fn visit_compound_identifier(&mut self, _parts: &[Ident]) -> ControlFlow<()> {
+        self.count("compound identifier");
+        ControlFlow::Continue(())
+    }
+}
+
+#[test]
+fn the_visitor_reaches_every_kind_of_node() {
+    let mut counter = Counter::default();
+    for sql in CANONICAL {
+        let _ = counter.visit_statement(&parse_statement(sql).unwrap());
+    }
+    let seen: Vec<&str> = counter.counts.keys().map(String::as_str).collect();
+    for kind in [
+        "Expression::BinaryOperation",
+        "Expression::UnaryOperation",
+        "Expression::Value",
+        "Expression::Placeholder",
+        "Expression::Identifier",
+        "Expression::CompoundIdentifier",
+        "Expression::Function",
+        "Expression::Cast",
+        "Expression::Wildcard",
+        "Expression::Subquery",
+        "Expression::InList",
+        "Expression::IsNull",
+        "Expression::Like",
+        "Statement::Query",
+        "Statement::Insert",
+        "Statement::Update",
+        "Statement::Delete",
+        "Statement::CreateTable",
+        "Statement::CreateView",
+        "Statement::CreateIndex",
+        "Statement::AlterTable",
+        "Statement::Drop",
+        "Statement::Truncate",
+        "Statement::StartTransaction",
+        "Statement::Commit",
+        "Statement::Rollback",
+        "Statement::Savepoint",
+        "Statement::ReleaseSavepoint",
+        "Statement::Show",
+        "Statement::Describe",
+        "Statement::Pragma",
+        "Statement::Merge",
+        "Query",
+        "Cte",
+        "Select",
+        "table name",
+        "identifier",
+        "compound identifier",
+    ] {
+        assert!(seen.contains(&kind), "{} not visited in {:?}", kind, seen);
+    }
+    // Identifiers are reached both as expressions and through their own hook
+    assert_eq!(
+        counter.counts["identifier"],
+        counter.counts["Expression::Identifier"]
+    );
+    assert_eq!(
+        counter.counts["compound identifier"],
+        counter.counts["Expression::CompoundIdentifier"]
+    );
+}
+
+#[test]
+fn the_visitor_counts_each_node_once() {
+    let mut counter = Counter::default();
+    let statement = parse_statement(
+        "WITH x AS (SELECT a FROM t) SELECT x.a, COUNT(*) FROM x JOIN u ON x.id = u.id \
+         WHERE b > (SELECT c FROM v) GROUP BY x.a",
+    )
+    .unwrap();
+    let _ = counter.visit_statement(&statement);
+    let counts: Vec<(&str, usize)> = counter
+        .counts
+        .iter()
+        .map(|(kind, &count)| (kind.as_str(), count))
+        .collect();
+    assert_eq!(
+        counts,
+        [
+            ("Cte", 1),
+            ("Expression::BinaryOperation", 2),
+            ("Expression::CompoundIdentifier", 4),
+            ("Expression::Function", 1),
+            ("Expression::Identifier", 3),
+            ("Expression::Subquery", 1),
+            ("Expression::Wildcard", 1),
+            ("Query", 3),
+            ("Select", 3),
+            ("Statement::Query", 1),
+            ("compound identifier", 4),
+            ("identifier", 3),
+            ("table name", 4),
+        ]
+    );
+}
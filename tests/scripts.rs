@@ -0,0 +1,41 @@
+use rust_sql_parser::parse_statements;
+
+#[test]
+fn trailing_semicolon() {
+    let statements = parse_statements("SELECT 1; SELECT 2;").unwrap();
+    assert_eq!(statements.len(), 2);
+}
+
+#[test]
+fn no_trailing_semicolon() {
+    let statements = parse_statements("CREATE TABLE t (a INT); INSERT INTO t VALUES (1)").unwrap();
+    assert_eq!(statements.len(), 2);
+    assert_eq!(statements[1].to_string(), "INSERT INTO t VALUES (1)");
+}
+
+#[test]
+fn consecutive_semicolons_are_skipped() {
+    let statements = parse_statements(";; SELECT 1;;; SELECT 2; ;").unwrap();
+    assert_eq!(statements.len(), 2);
+    assert!(parse_statements(" ; ;").unwrap().is_empty());
+    assert!(parse_statements("").unwrap().is_empty());
+}
+
+#[test]
+fn semicolons_in_strings_and_identifiers() {
+    let statements = parse_statements("SELECT 'a;b' FROM \"t;1\"; SELECT 2").unwrap();
+    assert_eq!(statements.len(), 2);
+}
+
+#[test]
+fn statements_must_end_at_a_semicolon() {
+    let e = parse_statements("SELECT 1 SELECT 2").unwrap_err();
+    assert!(e.to_string().contains("after end of statement 1"), "{}", e);
+}
+
+#[test]
+fn error_reports_the_failing_statement() {
+    let e = parse_statements("SELECT 1; SELECT 2; SELECT FROM t; SELECT 4").unwrap_err();
+    assert!(e.to_string().contains("in statement 3"), "{}", e);
+    assert_eq!(e.span().unwrap().start.column, 28);
+}
@@ -0,0 +1,75 @@
+#![cfg(feature = "serde")]
+
+use rust_sql_parser::ast::{Ident, Name, Statement, Token};
+use rust_sql_parser::error::ErrorKind;
+use rust_sql_parser::{parse_statement, tokenize};
+
+/// Statements covering each kind of node, including shared names and quoted identifiers
+const STATEMENTS: &[&str] = &[
+    "WITH x AS (SELECT a FROM t) SELECT DISTINCT x.a, COUNT(*) AS n FROM x \
+     LEFT JOIN u ON x.a = u.a GROUP BY x.a HAVING COUNT(*) > 1 ORDER BY n DESC LIMIT 5",
+    "SELECT a FROM t UNION ALL SELECT b FROM u EXCEPT SELECT c FROM v",
+    "SELECT \"Mixed Case\".a, -b::INT, 'it''s' || c, 1.5, NULL, TRUE FROM \"Mixed Case\"",
+    "SELECT a FROM t WHERE b IN (1, 2) AND c IS NOT NULL AND d NOT LIKE 'x%' OR NOT e",
+    "SELECT a FROM (SELECT a FROM t) AS s WHERE a = $1 OR a = ? OR a = :name",
+    "INSERT INTO t (a, b) VALUES (1, 'x') ON CONFLICT (a) DO UPDATE SET b = 'y'",
+    "UPDATE t SET a = a + 1 WHERE b = 2",
+    "DELETE FROM t WHERE a = 1",
+    "CREATE TABLE t (id INT PRIMARY KEY, name VARCHAR(20) NOT NULL DEFAULT 'x')",
+    "CREATE UNIQUE INDEX i ON t (a, b)",
+    "ALTER TABLE t ADD COLUMN c INT",
+    "DROP TABLE IF EXISTS t, u CASCADE",
+    "START TRANSACTION ISOLATION LEVEL SERIALIZABLE",
+    "PRAGMA foreign_keys = ON",
+];
+
+#[test]
+fn statements_round_trip_through_json() {
+    for sql in STATEMENTS {
+        let statement = parse_statement(sql).unwrap();
+        let json = serde_json::to_string(&statement).unwrap();
+        let back: Statement = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, statement, "{}", json);
+        assert_eq!(back.to_string(), statement.to_string());
+    }
+}
+
+#[test]
+fn tokens_round_trip_through_json() {
+    let tokens = tokenize("SELECT name, 'x' FROM \"T\" WHERE a <= 1.5").unwrap();
+    let json = serde_json::to_string(&tokens).unwrap();
+    assert_eq!(
+        serde_json::from_str::<Vec<Token>>(&json).unwrap(),
+        tokens,
+        "{}",
+        json
+    );
+}
+
+#[test]
+fn names_serialize_as_strings() {
+    let tokens = tokenize("users").unwrap();
+    let json = serde_json::to_value(&tokens[0]).unwrap();
+    assert!(json.to_string().contains("\"users\""), "{}", json);
+}
+
+#[test]
+fn names_round_trip_as_plain_strings() {
+    let name = Name::from("order id");
+    assert_eq!(serde_json::to_string(&name).unwrap(), "\"order id\"");
+    assert_eq!(serde_json::from_str::<Name>("\"order id\"").unwrap(), name);
+
+    let ident = Ident {
+        value: "Mixed".into(),
+        quote_style: Some('"'),
+    };
+    let json = serde_json::to_string(&ident).unwrap();
+    assert!(json.contains("\"value\":\"Mixed\""), "{}", json);
+    assert_eq!(serde_json::from_str::<Ident>(&json).unwrap(), ident);
+}
+
+#[test]
+fn error_kinds_serialize_as_their_code() {
+    let json = serde_json::to_string(&ErrorKind::UnexpectedEof).unwrap();
+    assert_eq!(json, format!("\"{}\"", ErrorKind::UnexpectedEof.code()));
+}
@@ -0,0 +1,96 @@
+use rust_sql_parser::ast::{ShowFilter, Statement};
+use rust_sql_parser::parse_statement;
+
+/// Real SHOW and DESCRIBE statements with their canonical form
+const STATEMENTS: &[(&str, &str)] = &[
+    ("SHOW TABLES", "SHOW TABLES"),
+    (
+        "show full TABLES from db like 'a%'",
+        "SHOW FULL TABLES FROM db LIKE 'a%'",
+    ),
+    ("SHOW COLUMNS FROM users", "SHOW COLUMNS FROM users"),
+    (
+        "SHOW COLUMNS IN users FROM db",
+        "SHOW COLUMNS FROM db.users",
+    ),
+    ("SHOW variables LIKE 'max%'", "SHOW variables LIKE 'max%'"),
+    (
+        "SHOW VARIABLES WHERE Variable_name = 'x'",
+        "SHOW VARIABLES WHERE Variable_name = 'x'",
+    ),
+    ("SHOW CREATE TABLE s.t", "SHOW CREATE TABLE s.t"),
+    ("SHOW CREATE VIEW v", "SHOW CREATE VIEW v"),
+    (
+        "SHOW TRANSACTION ISOLATION LEVEL",
+        "SHOW TRANSACTION ISOLATION LEVEL",
+    ),
+    ("SHOW search_path", "SHOW search_path"),
+    ("SHOW INDEX FROM t", "SHOW INDEX FROM t"),
+    ("SHOW DATABASES", "SHOW DATABASES"),
+    ("SHOW STATUS LIKE 'Threads%'", "SHOW STATUS LIKE 'Threads%'"),
+    ("SHOW ENGINE INNODB STATUS", "SHOW ENGINE INNODB STATUS"),
+    ("DESCRIBE users", "DESCRIBE users"),
+    ("DESC s.users", "DESCRIBE s.users"),
+];
+
+#[test]
+fn statements_parse_and_round_trip() {
+    for (sql, canonical) in STATEMENTS {
+        let statement = parse_statement(sql).unwrap_or_else(|e| panic!("{}: {}", sql, e));
+        assert_eq!(statement.to_string(), *canonical);
+        assert_eq!(parse_statement(canonical).unwrap(), statement);
+    }
+}
+
+#[test]
+fn show_create_names_the_object() {
+    match parse_statement("SHOW CREATE TABLE s.t").unwrap() {
+        Statement::Show {
+            object, name, from, ..
+        } => {
+            assert_eq!(object, "CREATE TABLE");
+            assert_eq!(name.unwrap().to_string(), "s.t");
+            assert_eq!(from, None);
+        }
+        statement => panic!("not a SHOW: {}", statement),
+    }
+}
+
+#[test]
+fn multi_word_objects() {
+    match parse_statement("SHOW TRANSACTION ISOLATION LEVEL").unwrap() {
+        Statement::Show { object, name, .. } => {
+            assert_eq!(object, "TRANSACTION ISOLATION LEVEL");
+            assert_eq!(name, None);
+        }
+        statement => panic!("not a SHOW: {}", statement),
+    }
+}
+
+#[test]
+fn filters() {
+    match parse_statement("SHOW FULL COLUMNS FROM t LIKE 'id%'").unwrap() {
+        Statement::Show {
+            full, from, filter, ..
+        } => {
+            assert!(full);
+            assert_eq!(from.unwrap().to_string(), "t");
+            assert_eq!(filter, Some(ShowFilter::Like("id%".into())));
+        }
+        statement => panic!("not a SHOW: {}", statement),
+    }
+}
+
+#[test]
+fn incomplete_statements_are_rejected() {
+    for sql in [
+        "SHOW",
+        "SHOW CREATE",
+        "SHOW CREATE TABLE",
+        "SHOW TABLES LIKE",
+        "SHOW TABLES LIKE x",
+        "DESCRIBE",
+    ] {
+        assert!(parse_statement(sql).is_err(), "{} should not parse", sql);
+    }
+}
@@ -0,0 +1,115 @@
+use rust_sql_parser::parse_expression;
+
+fn simplify(sql: &str) -> String {
+    parse_expression(sql)
+        .unwrap_or_else(|e| panic!("{}: {}", sql, e))
+        .simplify()
+        .to_string()
+}
+
+fn check(cases: &[(&str, &str)]) {
+    for (sql, simplified) in cases {
+        assert_eq!(simplify(sql), *simplified, "{}", sql);
+    }
+}
+
+#[test]
+fn identity_elements_are_dropped() {
+    check(&[
+        ("TRUE AND x", "x"),
+        ("x AND TRUE", "x"),
+        ("FALSE OR x", "x"),
+        ("x OR FALSE", "x"),
+        ("TRUE AND x AND TRUE AND y", "x AND y"),
+    ]);
+}
+
+#[test]
+fn absorbing_elements_absorb() {
+    check(&[
+        ("FALSE AND x", "FALSE"),
+        ("x AND FALSE", "FALSE"),
+        ("TRUE OR x", "TRUE"),
+        ("x OR TRUE", "TRUE"),
+        ("NULL AND FALSE", "FALSE"),
+        ("NULL OR TRUE", "TRUE"),
+    ]);
+}
+
+#[test]
+fn null_is_kept() {
+    check(&[
+        ("NULL AND x", "NULL AND x"),
+        ("x OR NULL", "x OR NULL"),
+        ("NULL AND NULL", "NULL"),
+        ("NOT NULL", "NULL"),
+        ("NULL = 1", "NULL"),
+        ("NULL IS NULL", "TRUE"),
+        ("1 IS NOT NULL", "TRUE"),
+    ]);
+}
+
+#[test]
+fn double_negation_is_removed() {
+    check(&[
+        ("NOT NOT x", "x"),
+        ("NOT NOT NOT x", "NOT x"),
+        ("NOT (NOT x AND TRUE)", "x"),
+        ("NOT NOT (a OR b)", "a OR b"),
+        ("NOT TRUE", "FALSE"),
+    ]);
+    // Folding constants alone keeps the negations
+    assert_eq!(
+        parse_expression("NOT NOT x")
+            .unwrap()
+            .fold_constants()
+            .to_string(),
+        "NOT NOT x"
+    );
+}
+
+#[test]
+fn literal_comparisons_are_folded() {
+    check(&[
+        ("1 < 2", "TRUE"),
+        ("'a' = 'b'", "FALSE"),
+        ("'a' < 'b'", "'a' < 'b'"),
+        ("1 = 1 AND x", "x"),
+    ]);
+}
+
+#[test]
+fn overflow_and_division_by_zero_are_left_unfolded() {
+    check(&[
+        (
+            "18446744073709551615 + 1 > 0",
+            "18446744073709551615 + 1 > 0",
+        ),
+        ("1 / 0 = 1", "1 / 0 = 1"),
+        ("TRUE AND 1 / 0 = 1", "1 / 0 = 1"),
+    ]);
+}
+
+#[test]
+fn operands_inside_other_nodes_are_simplified() {
+    check(&[
+        ("f(TRUE AND x, 1 + 1)", "f(x, 2)"),
+        ("x IN (1 + 1, NOT NOT y)", "x IN (2, y)"),
+        ("(TRUE AND x) IS NULL", "x IS NULL"),
+        ("name LIKE 'a' || '%'", "name LIKE 'a%'"),
+        ("(1 + 1)::INT", "2::INT"),
+        (
+            "(SELECT a FROM t WHERE TRUE AND b = 1 + 1)",
+            "(SELECT a FROM t WHERE b = 2)",
+        ),
+    ]);
+}
+
+#[test]
+fn generated_predicate() {
+    check(&[(
+        "TRUE AND (NOT NOT (a = 1 + 1) OR FALSE) AND (b IN (1, 2) OR 1 > 2) \
+         AND NOT (FALSE AND c) AND (d IS NULL OR NULL)",
+        "a = 2 AND b IN (1, 2) AND (d IS NULL OR NULL)",
+    )]);
+}
@@ -0,0 +1,101 @@
+use rust_sql_parser::source_map::SourceMap;
+use rust_sql_parser::tokenize_with_spans;
+
+/// Every offset that starts a character, and the end of the input
+fn boundaries(source: &str) -> impl Iterator<Item = usize> + '_ {
+    source
+        .char_indices()
+        .map(|(i, _)| i)
+        .chain(std::iter::once(source.len()))
+}
+
+#[test]
+fn crlf_lines() {
+    let map = SourceMap::new("SELECT a\r\nFROM t\r\n");
+    assert_eq!(map.line_count(), 3);
+    assert_eq!(map.line(1), Some("SELECT a"));
+    assert_eq!(map.line(2), Some("FROM t"));
+    assert_eq!(map.line(3), Some(""));
+    // The `\r` is the last column of its line, and the `\n` comes after it
+    assert_eq!(map.offset_to_line_col(8), (1, 9));
+    assert_eq!(map.offset_to_line_col(9), (1, 10));
+    assert_eq!(map.offset_to_line_col(10), (2, 1));
+    assert_eq!(map.line_col_to_offset(2, 1), Some(10));
+}
+
+#[test]
+fn offsets_at_the_end_of_the_input() {
+    // A last line without a newline
+    let map = SourceMap::new("SELECT 1\nFROM t");
+    assert_eq!(map.offset_to_line_col(15), (2, 7));
+    assert_eq!(map.line_col_to_offset(2, 7), Some(15));
+    assert_eq!(map.line_col_to_offset(2, 8), None);
+
+    // A newline at the very end starts an empty line
+    let map = SourceMap::new("SELECT 1\n");
+    assert_eq!(map.offset_to_line_col(9), (2, 1));
+    assert_eq!(map.line_col_to_offset(2, 1), Some(9));
+    assert_eq!(map.line_col_to_offset(3, 1), None);
+
+    let map = SourceMap::new("");
+    assert_eq!(map.line_count(), 1);
+    assert_eq!(map.offset_to_line_col(0), (1, 1));
+    assert_eq!(map.offset_to_line_col_utf16(0), (1, 1));
+    assert_eq!(map.line_col_to_offset(1, 1), Some(0));
+}
+
+#[test]
+#[should_panic]
+fn offsets_past_the_end_panic() {
+    SourceMap::new("a").offset_to_line_col(2);
+}
+
+#[test]
+fn utf16_columns_count_surrogate_pairs_twice() {
+    // é is one UTF-16 unit, each emoji two
+    let source = "SELECT 'é😀🦀' AS x";
+    let map = SourceMap::new(source);
+    let x = source.find('x').unwrap();
+    assert_eq!(map.offset_to_line_col(x), (1, 17));
+    assert_eq!(map.offset_to_line_col_utf16(x), (1, 19));
+    assert_eq!(map.line_col_utf16_to_offset(1, 19), Some(x));
+
+    let crab = source.find('🦀').unwrap();
+    assert_eq!(map.offset_to_line_col_utf16(crab), (1, 12));
+    assert_eq!(map.line_col_utf16_to_offset(1, 12), Some(crab));
+    // The second unit of a pair is not a character boundary
+    assert_eq!(map.line_col_utf16_to_offset(1, 13), None);
+}
+
+#[test]
+fn columns_convert_back_to_their_offsets() {
+    let source = "SELECT '😀'\r\n\tFROM é\n\nWHERE 𝑥 = 1\n";
+    let map = SourceMap::new(source);
+    for offset in boundaries(source) {
+        let (line, column) = map.offset_to_line_col(offset);
+        assert_eq!(
+            map.line_col_to_offset(line, column),
+            Some(offset),
+            "{}",
+            offset
+        );
+        let (line, column) = map.offset_to_line_col_utf16(offset);
+        assert_eq!(
+            map.line_col_utf16_to_offset(line, column),
+            Some(offset),
+            "{}",
+            offset
+        );
+    }
+}
+
+#[test]
+fn token_spans_agree_with_the_map() {
+    let source = "SELECT 'é😀',\r\n  b\n FROM t";
+    let map = SourceMap::new(source);
+    for (_, span) in tokenize_with_spans(source).unwrap() {
+        for location in [span.start, span.end] {
+            assert_eq!(map.location(location.offset), location);
+        }
+    }
+}
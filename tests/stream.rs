@@ -0,0 +1,112 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::io::Cursor;
+
+use rust_sql_parser::stream::StatementStream;
+
+/// Counts the bytes the current thread holds, so tests running alongside do not disturb it
+struct Counting;
+
+thread_local! {
+    static LIVE: Cell<usize> = const { Cell::new(0) };
+    static PEAK: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let live = LIVE.with(|live| {
+            live.set(live.get() + layout.size());
+            live.get()
+        });
+        PEAK.with(|peak| peak.set(peak.get().max(live)));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        LIVE.with(|live| live.set(live.get().saturating_sub(layout.size())));
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+const STATEMENTS: usize = 100_000;
+
+/// Every this many statements, one has a missing table name
+const BROKEN_EVERY: usize = 25_000;
+
+/// Builds a dump of `count` statements, two lines each, with semicolons in strings and
+/// comments that do not end them
+fn dump(count: usize) -> String {
+    let mut dump = String::new();
+    for i in 1..=count {
+        if i % BROKEN_EVERY == 0 {
+            dump.push_str("-- broken;\nSELECT a FROM ;\n");
+        } else {
+            dump.push_str(&format!(
+                "INSERT INTO t VALUES ({}, 'a;b'); /* ; */\n-- {};\n",
+                i, i
+            ));
+        }
+    }
+    dump
+}
+
+/// What streaming a dump gave
+struct Streamed {
+    parsed: usize,
+    /// The line, column and statement number of each error
+    errors: Vec<(usize, usize, Option<usize>)>,
+    /// The most memory held at once while streaming
+    peak: usize,
+}
+
+fn stream(count: usize) -> Streamed {
+    let input = Cursor::new(dump(count));
+    let before = LIVE.with(Cell::get);
+    PEAK.with(|peak| peak.set(before));
+
+    let mut parsed = 0;
+    let mut errors = Vec::new();
+    for result in StatementStream::new(input) {
+        match result {
+            Ok(_) => parsed += 1,
+            Err(e) => {
+                let start = e.span().unwrap().start;
+                errors.push((start.line, start.column, e.statement()));
+            }
+        }
+    }
+    Streamed {
+        parsed,
+        errors,
+        peak: PEAK.with(Cell::get) - before,
+    }
+}
+
+#[test]
+fn a_large_dump_streams_in_bounded_memory() {
+    let streamed = stream(STATEMENTS);
+    assert_eq!(streamed.parsed, STATEMENTS - 4);
+
+    // Each statement takes two lines, and the error is at the end of the second
+    let expected: Vec<_> = (1..=4)
+        .map(|n| {
+            let statement = n * BROKEN_EVERY;
+            (statement * 2, 15, Some(statement))
+        })
+        .collect();
+    assert_eq!(streamed.errors, expected);
+
+    // Streaming a hundred times as many statements needs no more memory
+    let small = stream(STATEMENTS / 100);
+    assert!(
+        streamed.peak <= small.peak + 4096,
+        "{} bytes for {} statements, {} for {}",
+        streamed.peak,
+        STATEMENTS,
+        small.peak,
+        STATEMENTS / 100
+    );
+}
@@ -0,0 +1,67 @@
+use rust_sql_parser::ast::{Keyword, Token};
+use rust_sql_parser::error::Problem;
+use rust_sql_parser::tokenizer::Tokenizer;
+use rust_sql_parser::{tokenize, tokenize_with_spans};
+
+#[test]
+fn spans_cover_each_token() {
+    let sql = "SELECT name,\n  'a''b' FROM t";
+    let tokens = tokenize_with_spans(sql).unwrap();
+    let texts: Vec<&str> = tokens
+        .iter()
+        .map(|(_, span)| &sql[span.start.offset..span.end.offset])
+        .collect();
+    assert_eq!(texts, ["SELECT", "name", ",", "'a''b'", "FROM", "t", ""]);
+
+    let (token, span) = &tokens[3];
+    assert_eq!(*token, Token::String("a'b".into()));
+    assert_eq!((span.start.line, span.start.column), (2, 3));
+    assert_eq!(tokens[4].0, Token::Keyword(Keyword::From));
+    assert_eq!(tokens.last().unwrap().0, Token::Eof);
+}
+
+#[test]
+fn same_tokens_as_tokenize() {
+    let sql = "SELECT a::INT, -1.5 || $1 FROM t WHERE b <> ?";
+    let with_spans: Vec<Token> = tokenize_with_spans(sql)
+        .unwrap()
+        .into_iter()
+        .map(|(token, _)| token)
+        .collect();
+    assert_eq!(with_spans, tokenize(sql).unwrap());
+}
+
+#[test]
+fn lexical_errors_are_returned() {
+    let e = tokenize_with_spans("SELECT 'a").unwrap_err();
+    assert_eq!(e.span().unwrap().start.column, 8);
+}
+
+#[test]
+fn try_new_reports_lexical_errors() {
+    let mut tokenizer = Tokenizer::try_new("a = 1").unwrap();
+    assert_eq!(tokenizer.next(), Some(Token::Identifier("a".into())));
+
+    let e = Tokenizer::try_new("a = 'b").err().unwrap();
+    assert!(matches!(
+        e.problem,
+        Problem::UnterminatedString { quote: '\'' }
+    ));
+    assert!(Tokenizer::try_new("a # b").is_err());
+}
+
+#[test]
+fn repeated_names_share_one_copy() {
+    let tokens = tokenize("SELECT id, name FROM users WHERE id = 1 AND \"name\" = 'name'").unwrap();
+    let names: Vec<_> = tokens
+        .iter()
+        .filter_map(|token| match token {
+            Token::Identifier(name) | Token::QuotedIdentifier(name) => Some(name),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(names, ["id", "name", "users", "id", "name"]);
+    assert_eq!(names[0].as_ptr(), names[3].as_ptr());
+    assert_eq!(names[1].as_ptr(), names[4].as_ptr());
+    assert_ne!(names[0].as_ptr(), names[1].as_ptr());
+}
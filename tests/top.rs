@@ -0,0 +1,77 @@
+use rust_sql_parser::ast::{Expression, SelectStatement, SetExpr, Statement, Top, Value};
+use rust_sql_parser::dialect::Dialect;
+use rust_sql_parser::parser::PrattParser;
+
+fn parse(sql: &str, dialect: Dialect) -> Result<Statement, String> {
+    PrattParser::new_with_dialect(sql, dialect)
+        .parse_statement()
+        .map_err(|e| e.message())
+}
+
+/// Returns the SELECT of a plain query in `dialect`
+fn select(sql: &str, dialect: Dialect) -> SelectStatement {
+    match parse(sql, dialect).unwrap_or_else(|e| panic!("{}: {}", sql, e)) {
+        Statement::Query(query) => match query.body {
+            SetExpr::Select(select) => *select,
+            body => panic!("not a SELECT: {}", body),
+        },
+        statement => panic!("not a query: {}", statement),
+    }
+}
+
+fn top(quantity: u64, percent: bool, with_ties: bool) -> Option<Top> {
+    Some(Top {
+        quantity: Expression::Value(Value::Number(quantity)),
+        percent,
+        with_ties,
+    })
+}
+
+#[test]
+fn top_clauses_in_sql_server() {
+    for (sql, expected) in [
+        ("SELECT TOP 10 * FROM t ORDER BY x", top(10, false, false)),
+        ("SELECT TOP (10) PERCENT a FROM t", top(10, true, false)),
+        ("SELECT DISTINCT TOP 5 a FROM t", top(5, false, false)),
+        (
+            "SELECT TOP 3 WITH TIES a FROM t ORDER BY a",
+            top(3, false, true),
+        ),
+        ("SELECT a FROM t", None),
+    ] {
+        assert_eq!(select(sql, Dialect::MsSql).top, expected, "{}", sql);
+    }
+}
+
+#[test]
+fn top_is_a_name_elsewhere() {
+    for dialect in [
+        Dialect::Generic,
+        Dialect::PostgreSql,
+        Dialect::MySql,
+        Dialect::SQLite,
+    ] {
+        for sql in [
+            "SELECT top FROM t",
+            "SELECT a AS top FROM t",
+            "SELECT a FROM top",
+        ] {
+            let select = select(sql, dialect);
+            assert_eq!(select.top, None);
+            assert_eq!(select.to_string(), sql);
+        }
+    }
+}
+
+#[test]
+fn the_same_input_in_both_modes() {
+    let sql = "SELECT TOP 10 a FROM t";
+    assert_eq!(select(sql, Dialect::MsSql).to_string(), sql);
+    let e = parse(sql, Dialect::Generic).unwrap_err();
+    assert!(e.starts_with("10 after end of statement"), "{}", e);
+
+    // Without TOP support `top` is read as a column, which the MsSql parse rejects
+    let sql = "SELECT top FROM t";
+    assert!(parse(sql, Dialect::Generic).is_ok());
+    assert!(parse(sql, Dialect::MsSql).is_err());
+}
@@ -0,0 +1,158 @@
+use rust_sql_parser::{parse_expression, parse_statement};
+
+#[test]
+fn expression_snapshots() {
+    for (sql, tree) in [
+        ("a + 2 * b", "(+ (ident a) (* 2 (ident b)))"),
+        ("-(a - b) - c", "(- (- (- (ident a) (ident b))) (ident c))"),
+        (
+            "x NOT IN (1, 'two', NULL)",
+            "(not in (ident x) 1 'two' NULL)",
+        ),
+        (
+            "f(a, count(*)) IS NOT NULL",
+            "(is not null (call f (ident a) (call count *)))",
+        ),
+        (
+            "name NOT LIKE 'a%' OR NOT TRUE",
+            "(OR (not like (ident name) 'a%') (NOT TRUE))",
+        ),
+        (
+            "t.a::VARCHAR(10) || $1",
+            "(|| (cast (ident t a) VARCHAR(10)) $1)",
+        ),
+        (
+            "\"Mixed\".x = ? AND y = :name",
+            "(AND (= (ident \"Mixed\" x) ?) (= (ident y) :name))",
+        ),
+        ("'it''s' <> -1.5e3", "(<> 'it''s' (- 1500.0))"),
+        (
+            "(SELECT max(b) FROM u) > 2.5",
+            "(> (subquery (query (select (items (call max (ident b))) (from (table u))))) 2.5)",
+        ),
+    ] {
+        assert_eq!(
+            parse_expression(sql).unwrap().to_tree_string(),
+            tree,
+            "{}",
+            sql
+        );
+    }
+}
+
+#[test]
+fn statement_snapshots() {
+    for (sql, tree) in [
+        (
+            "SELECT DISTINCT a, b + 1 AS c FROM t AS x LEFT JOIN u ON x.id = u.id WHERE a > 1 \
+             GROUP BY a HAVING count(*) > 1 ORDER BY a DESC LIMIT 10 OFFSET 5",
+            "(query (select distinct (items (ident a) (as (+ (ident b) 1) c)) \
+             (from (table t (as x)) (left-join (table u) (on (= (ident x id) (ident u id))))) \
+             (where (> (ident a) 1)) (group-by (ident a)) (having (> (call count *) 1))) \
+             (order-by (desc (ident a))) (limit 10) (offset 5))",
+        ),
+        (
+            "WITH w AS (SELECT 1) SELECT * FROM w UNION ALL SELECT 2",
+            "(query (with (cte w (query (select (items 1))))) \
+             (union-all (select (items *) (from (table w))) (select (items 2))))",
+        ),
+        (
+            "SELECT * FROM (SELECT a FROM t) AS d(x) CROSS JOIN v",
+            "(query (select (items *) (from (derived (query (select (items (ident a)) \
+             (from (table t)))) (as d x)) (cross-join (table v)))))",
+        ),
+        (
+            "INSERT INTO t (a, b) VALUES (1, 'x'), (2, NULL)",
+            "(insert t (columns a b) (values (row 1 'x') (row 2 NULL)))",
+        ),
+        (
+            "UPDATE t SET a = a + 1 WHERE b = 2",
+            "(update (table t) (set (= a (+ (ident a) 1))) (where (= (ident b) 2)))",
+        ),
+        (
+            "DELETE FROM t WHERE a IN (1, 2)",
+            "(delete (table t) (where (in (ident a) 1 2)))",
+        ),
+        // Other statements are shown as their SQL
+        (
+            "CREATE TABLE t (a INT PRIMARY KEY)",
+            "(sql \"CREATE TABLE t (a INT PRIMARY KEY)\")",
+        ),
+    ] {
+        assert_eq!(
+            parse_statement(sql).unwrap().to_tree_string(),
+            tree,
+            "{}",
+            sql
+        );
+    }
+}
+
+#[test]
+fn dot_snapshot() {
+    // What `examples/dot.rs` prints
+    let dot = parse_statement("SELECT a FROM t WHERE b > 1")
+        .unwrap()
+        .to_dot();
+    assert_eq!(
+        dot,
+        r#"digraph ast {
+    node [shape=box];
+    n0 [label="query"];
+    n1 [label="select"];
+    n2 [label="items"];
+    n3 [label="ident"];
+    n4 [label="a"];
+    n3 -> n4;
+    n2 -> n3;
+    n1 -> n2;
+    n5 [label="from"];
+    n6 [label="table"];
+    n7 [label="t"];
+    n6 -> n7;
+    n5 -> n6;
+    n1 -> n5;
+    n8 [label="where"];
+    n9 [label=">"];
+    n10 [label="ident"];
+    n11 [label="b"];
+    n10 -> n11;
+    n9 -> n10;
+    n12 [label="1"];
+    n9 -> n12;
+    n8 -> n9;
+    n1 -> n8;
+    n0 -> n1;
+}
+"#
+    );
+}
+
+#[test]
+fn equal_subtrees_get_their_own_nodes() {
+    assert_eq!(
+        parse_expression("a + a").unwrap().to_dot(),
+        r#"digraph ast {
+    node [shape=box];
+    n0 [label="+"];
+    n1 [label="ident"];
+    n2 [label="a"];
+    n1 -> n2;
+    n0 -> n1;
+    n3 [label="ident"];
+    n4 [label="a"];
+    n3 -> n4;
+    n0 -> n3;
+}
+"#
+    );
+}
+
+#[test]
+fn dot_labels_are_escaped() {
+    let dot = parse_expression(r#"'say "hi"' || 'back\slash'"#)
+        .unwrap()
+        .to_dot();
+    assert!(dot.contains(r#"n1 [label="'say \"hi\"'"];"#), "{}", dot);
+    assert!(dot.contains(r#"n2 [label="'back\\slash'"];"#), "{}", dot);
+}
@@ -0,0 +1,83 @@
+use rust_sql_parser::ast::{Expression, Float, UnaryOperator, Value};
+use rust_sql_parser::error::Problem;
+use rust_sql_parser::parse_expression;
+
+fn value(sql: &str) -> Value {
+    match &parse_expression(sql) {
+        Ok(Expression::Value(value)) => value.clone(),
+        other => panic!("{}: {:?}", sql, other),
+    }
+}
+
+#[test]
+fn each_literal_kind_has_its_variant() {
+    for (sql, expected, printed) in [
+        ("0", Value::Number(0), "0"),
+        ("007", Value::Number(7), "7"),
+        (
+            "18446744073709551615",
+            Value::Number(u64::MAX),
+            "18446744073709551615",
+        ),
+        ("0.0", Value::Float(Float(0.0)), "0.0"),
+        ("1.5", Value::Float(Float(1.5)), "1.5"),
+        ("1e-3", Value::Float(Float(0.001)), "0.001"),
+        ("2E+10", Value::Float(Float(2e10)), "20000000000.0"),
+        ("''", Value::SingleQuotedString(String::new()), "''"),
+        (
+            "'it''s'",
+            Value::SingleQuotedString("it's".into()),
+            "'it''s'",
+        ),
+        ("'a\nb'", Value::SingleQuotedString("a\nb".into()), "'a\nb'"),
+        ("'é😀'", Value::SingleQuotedString("é😀".into()), "'é😀'"),
+        ("TRUE", Value::Boolean(true), "TRUE"),
+        ("False", Value::Boolean(false), "FALSE"),
+        ("null", Value::Null, "NULL"),
+    ] {
+        assert_eq!(value(sql), expected, "{}", sql);
+        assert_eq!(expected.to_string(), printed, "{}", sql);
+        assert_eq!(value(printed), expected, "{}", printed);
+    }
+}
+
+#[test]
+fn floats_print_so_they_read_back() {
+    for x in [
+        0.1,
+        1e-7,
+        1e300,
+        123456789.125,
+        2.0,
+        f64::MAX,
+        f64::MIN_POSITIVE,
+    ] {
+        let printed = Float(x).to_string();
+        assert_eq!(value(&printed), Value::Float(Float(x)), "{}", printed);
+    }
+}
+
+#[test]
+fn signs_are_operators() {
+    match &parse_expression("-0.0").unwrap() {
+        Expression::UnaryOperation {
+            operator: UnaryOperator::Minus,
+            operand,
+        } => assert_eq!(**operand, Expression::Value(Value::Float(Float(0.0)))),
+        other => panic!("{:?}", other),
+    }
+}
+
+#[test]
+fn numbers_out_of_range_are_invalid() {
+    for sql in ["18446744073709551616", "1e309"] {
+        match parse_expression(sql).unwrap_err().problem {
+            Problem::InvalidNumber { text } => assert_eq!(text, sql),
+            other => panic!("{}: {:?}", sql, other),
+        }
+    }
+    // Neither hex nor digit separators are numbers
+    for sql in [".5", "1.", "1.5e", "0x10", "1_000"] {
+        assert!(parse_expression(sql).is_err(), "{}", sql);
+    }
+}
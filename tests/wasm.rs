@@ -0,0 +1,91 @@
+#![cfg(feature = "wasm")]
+
+use rust_sql_parser::wasm::{parse, tokenize, validate};
+use serde_json::{json, Value};
+
+/// Serializes what an export would convert, as the JSON-compatible serializer it uses does
+fn to_json<T: serde::Serialize>(value: &T) -> Value {
+    serde_json::to_value(value).unwrap()
+}
+
+#[test]
+fn parse_returns_statements_or_a_diagnostic() {
+    let statements = parse("SELECT a FROM t; DELETE FROM u").unwrap();
+    let json = to_json(&statements);
+    assert_eq!(json.as_array().unwrap().len(), 2);
+    assert!(json[0].get("Query").is_some(), "{}", json);
+
+    let diagnostic = parse("SELECT 1;\nDELETE t").unwrap_err();
+    assert_eq!(
+        to_json(&diagnostic),
+        json!({
+            "code": "E0001",
+            "message": "FROM after DELETE, found t",
+            "line": 2,
+            "column": 8,
+            "endLine": 2,
+            "endColumn": 9,
+        })
+    );
+}
+
+#[test]
+fn tokenize_returns_spanned_tokens_ending_with_eof() {
+    let diagnostic = to_json(&tokenize("SELECT\n  é").unwrap_err());
+    assert_eq!(diagnostic["line"], 2);
+    assert_eq!(diagnostic["column"], 3);
+
+    let tokens = to_json(&tokenize("SELECT 'é'").unwrap());
+    assert_eq!(
+        tokens,
+        json!([
+            {
+                "token": {"Keyword": "Select"},
+                "start": {"line": 1, "column": 1, "offset": 0},
+                "end": {"line": 1, "column": 7, "offset": 6},
+            },
+            {
+                "token": {"String": "é"},
+                "start": {"line": 1, "column": 8, "offset": 7},
+                "end": {"line": 1, "column": 11, "offset": 11},
+            },
+            {
+                "token": "Eof",
+                "start": {"line": 1, "column": 11, "offset": 11},
+                "end": {"line": 1, "column": 11, "offset": 11},
+            },
+        ])
+    );
+}
+
+#[test]
+fn validate_returns_nothing_or_the_first_error() {
+    assert!(validate("SELECT a FROM t; SELECT b FROM u").is_none());
+
+    let json = to_json(&validate("SELECT a FROM").unwrap());
+    assert_eq!(json["code"], "E0002");
+    assert_eq!((&json["line"], &json["column"]), (&json!(1), &json!(14)));
+
+    // Lexical errors are reported the same way
+    let json = to_json(&validate("SELECT a;\nSELECT 'open").unwrap());
+    assert_eq!(json["code"], "E0004");
+    assert_eq!((&json["line"], &json["column"]), (&json!(2), &json!(8)));
+}
+
+#[test]
+fn invalid_input_never_panics() {
+    for sql in [
+        "",
+        ";",
+        "'",
+        "\"",
+        "SELECT ((",
+        "/*",
+        "é",
+        "SELECT 1e999999",
+    ] {
+        let _ = parse(sql);
+        let _ = tokenize(sql);
+        let _ = validate(sql);
+    }
+}
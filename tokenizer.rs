@@ -1,235 +1,513 @@
-use crate::ast::{Keyword, Token};
-use crate::error::ParseError;
-use std::str::FromStr;
-
-/// Tokenizer struct
-pub struct Tokenizer {
-    input: Vec<char>,
-    position: usize,
-    tokens: Vec<Token>, // Store tokens separately
-}
-
-impl Tokenizer {
-    /// Creates a new tokenizer and tokenizes the entire input
-    pub fn new(input: &str) -> Self {
-        let mut tokenizer = Tokenizer {
-            input: input.chars().collect(),
-            position: 0,
-            tokens: vec![],
-        };
-        tokenizer.tokenize_input(); // Tokenize once on initialization
-        tokenizer
-    }
-
-    /// Tokenizes the entire input and returns the tokens
-    pub fn tokenize_string(&mut self) -> Result<Vec<Token>, ParseError> {
-        self.tokenize_input();  // Ensure input is fully tokenized
-        Ok(self.tokens.clone())
-    }
-
-    /// Tokenizes the entire input into the internal tokens vector
-    fn tokenize_input(&mut self) {
-        while let Some(token) = self.tokenize_next_token() {
-            match token {
-                Ok(token) => self.tokens.push(token),
-                Err(e) => {
-                    eprintln!("Tokenizer error: {:?}", e);
-                    self.tokens.push(Token::Eof);
-                    break;
-                }
-            }
-        }
-
-        // Add the Eof token at the end if not already present
-        if self.tokens.is_empty() || self.tokens.last() != Some(&Token::Eof) {
-            self.tokens.push(Token::Eof);
-        }
-    }
-
-    /// Returns the next character without advancing the position
-    fn peek(&self) -> Option<char> {
-        self.input.get(self.position).copied()
-    }
-
-    /// Returns the next character and advances the position
-    fn advance(&mut self) -> Option<char> {
-        if self.position < self.input.len() {
-            let ch = self.input[self.position];
-            self.position += 1;
-            Some(ch)
-        } else {
-            None
-        }
-    }
-
-    /// Tokenizes the next available token
-    fn tokenize_next_token(&mut self) -> Option<Result<Token, ParseError>> {
-        while let Some(ch) = self.peek() {
-            match ch {
-                // Skip whitespace
-                ' ' | '\t' | '\n' | '\r' => {
-                    self.advance();
-                }
-
-                // String literals
-                '"' => return Some(self.tokenize_string_literal()),
-
-                // Numbers
-                '0'..='9' => return Some(self.tokenize_number()),
-
-                // Identifiers or keywords
-                'a'..='z' | 'A'..='Z' | '_' => return Some(self.tokenize_identifier_or_keyword()),
-
-                // Single-character tokens
-                '(' => {
-                    self.advance();
-                    return Some(Ok(Token::LeftParentheses));
-                }
-                ')' => {
-                    self.advance();
-                    return Some(Ok(Token::RightParentheses));
-                }
-                ',' => {
-                    self.advance();
-                    return Some(Ok(Token::Comma));
-                }
-                ';' => {
-                    self.advance();
-                    return Some(Ok(Token::Semicolon));
-                }
-
-                // Multi-character operators
-                '=' => {
-                    self.advance();
-                    if self.peek() == Some('=') {
-                        self.advance();
-                        return Some(Ok(Token::Equal));
-                    }
-                    return Some(Ok(Token::Equal));
-                }
-                '!' => {
-                    self.advance();
-                    if self.peek() == Some('=') {
-                        self.advance();
-                        return Some(Ok(Token::NotEqual));
-                    }
-                    return Some(Err(ParseError::UnexpectedToken("Unexpected '!' without '='".to_string())));
-                }
-                '>' => {
-                    self.advance();
-                    if self.peek() == Some('=') {
-                        self.advance();
-                        return Some(Ok(Token::GreaterThanOrEqual));
-                    }
-                    return Some(Ok(Token::GreaterThan));
-                }
-                '<' => {
-                    self.advance();
-                    if self.peek() == Some('=') {
-                        self.advance();
-                        return Some(Ok(Token::LessThanOrEqual));
-                    }
-                    return Some(Ok(Token::LessThan));
-                }
-
-                // Single-character operators
-                '+' => {
-                    self.advance();
-                    return Some(Ok(Token::Plus));
-                }
-                '-' => {
-                    self.advance();
-                    return Some(Ok(Token::Minus));
-                }
-                '*' => {
-                    self.advance();
-                    return Some(Ok(Token::Multiply));
-                }
-                '/' => {
-                    self.advance();
-                    return Some(Ok(Token::Divide));
-                }
-
-                // Unknown character
-                _ => {
-                    let invalid_char = self.advance().unwrap();
-                    return Some(Err(ParseError::UnexpectedToken(format!("Unexpected character '{}'", invalid_char))));
-                }
-            }
-        }
-
-        // Return Eof if no more characters
-        Some(Ok(Token::Eof))
-    }
-
-    /// Tokenizes string literals
-    fn tokenize_string_literal(&mut self) -> Result<Token, ParseError> {
-        let mut value = String::new();
-        self.advance(); // Skip the opening quote
-
-        while let Some(ch) = self.peek() {
-            match ch {
-                '"' => {
-                    self.advance(); // Consume the closing quote
-                    return Ok(Token::String(value));
-                }
-                _ => value.push(self.advance().unwrap()),
-            }
-        }
-
-        Err(ParseError::UnexpectedEndOfInput("Unterminated string literal".to_string()))
-    }
-
-    /// Tokenizes numbers (u64 only)
-    fn tokenize_number(&mut self) -> Result<Token, ParseError> {
-        let mut value = String::new();
-
-        while let Some(ch) = self.peek() {
-            if ch.is_ascii_digit() {
-                value.push(self.advance().unwrap());
-            } else {
-                break;
-            }
-        }
-
-        match value.parse::<u64>() {
-            Ok(num) => Ok(Token::Number(num)),
-            Err(_) => Err(ParseError::ExpectedNumber(format!("Invalid number: {}", value))),
-        }
-    }
-
-    /// Tokenizes identifiers or keywords
-    fn tokenize_identifier_or_keyword(&mut self) -> Result<Token, ParseError> {
-        let mut value = String::new();
-
-        while let Some(ch) = self.peek() {
-            if ch.is_ascii_alphanumeric() || ch == '_' {
-                value.push(self.advance().unwrap());
-            } else {
-                break;
-            }
-        }
-
-        // Check if the value is a known keyword
-        match Keyword::from_str(&value) {
-            Ok(keyword) => Ok(Token::Keyword(keyword)),
-            Err(_) => Ok(Token::Identifier(value)),
-        }
-    }
-
-    /// Returns the next token without advancing the position
-    pub fn peek_token(&self) -> Option<Token> {
-        self.tokens.get(self.position).cloned()
-    }
-
-    /// Returns the next token and advances the position
-    pub fn next(&mut self) -> Option<Token> {
-        if self.position < self.tokens.len() {
-            let token = self.tokens[self.position].clone();
-            self.position += 1;
-            Some(token)
-        } else {
-            Some(Token::Eof)
-        }
-    }
-}
+use crate::ast::{Float, Keyword, Name, Token};
+use crate::error::{ParseError, ParseErrors, Problem};
+use std::collections::HashSet;
+use std::str::FromStr;
+
+/// Represents a position in the input; lines and columns count from 1, and columns count
+/// characters rather than bytes
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+    /// Byte offset from the start of the input
+    pub offset: usize,
+}
+
+impl Default for Location {
+    fn default() -> Self {
+        Location { line: 1, column: 1, offset: 0 }
+    }
+}
+
+impl Location {
+    /// Converts this location, taken within a piece of a larger input that starts at
+    /// `origin`, to a location in the larger input
+    pub(crate) fn offset_by(self, origin: Location) -> Location {
+        Location {
+            line: origin.line + self.line - 1,
+            column: if self.line == 1 { origin.column + self.column - 1 } else { self.column },
+            offset: origin.offset + self.offset,
+        }
+    }
+}
+
+/// Represents the stretch of input a token covers, from `start` up to but not including `end`
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+pub struct Span {
+    pub start: Location,
+    pub end: Location,
+}
+
+/// Tokenizer struct
+pub struct Tokenizer {
+    input: Vec<char>,
+    position: usize,
+    /// Where `position` is, as line, column and byte offset
+    location: Location,
+    tokens: Vec<Token>, // Store tokens separately
+    spans: Vec<Span>, // The span of each token in `tokens`
+    token_position: usize,
+    /// Lexical errors in the order they were found; without recovery, only the one that ended
+    /// tokenizing early
+    errors: Vec<ParseError>,
+    /// Whether to carry on past lexical errors, dropping the offending text
+    recover: bool,
+    /// One copy of each distinct name seen, shared by every token and node holding it
+    names: HashSet<Name>,
+}
+
+impl Tokenizer {
+    /// Creates a new tokenizer and tokenizes the entire input. A lexical error ends the tokens
+    /// early, with `Eof`, and is only reported by `error`; `try_new` returns it instead.
+    pub fn new(input: &str) -> Self {
+        Self::new_with_recovery(input, false)
+    }
+
+    /// Creates a new tokenizer like `new`, or returns the lexical error that stopped it
+    pub fn try_new(input: &str) -> Result<Self, ParseError> {
+        let tokenizer = Self::new(input);
+        match tokenizer.errors.first() {
+            Some(e) => Err(e.clone()),
+            None => Ok(tokenizer),
+        }
+    }
+
+    /// Creates a tokenizer that skips over lexical errors instead of stopping at the first,
+    /// so that `errors` returns all of them
+    pub fn new_recovering(input: &str) -> Self {
+        Self::new_with_recovery(input, true)
+    }
+
+    fn new_with_recovery(input: &str, recover: bool) -> Self {
+        let mut tokenizer = Tokenizer {
+            input: vec![],
+            position: 0,
+            location: Location::default(),
+            tokens: vec![],
+            spans: vec![],
+            token_position: 0,
+            errors: vec![],
+            recover,
+            names: HashSet::new(),
+        };
+        tokenizer.reset(input);
+        tokenizer
+    }
+
+    /// Tokenizes `input` in place of the current input, reusing the buffers already
+    /// allocated. The result is the same as from a new tokenizer with the same error
+    /// recovery, so one tokenizer can serve many inputs in turn.
+    pub fn reset(&mut self, input: &str) {
+        self.input.clear();
+        self.input.extend(input.chars());
+        self.position = 0;
+        self.location = Location::default();
+        self.tokens.clear();
+        self.spans.clear();
+        self.token_position = 0;
+        self.errors.clear();
+        // Names are only shared within one input, so the set cannot grow without bound
+        self.names.clear();
+        self.tokenize_input(); // Tokenize once per input
+    }
+
+    /// Tokenizes the entire input, returning the tokens that could be read and every lexical
+    /// error in source order
+    pub fn tokenize_recovering(input: &str) -> (Vec<Token>, ParseErrors) {
+        let tokenizer = Self::new_recovering(input);
+        (tokenizer.tokens, ParseErrors(tokenizer.errors))
+    }
+
+    /// Returns the tokens of the entire input, or the first lexical error
+    pub fn tokenize_string(&mut self) -> Result<Vec<Token>, ParseError> {
+        match self.errors.first() {
+            Some(e) => Err(e.clone()),
+            None => Ok(self.tokens.clone()),
+        }
+    }
+
+    /// Returns the tokens of the entire input paired with the stretch of input each covers,
+    /// or the first lexical error. `Eof` comes last, with an empty span at the end.
+    pub fn tokenize_with_spans(&mut self) -> Result<Vec<(Token, Span)>, ParseError> {
+        match self.errors.first() {
+            Some(e) => Err(e.clone()),
+            None => Ok(self.tokens.iter().cloned().zip(self.spans.iter().copied()).collect()),
+        }
+    }
+
+    /// Returns the first lexical error, located where it occurred. Without recovery it
+    /// stopped tokenizing; the tokens before it are still available, followed by `Eof`.
+    pub fn error(&self) -> Option<&ParseError> {
+        self.errors.first()
+    }
+
+    /// Returns every lexical error found, in source order
+    pub fn errors(&self) -> &[ParseError] {
+        &self.errors
+    }
+
+    /// Tokenizes the entire input into the internal tokens vector
+    fn tokenize_input(&mut self) {
+        loop {
+            self.skip_whitespace();
+            let start = self.location;
+            let token = self.tokenize_next_token();
+            let span = Span { start, end: self.location };
+            match token {
+                Some(Ok(Token::Eof)) | None => break,
+                Some(Ok(token)) => {
+                    self.tokens.push(token);
+                    self.spans.push(span);
+                }
+                Some(Err(e)) => {
+                    self.errors.push(e.with_span(span));
+                    if !self.recover {
+                        break;
+                    }
+                }
+            }
+        }
+
+        // End with an Eof token placed where tokenizing stopped
+        self.tokens.push(Token::Eof);
+        self.spans.push(Span { start: self.location, end: self.location });
+    }
+
+    /// Skips whitespace between tokens
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(' ' | '\t' | '\n' | '\r')) {
+            self.advance();
+        }
+    }
+
+    /// Returns the next character without advancing the position
+    fn peek(&self) -> Option<char> {
+        self.input.get(self.position).copied()
+    }
+
+    /// Returns the character `offset` places after the next one without advancing
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.input.get(self.position + offset).copied()
+    }
+
+    /// Returns the next character and advances the position
+    fn advance(&mut self) -> Option<char> {
+        if self.position < self.input.len() {
+            let ch = self.input[self.position];
+            self.position += 1;
+            self.location.offset += ch.len_utf8();
+            if ch == '\n' {
+                self.location.line += 1;
+                self.location.column = 1;
+            } else {
+                self.location.column += 1;
+            }
+            Some(ch)
+        } else {
+            None
+        }
+    }
+
+    /// Tokenizes the next available token
+    fn tokenize_next_token(&mut self) -> Option<Result<Token, ParseError>> {
+        while let Some(ch) = self.peek() {
+            match ch {
+                // Skip whitespace
+                ' ' | '\t' | '\n' | '\r' => {
+                    self.advance();
+                }
+
+                // String literals
+                '\'' => return Some(self.tokenize_string_literal(ch).map(Token::String)),
+
+                // Quoted identifiers
+                '"' => {
+                    return Some(self.tokenize_string_literal(ch).map(|name| Token::QuotedIdentifier(self.intern(&name))))
+                }
+
+                // Placeholders
+                '$' => return Some(self.tokenize_placeholder()),
+                '?' => {
+                    self.advance();
+                    return Some(Ok(Token::Placeholder("?".to_string())));
+                }
+
+                // Numbers
+                '0'..='9' => return Some(self.tokenize_number()),
+
+                // Identifiers or keywords
+                'a'..='z' | 'A'..='Z' | '_' => return Some(self.tokenize_identifier_or_keyword()),
+
+                // Single-character tokens
+                '(' => {
+                    self.advance();
+                    return Some(Ok(Token::LeftParentheses));
+                }
+                ')' => {
+                    self.advance();
+                    return Some(Ok(Token::RightParentheses));
+                }
+                ',' => {
+                    self.advance();
+                    return Some(Ok(Token::Comma));
+                }
+                ';' => {
+                    self.advance();
+                    return Some(Ok(Token::Semicolon));
+                }
+                '.' => {
+                    self.advance();
+                    return Some(Ok(Token::Dot));
+                }
+                '[' => {
+                    self.advance();
+                    return Some(Ok(Token::LeftBracket));
+                }
+                ']' => {
+                    self.advance();
+                    return Some(Ok(Token::RightBracket));
+                }
+
+                // Multi-character operators
+                ':' => {
+                    self.advance();
+                    if self.peek() == Some(':') {
+                        self.advance();
+                        return Some(Ok(Token::DoubleColon));
+                    }
+                    if matches!(self.peek(), Some(ch) if ch.is_ascii_alphabetic() || ch == '_') {
+                        return Some(Ok(self.tokenize_named_placeholder()));
+                    }
+                    return Some(Err(ParseError::new(Problem::UnexpectedCharacter { found: ':' })));
+                }
+                '=' => {
+                    self.advance();
+                    if self.peek() == Some('=') {
+                        self.advance();
+                        return Some(Ok(Token::Equal));
+                    }
+                    return Some(Ok(Token::Equal));
+                }
+                '!' => {
+                    self.advance();
+                    if self.peek() == Some('=') {
+                        self.advance();
+                        return Some(Ok(Token::NotEqual));
+                    }
+                    return Some(Err(ParseError::new(Problem::UnexpectedCharacter { found: '!' })));
+                }
+                '>' => {
+                    self.advance();
+                    if self.peek() == Some('=') {
+                        self.advance();
+                        return Some(Ok(Token::GreaterThanOrEqual));
+                    }
+                    return Some(Ok(Token::GreaterThan));
+                }
+                '<' => {
+                    self.advance();
+                    if self.peek() == Some('=') {
+                        self.advance();
+                        return Some(Ok(Token::LessThanOrEqual));
+                    }
+                    if self.peek() == Some('>') {
+                        self.advance();
+                        return Some(Ok(Token::NotEqual));
+                    }
+                    return Some(Ok(Token::LessThan));
+                }
+                '|' => {
+                    self.advance();
+                    if self.peek() == Some('|') {
+                        self.advance();
+                        return Some(Ok(Token::Concat));
+                    }
+                    return Some(Err(ParseError::new(Problem::UnexpectedCharacter { found: '|' })));
+                }
+
+                // Single-character operators
+                '+' => {
+                    self.advance();
+                    return Some(Ok(Token::Plus));
+                }
+                '-' => {
+                    self.advance();
+                    return Some(Ok(Token::Minus));
+                }
+                '*' => {
+                    self.advance();
+                    return Some(Ok(Token::Multiply));
+                }
+                '/' => {
+                    self.advance();
+                    return Some(Ok(Token::Divide));
+                }
+
+                // Unknown character
+                _ => {
+                    let invalid_char = self.advance().unwrap();
+                    return Some(Err(ParseError::new(Problem::UnexpectedCharacter { found: invalid_char })));
+                }
+            }
+        }
+
+        // Return Eof if no more characters
+        Some(Ok(Token::Eof))
+    }
+
+    /// Tokenizes text enclosed in `quote`, where a doubled quote escapes itself
+    fn tokenize_string_literal(&mut self, quote: char) -> Result<String, ParseError> {
+        let mut value = String::new();
+        self.advance(); // Skip the opening quote
+
+        while let Some(ch) = self.advance() {
+            if ch != quote {
+                value.push(ch);
+            } else if self.peek() == Some(quote) {
+                self.advance(); // Consume the escaped quote
+                value.push(quote);
+            } else {
+                return Ok(value);
+            }
+        }
+
+        Err(ParseError::new(Problem::UnterminatedString { quote }))
+    }
+
+    /// Tokenizes `$n` placeholders
+    fn tokenize_placeholder(&mut self) -> Result<Token, ParseError> {
+        let mut value = String::new();
+        value.push(self.advance().unwrap()); // Consume $
+
+        while let Some(ch) = self.peek() {
+            if ch.is_ascii_digit() {
+                value.push(self.advance().unwrap());
+            } else {
+                break;
+            }
+        }
+
+        if value.len() == 1 {
+            return Err(ParseError::new(Problem::UnexpectedCharacter { found: '$' }));
+        }
+        Ok(Token::Placeholder(value))
+    }
+
+    /// Tokenizes the name of a `:name` placeholder; the colon is already consumed
+    fn tokenize_named_placeholder(&mut self) -> Token {
+        let mut value = String::from(":");
+
+        while let Some(ch) = self.peek() {
+            if ch.is_ascii_alphanumeric() || ch == '_' {
+                value.push(self.advance().unwrap());
+            } else {
+                break;
+            }
+        }
+        Token::Placeholder(value)
+    }
+
+    /// Tokenizes numbers: integers as u64, and numbers with a fraction or exponent as f64
+    fn tokenize_number(&mut self) -> Result<Token, ParseError> {
+        let mut value = String::new();
+        self.take_digits(&mut value);
+        let mut is_float = false;
+
+        // A dot only starts a fraction when a digit follows it
+        if self.peek() == Some('.') && self.peek_at(1).is_some_and(|ch| ch.is_ascii_digit()) {
+            value.push(self.advance().unwrap());
+            self.take_digits(&mut value);
+            is_float = true;
+        }
+        if matches!(self.peek(), Some('e' | 'E')) {
+            let sign = usize::from(matches!(self.peek_at(1), Some('+' | '-')));
+            if self.peek_at(1 + sign).is_some_and(|ch| ch.is_ascii_digit()) {
+                for _ in 0..=sign {
+                    value.push(self.advance().unwrap());
+                }
+                self.take_digits(&mut value);
+                is_float = true;
+            }
+        }
+
+        if is_float {
+            return match value.parse::<f64>() {
+                Ok(num) if num.is_finite() => Ok(Token::Float(Float(num))),
+                _ => Err(ParseError::new(Problem::InvalidNumber { text: value })),
+            };
+        }
+        match value.parse::<u64>() {
+            Ok(num) => Ok(Token::Number(num)),
+            Err(_) => Err(ParseError::new(Problem::InvalidNumber { text: value })),
+        }
+    }
+
+    /// Appends the digits at the current position to `value`
+    fn take_digits(&mut self, value: &mut String) {
+        while let Some(ch) = self.peek() {
+            if ch.is_ascii_digit() {
+                value.push(self.advance().unwrap());
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Tokenizes identifiers or keywords
+    fn tokenize_identifier_or_keyword(&mut self) -> Result<Token, ParseError> {
+        let mut value = String::new();
+
+        while let Some(ch) = self.peek() {
+            if ch.is_ascii_alphanumeric() || ch == '_' {
+                value.push(self.advance().unwrap());
+            } else {
+                break;
+            }
+        }
+
+        // Check if the value is a known keyword
+        match Keyword::from_str(&value) {
+            Ok(keyword) => Ok(Token::Keyword(keyword)),
+            Err(_) => Ok(Token::Identifier(self.intern(&value))),
+        }
+    }
+
+    /// Returns the shared copy of `name`, making one if it is new
+    pub(crate) fn intern(&mut self, name: &str) -> Name {
+        if let Some(name) = self.names.get(name) {
+            return name.clone();
+        }
+        let name = Name::from(name);
+        self.names.insert(name.clone());
+        name
+    }
+
+    /// Returns the next token without advancing the position
+    pub fn peek_token(&self) -> Option<Token> {
+        self.tokens.get(self.token_position).cloned()
+    }
+
+    /// Returns the token `n` places after the next token without advancing the position
+    pub fn peek_nth_token(&self, n: usize) -> Option<Token> {
+        self.tokens.get(self.token_position + n).cloned()
+    }
+
+    /// Returns the span of the token most recently returned by `next`
+    pub fn span(&self) -> Span {
+        let index = self.token_position.saturating_sub(1).min(self.spans.len() - 1);
+        self.spans[index]
+    }
+
+    /// Returns the next token and advances the position
+    // Not an `Iterator`: past the end it keeps returning `Token::Eof` rather than `None`
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Option<Token> {
+        if self.token_position < self.tokens.len() {
+            let token = self.tokens[self.token_position].clone();
+            self.token_position += 1;
+            Some(token)
+        } else {
+            Some(Token::Eof)
+        }
+    }
+}
@@ -0,0 +1,529 @@
+use crate::ast::{
+    Distinct, Expression, GroupByExpr, InsertSource, Join, JoinConstraint, JoinType, OrderByItem,
+    Query, SelectItem, SelectStatement, SetExpr, Statement, TableAlias, TableReference,
+    TableWithJoins,
+};
+
+impl Expression {
+    /// Returns this expression as a one-line S-expression such as
+    /// `(+ (ident a) (* 2 (ident b)))`, whose layout is stable enough for snapshot tests
+    pub fn to_tree_string(&self) -> String {
+        let mut tree = TreeWriter::default();
+        tree.expression(self);
+        tree.finish().to_sexp()
+    }
+
+    /// Returns this expression as a Graphviz digraph with one node per tree node of
+    /// `to_tree_string`; node ids follow a pre-order walk, so the output is deterministic
+    pub fn to_dot(&self) -> String {
+        let mut tree = TreeWriter::default();
+        tree.expression(self);
+        tree.finish().to_dot()
+    }
+}
+
+impl Statement {
+    /// Returns this statement as a one-line S-expression in the style of
+    /// `Expression::to_tree_string`. Queries and DML are broken down clause by clause,
+    /// other statements are shown as their SQL text.
+    pub fn to_tree_string(&self) -> String {
+        let mut tree = TreeWriter::default();
+        tree.statement(self);
+        tree.finish().to_sexp()
+    }
+
+    /// Returns this statement as a Graphviz digraph in the style of `Expression::to_dot`
+    pub fn to_dot(&self) -> String {
+        let mut tree = TreeWriter::default();
+        tree.statement(self);
+        tree.finish().to_dot()
+    }
+}
+
+/// A node of a dump: an atom such as `1`, or a list such as `(+ 1 2)` whose label is its head
+struct Node {
+    label: String,
+    children: Vec<Node>,
+    is_list: bool,
+}
+
+impl Node {
+    fn to_sexp(&self) -> String {
+        let mut out = String::new();
+        self.write_sexp(&mut out);
+        out
+    }
+
+    fn write_sexp(&self, out: &mut String) {
+        if !self.is_list {
+            out.push_str(&self.label);
+            return;
+        }
+        out.push('(');
+        out.push_str(&self.label);
+        for child in &self.children {
+            out.push(' ');
+            child.write_sexp(out);
+        }
+        out.push(')');
+    }
+
+    fn to_dot(&self) -> String {
+        let mut out = String::from("digraph ast {\n    node [shape=box];\n");
+        self.write_dot(&mut out, &mut 0);
+        out.push_str("}\n");
+        out
+    }
+
+    /// Writes this node and its subtree, numbering nodes from `next_id`; returns this node's id
+    fn write_dot(&self, out: &mut String, next_id: &mut usize) -> usize {
+        let id = *next_id;
+        *next_id += 1;
+        out.push_str(&format!(
+            "    n{} [label=\"{}\"];\n",
+            id,
+            escape_dot_label(&self.label)
+        ));
+        for child in &self.children {
+            let child_id = child.write_dot(out, next_id);
+            out.push_str(&format!("    n{} -> n{};\n", id, child_id));
+        }
+        id
+    }
+}
+
+/// Escapes a label for a double-quoted DOT string
+fn escape_dot_label(label: &str) -> String {
+    let mut escaped = String::with_capacity(label.len());
+    for c in label.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Builds a `Node` tree from nested `open`, `atom` and `close` calls
+#[derive(Default)]
+struct TreeWriter {
+    stack: Vec<Node>,
+    roots: Vec<Node>,
+}
+
+impl TreeWriter {
+    fn finish(mut self) -> Node {
+        assert!(self.stack.is_empty(), "unclosed tree node");
+        self.roots.pop().expect("tree has a root node")
+    }
+
+    fn push(&mut self, node: Node) {
+        match self.stack.last_mut() {
+            Some(parent) => parent.children.push(node),
+            None => self.roots.push(node),
+        }
+    }
+
+    fn atom(&mut self, atom: &str) {
+        self.push(Node {
+            label: atom.to_string(),
+            children: vec![],
+            is_list: false,
+        });
+    }
+
+    fn open(&mut self, head: &str) {
+        self.stack.push(Node {
+            label: head.to_string(),
+            children: vec![],
+            is_list: true,
+        });
+    }
+
+    fn close(&mut self) {
+        let node = self.stack.pop().expect("close matches an open");
+        self.push(node);
+    }
+
+    /// Writes `(head item...)`, or nothing when there are no items
+    fn list<'a, T: 'a>(
+        &mut self,
+        head: &str,
+        items: impl IntoIterator<Item = &'a T>,
+        mut write: impl FnMut(&mut Self, &'a T),
+    ) {
+        let mut items = items.into_iter().peekable();
+        if items.peek().is_none() {
+            return;
+        }
+        self.open(head);
+        for item in items {
+            write(self, item);
+        }
+        self.close();
+    }
+
+    fn expressions<'a>(&mut self, head: &str, exprs: impl IntoIterator<Item = &'a Expression>) {
+        self.list(head, exprs, Self::expression);
+    }
+
+    fn expression(&mut self, expr: &Expression) {
+        match expr {
+            Expression::BinaryOperation {
+                left_operand,
+                operator,
+                right_operand,
+            } => {
+                self.open(&operator.to_string());
+                self.expression(left_operand);
+                self.expression(right_operand);
+                self.close();
+            }
+            Expression::UnaryOperation { operator, operand } => {
+                self.open(&operator.to_string());
+                self.expression(operand);
+                self.close();
+            }
+            Expression::Identifier(ident) => {
+                self.open("ident");
+                self.atom(&ident.to_string());
+                self.close();
+            }
+            Expression::CompoundIdentifier(parts) => {
+                self.open("ident");
+                for part in parts {
+                    self.atom(&part.to_string());
+                }
+                self.close();
+            }
+            Expression::Function { name, args } => {
+                self.open("call");
+                self.atom(name);
+                for arg in args {
+                    self.expression(arg);
+                }
+                self.close();
+            }
+            Expression::Cast { expr, data_type } => {
+                self.open("cast");
+                self.expression(expr);
+                self.atom(&data_type.to_string());
+                self.close();
+            }
+            Expression::Subquery(query) => {
+                self.open("subquery");
+                self.query(query);
+                self.close();
+            }
+            Expression::InList {
+                expr,
+                list,
+                negated,
+            } => {
+                self.open(if *negated { "not in" } else { "in" });
+                self.expression(expr);
+                for item in list {
+                    self.expression(item);
+                }
+                self.close();
+            }
+            Expression::IsNull { expr, negated } => {
+                self.open(if *negated { "is not null" } else { "is null" });
+                self.expression(expr);
+                self.close();
+            }
+            Expression::Like {
+                expr,
+                pattern,
+                negated,
+            } => {
+                self.open(if *negated { "not like" } else { "like" });
+                self.expression(expr);
+                self.expression(pattern);
+                self.close();
+            }
+            // Literals, placeholders and `*` read the same as in SQL
+            _ => self.atom(&expr.to_string()),
+        }
+    }
+
+    fn statement(&mut self, statement: &Statement) {
+        match statement {
+            Statement::Query(query) => self.query(query),
+            Statement::Insert {
+                table,
+                columns,
+                source,
+                on_conflict,
+                returning,
+            } => {
+                self.open("insert");
+                self.atom(&table.to_string());
+                self.list("columns", columns, |tree, column| tree.atom(column));
+                match source {
+                    InsertSource::Values(rows) => {
+                        self.list("values", rows, |tree, row| tree.expressions("row", row))
+                    }
+                    InsertSource::Query(query) => self.query(query),
+                }
+                if let Some(on_conflict) = on_conflict {
+                    self.open("sql");
+                    self.atom(&format!("{:?}", on_conflict.to_string()));
+                    self.close();
+                }
+                self.select_items("returning", returning.iter().flatten());
+                self.close();
+            }
+            Statement::Update {
+                table,
+                assignments,
+                from,
+                selection,
+                returning,
+            } => {
+                self.open("update");
+                self.table_reference(table);
+                self.list("set", assignments, |tree, assignment| {
+                    tree.open("=");
+                    tree.atom(&assignment.column.join("."));
+                    tree.expression(&assignment.value);
+                    tree.close();
+                });
+                if let Some(from) = from {
+                    self.from("from", from);
+                }
+                self.expressions("where", selection);
+                self.select_items("returning", returning.iter().flatten());
+                self.close();
+            }
+            Statement::Delete {
+                table,
+                using,
+                selection,
+                returning,
+            } => {
+                self.open("delete");
+                self.table_reference(table);
+                if let Some(using) = using {
+                    self.from("using", using);
+                }
+                self.expressions("where", selection);
+                self.select_items("returning", returning.iter().flatten());
+                self.close();
+            }
+            _ => {
+                self.open("sql");
+                self.atom(&format!("{:?}", statement.to_string()));
+                self.close();
+            }
+        }
+    }
+
+    fn query(&mut self, query: &Query) {
+        self.open("query");
+        if let Some(with) = &query.with {
+            self.open(if with.recursive {
+                "with-recursive"
+            } else {
+                "with"
+            });
+            for cte in &with.ctes {
+                self.open("cte");
+                self.atom(&cte.name);
+                self.list("columns", &cte.columns, |tree, column| tree.atom(column));
+                self.query(&cte.query);
+                self.close();
+            }
+            self.close();
+        }
+        self.set_expr(&query.body);
+        self.list("order-by", &query.order_by, Self::order_by_item);
+        self.expressions(
+            if query.with_ties {
+                "limit-with-ties"
+            } else {
+                "limit"
+            },
+            &query.limit,
+        );
+        self.expressions("offset", &query.offset);
+        if let Some(locking) = &query.locking {
+            self.open("locking");
+            self.atom(&format!("{:?}", locking.to_string()));
+            self.close();
+        }
+        self.close();
+    }
+
+    fn set_expr(&mut self, body: &SetExpr) {
+        match body {
+            SetExpr::Select(select) => self.select(select),
+            SetExpr::Query(query) => self.query(query),
+            SetExpr::SetOperation {
+                op,
+                all,
+                left,
+                right,
+            } => {
+                let op = op.to_string().to_lowercase();
+                self.open(&if *all { format!("{}-all", op) } else { op });
+                self.set_expr(left);
+                self.set_expr(right);
+                self.close();
+            }
+            SetExpr::Values(rows) => {
+                self.list("values", rows, |tree, row| tree.expressions("row", row))
+            }
+        }
+    }
+
+    fn select(&mut self, select: &SelectStatement) {
+        self.open("select");
+        match &select.distinct {
+            Some(Distinct::Distinct) => self.atom("distinct"),
+            Some(Distinct::DistinctOn(exprs)) => self.expressions("distinct-on", exprs),
+            Some(Distinct::All) | None => {}
+        }
+        if let Some(top) = &select.top {
+            self.open("top");
+            self.expression(&top.quantity);
+            if top.percent {
+                self.atom("percent");
+            }
+            if top.with_ties {
+                self.atom("with-ties");
+            }
+            self.close();
+        }
+        self.select_items("items", &select.projection);
+        if let Some(from) = &select.from {
+            self.from("from", from);
+        }
+        self.expressions("where", &select.selection);
+        self.list("group-by", &select.group_by, |tree, item| match item {
+            GroupByExpr::Expression(expr) => tree.expression(expr),
+            GroupByExpr::Rollup(sets) => tree.grouping_sets("rollup", sets),
+            GroupByExpr::Cube(sets) => tree.grouping_sets("cube", sets),
+            GroupByExpr::GroupingSets(sets) => tree.grouping_sets("grouping-sets", sets),
+        });
+        self.expressions("having", &select.having);
+        self.close();
+    }
+
+    fn grouping_sets(&mut self, head: &str, sets: &[Vec<Expression>]) {
+        self.open(head);
+        for set in sets {
+            self.open("set");
+            for expr in set {
+                self.expression(expr);
+            }
+            self.close();
+        }
+        self.close();
+    }
+
+    fn select_items<'a>(&mut self, head: &str, items: impl IntoIterator<Item = &'a SelectItem>) {
+        self.list(head, items, |tree, item| match item {
+            SelectItem::Expression(expr) => tree.expression(expr),
+            SelectItem::ExprWithAlias { expr, alias } => {
+                tree.open("as");
+                tree.expression(expr);
+                tree.atom(&alias.to_string());
+                tree.close();
+            }
+            SelectItem::Wildcard => tree.atom("*"),
+            SelectItem::QualifiedWildcard(parts) => tree.atom(&format!("{}.*", parts.join("."))),
+        });
+    }
+
+    fn order_by_item(&mut self, item: &OrderByItem) {
+        let mut head = match item.asc {
+            Some(true) => "asc".to_string(),
+            Some(false) => "desc".to_string(),
+            None => "order".to_string(),
+        };
+        match item.nulls_first {
+            Some(true) => head.push_str("-nulls-first"),
+            Some(false) => head.push_str("-nulls-last"),
+            None => {}
+        }
+        if head == "order" {
+            return self.expression(&item.expr);
+        }
+        self.open(&head);
+        self.expression(&item.expr);
+        self.close();
+    }
+
+    fn from(&mut self, head: &str, from: &TableWithJoins) {
+        self.open(head);
+        self.table_reference(&from.relation);
+        for join in &from.joins {
+            self.join(join);
+        }
+        self.close();
+    }
+
+    fn join(&mut self, join: &Join) {
+        let kind = match join.join_type {
+            JoinType::Inner => "join",
+            JoinType::LeftOuter => "left-join",
+            JoinType::RightOuter => "right-join",
+            JoinType::FullOuter => "full-join",
+            JoinType::Cross => "cross-join",
+            JoinType::Implicit => "comma-join",
+        };
+        match join.constraint {
+            JoinConstraint::Natural => self.open(&format!("natural-{}", kind)),
+            _ => self.open(kind),
+        }
+        self.table_reference(&join.relation);
+        match &join.constraint {
+            JoinConstraint::On(expr) => self.expressions("on", [expr]),
+            JoinConstraint::Using(columns) => {
+                self.list("using", columns, |tree, column| tree.atom(column))
+            }
+            JoinConstraint::Natural | JoinConstraint::None => {}
+        }
+        self.close();
+    }
+
+    fn table_reference(&mut self, table: &TableReference) {
+        let alias = match table {
+            TableReference::Table { name, alias } => {
+                self.open("table");
+                self.atom(&name.to_string());
+                alias
+            }
+            TableReference::Derived { subquery, alias } => {
+                self.open("derived");
+                self.query(subquery);
+                alias
+            }
+            TableReference::Function { name, args, alias } => {
+                self.open("call");
+                self.atom(&name.to_string());
+                for arg in args {
+                    self.expression(arg);
+                }
+                alias
+            }
+        };
+        if let Some(alias) = alias {
+            self.alias(alias);
+        }
+        self.close();
+    }
+
+    fn alias(&mut self, alias: &TableAlias) {
+        self.open("as");
+        self.atom(&alias.name.to_string());
+        for column in &alias.columns {
+            self.atom(&column.to_string());
+        }
+        self.close();
+    }
+}
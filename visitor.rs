@@ -0,0 +1,850 @@
+use std::ops::ControlFlow;
+
+use crate::ast::{
+    AlterTableOperation, ColumnOption, Cte, Distinct, Expression, GroupByExpr, Ident, InsertSource,
+    JoinConstraint, MergeAction, ObjectName, ObjectType, OnConflictAction, Query, SelectItem,
+    SelectStatement, SetExpr, ShowFilter, Statement, TableConstraint, TableReference,
+    TableWithJoins,
+};
+
+/// Walks the AST read-only; every method defaults to visiting the node's children, so an
+/// implementation only overrides the nodes it cares about. Work done before calling the
+/// matching `walk_*` function runs on the way down, work done after it on the way up.
+/// Returning `ControlFlow::Break` stops the whole walk.
+pub trait Visitor {
+    /// The value a visitor stops with
+    type Break;
+
+    fn visit_statement(&mut self, statement: &Statement) -> ControlFlow<Self::Break> {
+        walk_statement(self, statement)
+    }
+
+    fn visit_query(&mut self, query: &Query) -> ControlFlow<Self::Break> {
+        walk_query(self, query)
+    }
+
+    fn visit_cte(&mut self, cte: &Cte) -> ControlFlow<Self::Break> {
+        walk_cte(self, cte)
+    }
+
+    fn visit_select(&mut self, select: &SelectStatement) -> ControlFlow<Self::Break> {
+        walk_select(self, select)
+    }
+
+    fn visit_table_reference(&mut self, table: &TableReference) -> ControlFlow<Self::Break> {
+        walk_table_reference(self, table)
+    }
+
+    /// The default visits everything inside `expr` without calling itself per level; an
+    /// override that calls `walk_expression` recurses once per level of nesting instead
+    fn visit_expression(&mut self, expr: &Expression) -> ControlFlow<Self::Break> {
+        walk_expression_tree(self, expr)
+    }
+
+    /// Called for every name that refers to a table or view, in FROM clauses and DML/DDL targets
+    fn visit_table_name(&mut self, _name: &ObjectName) -> ControlFlow<Self::Break> {
+        ControlFlow::Continue(())
+    }
+
+    /// Called for every bare column or alias reference inside an expression
+    fn visit_identifier(&mut self, _ident: &Ident) -> ControlFlow<Self::Break> {
+        ControlFlow::Continue(())
+    }
+
+    /// Called for every qualified reference such as `t.a` inside an expression
+    fn visit_compound_identifier(&mut self, _parts: &[Ident]) -> ControlFlow<Self::Break> {
+        ControlFlow::Continue(())
+    }
+}
+
+/// Visits the children of `statement`
+pub fn walk_statement<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    statement: &Statement,
+) -> ControlFlow<V::Break> {
+    match statement {
+        Statement::Query(query) => visitor.visit_query(query)?,
+        Statement::Insert {
+            table,
+            source,
+            on_conflict,
+            returning,
+            ..
+        } => {
+            visitor.visit_table_name(table)?;
+            match source {
+                InsertSource::Values(rows) => walk_expressions(visitor, rows.iter().flatten())?,
+                InsertSource::Query(query) => visitor.visit_query(query)?,
+            }
+            if let Some(OnConflictAction::DoUpdate {
+                assignments,
+                selection,
+            }) = on_conflict.as_ref().map(|on_conflict| &on_conflict.action)
+            {
+                walk_expressions(visitor, assignments.iter().map(|a| &a.value))?;
+                walk_expressions(visitor, selection)?;
+            }
+            walk_select_items(visitor, returning.iter().flatten())?;
+        }
+        Statement::Update {
+            table,
+            assignments,
+            from,
+            selection,
+            returning,
+        } => {
+            visitor.visit_table_reference(table)?;
+            walk_expressions(visitor, assignments.iter().map(|a| &a.value))?;
+            if let Some(from) = from {
+                walk_table_with_joins(visitor, from)?;
+            }
+            walk_expressions(visitor, selection)?;
+            walk_select_items(visitor, returning.iter().flatten())?;
+        }
+        Statement::Delete {
+            table,
+            using,
+            selection,
+            returning,
+        } => {
+            visitor.visit_table_reference(table)?;
+            if let Some(using) = using {
+                walk_table_with_joins(visitor, using)?;
+            }
+            walk_expressions(visitor, selection)?;
+            walk_select_items(visitor, returning.iter().flatten())?;
+        }
+        Statement::CreateTable {
+            name,
+            columns,
+            constraints,
+            query,
+            ..
+        } => {
+            visitor.visit_table_name(name)?;
+            for column in columns {
+                for option in &column.options {
+                    if let ColumnOption::Default(expr) = option {
+                        visitor.visit_expression(expr)?;
+                    }
+                }
+            }
+            for constraint in constraints {
+                match constraint {
+                    TableConstraint::ForeignKey { foreign_table, .. } => {
+                        visitor.visit_table_name(foreign_table)?
+                    }
+                    TableConstraint::Check { expr, .. } => visitor.visit_expression(expr)?,
+                    TableConstraint::PrimaryKey { .. } | TableConstraint::Unique { .. } => {}
+                }
+            }
+            if let Some(query) = query {
+                visitor.visit_query(query)?;
+            }
+        }
+        Statement::CreateView { name, query, .. } => {
+            visitor.visit_table_name(name)?;
+            visitor.visit_query(query)?;
+        }
+        Statement::CreateIndex { table, columns, .. } => {
+            visitor.visit_table_name(table)?;
+            walk_expressions(visitor, columns.iter().map(|item| &item.expr))?;
+        }
+        Statement::AlterTable {
+            name, operation, ..
+        } => {
+            visitor.visit_table_name(name)?;
+            if let AlterTableOperation::AddColumn { column_def, .. } = operation {
+                for option in &column_def.options {
+                    if let ColumnOption::Default(expr) = option {
+                        visitor.visit_expression(expr)?;
+                    }
+                }
+            }
+        }
+        Statement::Drop {
+            object_type,
+            names,
+            table,
+            ..
+        } => {
+            // Index names are not relations, but the table an index is dropped from is
+            if *object_type != ObjectType::Index {
+                for name in names {
+                    visitor.visit_table_name(name)?;
+                }
+            }
+            if let Some(table) = table {
+                visitor.visit_table_name(table)?;
+            }
+        }
+        Statement::Truncate { tables, .. } => {
+            for table in tables {
+                visitor.visit_table_name(table)?;
+            }
+        }
+        Statement::Show { filter, .. } => {
+            if let Some(ShowFilter::Where(expr)) = filter {
+                visitor.visit_expression(expr)?;
+            }
+        }
+        Statement::Describe { table } => visitor.visit_table_name(table)?,
+        Statement::Merge {
+            target,
+            source,
+            on,
+            clauses,
+        } => {
+            visitor.visit_table_reference(target)?;
+            visitor.visit_table_reference(source)?;
+            visitor.visit_expression(on)?;
+            for clause in clauses {
+                walk_expressions(visitor, &clause.predicate)?;
+                match &clause.action {
+                    MergeAction::Update { assignments } => {
+                        walk_expressions(visitor, assignments.iter().map(|a| &a.value))?
+                    }
+                    MergeAction::Insert { values, .. } => walk_expressions(visitor, values)?,
+                    MergeAction::Delete | MergeAction::DoNothing => {}
+                }
+            }
+        }
+        Statement::StartTransaction { .. }
+        | Statement::Commit
+        | Statement::Rollback { .. }
+        | Statement::Savepoint { .. }
+        | Statement::ReleaseSavepoint { .. }
+        | Statement::Pragma { .. } => {}
+    }
+    ControlFlow::Continue(())
+}
+
+/// Visits the CTEs, body, ordering and row limits of `query`
+pub fn walk_query<V: Visitor + ?Sized>(visitor: &mut V, query: &Query) -> ControlFlow<V::Break> {
+    if let Some(with) = &query.with {
+        for cte in &with.ctes {
+            visitor.visit_cte(cte)?;
+        }
+    }
+    walk_set_expr(visitor, &query.body)?;
+    walk_expressions(visitor, query.order_by.iter().map(|item| &item.expr))?;
+    walk_expressions(visitor, &query.limit)?;
+    walk_expressions(visitor, &query.offset)?;
+    if let Some(locking) = &query.locking {
+        for table in &locking.of {
+            visitor.visit_table_name(table)?;
+        }
+    }
+    ControlFlow::Continue(())
+}
+
+/// Visits the query that defines `cte`
+pub fn walk_cte<V: Visitor + ?Sized>(visitor: &mut V, cte: &Cte) -> ControlFlow<V::Break> {
+    visitor.visit_query(&cte.query)
+}
+
+fn walk_set_expr<V: Visitor + ?Sized>(visitor: &mut V, body: &SetExpr) -> ControlFlow<V::Break> {
+    match body {
+        SetExpr::Select(select) => visitor.visit_select(select),
+        SetExpr::Query(query) => visitor.visit_query(query),
+        SetExpr::SetOperation { left, right, .. } => {
+            walk_set_expr(visitor, left)?;
+            walk_set_expr(visitor, right)
+        }
+        SetExpr::Values(rows) => walk_expressions(visitor, rows.iter().flatten()),
+    }
+}
+
+/// Visits the clauses of `select` in the order they are written
+pub fn walk_select<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    select: &SelectStatement,
+) -> ControlFlow<V::Break> {
+    if let Some(Distinct::DistinctOn(exprs)) = &select.distinct {
+        walk_expressions(visitor, exprs)?;
+    }
+    if let Some(top) = &select.top {
+        visitor.visit_expression(&top.quantity)?;
+    }
+    walk_select_items(visitor, &select.projection)?;
+    if let Some(from) = &select.from {
+        walk_table_with_joins(visitor, from)?;
+    }
+    walk_expressions(visitor, &select.selection)?;
+    for item in &select.group_by {
+        match item {
+            GroupByExpr::Expression(expr) => visitor.visit_expression(expr)?,
+            GroupByExpr::Rollup(sets)
+            | GroupByExpr::Cube(sets)
+            | GroupByExpr::GroupingSets(sets) => walk_expressions(visitor, sets.iter().flatten())?,
+        }
+    }
+    walk_expressions(visitor, &select.having)
+}
+
+/// Visits the relations and join conditions of a FROM clause
+pub fn walk_table_with_joins<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    from: &TableWithJoins,
+) -> ControlFlow<V::Break> {
+    visitor.visit_table_reference(&from.relation)?;
+    for join in &from.joins {
+        visitor.visit_table_reference(&join.relation)?;
+        if let JoinConstraint::On(expr) = &join.constraint {
+            visitor.visit_expression(expr)?;
+        }
+    }
+    ControlFlow::Continue(())
+}
+
+/// Visits the table name, subquery or function arguments of `table`
+pub fn walk_table_reference<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    table: &TableReference,
+) -> ControlFlow<V::Break> {
+    match table {
+        TableReference::Table { name, .. } => visitor.visit_table_name(name),
+        TableReference::Derived { subquery, .. } => visitor.visit_query(subquery),
+        TableReference::Function { args, .. } => walk_expressions(visitor, args),
+    }
+}
+
+/// Visits the operands, arguments or subquery of `expr`
+pub fn walk_expression<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    expr: &Expression,
+) -> ControlFlow<V::Break> {
+    match expr {
+        Expression::BinaryOperation {
+            left_operand,
+            right_operand,
+            ..
+        } => {
+            visitor.visit_expression(left_operand)?;
+            visitor.visit_expression(right_operand)
+        }
+        Expression::UnaryOperation { operand, .. } => visitor.visit_expression(operand),
+        Expression::Identifier(ident) => visitor.visit_identifier(ident),
+        Expression::CompoundIdentifier(parts) => visitor.visit_compound_identifier(parts),
+        Expression::Function { args, .. } => walk_expressions(visitor, args),
+        Expression::Cast { expr, .. } => visitor.visit_expression(expr),
+        Expression::Subquery(query) => visitor.visit_query(query),
+        Expression::InList { expr, list, .. } => {
+            visitor.visit_expression(expr)?;
+            walk_expressions(visitor, list)
+        }
+        Expression::IsNull { expr, .. } => visitor.visit_expression(expr),
+        Expression::Like { expr, pattern, .. } => {
+            visitor.visit_expression(expr)?;
+            visitor.visit_expression(pattern)
+        }
+        Expression::Value(_) | Expression::Placeholder { .. } | Expression::Wildcard => {
+            ControlFlow::Continue(())
+        }
+    }
+}
+
+/// Visits everything inside `expr` as the default `visit_expression` calling itself on each
+/// operand would, but keeps the operands still to visit on a heap stack, since a generated
+/// chain of many thousands of `OR` terms nests that deep on its left
+pub fn walk_expression_tree<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    expr: &Expression,
+) -> ControlFlow<V::Break> {
+    let mut stack = vec![expr];
+    while let Some(expr) = stack.pop() {
+        match expr {
+            Expression::Identifier(ident) => visitor.visit_identifier(ident)?,
+            Expression::CompoundIdentifier(parts) => visitor.visit_compound_identifier(parts)?,
+            Expression::Subquery(query) => visitor.visit_query(query)?,
+            _ => push_operands(expr, &mut stack),
+        }
+    }
+    ControlFlow::Continue(())
+}
+
+/// Pushes the expressions directly inside `expr` onto `stack` so that they pop off in the
+/// order `walk_expression` visits them
+pub(crate) fn push_operands<'a>(expr: &'a Expression, stack: &mut Vec<&'a Expression>) {
+    match expr {
+        Expression::BinaryOperation {
+            left_operand,
+            right_operand,
+            ..
+        } => {
+            stack.push(right_operand);
+            stack.push(left_operand);
+        }
+        Expression::UnaryOperation { operand: expr, .. }
+        | Expression::Cast { expr, .. }
+        | Expression::IsNull { expr, .. } => stack.push(expr),
+        Expression::Function { args, .. } => stack.extend(args.iter().rev()),
+        Expression::InList { expr, list, .. } => {
+            stack.extend(list.iter().rev());
+            stack.push(expr);
+        }
+        Expression::Like { expr, pattern, .. } => {
+            stack.push(pattern);
+            stack.push(expr);
+        }
+        Expression::Value(_)
+        | Expression::Placeholder { .. }
+        | Expression::Identifier(_)
+        | Expression::CompoundIdentifier(_)
+        | Expression::Wildcard
+        | Expression::Subquery(_) => {}
+    }
+}
+
+fn walk_expressions<'a, V, I>(visitor: &mut V, exprs: I) -> ControlFlow<V::Break>
+where
+    V: Visitor + ?Sized,
+    I: IntoIterator<Item = &'a Expression>,
+{
+    for expr in exprs {
+        visitor.visit_expression(expr)?;
+    }
+    ControlFlow::Continue(())
+}
+
+fn walk_select_items<'a, V, I>(visitor: &mut V, items: I) -> ControlFlow<V::Break>
+where
+    V: Visitor + ?Sized,
+    I: IntoIterator<Item = &'a SelectItem>,
+{
+    for item in items {
+        match item {
+            SelectItem::Expression(expr) | SelectItem::ExprWithAlias { expr, .. } => {
+                visitor.visit_expression(expr)?
+            }
+            SelectItem::Wildcard | SelectItem::QualifiedWildcard(_) => {}
+        }
+    }
+    ControlFlow::Continue(())
+}
+
+/// Walks the AST with mutable access in the same order as `Visitor`, so a rewrite only
+/// overrides the nodes it changes; any node can be replaced in place through its reference
+pub trait VisitorMut {
+    /// The value a visitor stops with
+    type Break;
+
+    fn visit_statement_mut(&mut self, statement: &mut Statement) -> ControlFlow<Self::Break> {
+        walk_statement_mut(self, statement)
+    }
+
+    fn visit_query_mut(&mut self, query: &mut Query) -> ControlFlow<Self::Break> {
+        walk_query_mut(self, query)
+    }
+
+    fn visit_cte_mut(&mut self, cte: &mut Cte) -> ControlFlow<Self::Break> {
+        walk_cte_mut(self, cte)
+    }
+
+    fn visit_select_mut(&mut self, select: &mut SelectStatement) -> ControlFlow<Self::Break> {
+        walk_select_mut(self, select)
+    }
+
+    fn visit_table_reference_mut(
+        &mut self,
+        table: &mut TableReference,
+    ) -> ControlFlow<Self::Break> {
+        walk_table_reference_mut(self, table)
+    }
+
+    /// The default visits everything inside `expr` without calling itself per level; an
+    /// override that calls `walk_expression_mut` recurses once per level of nesting instead
+    fn visit_expression_mut(&mut self, expr: &mut Expression) -> ControlFlow<Self::Break> {
+        walk_expression_tree_mut(self, expr)
+    }
+
+    /// Called for every name that refers to a table or view, in FROM clauses and DML/DDL targets
+    fn visit_table_name_mut(&mut self, _name: &mut ObjectName) -> ControlFlow<Self::Break> {
+        ControlFlow::Continue(())
+    }
+
+    /// Called for every bare column or alias reference inside an expression
+    fn visit_identifier_mut(&mut self, _ident: &mut Ident) -> ControlFlow<Self::Break> {
+        ControlFlow::Continue(())
+    }
+
+    /// Called for every qualified reference such as `t.a` inside an expression
+    fn visit_compound_identifier_mut(
+        &mut self,
+        _parts: &mut Vec<Ident>,
+    ) -> ControlFlow<Self::Break> {
+        ControlFlow::Continue(())
+    }
+}
+
+/// Visits the children of `statement` mutably
+pub fn walk_statement_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    statement: &mut Statement,
+) -> ControlFlow<V::Break> {
+    match statement {
+        Statement::Query(query) => visitor.visit_query_mut(query)?,
+        Statement::Insert {
+            table,
+            source,
+            on_conflict,
+            returning,
+            ..
+        } => {
+            visitor.visit_table_name_mut(table)?;
+            match source {
+                InsertSource::Values(rows) => {
+                    walk_expressions_mut(visitor, rows.iter_mut().flatten())?
+                }
+                InsertSource::Query(query) => visitor.visit_query_mut(query)?,
+            }
+            if let Some(OnConflictAction::DoUpdate {
+                assignments,
+                selection,
+            }) = on_conflict
+                .as_mut()
+                .map(|on_conflict| &mut on_conflict.action)
+            {
+                walk_expressions_mut(visitor, assignments.iter_mut().map(|a| &mut a.value))?;
+                walk_expressions_mut(visitor, selection)?;
+            }
+            walk_select_items_mut(visitor, returning.iter_mut().flatten())?;
+        }
+        Statement::Update {
+            table,
+            assignments,
+            from,
+            selection,
+            returning,
+        } => {
+            visitor.visit_table_reference_mut(table)?;
+            walk_expressions_mut(visitor, assignments.iter_mut().map(|a| &mut a.value))?;
+            if let Some(from) = from {
+                walk_table_with_joins_mut(visitor, from)?;
+            }
+            walk_expressions_mut(visitor, selection)?;
+            walk_select_items_mut(visitor, returning.iter_mut().flatten())?;
+        }
+        Statement::Delete {
+            table,
+            using,
+            selection,
+            returning,
+        } => {
+            visitor.visit_table_reference_mut(table)?;
+            if let Some(using) = using {
+                walk_table_with_joins_mut(visitor, using)?;
+            }
+            walk_expressions_mut(visitor, selection)?;
+            walk_select_items_mut(visitor, returning.iter_mut().flatten())?;
+        }
+        Statement::CreateTable {
+            name,
+            columns,
+            constraints,
+            query,
+            ..
+        } => {
+            visitor.visit_table_name_mut(name)?;
+            for column in columns {
+                for option in &mut column.options {
+                    if let ColumnOption::Default(expr) = option {
+                        visitor.visit_expression_mut(expr)?;
+                    }
+                }
+            }
+            for constraint in constraints {
+                match constraint {
+                    TableConstraint::ForeignKey { foreign_table, .. } => {
+                        visitor.visit_table_name_mut(foreign_table)?
+                    }
+                    TableConstraint::Check { expr, .. } => visitor.visit_expression_mut(expr)?,
+                    TableConstraint::PrimaryKey { .. } | TableConstraint::Unique { .. } => {}
+                }
+            }
+            if let Some(query) = query {
+                visitor.visit_query_mut(query)?;
+            }
+        }
+        Statement::CreateView { name, query, .. } => {
+            visitor.visit_table_name_mut(name)?;
+            visitor.visit_query_mut(query)?;
+        }
+        Statement::CreateIndex { table, columns, .. } => {
+            visitor.visit_table_name_mut(table)?;
+            walk_expressions_mut(visitor, columns.iter_mut().map(|item| &mut item.expr))?;
+        }
+        Statement::AlterTable {
+            name, operation, ..
+        } => {
+            visitor.visit_table_name_mut(name)?;
+            if let AlterTableOperation::AddColumn { column_def, .. } = operation {
+                for option in &mut column_def.options {
+                    if let ColumnOption::Default(expr) = option {
+                        visitor.visit_expression_mut(expr)?;
+                    }
+                }
+            }
+        }
+        Statement::Drop {
+            object_type,
+            names,
+            table,
+            ..
+        } => {
+            // Index names are not relations, but the table an index is dropped from is
+            if *object_type != ObjectType::Index {
+                for name in names {
+                    visitor.visit_table_name_mut(name)?;
+                }
+            }
+            if let Some(table) = table {
+                visitor.visit_table_name_mut(table)?;
+            }
+        }
+        Statement::Truncate { tables, .. } => {
+            for table in tables {
+                visitor.visit_table_name_mut(table)?;
+            }
+        }
+        Statement::Show { filter, .. } => {
+            if let Some(ShowFilter::Where(expr)) = filter {
+                visitor.visit_expression_mut(expr)?;
+            }
+        }
+        Statement::Describe { table } => visitor.visit_table_name_mut(table)?,
+        Statement::Merge {
+            target,
+            source,
+            on,
+            clauses,
+        } => {
+            visitor.visit_table_reference_mut(target)?;
+            visitor.visit_table_reference_mut(source)?;
+            visitor.visit_expression_mut(on)?;
+            for clause in clauses {
+                walk_expressions_mut(visitor, &mut clause.predicate)?;
+                match &mut clause.action {
+                    MergeAction::Update { assignments } => {
+                        walk_expressions_mut(visitor, assignments.iter_mut().map(|a| &mut a.value))?
+                    }
+                    MergeAction::Insert { values, .. } => walk_expressions_mut(visitor, values)?,
+                    MergeAction::Delete | MergeAction::DoNothing => {}
+                }
+            }
+        }
+        Statement::StartTransaction { .. }
+        | Statement::Commit
+        | Statement::Rollback { .. }
+        | Statement::Savepoint { .. }
+        | Statement::ReleaseSavepoint { .. }
+        | Statement::Pragma { .. } => {}
+    }
+    ControlFlow::Continue(())
+}
+
+/// Visits the CTEs, body, ordering and row limits of `query`, mutably
+pub fn walk_query_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    query: &mut Query,
+) -> ControlFlow<V::Break> {
+    if let Some(with) = &mut query.with {
+        for cte in &mut with.ctes {
+            visitor.visit_cte_mut(cte)?;
+        }
+    }
+    walk_set_expr_mut(visitor, &mut query.body)?;
+    walk_expressions_mut(
+        visitor,
+        query.order_by.iter_mut().map(|item| &mut item.expr),
+    )?;
+    walk_expressions_mut(visitor, &mut query.limit)?;
+    walk_expressions_mut(visitor, &mut query.offset)?;
+    if let Some(locking) = &mut query.locking {
+        for table in &mut locking.of {
+            visitor.visit_table_name_mut(table)?;
+        }
+    }
+    ControlFlow::Continue(())
+}
+
+/// Visits the query that defines `cte`, mutably
+pub fn walk_cte_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    cte: &mut Cte,
+) -> ControlFlow<V::Break> {
+    visitor.visit_query_mut(&mut cte.query)
+}
+
+fn walk_set_expr_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    body: &mut SetExpr,
+) -> ControlFlow<V::Break> {
+    match body {
+        SetExpr::Select(select) => visitor.visit_select_mut(select),
+        SetExpr::Query(query) => visitor.visit_query_mut(query),
+        SetExpr::SetOperation { left, right, .. } => {
+            walk_set_expr_mut(visitor, left)?;
+            walk_set_expr_mut(visitor, right)
+        }
+        SetExpr::Values(rows) => walk_expressions_mut(visitor, rows.iter_mut().flatten()),
+    }
+}
+
+/// Visits the clauses of `select` in the order they are written, mutably
+pub fn walk_select_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    select: &mut SelectStatement,
+) -> ControlFlow<V::Break> {
+    if let Some(Distinct::DistinctOn(exprs)) = &mut select.distinct {
+        walk_expressions_mut(visitor, exprs)?;
+    }
+    if let Some(top) = &mut select.top {
+        visitor.visit_expression_mut(&mut top.quantity)?;
+    }
+    walk_select_items_mut(visitor, &mut select.projection)?;
+    if let Some(from) = &mut select.from {
+        walk_table_with_joins_mut(visitor, from)?;
+    }
+    walk_expressions_mut(visitor, &mut select.selection)?;
+    for item in &mut select.group_by {
+        match item {
+            GroupByExpr::Expression(expr) => visitor.visit_expression_mut(expr)?,
+            GroupByExpr::Rollup(sets)
+            | GroupByExpr::Cube(sets)
+            | GroupByExpr::GroupingSets(sets) => {
+                walk_expressions_mut(visitor, sets.iter_mut().flatten())?
+            }
+        }
+    }
+    walk_expressions_mut(visitor, &mut select.having)
+}
+
+/// Visits the relations and join conditions of a FROM clause, mutably
+pub fn walk_table_with_joins_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    from: &mut TableWithJoins,
+) -> ControlFlow<V::Break> {
+    visitor.visit_table_reference_mut(&mut from.relation)?;
+    for join in &mut from.joins {
+        visitor.visit_table_reference_mut(&mut join.relation)?;
+        if let JoinConstraint::On(expr) = &mut join.constraint {
+            visitor.visit_expression_mut(expr)?;
+        }
+    }
+    ControlFlow::Continue(())
+}
+
+/// Visits the table name, subquery or function arguments of `table`, mutably
+pub fn walk_table_reference_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    table: &mut TableReference,
+) -> ControlFlow<V::Break> {
+    match table {
+        TableReference::Table { name, .. } => visitor.visit_table_name_mut(name),
+        TableReference::Derived { subquery, .. } => visitor.visit_query_mut(subquery),
+        TableReference::Function { args, .. } => walk_expressions_mut(visitor, args),
+    }
+}
+
+/// Visits the operands, arguments or subquery of `expr`, mutably
+pub fn walk_expression_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    expr: &mut Expression,
+) -> ControlFlow<V::Break> {
+    match expr {
+        Expression::BinaryOperation {
+            left_operand,
+            right_operand,
+            ..
+        } => {
+            visitor.visit_expression_mut(left_operand)?;
+            visitor.visit_expression_mut(right_operand)
+        }
+        Expression::UnaryOperation { operand, .. } => visitor.visit_expression_mut(operand),
+        Expression::Identifier(ident) => visitor.visit_identifier_mut(ident),
+        Expression::CompoundIdentifier(parts) => visitor.visit_compound_identifier_mut(parts),
+        Expression::Function { args, .. } => walk_expressions_mut(visitor, args),
+        Expression::Cast { expr, .. } => visitor.visit_expression_mut(expr),
+        Expression::Subquery(query) => visitor.visit_query_mut(query),
+        Expression::InList { expr, list, .. } => {
+            visitor.visit_expression_mut(expr)?;
+            walk_expressions_mut(visitor, list)
+        }
+        Expression::IsNull { expr, .. } => visitor.visit_expression_mut(expr),
+        Expression::Like { expr, pattern, .. } => {
+            visitor.visit_expression_mut(expr)?;
+            visitor.visit_expression_mut(pattern)
+        }
+        Expression::Value(_) | Expression::Placeholder { .. } | Expression::Wildcard => {
+            ControlFlow::Continue(())
+        }
+    }
+}
+
+/// Visits everything inside `expr` mutably as the default `visit_expression_mut` calling
+/// itself on each operand would, but keeps the operands still to visit on a heap stack
+pub fn walk_expression_tree_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    expr: &mut Expression,
+) -> ControlFlow<V::Break> {
+    let mut stack = vec![expr];
+    while let Some(expr) = stack.pop() {
+        match expr {
+            Expression::BinaryOperation {
+                left_operand,
+                right_operand,
+                ..
+            } => {
+                stack.push(right_operand);
+                stack.push(left_operand);
+            }
+            Expression::UnaryOperation { operand: expr, .. }
+            | Expression::Cast { expr, .. }
+            | Expression::IsNull { expr, .. } => stack.push(expr),
+            Expression::Function { args, .. } => stack.extend(args.iter_mut().rev()),
+            Expression::InList { expr, list, .. } => {
+                stack.extend(list.iter_mut().rev());
+                stack.push(expr);
+            }
+            Expression::Like { expr, pattern, .. } => {
+                stack.push(pattern);
+                stack.push(expr);
+            }
+            Expression::Identifier(ident) => visitor.visit_identifier_mut(ident)?,
+            Expression::CompoundIdentifier(parts) => {
+                visitor.visit_compound_identifier_mut(parts)?
+            }
+            Expression::Subquery(query) => visitor.visit_query_mut(query)?,
+            Expression::Value(_) | Expression::Placeholder { .. } | Expression::Wildcard => {}
+        }
+    }
+    ControlFlow::Continue(())
+}
+
+fn walk_expressions_mut<'a, V, I>(visitor: &mut V, exprs: I) -> ControlFlow<V::Break>
+where
+    V: VisitorMut + ?Sized,
+    I: IntoIterator<Item = &'a mut Expression>,
+{
+    for expr in exprs {
+        visitor.visit_expression_mut(expr)?;
+    }
+    ControlFlow::Continue(())
+}
+
+fn walk_select_items_mut<'a, V, I>(visitor: &mut V, items: I) -> ControlFlow<V::Break>
+where
+    V: VisitorMut + ?Sized,
+    I: IntoIterator<Item = &'a mut SelectItem>,
+{
+    for item in items {
+        match item {
+            SelectItem::Expression(expr) | SelectItem::ExprWithAlias { expr, .. } => {
+                visitor.visit_expression_mut(expr)?
+            }
+            SelectItem::Wildcard | SelectItem::QualifiedWildcard(_) => {}
+        }
+    }
+    ControlFlow::Continue(())
+}
@@ -0,0 +1,150 @@
+//! JavaScript bindings for WebAssembly builds, enabled by the `wasm` feature, which also
+//! turns on `serde`. Values cross the boundary as plain objects built by
+//! `serde-wasm-bindgen`, and errors as diagnostic objects rather than strings; nothing here
+//! unwraps, so invalid input cannot panic. Tokens and AST nodes use their serde layout.
+//!
+//! Each export wraps a function that returns the Rust values it converts, so that what
+//! JavaScript receives can be checked natively by serializing those to JSON.
+
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+use crate::ast::{Statement, Token};
+use crate::error::{ErrorKind, ParseError};
+use crate::parser::PrattParser;
+use crate::tokenizer::{Location, Tokenizer};
+
+/// Represents an error as JavaScript sees it; `line` and `column` count from 1 and are
+/// missing when the error has no position
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Diagnostic {
+    /// The error's code, or `None` for a failure to convert a value
+    pub code: Option<ErrorKind>,
+    pub message: String,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    pub end_line: Option<usize>,
+    pub end_column: Option<usize>,
+}
+
+impl From<&ParseError> for Diagnostic {
+    fn from(e: &ParseError) -> Self {
+        let span = e.span();
+        Diagnostic {
+            code: Some(e.kind()),
+            message: e.message(),
+            line: span.map(|span| span.start.line),
+            column: span.map(|span| span.start.column),
+            end_line: span.map(|span| span.end.line),
+            end_column: span.map(|span| span.end.column),
+        }
+    }
+}
+
+/// Represents a token's start or end as JavaScript sees it
+#[derive(Debug, Serialize)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+    pub offset: usize,
+}
+
+impl From<Location> for Position {
+    fn from(location: Location) -> Self {
+        Position {
+            line: location.line,
+            column: location.column,
+            offset: location.offset,
+        }
+    }
+}
+
+/// Represents a token and where it is in the input
+#[derive(Debug, Serialize)]
+pub struct SpannedToken {
+    pub token: Token,
+    pub start: Position,
+    pub end: Position,
+}
+
+/// Parses a `;`-separated script and returns its statements as an array of AST objects,
+/// or throws a diagnostic for the first error
+#[wasm_bindgen(js_name = parseSql)]
+pub fn parse_sql(sql: &str) -> Result<JsValue, JsValue> {
+    let statements = parse(sql).map_err(|diagnostic| thrown(&diagnostic))?;
+    to_js(&statements)
+}
+
+/// Returns the tokens of `sql` as `{ token, start, end }` objects, ending with `Eof`, or
+/// throws a diagnostic for the first lexical error
+#[wasm_bindgen(js_name = tokenizeSql)]
+pub fn tokenize_sql(sql: &str) -> Result<JsValue, JsValue> {
+    let tokens = tokenize(sql).map_err(|diagnostic| thrown(&diagnostic))?;
+    to_js(&tokens)
+}
+
+/// Returns `null` if `sql` parses, or a diagnostic for its first error
+#[wasm_bindgen(js_name = validateSql)]
+pub fn validate_sql(sql: &str) -> JsValue {
+    match validate(sql) {
+        None => JsValue::NULL,
+        Some(diagnostic) => thrown(&diagnostic),
+    }
+}
+
+/// Returns what `parseSql` converts: the statements of a script, or its first error
+pub fn parse(sql: &str) -> Result<Vec<Statement>, Diagnostic> {
+    PrattParser::parse_statements(sql).map_err(|e| Diagnostic::from(&e))
+}
+
+/// Returns what `tokenizeSql` converts: the tokens of `sql` with their spans, ending with
+/// `Eof`, or its first lexical error
+pub fn tokenize(sql: &str) -> Result<Vec<SpannedToken>, Diagnostic> {
+    let mut tokenizer = Tokenizer::new(sql);
+    if let Some(e) = tokenizer.error() {
+        return Err(Diagnostic::from(e));
+    }
+    let mut tokens = vec![];
+    loop {
+        let token = tokenizer.next().unwrap_or(Token::Eof);
+        let span = tokenizer.span();
+        let end = token == Token::Eof;
+        tokens.push(SpannedToken {
+            token,
+            start: span.start.into(),
+            end: span.end.into(),
+        });
+        if end {
+            return Ok(tokens);
+        }
+    }
+}
+
+/// Returns what `validateSql` converts: `None` if `sql` parses, or its first error
+pub fn validate(sql: &str) -> Option<Diagnostic> {
+    parse(sql).err()
+}
+
+fn thrown(diagnostic: &Diagnostic) -> JsValue {
+    to_js(diagnostic).unwrap_or_else(|e| e)
+}
+
+/// Converts `value` to a plain JavaScript value. Numbers beyond 2^53 cannot be represented
+/// and are reported as a diagnostic without a code or position.
+fn to_js<T: Serialize>(value: &T) -> Result<JsValue, JsValue> {
+    let serializer = serde_wasm_bindgen::Serializer::json_compatible();
+    value.serialize(&serializer).map_err(|e| {
+        let diagnostic = Diagnostic {
+            code: None,
+            message: e.to_string(),
+            line: None,
+            column: None,
+            end_line: None,
+            end_column: None,
+        };
+        diagnostic
+            .serialize(&serializer)
+            .unwrap_or_else(|_| JsValue::from_str(&diagnostic.message))
+    })
+}